@@ -1,4 +1,5 @@
 use clap::Parser;
+use symex::{executor::state::ArgumentValue, Endianness, WordSize};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -41,10 +42,38 @@ pub struct Args {
     pub all_features: bool,
 
     /// Name of function to run. Should be a full module path, excluding the
-    /// root module.
+    /// root module. Both mangled and demangled names are accepted.
     #[clap(short, long)]
     pub function: Option<String>,
 
+    /// Reports every returning path that can exceed this many cycles, along
+    /// with inputs that make it do so.
+    #[clap(long)]
+    pub max_cycles: Option<u64>,
+
+    /// Stops exploring once this many paths have completed.
+    #[clap(long)]
+    pub max_paths: Option<usize>,
+
+    /// Lists the addresses of all instructions that were never executed.
+    #[clap(long)]
+    pub list_uncovered: bool,
+
+    /// Overrides the word size, in bits, inferred from the binary. Must be
+    /// one of 16, 32 or 64.
+    #[clap(long, requires = "endianness", value_parser = parse_word_size)]
+    pub word_size: Option<WordSize>,
+
+    /// Overrides the endianness inferred from the binary.
+    #[clap(long, requires = "word_size")]
+    pub endianness: Option<ByteOrder>,
+
+    /// Sets the value of an argument register of the analyzed functions, given
+    /// as `<register>=<value>`. The value is a number, a range `low..high` or
+    /// a name for any value, e.g. `--arg R0=0..10`.
+    #[clap(long = "arg", value_parser = parse_argument)]
+    pub arguments: Vec<(String, ArgumentValue)>,
+
     #[clap(short, long, default_value = "bitwuzla")]
     /// Denotes the solver to use during analysis.
     pub solver: Solver,
@@ -54,6 +83,52 @@ pub struct Args {
     pub mode: Mode,
 }
 
+#[derive(Parser, clap::ValueEnum, Debug, Clone, Copy)]
+/// The byte order of the target.
+pub enum ByteOrder {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+impl From<ByteOrder> for Endianness {
+    fn from(value: ByteOrder) -> Self {
+        match value {
+            ByteOrder::Little => Self::Little,
+            ByteOrder::Big => Self::Big,
+        }
+    }
+}
+
+fn parse_word_size(bits: &str) -> Result<WordSize, String> {
+    let bits = bits.parse::<u32>().map_err(|e| e.to_string())?;
+    WordSize::try_from(bits).map_err(|e| e.to_string())
+}
+
+fn parse_argument(argument: &str) -> Result<(String, ArgumentValue), String> {
+    let Some((register, value)) = argument.split_once('=') else {
+        return Err(format!("expected <register>=<value>, found {argument}"));
+    };
+    let value = match value.split_once("..") {
+        Some((low, high)) => ArgumentValue::Range {
+            low: parse_number(low)?,
+            high: parse_number(high)?,
+        },
+        None if value.starts_with(|c: char| c.is_ascii_digit()) => ArgumentValue::Concrete(parse_number(value)?),
+        None => ArgumentValue::Symbolic(value.to_string()),
+    };
+    Ok((register.to_string(), value))
+}
+
+fn parse_number(number: &str) -> Result<u64, String> {
+    match number.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => number.parse(),
+    }
+    .map_err(|e| format!("{number} is not a valid number, {e}"))
+}
+
 #[derive(Parser, clap::ValueEnum, Debug, Clone)]
 /// Enumerates all of the supported solvers.
 pub enum Solver {
@@ -70,10 +145,14 @@ pub enum Solver {
 pub enum Mode {
     /// Analyses a single (or multiple functions).
     Function(FunctionArguments),
+    /// Reports the worst case execution time, in cycles, of a single (or
+    /// multiple functions).
+    Wcet(FunctionArguments),
 }
 
 #[derive(Parser, Debug)]
 pub struct FunctionArguments {
-    /// The name of the function to analyze.
-    pub name: String,
+    /// The names of the functions to analyze.
+    #[clap(required = true)]
+    pub name: Vec<String>,
 }
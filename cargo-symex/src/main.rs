@@ -11,7 +11,16 @@ mod build;
 
 use args::{Args, FunctionArguments, Mode, Solver};
 use build::{Features, Settings, Target};
-use symex::{arch::NoArchitectureOverride, defaults::logger::SimplePathLogger, executor::hooks::LangagueHooks, manager::SymexArbiter};
+use symex::{
+    arch::NoArchitectureOverride,
+    defaults::logger::SimplePathLogger,
+    executor::{coverage::CoverageReport, hooks::LangagueHooks, FailureReason, PathResult},
+    AnalysisOptions,
+    AnalysisSummary,
+    Analyzer,
+    Endianness,
+    GAError,
+};
 
 fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
@@ -62,40 +71,133 @@ fn run() -> Result<()> {
         }
     };
 
+    let layout = args.word_size.zip(args.endianness.map(Endianness::from));
+    let options = AnalysisOptions {
+        layout,
+        arguments: args.arguments.clone(),
+        max_cycles: args.max_cycles,
+        max_paths: args.max_paths,
+        language: LangagueHooks::Rust,
+    };
+
     #[cfg(any(feature = "bitwuzla", feature = "boolector"))]
     match (args.mode, args.solver) {
         #[cfg(feature = "bitwuzla")]
-        (Mode::Function(FunctionArguments { name }), Solver::Bitwuzla) => run_elf::<symex::defaults::bitwuzla::DefaultComposition>(path, name, &LangagueHooks::Rust),
+        (Mode::Function(FunctionArguments { name }), Solver::Bitwuzla) => run_elf::<symex::defaults::bitwuzla::DefaultComposition>(path, name, options, args.list_uncovered),
+        #[cfg(feature = "boolector")]
+        (Mode::Function(FunctionArguments { name }), Solver::Boolector) => run_elf::<symex::defaults::boolector::DefaultComposition>(path, name, options, args.list_uncovered),
+        #[cfg(feature = "bitwuzla")]
+        (Mode::Wcet(FunctionArguments { name }), Solver::Bitwuzla) => run_wcet::<symex::defaults::bitwuzla::DefaultComposition>(path, name, options, args.list_uncovered),
         #[cfg(feature = "boolector")]
-        (Mode::Function(FunctionArguments { name }), Solver::Boolector) => run_elf::<symex::defaults::boolector::DefaultComposition>(path, name, &LangagueHooks::Rust),
+        (Mode::Wcet(FunctionArguments { name }), Solver::Boolector) => run_wcet::<symex::defaults::boolector::DefaultComposition>(path, name, options, args.list_uncovered),
     }?;
 
     Ok(())
 }
-fn run_elf<C>(path: String, function_name: String, language: &LangagueHooks) -> Result<()>
+fn run_elf<C>(path: String, function_names: Vec<String>, options: AnalysisOptions, list_uncovered: bool) -> Result<()>
+where
+    C: symex::Composition<Logger = SimplePathLogger, StateContainer = (), ArchitectureOverride = NoArchitectureOverride>,
+    C::Memory: symex::smt::SmtMap<ProgramMemory = std::sync::Arc<symex::project::Project<C::SMT>>>,
+{
+    let mut analyzer = Analyzer::<C>::new(&path, options)?;
+    for function_name in function_names {
+        let Some(summary) = analyze_function(&mut analyzer, &function_name)? else {
+            continue;
+        };
+
+        println!("==================== {function_name} ====================");
+        for path in &summary.paths {
+            if let PathResult::Failure(FailureReason::StackOverflow(location)) = &path.result {
+                println!("STACK OVERFLOW: path left the stack region @ {location}");
+            }
+            if let (Some(max_cycles), Some(witness)) = (summary.max_cycles, &path.cycle_bound_witness) {
+                println!(
+                    "CYCLE BOUND VIOLATION: path took {} cycles over {} instructions, bound is {max_cycles}",
+                    path.cycles, path.instructions
+                );
+                let inputs = witness.iter().map(|(name, value)| format!("{name} = {value:#x}")).collect::<Vec<_>>();
+                println!("Exceeded with inputs: {}", inputs.join(", "));
+            }
+            println!("{}", path.log);
+        }
+
+        if let Some(max_cycles) = summary.max_cycles {
+            match summary.cycle_bound_violations() {
+                0 => println!("All paths in {function_name} complete within {max_cycles} cycles"),
+                n => println!("{n} path(s) in {function_name} exceed {max_cycles} cycles"),
+            }
+        }
+        if summary.truncated {
+            print_truncated(summary.paths.len());
+        }
+    }
+    print_totals(&mut analyzer, list_uncovered)
+}
+
+fn run_wcet<C>(path: String, function_names: Vec<String>, options: AnalysisOptions, list_uncovered: bool) -> Result<()>
 where
     C: symex::Composition<Logger = SimplePathLogger, StateContainer = (), ArchitectureOverride = NoArchitectureOverride>,
     C::Memory: symex::smt::SmtMap<ProgramMemory = std::sync::Arc<symex::project::Project<C::SMT>>>,
 {
-    let mut executor: SymexArbiter<C> = symex::initiation::SymexConstructor::new(&path)
-        .load_binary()
-        .unwrap()
-        .discover()
-        .unwrap()
-        .configure_smt::<C::SMT>()
-        .compose(|| (), SimplePathLogger::from_sub_programs)
-        .unwrap();
-
-    let sub_program = executor.get_symbol_map().get_by_name(&function_name).unwrap().clone();
-    let result = executor.run(&sub_program.name, language)?;
-    for path in result {
-        let (_state, path, _result) = path?;
-        println!("{path}");
+    let mut analyzer = Analyzer::<C>::new(&path, options)?;
+    for function_name in function_names {
+        let Some(summary) = analyze_function(&mut analyzer, &function_name)? else {
+            continue;
+        };
+
+        println!("==================== {function_name} ====================");
+        match summary.worst_case() {
+            Some(path) => {
+                println!(
+                    "Worst case execution time over {} path(s): {} cycles over {} instructions",
+                    summary.paths.len(),
+                    path.cycles,
+                    path.instructions
+                );
+                println!("{}", path.log);
+            }
+            None => println!("No paths completed for {function_name}"),
+        }
+        if summary.truncated {
+            print_truncated(summary.paths.len());
+        }
+    }
+    print_totals(&mut analyzer, list_uncovered)
+}
+
+/// Analyzes `function_name`, or returns `None` if the binary has no such
+/// function.
+fn analyze_function<C: symex::Composition>(analyzer: &mut Analyzer<C>, function_name: &str) -> Result<Option<AnalysisSummary<C>>> {
+    match analyzer.analyze(function_name) {
+        Ok(summary) => Ok(Some(summary)),
+        Err(error) if matches!(error.downcast_ref::<GAError>(), Some(GAError::EntryFunctionNotFound(_))) => {
+            eprintln!("Could not find function {function_name}, skipping it");
+            Ok(None)
+        }
+        Err(error) => Err(error),
     }
+}
 
+/// Prints the solver statistics and coverage of every function analyzed.
+fn print_totals<C: symex::Composition>(analyzer: &mut Analyzer<C>, list_uncovered: bool) -> Result<()> {
+    println!("Solver statistics: {}", analyzer.statistics());
+    print_coverage(&analyzer.coverage()?, list_uncovered);
     Ok(())
 }
 
+fn print_truncated(paths: usize) {
+    println!("Exploration truncated after {paths} path(s), the remaining paths were not explored");
+}
+
+fn print_coverage(coverage: &CoverageReport, list_uncovered: bool) {
+    println!("Instruction coverage: {coverage}");
+    if list_uncovered {
+        for address in coverage.uncovered() {
+            println!("Uncovered instruction at {address:#x}");
+        }
+    }
+}
+
 fn settings_from_args(opts: &Args) -> Settings {
     let target = if let Some(name) = &opts.bin {
         Target::Bin(name.clone())
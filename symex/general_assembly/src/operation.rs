@@ -59,6 +59,33 @@ pub enum Operation {
         operand2: Operand,
     },
 
+    /// Add with carry, storing the result, the carry out and the overflow.
+    ///
+    /// ```ignore
+    /// destination = operand1 + operand2 + carry_in
+    /// carry_out_flag = unsigned overflow of the addition
+    /// overflow_flag = signed overflow of the addition
+    /// ```
+    ///
+    /// This is equivalent to an [`Adc`](Operation::Adc) followed by a
+    /// [`SetCFlag`](Operation::SetCFlag) and a
+    /// [`SetVFlag`](Operation::SetVFlag) with `carry` set, but computes the
+    /// addition only once.
+    AddWithCarry {
+        /// Where to store the result of the addition.
+        destination: Operand,
+        /// Where to store the carry out, typically a flag.
+        carry_out_flag: Operand,
+        /// Where to store the signed overflow, typically a flag.
+        overflow_flag: Operand,
+        /// Left hand side of the addition.
+        operand1: Operand,
+        /// Right hand side of the addition.
+        operand2: Operand,
+        /// The carry in, only the least significant bit is used.
+        carry_in: Operand,
+    },
+
     /// Subtraction.
     ///
     /// ```ignore
@@ -84,6 +111,44 @@ pub enum Operation {
         signed: bool,
     },
 
+    /// Saturating addition of the `bits` least significant bits of the
+    /// operands.
+    ///
+    /// The result is clamped to the largest or smallest value representable
+    /// in `bits` bits and then sign or zero extended to the word size
+    /// depending on `signed`.
+    ///
+    /// ```ignore
+    /// destination = clamp(operand1[bits-1:0] + operand2[bits-1:0])
+    /// ```
+    #[allow(missing_docs)]
+    SaturatingAdd {
+        destination: Operand,
+        operand1: Operand,
+        operand2: Operand,
+        signed: bool,
+        bits: u32,
+    },
+
+    /// Saturating subtraction of the `bits` least significant bits of the
+    /// operands.
+    ///
+    /// The result is clamped to the largest or smallest value representable
+    /// in `bits` bits and then sign or zero extended to the word size
+    /// depending on `signed`.
+    ///
+    /// ```ignore
+    /// destination = clamp(operand1[bits-1:0] - operand2[bits-1:0])
+    /// ```
+    #[allow(missing_docs)]
+    SaturatingSub {
+        destination: Operand,
+        operand1: Operand,
+        operand2: Operand,
+        signed: bool,
+        bits: u32,
+    },
+
     /// Multiplication.
     ///
     /// ```ignore
@@ -96,6 +161,30 @@ pub enum Operation {
         operand2: Operand,
     },
 
+    /// Multiplication that reports whether the product overflowed.
+    ///
+    /// ```ignore
+    /// product = operand1 * operand2 // double width
+    /// destination = product[word_size-1:0]
+    /// overflow_flag = product does not fit in word_size bits
+    /// ```
+    ///
+    /// When `signed` is set the operands are treated as two's complement and
+    /// the product overflows if the high half is not the sign extension of
+    /// the low half, otherwise it overflows if the high half is nonzero.
+    MulChecked {
+        /// Where to store the low half of the product.
+        destination: Operand,
+        /// Left hand side of the multiplication.
+        operand1: Operand,
+        /// Right hand side of the multiplication.
+        operand2: Operand,
+        /// Whether the operands are signed.
+        signed: bool,
+        /// Where to store whether the product overflowed, typically a flag.
+        overflow_flag: Operand,
+    },
+
     /// Signed division.
     ///
     /// ```ignore
@@ -219,6 +308,9 @@ pub enum Operation {
     ///
     /// Rotates the `operand` `shift` steps
     /// and stores the result in `destination`.
+    ///
+    /// Kept for backwards compatibility, this is equivalent to
+    /// [`Operation::RotateRight`].
     #[allow(missing_docs)]
     Sror {
         destination: Operand,
@@ -226,6 +318,36 @@ pub enum Operation {
         shift: Operand,
     },
 
+    /// Rotate left.
+    ///
+    /// Rotates the `operand` `amount` steps, modulo the width of the operand,
+    /// and stores the result in `destination`.
+    ///
+    /// ```ignore
+    /// destination = operand.rotate_left(amount)
+    /// ```
+    #[allow(missing_docs)]
+    RotateLeft {
+        destination: Operand,
+        operand: Operand,
+        amount: Operand,
+    },
+
+    /// Rotate right.
+    ///
+    /// Rotates the `operand` `amount` steps, modulo the width of the operand,
+    /// and stores the result in `destination`.
+    ///
+    /// ```ignore
+    /// destination = operand.rotate_right(amount)
+    /// ```
+    #[allow(missing_docs)]
+    RotateRight {
+        destination: Operand,
+        operand: Operand,
+        amount: Operand,
+    },
+
     /// Zero extend
     ///
     /// Zero extends `bits` bits from operand and stores it in destination.
@@ -258,6 +380,82 @@ pub enum Operation {
         stop_bit: u32,
     },
 
+    /// Sets a single bit of the operand.
+    ///
+    /// ```ignore
+    /// destination = operand
+    /// destination[bit] = value[0]
+    /// ```
+    ///
+    /// `bit` may be symbolic, if it is outside of the operand the operand is
+    /// stored unchanged.
+    SetBit {
+        /// Where to store the result.
+        destination: Operand,
+        /// The value to set the bit in.
+        operand: Operand,
+        /// The index of the bit to set.
+        bit: Operand,
+        /// The new value of the bit, only the least significant bit is used.
+        value: Operand,
+    },
+
+    /// Reads a single bit of the operand.
+    ///
+    /// ```ignore
+    /// destination = operand[bit]
+    /// ```
+    ///
+    /// `bit` may be symbolic, if it is outside of the operand the result is
+    /// zero. The bit is zero extended to the width of the operand.
+    GetBit {
+        /// Where to store the result.
+        destination: Operand,
+        /// The value to read the bit from.
+        operand: Operand,
+        /// The index of the bit to read.
+        bit: Operand,
+    },
+
+    /// Copies `length` bytes from `source` to `destination`.
+    ///
+    /// ```ignore
+    /// for i in 0..length {
+    ///     mem[destination + i] = mem[source + i]
+    /// }
+    /// ```
+    ///
+    /// All bytes are read before any are written, so overlapping regions are
+    /// copied as if through a temporary buffer. A symbolic `length` forks the
+    /// path once per possible length.
+    Memcpy {
+        /// The address to copy to.
+        destination: Operand,
+        /// The address to copy from.
+        source: Operand,
+        /// The number of bytes to copy.
+        length: Operand,
+    },
+
+    /// Fills `length` bytes at `destination` with the least significant byte
+    /// of `value`.
+    ///
+    /// ```ignore
+    /// for i in 0..length {
+    ///     mem[destination + i] = value[7:0]
+    /// }
+    /// ```
+    ///
+    /// A symbolic `length` forks the path once per possible length.
+    Memset {
+        /// The address to start filling at.
+        destination: Operand,
+        /// The value to fill with.
+        value: Operand,
+        /// The number of bytes to fill.
+        length: Operand,
+    },
+
     /// Count the number of ones in the operand.
     #[allow(missing_docs)]
     CountOnes {
@@ -288,6 +486,33 @@ pub enum Operation {
         operand: Operand,
     },
 
+    /// Count the number of trailing ones (least significant to most
+    /// significant).
+    #[allow(missing_docs)]
+    CountTrailingOnes {
+        destination: Operand,
+        operand: Operand,
+    },
+
+    /// Count the number of trailing zeroes (least significant to most
+    /// significant).
+    #[allow(missing_docs)]
+    CountTrailingZeroes {
+        destination: Operand,
+        operand: Operand,
+    },
+
+    /// Reverses the order of the `bits` least significant bits of the
+    /// operand.
+    BitReverse {
+        /// Where to store the result.
+        destination: Operand,
+        /// The value to reverse.
+        operand: Operand,
+        /// The number of bits to reverse.
+        bits: u32,
+    },
+
     /// Sign extend.
     SignExtend {
         /// Where to store the result.
@@ -406,6 +631,20 @@ pub enum Operation {
         otherwise: Vec<Operation>,
     },
 
+    /// Repeatedly executes operations while the condition holds.
+    ///
+    /// Before each iteration `compute_condition` is executed and the
+    /// `condition` is evaluated. If it yields true `operations` are executed
+    /// and the loop restarts, otherwise execution resumes after the loop.
+    While {
+        /// Operations that compute the condition.
+        compute_condition: Vec<Operation>,
+        /// The condition that decides whether or not to run another iteration.
+        condition: Operand,
+        /// The body of the loop.
+        operations: Vec<Operation>,
+    },
+
     /// Compares two operands.
     Compare {
         /// The left hand side of the comparison.
@@ -418,6 +657,14 @@ pub enum Operation {
         destination: Operand,
     },
 
+    /// Constrains the path such that the condition holds.
+    ///
+    /// Paths where the condition can not hold are discarded.
+    Assume {
+        /// The condition that is assumed to hold.
+        condition: Operand,
+    },
+
     /// Aborts the execution returning the error message to the user.
     Abort {
         /// Error message to be printed to the user.
@@ -0,0 +1,216 @@
+//! Runs functions to completion and summarizes the results.
+//!
+//! This bundles [`SymexConstructor`], [`SymexArbiter`] and
+//! [`Runner`](crate::manager::Runner) for the common case of analyzing
+//! functions in an ELF file, use those directly for anything else.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{
+    arch::NoArchitectureOverride,
+    defaults::logger::SimplePathLogger,
+    executor::{coverage::CoverageReport, hooks::LangagueHooks, state::ArgumentValue, PathResult},
+    initiation::SymexConstructor,
+    manager::SymexArbiter,
+    project::Project,
+    smt::{SmtMap, Statistics},
+    Composition,
+    Endianness,
+    WordSize,
+};
+
+/// Options for an [`Analyzer`].
+pub struct AnalysisOptions {
+    /// Overrides the word size and endianness inferred from the binary.
+    pub layout: Option<(WordSize, Endianness)>,
+    /// Values of the entry function arguments, see
+    /// [`SymexArbiter::set_argument`](crate::manager::SymexArbiter::set_argument).
+    pub arguments: Vec<(String, ArgumentValue)>,
+    /// The number of cycles a path may take, see
+    /// [`AnalysisSummary::cycle_bound_violations`].
+    pub max_cycles: Option<u64>,
+    /// Stops exploring once this many paths have completed, see
+    /// [`SymexArbiter::set_max_paths`](crate::manager::SymexArbiter::set_max_paths).
+    pub max_paths: Option<usize>,
+    /// The language specific hooks to add.
+    pub language: LangagueHooks,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            layout: None,
+            arguments: Vec::new(),
+            max_cycles: None,
+            max_paths: None,
+            language: LangagueHooks::None,
+        }
+    }
+}
+
+/// The outcome of a single path.
+#[derive(Clone)]
+pub struct PathSummary<C: Composition> {
+    /// How the path ended.
+    pub result: PathResult<C>,
+    /// The number of cycles the path took.
+    pub cycles: u64,
+    /// The number of instructions the path executed.
+    pub instructions: usize,
+    /// Values of the named inputs under which the path takes more than
+    /// [`AnalysisOptions::max_cycles`], if the path returned and any inputs do.
+    pub cycle_bound_witness: Option<BTreeMap<String, u64>>,
+    /// The log the path recorded while it was explored.
+    pub log: C::Logger,
+}
+
+/// The outcome of all paths through a function, see [`Analyzer::analyze`].
+#[derive(Clone)]
+pub struct AnalysisSummary<C: Composition> {
+    /// Every path, in the order they completed.
+    pub paths: Vec<PathSummary<C>>,
+    /// The cycle budget the paths were checked against, if any.
+    pub max_cycles: Option<u64>,
+    /// True if exploration stopped at [`AnalysisOptions::max_paths`] with
+    /// paths left to explore.
+    pub truncated: bool,
+}
+
+impl<C: Composition> AnalysisSummary<C> {
+    /// Returns the number of paths that completed successfully.
+    #[must_use]
+    pub fn successes(&self) -> usize {
+        self.paths.iter().filter(|path| matches!(path.result, PathResult::Success(..))).count()
+    }
+
+    /// Returns the number of paths that ended in a failure.
+    #[must_use]
+    pub fn failures(&self) -> usize {
+        self.paths.iter().filter(|path| matches!(path.result, PathResult::Failure(_))).count()
+    }
+
+    /// Returns the path that took the most cycles, the first one if several
+    /// took equally long.
+    #[must_use]
+    pub fn worst_case(&self) -> Option<&PathSummary<C>> {
+        self.paths.iter().reduce(|worst, path| match path.cycles > worst.cycles {
+            true => path,
+            false => worst,
+        })
+    }
+
+    /// Returns the worst case execution time in cycles.
+    #[must_use]
+    pub fn wcet(&self) -> Option<u64> {
+        self.worst_case().map(|path| path.cycles)
+    }
+
+    /// Returns the number of returning paths that can take more than
+    /// [`AnalysisOptions::max_cycles`], see [`PathSummary::cycle_bound_witness`].
+    #[must_use]
+    pub fn cycle_bound_violations(&self) -> usize {
+        self.paths.iter().filter(|path| path.cycle_bound_witness.is_some()).count()
+    }
+}
+
+/// Analyzes functions in a binary that is only loaded once, the coverage and
+/// solver statistics cover every function analyzed.
+pub struct Analyzer<C: Composition> {
+    executor: SymexArbiter<C>,
+    options: AnalysisOptions,
+}
+
+impl<C> Analyzer<C>
+where
+    C: Composition<Logger = SimplePathLogger, StateContainer = (), ArchitectureOverride = NoArchitectureOverride>,
+    C::Memory: SmtMap<ProgramMemory = Arc<Project<C::SMT>>>,
+{
+    /// Loads the ELF file at `path`.
+    pub fn new(path: &str, options: AnalysisOptions) -> crate::Result<Self> {
+        let mut constructor = SymexConstructor::new(path);
+        if let Some((word_size, endianness)) = options.layout {
+            constructor = constructor.override_layout(word_size, endianness)?;
+        }
+        let executor = constructor
+            .load_binary()?
+            .discover()?
+            .configure_smt::<C::SMT>()
+            .compose::<C, _, _>(|| (), SimplePathLogger::from_sub_programs)?;
+        Ok(Self::from_executor(executor, options))
+    }
+}
+
+impl<C: Composition> Analyzer<C> {
+    /// Analyzes functions in an already composed binary.
+    ///
+    /// The layout in `options` is ignored as the binary has been loaded.
+    pub fn from_executor(mut executor: SymexArbiter<C>, options: AnalysisOptions) -> Self {
+        for (register, value) in &options.arguments {
+            executor.set_argument(register, value.clone());
+        }
+        if let Some(max_paths) = options.max_paths {
+            executor.set_max_paths(max_paths);
+        }
+        Self { executor, options }
+    }
+
+    /// Explores every path through `function` and collects the results.
+    pub fn analyze(&mut self, function: &str) -> crate::Result<AnalysisSummary<C>> {
+        let mut paths = Vec::new();
+        let mut runner = self.executor.run(function, &self.options.language)?;
+        for path in runner.by_ref() {
+            let (mut state, log, result) = path?;
+            let cycle_bound_witness = match (&result, self.options.max_cycles) {
+                (PathResult::Success(..), Some(max_cycles)) => state.cycle_bound_witness(max_cycles)?,
+                _ => None,
+            };
+            paths.push(PathSummary {
+                result,
+                cycles: state.get_cycle_count(),
+                instructions: state.instruction_count(),
+                cycle_bound_witness,
+                log,
+            });
+        }
+
+        Ok(AnalysisSummary {
+            paths,
+            max_cycles: self.options.max_cycles,
+            truncated: runner.truncated(),
+        })
+    }
+
+    /// Returns the instruction coverage of every function analyzed so far.
+    pub fn coverage(&mut self) -> crate::Result<CoverageReport> {
+        self.executor.coverage()
+    }
+
+    /// Returns the solver statistics of every function analyzed so far.
+    #[must_use]
+    pub fn statistics(&self) -> Statistics {
+        self.executor.statistics()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AnalysisOptions, Analyzer};
+    use crate::{defaults::bitwuzla::DefaultComposition, initiation::test::raw_executor};
+
+    #[test]
+    fn test_coverage_spans_every_analyzed_function() {
+        // first: movs r0, #1; bx lr
+        // second: movs r0, #2; bx lr
+        let executor = raw_executor::<DefaultComposition>(&[0x01, 0x20, 0x70, 0x47, 0x02, 0x20, 0x70, 0x47], &[("first", 0x1001), ("second", 0x1005)]);
+        let mut analyzer = Analyzer::from_executor(executor, AnalysisOptions::default());
+
+        let first = analyzer.analyze("first").unwrap();
+        assert_eq!((first.successes(), first.paths.len()), (1, 1));
+        assert!(!analyzer.coverage().unwrap().uncovered().is_empty());
+
+        let second = analyzer.analyze("second").unwrap();
+        assert_eq!((second.successes(), second.paths.len()), (1, 1));
+        assert!(analyzer.coverage().unwrap().uncovered().is_empty());
+        assert!(analyzer.analyze("third").is_err());
+    }
+}
@@ -100,6 +100,10 @@ pub enum InterfaceRegister {
     ProgramCounter,
     /// The return address register, "LR" on ARM
     ReturnAddress,
+    /// The register holding the return value of a function, "R0" on ARM.
+    ReturnValue,
+    /// The stack pointer register, "SP" on ARM.
+    StackPointer,
 }
 
 /// Enumerates the discoverable machine code formats.
@@ -185,6 +189,23 @@ pub trait Architecture<Override: ArchitectureOverride>: Debug + Display + Into<S
     where
         C: Composition<ArchitectureOverride = Override>;
 
+    /// Returns the number of cycles `instruction` takes to execute in the
+    /// current state.
+    ///
+    /// By default this evaluates the [`CycleCount`] that was attached to the
+    /// instruction when it was translated.
+    ///
+    /// [`CycleCount`]: crate::executor::instruction::CycleCount
+    fn cycle_cost<C>(instruction: &Instruction<C>, state: &mut GAState<C>) -> u64
+    where
+        C: Composition<ArchitectureOverride = Override>,
+    {
+        match instruction.max_cycle {
+            crate::executor::instruction::CycleCount::Value(v) => v as u64,
+            crate::executor::instruction::CycleCount::Function(f) => f(state) as u64,
+        }
+    }
+
     /// Initiates the [`GAState`] to support execution.
     fn initiate_state<C>(state: &mut GAState<C>)
     where
@@ -194,6 +215,11 @@ pub trait Architecture<Override: ArchitectureOverride>: Debug + Display + Into<S
     #[must_use]
     fn get_register_name(reg: InterfaceRegister) -> &'static str;
 
+    /// Architecture dependent registers used to pass the first function
+    /// arguments, in order. Any further arguments are passed on the stack.
+    #[must_use]
+    fn argument_registers() -> &'static [&'static str];
+
     /// Creates a new instance of the architecture
     fn new() -> Self
     where
@@ -273,6 +299,10 @@ impl Architecture<Self> for NoArchitectureOverride {
         unimplemented!("NoArchitectureOverride is not an architecture. Runtime checks failed.");
     }
 
+    fn argument_registers() -> &'static [&'static str] {
+        unimplemented!("NoArchitectureOverride is not an architecture. Runtime checks failed.");
+    }
+
     fn nan_encoding(_ty: OperandType) -> u64 {
         unimplemented!("NoArchitectureOverride is not an architecture. Runtime checks failed.");
     }
@@ -350,6 +380,19 @@ impl<Override: ArchitectureOverride> SupportedArchitecture<Override> {
         }
     }
 
+    /// Returns the cycle cost function for the architecture.
+    pub fn cycle_cost<C>(&self) -> fn(&Instruction<C>, &mut GAState<C>) -> u64
+    where
+        C: Composition<ArchitectureOverride = Override>,
+    {
+        match self {
+            Self::Armv6M(_) => ArmV6M::cycle_cost,
+            Self::Armv7EM(_) => ArmV7EM::cycle_cost,
+            Self::RISCV(_) => RISCV::cycle_cost,
+            Self::Override(_) => C::ArchitectureOverride::cycle_cost,
+        }
+    }
+
     /// Allows the architecture to define behaviour that must happen after an
     /// instruction is executed.
     pub fn register_name_to_number(&self, name: &str) -> Option<u64> {
@@ -423,6 +466,15 @@ impl<Override: ArchitectureOverride> SupportedArchitecture<Override> {
         }
     }
 
+    pub fn argument_registers(&self) -> &'static [&'static str] {
+        match self {
+            Self::Armv6M(_) => <ArmV6M as Architecture<Override>>::argument_registers(),
+            Self::Armv7EM(_) => <ArmV7EM as Architecture<Override>>::argument_registers(),
+            Self::RISCV(_) => <RISCV as Architecture<Override>>::argument_registers(),
+            Self::Override(_) => Override::argument_registers(),
+        }
+    }
+
     #[allow(unused)]
     fn as_riscv(&mut self) -> &mut RISCV {
         match self {
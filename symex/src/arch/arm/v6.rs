@@ -6,7 +6,7 @@ use armv6_m_instruction_parser::Error;
 
 use crate::{
     arch::{ArchError, Architecture, ArchitectureOverride, InterfaceRegister, ParseError, SupportedArchitecture},
-    executor::{hooks::PCHook, state::GAState},
+    executor::{hooks::PCHook, instruction::Instruction, state::GAState},
     smt::{SmtExpr, SmtMap},
     trace,
     Composition,
@@ -41,13 +41,26 @@ impl<Override: ArchitectureOverride> Architecture<Override> for ArmV6M {
     {
     }
 
+    fn cycle_cost<C>(instruction: &Instruction<C>, state: &mut GAState<C>) -> u64
+    where
+        C: Composition<ArchitectureOverride = Override>,
+    {
+        timing::cycle_cost(instruction, state)
+    }
+
     fn get_register_name(reg: InterfaceRegister) -> &'static str {
         match reg {
             InterfaceRegister::ProgramCounter => "PC",
             InterfaceRegister::ReturnAddress => "LR",
+            InterfaceRegister::ReturnValue => "R0",
+            InterfaceRegister::StackPointer => "SP",
         }
     }
 
+    fn argument_registers() -> &'static [&'static str] {
+        &["R0", "R1", "R2", "R3"]
+    }
+
     #[allow(clippy::cast_possible_truncation)]
     fn add_hooks<C: crate::Composition>(&self, cfg: &mut crate::executor::hooks::HookContainer<C>, map: &mut crate::project::dwarf_helper::SubProgramMap) {
         let symbolic_sized = |state: &mut GAState<_>| {
@@ -1002,10 +1002,10 @@ impl ArmV6M {
                         operand1: m,
                         operand2: mask,
                     },
-                    GAOperation::Sror {
+                    GAOperation::RotateRight {
                         destination: dn.clone(),
                         operand: dn.clone(),
-                        shift,
+                        amount: shift,
                     },
                     GAOperation::SetNFlag(dn.clone()),
                     GAOperation::SetZFlag(dn.clone()),
@@ -1371,8 +1371,7 @@ impl ArmV6M {
             instruction_size: instruction_width,
             operations,
             max_cycle: max_cycle_count,
-            // TODO! Add in checks for whether or not this is true
-            memory_access: false,
+            memory_access: super::timing::accesses_memory(&instr.operation),
         }
     }
 }
@@ -1,31 +1,85 @@
 //! Provides cycle counting for the armv6-m instruction set.
 
+use std::ops::RangeInclusive;
+
 use armv6_m_instruction_parser::{instructons::Operation, registers::Register};
 
 use crate::{
-    executor::{instruction::CycleCount, state::GAState},
+    executor::{
+        instruction::{CycleCount, Instruction},
+        state::GAState,
+    },
     smt::{SmtExpr, SmtMap},
 };
 
+/// Cycles lost refilling the two stage pipeline after a taken branch.
+const BRANCH_PENALTY: usize = 1;
+
+/// Wait states added to a memory access outside of the single-cycle I/O port.
+const MEMORY_WAIT_STATES: u64 = 1;
+
+/// The single-cycle I/O port, SIO on the rp2040. Make this configurable later.
+const SINGLE_CYCLE_IO: RangeInclusive<u64> = 0xd000_0000..=0xdfff_ffff;
+
+/// Returns the number of cycles `instruction` took on a Cortex-M0+ core.
+///
+/// The cycles from [`cycle_count_m0plus_core`] include branch penalties,
+/// memory wait states are added here for instructions that access memory
+/// unless the accessed address is in the single-cycle I/O port. The address is
+/// read from the `LastAddr` register, which is cleared afterwards so that a
+/// later access that does not set it can not reuse a stale address.
+pub(crate) fn cycle_cost<C: crate::Composition>(instruction: &Instruction<C>, state: &mut GAState<C>) -> u64 {
+    let cycles = instruction.max_cycle.get_cycle_count(state) as u64;
+    if !instruction.memory_access {
+        return cycles;
+    }
+
+    let address = state.memory.get_register("LastAddr").ok().and_then(|address| address.get_constant());
+    let cleared = state.memory.from_u64(0, 32);
+    if state.memory.set_register("LastAddr", cleared).is_err() {
+        return cycles + MEMORY_WAIT_STATES;
+    }
+    match address {
+        Some(address) if SINGLE_CYCLE_IO.contains(&address) => cycles,
+        _ => cycles + MEMORY_WAIT_STATES,
+    }
+}
+
+/// Returns `true` if `operation` loads from or stores to memory.
+pub(crate) const fn accesses_memory(operation: &Operation) -> bool {
+    matches!(
+        operation,
+        Operation::LDM { .. }
+            | Operation::LDRImm { .. }
+            | Operation::LDRLiteral { .. }
+            | Operation::LDRReg { .. }
+            | Operation::LDRBImm { .. }
+            | Operation::LDRBReg { .. }
+            | Operation::LDRHImm { .. }
+            | Operation::LDRHReg { .. }
+            | Operation::LDRSBReg { .. }
+            | Operation::LDRSH { .. }
+            | Operation::POP { .. }
+            | Operation::PUSH { .. }
+            | Operation::STM { .. }
+            | Operation::STRImm { .. }
+            | Operation::STRReg { .. }
+            | Operation::STRBImm { .. }
+            | Operation::STRBReg { .. }
+            | Operation::STRHImm { .. }
+            | Operation::STRHReg { .. }
+    )
+}
+
+/// Returns the cycles of `operation` on a Cortex-M0+ core, excluding the
+/// memory wait states added by [`cycle_cost`].
 #[allow(clippy::match_same_arms, clippy::too_many_lines)]
 pub(crate) fn cycle_count_m0plus_core<C: crate::Composition>(operation: &Operation) -> CycleCount<C> {
-    // SIO based on the rp2040 make this configurable later
-    let address_max_cycle_function = |state: &mut GAState<C>| {
-        let Some(address) = state.memory.get_register("LastAddr").unwrap().get_constant() else {
-            return 2;
-        };
-
-        if (0xd000_0000..=0xdfff_ffff).contains(&address) {
-            1
-        } else {
-            2
-        }
-    };
     match operation {
         Operation::ADCReg { m: _, n: _, d: _ } => CycleCount::Value(1),
         Operation::ADDImm { imm: _, n: _, d: _ } => CycleCount::Value(1),
         Operation::ADDReg { m: _, n: _, d } => {
-            let max_cycle = if *d == Register::PC { 2 } else { 1 };
+            let max_cycle = if *d == Register::PC { 1 + BRANCH_PENALTY } else { 1 };
             CycleCount::Value(max_cycle)
         }
         Operation::ADDImmSP { d: _, imm: _ } => CycleCount::Value(1),
@@ -37,7 +91,7 @@ pub(crate) fn cycle_count_m0plus_core<C: crate::Composition>(operation: &Operati
         Operation::B { cond: _, imm: _ } => {
             let max_cycle = |state: &mut GAState<C>| {
                 if state.get_has_jumped() {
-                    2
+                    1 + BRANCH_PENALTY
                 } else {
                     1
                 }
@@ -46,9 +100,9 @@ pub(crate) fn cycle_count_m0plus_core<C: crate::Composition>(operation: &Operati
         }
         Operation::BICReg { m: _, dn: _ } => CycleCount::Value(1),
         Operation::BKPT { imm: _ } => CycleCount::Value(0),
-        Operation::BL { imm: _ } => CycleCount::Value(3),
-        Operation::BLXReg { m: _ } => CycleCount::Value(2),
-        Operation::BX { m: _ } => CycleCount::Value(2),
+        Operation::BL { imm: _ } => CycleCount::Value(2 + BRANCH_PENALTY),
+        Operation::BLXReg { m: _ } => CycleCount::Value(1 + BRANCH_PENALTY),
+        Operation::BX { m: _ } => CycleCount::Value(1 + BRANCH_PENALTY),
         Operation::CMNReg { m: _, n: _ } => CycleCount::Value(1),
         Operation::CMPImm { n: _, imm: _ } => CycleCount::Value(1),
         Operation::CMPReg { m: _, n: _ } => CycleCount::Value(1),
@@ -61,29 +115,23 @@ pub(crate) fn cycle_count_m0plus_core<C: crate::Composition>(operation: &Operati
         Operation::DSB { option: _ } => CycleCount::Value(3),
         Operation::EORReg { m: _, dn: _ } => CycleCount::Value(1),
         Operation::ISB { option: _ } => CycleCount::Value(3),
-        Operation::LDM { n: _, reg_list } => {
-            let max_cycle = 1 + reg_list.len();
-            CycleCount::Value(max_cycle)
-        }
-
-        // \/\/\/\/ Can be one depending on core implementation and address \/\/\/\/
-        Operation::LDRImm { imm: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::LDRLiteral { t: _, imm: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::LDRReg { m: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::LDRBImm { imm: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::LDRBReg { m: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::LDRHImm { imm: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::LDRHReg { m: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::LDRSBReg { m: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::LDRSH { m: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        // /\/\/\/\ Can be one depending on core implementation and address /\/\/\/\
+        Operation::LDM { n: _, reg_list } => CycleCount::Value(reg_list.len()),
+        Operation::LDRImm { imm: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::LDRLiteral { t: _, imm: _ } => CycleCount::Value(1),
+        Operation::LDRReg { m: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::LDRBImm { imm: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::LDRBReg { m: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::LDRHImm { imm: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::LDRHReg { m: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::LDRSBReg { m: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::LDRSH { m: _, n: _, t: _ } => CycleCount::Value(1),
         Operation::LSLImm { imm: _, m: _, d: _ } => CycleCount::Value(1),
         Operation::LSLReg { m: _, dn: _ } => CycleCount::Value(1),
         Operation::LSRImm { imm: _, m: _, d: _ } => CycleCount::Value(1),
         Operation::LSRReg { m: _, dn: _ } => CycleCount::Value(1),
         Operation::MOVImm { d: _, imm: _ } => CycleCount::Value(1),
         Operation::MOVReg { m: _, d, set_flags: _ } => {
-            let max_cycle = if *d == Register::PC { 2 } else { 1 };
+            let max_cycle = if *d == Register::PC { 1 + BRANCH_PENALTY } else { 1 };
             CycleCount::Value(max_cycle)
         }
         Operation::MRS { d: _, sysm: _ } => CycleCount::Value(3),
@@ -97,10 +145,11 @@ pub(crate) fn cycle_count_m0plus_core<C: crate::Composition>(operation: &Operati
         Operation::NOP => CycleCount::Value(1),
         Operation::ORRReg { m: _, dn: _ } => CycleCount::Value(1),
         Operation::POP { reg_list } => {
-            let max_cycle = if reg_list.contains(&Register::PC) { 3 } else { 1 } + reg_list.len();
+            // Loading the PC stalls the pipeline for one cycle before it is refilled.
+            let max_cycle = if reg_list.contains(&Register::PC) { 1 + BRANCH_PENALTY } else { 0 } + reg_list.len();
             CycleCount::Value(max_cycle)
         }
-        Operation::PUSH { reg_list } => CycleCount::Value(1 + reg_list.len()),
+        Operation::PUSH { reg_list } => CycleCount::Value(reg_list.len()),
         Operation::REV { m: _, d: _ } => CycleCount::Value(1),
         Operation::REV16 { m: _, d: _ } => CycleCount::Value(1),
         Operation::REVSH { m: _, d: _ } => CycleCount::Value(1),
@@ -108,16 +157,14 @@ pub(crate) fn cycle_count_m0plus_core<C: crate::Composition>(operation: &Operati
         Operation::RSBImm { n: _, d: _ } => CycleCount::Value(1),
         Operation::SBCReg { m: _, dn: _ } => CycleCount::Value(1),
         Operation::SEV => CycleCount::Value(1),
-        Operation::STM { n: _, reg_list } => CycleCount::Value(1 + reg_list.len()),
+        Operation::STM { n: _, reg_list } => CycleCount::Value(reg_list.len()),
 
-        // \/\/\/\/ Can be one depending on core implementation and address \/\/\/\/
-        Operation::STRImm { imm: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::STRReg { m: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::STRBImm { imm: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::STRBReg { m: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::STRHImm { imm: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        Operation::STRHReg { m: _, n: _, t: _ } => CycleCount::Function(address_max_cycle_function),
-        // /\/\/\/\ Can be one depending on core implementation and address /\/\/\/\
+        Operation::STRImm { imm: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::STRReg { m: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::STRBImm { imm: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::STRBReg { m: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::STRHImm { imm: _, n: _, t: _ } => CycleCount::Value(1),
+        Operation::STRHReg { m: _, n: _, t: _ } => CycleCount::Value(1),
         Operation::SUBImm { imm: _, n: _, d: _ } => CycleCount::Value(1),
         Operation::SUBReg { m: _, n: _, d: _ } => CycleCount::Value(1),
         Operation::SUBImmSP { imm: _ } => CycleCount::Value(1),
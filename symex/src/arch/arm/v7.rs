@@ -397,9 +397,15 @@ impl<Override: ArchitectureOverride> Architecture<Override> for ArmV7EM {
         match reg {
             InterfaceRegister::ProgramCounter => "PC",
             InterfaceRegister::ReturnAddress => "LR",
+            InterfaceRegister::ReturnValue => "R0",
+            InterfaceRegister::StackPointer => "SP",
         }
     }
 
+    fn argument_registers() -> &'static [&'static str] {
+        &["R0", "R1", "R2", "R3"]
+    }
+
     #[allow(clippy::cast_possible_truncation)]
     fn add_hooks<C: crate::Composition>(&self, cfg: &mut HookContainer<C>, map: &mut SubProgramMap) {
         trace!("Adding armv7em hooks");
@@ -1950,19 +1950,11 @@ impl Convert for (usize, V7Operation) {
             V7Operation::Qsub8(_) => todo!("Need to figure out how to do saturating operations"),
             V7Operation::Rbit(rbit) => {
                 consume!((rd.local_into(),rm.local_into()) from rbit);
-                pseudo!([
-                    rd:u32;
-                    rm:u32;
-                    let result = 0u32;
-                    let source = rm;
-                    for _ignored in 0..32u32 {
-                        let val = source<0>;
-                        source >>= 1u32;
-                        result <<= 1u32;
-                        result |= Resize(val,u32);
-                    }
-                    rd = result;
-                ])
+                vec![Operation::BitReverse {
+                    destination: rd,
+                    operand: rm,
+                    bits: 32,
+                }]
             }
             V7Operation::Rev(rev) => {
                 consume!((rd.local_into(),rm.local_into()) from rev);
@@ -2020,10 +2012,10 @@ impl Convert for (usize, V7Operation) {
             V7Operation::RorImmediate(ror) => {
                 consume!((s,rd.local_into(), rm.local_into(),imm) from ror);
                 let shift_n = imm.local_into();
-                let mut ret = vec![Operation::Sror {
+                let mut ret = vec![Operation::RotateRight {
                     destination: rd.clone(),
                     operand: rm.clone(),
-                    shift: shift_n.clone(),
+                    amount: shift_n.clone(),
                 }];
                 if let Some(true) = s {
                     ret.extend([Operation::SetZFlag(rd.clone()), Operation::SetNFlag(rd.clone()), Operation::SetCFlagRor(rd.clone())]);
@@ -2048,10 +2040,10 @@ impl Convert for (usize, V7Operation) {
                         operand1: rm.clone(),
                         operand2: mask,
                     },
-                    Operation::Sror {
+                    Operation::RotateRight {
                         destination: rd.clone(),
                         operand: rn.clone(),
-                        shift: shift_n.clone(),
+                        amount: shift_n.clone(),
                     },
                 ];
                 if s {
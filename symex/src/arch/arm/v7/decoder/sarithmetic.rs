@@ -138,7 +138,7 @@ impl Decode for SmlalSelective {
             }
 
 
-            let mul = Resize(operand2,i128) * Resize(operand2,i128);
+            let mul = Resize(operand1,i128) * Resize(operand2,i128);
             let result = mul + Resize(add,i128);
             rdhi = Resize(result<63:32>,u32);
             rdlo = Resize(result<31:0>,u32);
@@ -18,13 +18,6 @@ impl Decode for Udiv {
             rm:u32 = rm;
             rd:u32 = rd;
             rn:u32 = rn;
-            let zero:u32 = 0.local_into();
-            Ite(rm == zero,
-                {
-                    Abort("UsageFault cannot divide by zero.");
-                },
-                {}
-            );
             let result = rn/rm;
             rd = result;
         ])
@@ -179,7 +179,7 @@ fn test_adc_no_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R0 = 1;
@@ -216,7 +216,7 @@ fn test_adc_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R0 = 1;
@@ -350,7 +350,7 @@ fn test_adc_imm_no_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 2;
@@ -383,7 +383,7 @@ fn test_adc_immediate_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R0 = 1;
@@ -442,7 +442,7 @@ fn test_add_no_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R0 = 1;
@@ -479,7 +479,7 @@ fn test_add_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R0 = 1;
@@ -613,7 +613,7 @@ fn test_add_imm_no_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 2;
@@ -641,7 +641,7 @@ fn test_add_immediate_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R0 = 1;
@@ -706,7 +706,7 @@ fn test_add_sp_immediate() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 8;
@@ -778,7 +778,7 @@ fn test_add_sp_reg() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 16;
@@ -867,7 +867,7 @@ fn test_adr() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 16;
@@ -932,7 +932,7 @@ fn test_and_no_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R0 = 1;
@@ -969,7 +969,7 @@ fn test_and_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R0 = 1;
@@ -1136,7 +1136,7 @@ fn test_and_imm_no_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 2;
@@ -1170,7 +1170,7 @@ fn test_and_immediate_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x0000_0002;
@@ -1300,7 +1300,7 @@ fn test_asr_immediate() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x8000_0000;
@@ -1334,7 +1334,7 @@ fn test_asr_immediate_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x8000_0001;
@@ -1458,7 +1458,7 @@ fn test_asr() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x8000_0000;
@@ -1493,7 +1493,7 @@ fn test_asr_set_flag() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x8000_0001;
@@ -1621,7 +1621,7 @@ fn test_b() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -1650,7 +1650,7 @@ fn test_b_conditional() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -1743,7 +1743,7 @@ fn test_bx() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -1773,7 +1773,7 @@ fn test_bfc() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -1802,7 +1802,7 @@ fn test_bfi() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -1832,7 +1832,7 @@ fn test_bfi_panic() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -1861,7 +1861,7 @@ fn test_bic_imm() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -1898,7 +1898,7 @@ fn test_bic_imm_set_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -1965,7 +1965,7 @@ fn test_bic_reg() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -2002,7 +2002,7 @@ fn test_bic_reg_set_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register PC = 0;
@@ -2069,7 +2069,7 @@ fn test_bl() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     let v6 = vec![
         GAOperation::Move {
@@ -2152,7 +2152,7 @@ fn test_cmp_imm() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -2210,7 +2210,7 @@ fn test_ldr_imm() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -2368,7 +2368,7 @@ fn test_ldr_literal() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -2397,7 +2397,7 @@ fn test_ldr_register() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x4;
@@ -2494,7 +2494,7 @@ fn test_ldrh_imm() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -2647,7 +2647,7 @@ fn test_ldrb_imm() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -2830,7 +2830,7 @@ fn test_lsl_immediate() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -2899,7 +2899,7 @@ fn test_lsr_immediate() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 24;
@@ -2968,7 +2968,7 @@ fn test_mov_imm_no_set_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -3006,7 +3006,7 @@ fn test_mov_imm_set_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -3074,7 +3074,7 @@ fn test_mov_reg_no_set_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -3107,7 +3107,7 @@ fn test_mov_reg_set_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -3164,7 +3164,7 @@ fn test_mul() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -3234,7 +3234,7 @@ fn test_pop() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -3280,7 +3280,7 @@ fn test_push() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -3324,7 +3324,7 @@ fn test_rsb() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x3;
@@ -3389,7 +3389,7 @@ fn test_strb_imm() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x8000_1234;
@@ -3517,7 +3517,7 @@ fn test_strh_imm() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x8000_1234;
@@ -3657,7 +3657,7 @@ fn test_str_imm() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x8000_1234;
@@ -3793,7 +3793,7 @@ fn test_sub_imm_no_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register SP = 0x104;
@@ -3899,7 +3899,7 @@ fn test_sub_imm_set_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register SP = 0x104;
@@ -3973,7 +3973,7 @@ fn test_sub_reg_no_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register SP = 0x104;
@@ -4086,7 +4086,7 @@ fn test_sub_reg_set_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register SP = 0x104;
@@ -4164,7 +4164,7 @@ fn test_sub_sp_imm_no_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register SP = 0x104;
@@ -4268,7 +4268,7 @@ fn test_sub_sp_imm_set_flags() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register SP = 0x104;
@@ -4330,7 +4330,7 @@ fn test_sub_uxth() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x123;
@@ -4392,7 +4392,7 @@ fn test_tb() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0x123;
@@ -4449,7 +4449,7 @@ fn test_bfi_2() {
     let mut vm = setup_test_vm();
     let project = vm.project.clone();
 
-    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+    let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
     initiate!(executor {
         register R1 = 0b11_0011;
@@ -197,9 +197,15 @@ impl<Override: ArchitectureOverride> Architecture<Override> for RISCV {
         match reg {
             InterfaceRegister::ProgramCounter => "PC",
             InterfaceRegister::ReturnAddress => "RA",
+            InterfaceRegister::ReturnValue => "A0",
+            InterfaceRegister::StackPointer => "SP",
         }
     }
 
+    fn argument_registers() -> &'static [&'static str] {
+        &["A0", "A1", "A2", "A3", "A4", "A5", "A6", "A7"]
+    }
+
     fn new() -> Self
     where
         Self: Sized,
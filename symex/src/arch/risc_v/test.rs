@@ -109,7 +109,7 @@ fn setup_test_vm() -> VM<DefaultCompositionNoLogger> {
 
 fn translate_instruction(instruction_bytes: [u8; 4]) -> Instruction<DefaultCompositionNoLogger> {
     let mut vm = setup_test_vm();
-    let mut state = vm.paths.get_path().unwrap().state;
+    let mut state = vm.paths.get_path().unwrap().state.materialize();
 
     RISCV::translate(&instruction_bytes, &mut state).expect("Failed to translate instruction")
 }
@@ -117,7 +117,7 @@ fn translate_instruction(instruction_bytes: [u8; 4]) -> Instruction<DefaultCompo
 fn init_executor(vm: &mut VM<DefaultCompositionNoLogger>) -> GAExecutor<'_, DefaultCompositionNoLogger> {
     let project = vm.project.clone();
 
-    let state = vm.paths.get_path().unwrap().state;
+    let state = vm.paths.get_path().unwrap().state.materialize();
 
     GAExecutor::from_state(state, vm, project)
 }
@@ -28,6 +28,7 @@ impl Composition for DefaultComposition {
     type Logger = SimplePathLogger;
     type Memory = BitwuzlaMemory<()>;
     type MemoryFilter = MemoryBucketingFilter<Self>;
+    type PathPayload = ();
     type PathSelector = DFSPathSelection<Self>;
     type ProgramMemory = std::sync::Arc<Project<Self::SMT>>;
     type SMT = Bitwuzla;
@@ -45,6 +46,7 @@ impl Composition for DefaultCompositionNoLogger {
     type Logger = NoLogger;
     type Memory = BitwuzlaMemory<()>;
     type MemoryFilter = MemoryBucketingFilter<Self>;
+    type PathPayload = ();
     type PathSelector = DFSPathSelection<Self>;
     type ProgramMemory = std::sync::Arc<Project<Self::SMT>>;
     type SMT = Bitwuzla;
@@ -63,6 +65,7 @@ impl<State: UserStateContainer> Composition for UserState<State> {
     type Logger = SimplePathLogger;
     type Memory = BitwuzlaMemory<State>;
     type MemoryFilter = MemoryBucketingFilter<Self>;
+    type PathPayload = ();
     type PathSelector = DFSPathSelection<Self>;
     type ProgramMemory = std::sync::Arc<Project<Self::SMT>>;
     type SMT = Bitwuzla;
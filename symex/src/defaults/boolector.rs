@@ -27,6 +27,7 @@ impl Composition for DefaultComposition {
     type Logger = SimplePathLogger;
     type Memory = BoolectorMemory<()>;
     type MemoryFilter = MemoryBucketingFilter<Self>;
+    type PathPayload = ();
     type PathSelector = DFSPathSelection<Self>;
     type ProgramMemory = Arc<Project<Boolector>>;
     type SMT = Boolector;
@@ -46,6 +47,7 @@ impl Composition for DefaultCompositionNoLogger {
     type Logger = NoLogger;
     type Memory = BoolectorMemory<()>;
     type MemoryFilter = MemoryBucketingFilter<Self>;
+    type PathPayload = ();
     type PathSelector = DFSPathSelection<Self>;
     type ProgramMemory = Arc<Project<Boolector>>;
     type SMT = Boolector;
@@ -64,6 +66,7 @@ impl<State: UserStateContainer> Composition for UserState<State> {
     type Logger = SimplePathLogger;
     type Memory = BoolectorMemory<State>;
     type MemoryFilter = MemoryBucketingFilter<Self>;
+    type PathPayload = ();
     type PathSelector = DFSPathSelection<Self>;
     type ProgramMemory = Arc<Project<Boolector>>;
     type SMT = Boolector;
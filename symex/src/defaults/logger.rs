@@ -7,7 +7,7 @@ use crate::{
     executor::{state::GAState, PathResult},
     logging::{Logger, Region, RegionMetaData},
     manager::SymexArbiter,
-    project::dwarf_helper::{CallStack, SubProgram, SubProgramMap},
+    project::dwarf_helper::{demangle, CallStack, SubProgram, SubProgramMap},
     Composition,
 };
 
@@ -22,6 +22,7 @@ pub struct PathLog {
     visited: Vec<String>,
     backtrace: Vec<(String, String)>,
     function_arguments: Vec<(String, String)>,
+    call_stack: Vec<(String, u64)>,
     log_idx: usize,
 }
 
@@ -42,6 +43,13 @@ pub struct SimplePathLogger {
     backtrace: Vec<(String, String)>,
     function_arguments: Vec<(String, String)>,
     pc: u64,
+    /// The source location of `pc`, or the raw address if no line info exists.
+    location: String,
+    /// Values of the named inputs that lead to a successful path.
+    inputs: Vec<(String, String)>,
+    /// The function and address of every frame of a failing path, innermost
+    /// first.
+    call_stack: Vec<(String, u64)>,
 }
 
 impl PathLog {
@@ -55,6 +63,7 @@ impl PathLog {
             visited: Vec::new(),
             backtrace: Vec::new(),
             function_arguments: Vec::new(),
+            call_stack: Vec::new(),
             log_idx,
         }
     }
@@ -105,6 +114,10 @@ impl PathLogger<'_> {
         self.path.execution_time = time;
     }
 
+    fn call_stack(&mut self, call_stack: Vec<(String, u64)>) {
+        self.path.call_stack = call_stack;
+    }
+
     fn visit<C: Composition>(&mut self, func: String, _state: &mut GAState<C>) {
         if Some(&func) != self.path.visited.last() {
             // let bt = state.get_back_trace(&[]);
@@ -134,6 +147,37 @@ impl PathLogger<'_> {
     }
 }
 
+/// Names the frames of the call stack of `state`, innermost first.
+///
+/// The innermost frame is at the last executed instruction and every other
+/// frame is at the return address of the frame it called.
+fn name_call_stack<C: Composition>(regions: &SubProgramMap, state: &GAState<C>) -> Vec<(String, u64)> {
+    let mut pc = state.last_pc;
+    state
+        .call_stack()
+        .iter()
+        .rev()
+        .map(|frame| {
+            let name = regions
+                .get_by_entry(frame.function)
+                .map_or_else(|| format!("{:#x}", frame.function), SubProgram::demangled_name);
+            let address = pc;
+            pc = frame.return_address;
+            (name, address)
+        })
+        .collect()
+}
+
+/// Formats a call stack as one numbered frame per line.
+fn format_call_stack(call_stack: &[(String, u64)]) -> String {
+    call_stack
+        .iter()
+        .enumerate()
+        .map(|(idx, (name, pc))| format!("#{idx} {name} @ {pc:#x}"))
+        .collect::<Vec<_>>()
+        .join("\r\n\t")
+}
+
 impl SimplePathLogger {
     pub fn from_sub_programs(state: &SubProgramMap) -> Self {
         Self {
@@ -147,8 +191,23 @@ impl SimplePathLogger {
             regions: state.clone(),
             current_region: None,
             pc: 0,
+            location: String::new(),
+            inputs: Vec::new(),
             backtrace: Vec::new(),
             function_arguments: Vec::new(),
+            call_stack: Vec::new(),
+        }
+    }
+
+    /// Annotates `message` with the current source location and enclosing
+    /// function unless it already refers to the current program counter.
+    fn locate(&self, message: String) -> String {
+        if self.location.is_empty() || message.contains(&format!("{:#x}", self.pc)) {
+            return message;
+        }
+        match self.regions.symbolize(self.pc) {
+            Some(symbol) => format!("{message} @ {} <{symbol}>", self.location),
+            None => format!("{message} @ {}", self.location),
         }
     }
 }
@@ -170,16 +229,17 @@ impl Logger for SimplePathLogger {
         debug!("Matching subprograms {subprogram:?}");
         let subprogram = subprogram.first().cloned();
         // println!("Logging in {subprogram:?}");
-        self.statements.push((subprogram, format!("[{}]: {}", "WARN".yellow(), warning.to_string())));
+        self.statements.push((subprogram, format!("[{}]: {}", "WARN".yellow(), self.locate(warning.to_string()))));
     }
 
     fn error<T: ToString>(&mut self, warning: T) {
         let subprogram = self.regions.in_bounds(self.pc).first().cloned();
-        self.statements.push((subprogram, format!("[{}]: {}", "ERROR".red(), warning.to_string())));
+        self.statements.push((subprogram, format!("[{}]: {}", "ERROR".red(), self.locate(warning.to_string()))));
     }
 
-    fn update_delimiter<T: Into<Self::RegionDelimiter>, C: Composition>(&mut self, region: T, _state: &mut GAState<C>) {
+    fn update_delimiter<T: Into<Self::RegionDelimiter>, C: Composition>(&mut self, region: T, state: &mut GAState<C>) {
         self.pc = region.into();
+        self.location = state.debug_string_address(self.pc);
         let region = self.regions.get_by_address(&self.pc).cloned();
 
         if region
@@ -187,17 +247,20 @@ impl Logger for SimplePathLogger {
             .is_some_and(|el| self.current_region.as_ref().is_some_and(|other| other != el) || self.current_region.is_none())
         {
             let region = unsafe { region.as_ref().unwrap_unchecked() };
-            self.visited.push(region.name.to_string());
+            self.visited.push(region.demangled_name());
         }
         self.current_region = region;
     }
 
     fn record_path_result<C: crate::Composition>(&mut self, path_result: PathResult<C>) {
+        if let PathResult::Success(_, inputs, _) = &path_result {
+            self.inputs = inputs.iter().map(|(name, value)| (name.clone(), format!("{value:#x}"))).collect();
+        }
         let res = format!("Result: {}", match path_result {
             PathResult::Suppress => "Path suppressed".yellow(),
-            PathResult::Success(Some(expression)) => format!("Success ({expression:?})").green(),
-            PathResult::Success(None) => "Success".green(),
-            PathResult::Failure(cause) => format!("Failure {cause}",).red(),
+            PathResult::Success(Some(expression), ..) => format!("Success ({expression:?})").green(),
+            PathResult::Success(None, ..) => "Success".green(),
+            PathResult::Failure(cause) => format!("Failure {}", self.locate(cause.to_string())).red(),
             PathResult::AssumptionUnsat => "Unsatisfiable".red(),
         });
         self.result = res;
@@ -246,8 +309,11 @@ impl Logger for SimplePathLogger {
             regions: state.get_symbol_map().clone(),
             current_region: None,
             pc: 0,
+            location: String::new(),
+            inputs: Vec::new(),
             backtrace: Vec::new(),
             function_arguments: Vec::new(),
+            call_stack: Vec::new(),
         }
     }
 
@@ -271,6 +337,10 @@ impl Logger for SimplePathLogger {
             .map(|var| (var.name.clone().unwrap_or_else(|| "NO NAME".to_string()), var.value.to_string()))
             .collect();
     }
+
+    fn record_call_stack<C: Composition>(&mut self, state: &GAState<C>) {
+        self.call_stack = name_call_stack(&self.regions, state);
+    }
 }
 
 impl Logger for SimpleLogger {
@@ -285,7 +355,7 @@ impl Logger for SimpleLogger {
         self.pc = region.into();
         self.current_region = self.regions.get_by_address(&self.pc).cloned();
         if let Some(region) = &self.current_region {
-            let name = region.name.clone();
+            let name = region.demangled_name();
             self.path_logger().visit(name, state);
         }
     }
@@ -297,8 +367,8 @@ impl Logger for SimpleLogger {
     fn record_path_result<C: crate::Composition>(&mut self, path_result: crate::executor::PathResult<C>) {
         let res = format!("Result: {}", match path_result {
             PathResult::Suppress => "Path suppressed".yellow(),
-            PathResult::Success(Some(expression)) => format!("Success ({expression:?})").green(),
-            PathResult::Success(None) => "Success".green(),
+            PathResult::Success(Some(expression), ..) => format!("Success ({expression:?})").green(),
+            PathResult::Success(None, ..) => "Success".green(),
             PathResult::Failure(cause) => format!("Failure {cause}",).red(),
             PathResult::AssumptionUnsat => "Unsatisfiable".red(),
         });
@@ -360,6 +430,11 @@ impl Logger for SimpleLogger {
     fn record_backtrace(&mut self, bt: Option<CallStack>) {
         self.path_logger().record_backtrace(bt);
     }
+
+    fn record_call_stack<C: Composition>(&mut self, state: &GAState<C>) {
+        let call_stack = name_call_stack(&self.regions, state);
+        self.path_logger().call_stack(call_stack);
+    }
 }
 
 impl SimpleLogger {
@@ -391,7 +466,7 @@ impl ToString for SubProgram {
             Some((name, line)) => format!("<({name}@{line})>"),
             None => String::new(),
         };
-        format!("Subprogram({}) {file}", self.name)
+        format!("Subprogram({}) {file}", self.demangled_name())
     }
 }
 impl Region for SubProgram {
@@ -438,8 +513,11 @@ impl Display for SimplePathLogger {
             regions: _regions,
             current_region: _,
             pc: _,
+            location: _,
+            inputs,
             backtrace: _,
             function_arguments,
+            call_stack,
         } = self;
 
         write!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ PATH {log_idx} ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\r\n")?;
@@ -454,7 +532,7 @@ impl Display for SimplePathLogger {
                     call_file: _,
                 }) = program
                 {
-                    write!(f, "<{name} ({file})> -> ")?;
+                    write!(f, "<{} ({file})> -> ", demangle(name).as_deref().unwrap_or(name))?;
                 }
                 if let Some(SubProgram {
                     name,
@@ -463,7 +541,7 @@ impl Display for SimplePathLogger {
                     call_file: _,
                 }) = program
                 {
-                    write!(f, "<{name}> -> ")?;
+                    write!(f, "<{}> -> ", demangle(name).as_deref().unwrap_or(name))?;
                 }
 
                 write!(f, "{statement}\r\n")?;
@@ -498,6 +576,16 @@ impl Display for SimplePathLogger {
                 .collect::<Vec<String>>()
                 .join("\r\n\t")
         )?;
+        if !inputs.is_empty() {
+            write!(
+                f,
+                "Inputs : \r\n\t{}\r\n",
+                inputs.iter().map(|(name, val)| format!("{name} = {val}")).collect::<Vec<String>>().join("\r\n\t")
+            )?;
+        }
+        if !call_stack.is_empty() {
+            write!(f, "Call stack : \r\n\t{}\r\n", format_call_stack(call_stack))?;
+        }
         write!(f, "Final state : \r\n\t{final_state}\r\n")?;
         write!(f, "Execution took : {execution_time}\r\n")?;
 
@@ -518,6 +606,7 @@ impl Display for PathLog {
             log_idx,
             backtrace,
             function_arguments,
+            call_stack,
         } = self;
 
         write!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ PATH {log_idx} ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\r\n")?;
@@ -532,7 +621,7 @@ impl Display for PathLog {
                     call_file: _,
                 }) = program
                 {
-                    write!(f, "<{name} ({file})> -> ")?;
+                    write!(f, "<{} ({file})> -> ", demangle(name).as_deref().unwrap_or(name))?;
                 }
                 if let Some(SubProgram {
                     name,
@@ -541,7 +630,7 @@ impl Display for PathLog {
                     call_file: _,
                 }) = program
                 {
-                    write!(f, "<{name}> -> ")?;
+                    write!(f, "<{}> -> ", demangle(name).as_deref().unwrap_or(name))?;
                 }
 
                 write!(f, "{statement}\r\n")?;
@@ -575,6 +664,9 @@ impl Display for PathLog {
                 .collect::<Vec<String>>()
                 .join("\r\n\t")
         )?;
+        if !call_stack.is_empty() {
+            write!(f, "Call stack : \r\n\t{}\r\n", format_call_stack(call_stack))?;
+        }
         write!(f, "Final state : \r\n\t{final_state}\r\n")?;
         write!(f, "Execution took : {execution_time}\r\n")?;
 
@@ -28,6 +28,7 @@ impl Composition for DefaultComposition {
     type ArchitectureOverride = NoArchitectureOverride;
     type Logger = SimplePathLogger;
     type Memory = Z3Array<()>;
+    type PathPayload = ();
     type PathSelector = DFSPathSelection<Self>;
     type ProgramMemory = &'static Project;
     type SMT = Z3Solver;
@@ -48,6 +49,7 @@ impl Composition for DefaultCompositionNoLogger {
     type ArchitectureOverride = NoArchitectureOverride;
     type Logger = NoLogger;
     type Memory = Z3Array<()>;
+    type PathPayload = ();
     type PathSelector = DFSPathSelection<Self>;
     type ProgramMemory = &'static Project;
     type SMT = Z3Solver;
@@ -69,6 +71,7 @@ impl<State: UserStateContainer> Composition for UserState<State> {
     type ArchitectureOverride = NoArchitectureOverride;
     type Logger = SimplePathLogger;
     type Memory = Z3Array<State>;
+    type PathPayload = ();
     type PathSelector = DFSPathSelection<Self>;
     type ProgramMemory = &'static Project;
     type SMT = Z3Solver;
@@ -0,0 +1,111 @@
+//! Tracks which instructions have been executed across all paths.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashSet},
+    fmt::Display,
+    rc::Rc,
+};
+
+/// A set of executed program counters shared between all states created from
+/// the same [`SymexArbiter`](crate::manager::SymexArbiter).
+#[derive(Clone, Debug, Default)]
+pub struct Coverage(Rc<RefCell<HashSet<u64>>>);
+
+impl Coverage {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the instruction at `pc` as executed.
+    #[inline]
+    pub fn record(&self, pc: u64) {
+        self.0.borrow_mut().insert(pc);
+    }
+
+    #[must_use]
+    pub fn contains(&self, pc: u64) -> bool {
+        self.0.borrow().contains(&pc)
+    }
+
+    /// Builds a report over the instruction addresses in `instructions`.
+    ///
+    /// Executed addresses that are not part of `instructions` are ignored.
+    #[must_use]
+    pub fn report<I: IntoIterator<Item = u64>>(&self, instructions: I) -> CoverageReport {
+        let executed = self.0.borrow();
+        let (covered, uncovered) = instructions.into_iter().collect::<BTreeSet<u64>>().into_iter().partition(|pc| executed.contains(pc));
+        CoverageReport { covered, uncovered }
+    }
+}
+
+/// Covered and uncovered instruction addresses, in ascending order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    covered: Vec<u64>,
+    uncovered: Vec<u64>,
+}
+
+impl CoverageReport {
+    #[must_use]
+    pub fn covered(&self) -> &[u64] {
+        &self.covered
+    }
+
+    #[must_use]
+    pub fn uncovered(&self) -> &[u64] {
+        &self.uncovered
+    }
+
+    /// Returns the total number of instructions in the program.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.covered.len() + self.uncovered.len()
+    }
+
+    /// Returns the percentage of instructions that were executed.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percentage(&self) -> f64 {
+        if self.total() == 0 {
+            return 0.;
+        }
+        100. * self.covered.len() as f64 / self.total() as f64
+    }
+}
+
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} instructions ({:.2}%)", self.covered.len(), self.total(), self.percentage())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Coverage;
+
+    #[test]
+    fn test_coverage_report() {
+        let coverage = Coverage::new();
+        let shared = coverage.clone();
+        shared.record(0x1002);
+        shared.record(0x1006);
+        // Not part of the program, should not be reported.
+        shared.record(0x2000);
+
+        let report = coverage.report([0x1006, 0x1000, 0x1002, 0x1004]);
+        assert_eq!(report.covered(), &[0x1002, 0x1006]);
+        assert_eq!(report.uncovered(), &[0x1000, 0x1004]);
+        assert_eq!(report.total(), 4);
+        assert!((report.percentage() - 50.).abs() < f64::EPSILON);
+        assert_eq!(report.to_string(), "2/4 instructions (50.00%)");
+    }
+
+    #[test]
+    fn test_empty_coverage_report() {
+        let report = Coverage::new().report([]);
+        assert_eq!(report.total(), 0);
+        assert!(report.percentage().abs() < f64::EPSILON);
+    }
+}
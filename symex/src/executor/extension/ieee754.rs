@@ -21,8 +21,27 @@ pub struct FpState {
 impl FpState {
     /// Creates a new instance of FP state.
     pub const fn new() -> Self {
-        Self {
-            rounding_mode: RoundingMode::TiesTowardZero,
+        Self::with_rounding_mode(RoundingMode::TiesTowardZero)
+    }
+
+    /// Creates a new instance of FP state where operations that do not
+    /// request a rounding mode use `rounding_mode`.
+    pub const fn with_rounding_mode(rounding_mode: RoundingMode) -> Self {
+        Self { rounding_mode }
+    }
+
+    /// Sets the rounding mode used by operations that do not request one.
+    pub fn set_rounding_mode(&mut self, rounding_mode: RoundingMode) -> crate::Result<()> {
+        Self::validate(&rounding_mode)?;
+        self.rounding_mode = rounding_mode;
+        Ok(())
+    }
+
+    /// Returns an error if `rounding_mode` is not supported by the solvers.
+    pub fn validate(rounding_mode: &RoundingMode) -> crate::Result<()> {
+        match rounding_mode {
+            RoundingMode::Exact => Err(GAError::InvalidRoundingMode.into()),
+            _ => Ok(()),
         }
     }
 }
@@ -53,7 +72,7 @@ where
                     ResultOrHook::Hooks(_) => todo!("How do we handle multiple hooks."),
                     ResultOrHook::Result(Ok(val)) => val,
                     ResultOrHook::Result(Err(r)) => return ResultOrTerminate::Result(Err(r).context("While looking up an address for floating point arithmetic")),
-                    ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(format!("{e} @ {}", self.state.debug_string())),
+                    ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(e.at(&self.state.debug_string())),
                 };
                 let res = read.to_fp(operand.ty, destination_ty, rm, true);
                 ResultOrTerminate::Result(res)
@@ -120,7 +139,7 @@ where
                 match self.state.writer().write_memory(&address, value.clone()) {
                     ResultOrHook::Hook(hook) => ResultOrTerminate::Result(hook(&mut self.state, address, value).context("While writing a floating point value to an address")),
                     ResultOrHook::Hooks(_) => todo!(),
-                    ResultOrHook::EndFailure(e) => ResultOrTerminate::Failure(format!("{e} @ {}", self.state.debug_string())),
+                    ResultOrHook::EndFailure(e) => ResultOrTerminate::Failure(e.at(&self.state.debug_string())),
                     ResultOrHook::Result(Ok(v)) => ResultOrTerminate::Result(Ok(v)),
                     ResultOrHook::Result(Err(e)) => {
                         ResultOrTerminate::Result(Err(e).context(format!("While writing a floating point value to an address @ {}", self.state.debug_string())))
@@ -147,11 +166,17 @@ where
             // it only affects an intermediate value. A write to program memory however, is not
             // explicitly prohibited.
             OperandStorage::Immediate { value: _, ty: _ } => ResultOrTerminate::Result(Err(crate::MemoryError::TriedToAssignToImmediateField).context("FP store")),
-            OperandStorage::CoreRegister { id, ty: _, signed } => {
+            OperandStorage::CoreRegister { id, ty, signed } => {
                 let value = match value.to_bv(rm, signed) {
                     Ok(val) => val,
                     Err(e) => return ResultOrTerminate::Result(Err(e).context("While writing a fp value to a core-register")),
                 };
+                // Integers rounded from narrow formats are narrower than the register.
+                let value = match ty {
+                    OperandType::Integral { size, signed: true } if size > value.size() => value.sign_ext(size),
+                    OperandType::Integral { size, signed: false } if size > value.size() => value.zero_ext(size),
+                    _ => value,
+                };
                 self.set_operand_value(&general_assembly::operand::Operand::Register(id), value, logger)
             }
             OperandStorage::CoreOperand { operand, ty: _, signed } => {
@@ -173,6 +198,9 @@ where
     pub fn execute_ieee754(&mut self, op: Operations, logger: &C::Logger) -> ResultOrTerminate<()> {
         match op {
             Operations::RoundToInt { source, destination, rounding } => {
+                if let Some(rounding) = &rounding {
+                    extract!(Ok(FpState::validate(rounding)));
+                }
                 let value = extract!(Ok(self.get_fp_operand_value(source.clone(), source.ty, self.rm(None), logger)));
                 crate::debug!("RoundToInt operand value {:?}", value);
                 let value = match value.round_to_integral(self.rm(rounding.clone())) {
@@ -319,7 +347,19 @@ where
             }
             Operations::TotalOrder { lhs: _, rhs: _, abs: _ } => todo!(),
             Operations::Convert { source, destination, rounding } => {
+                if let Some(rounding) = &rounding {
+                    extract!(Ok(FpState::validate(rounding)));
+                }
                 let source_val = extract!(Ok(self.get_fp_operand_value(source, destination.ty.clone(), self.rm(rounding.clone()), logger)));
+                // Values read from floating point storage keep their own format.
+                let source_val = match (source_val.ty(), &destination.ty) {
+                    (OperandType::Integral { .. }, _) | (_, OperandType::Integral { .. }) => source_val,
+                    (ty, destination_ty) if ty == *destination_ty => source_val,
+                    (_, destination_ty) => match source_val.convert(destination_ty.clone(), self.rm(rounding.clone())) {
+                        Ok(val) => val,
+                        Err(e) => return ResultOrTerminate::Result(Err(e).context("While converting between floating point formats")),
+                    },
+                };
                 self.set_fp_operand_value(destination, source_val, logger, self.rm(rounding))
             }
         }
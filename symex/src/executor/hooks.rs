@@ -4,12 +4,13 @@ use anyhow::Context;
 use general_assembly::extension::ieee754::{OperandType, RoundingMode};
 use hashbrown::HashMap;
 
-use super::{state::GAState, ResultOrTerminate};
+use super::{state::GAState, FailureReason, ResultOrTerminate};
 use crate::{
     arch::InterfaceRegister,
-    executor::memory_interface::{Reader, Writer},
+    executor::memory_interface::{Access, Permissions, Reader, Writer},
+    memory::BITS_IN_BYTE,
     project::dwarf_helper::SubProgramMap,
-    smt::{SmtMap, SmtSolver},
+    smt::{SmtExpr, SmtMap, SmtSolver, SolverError},
     trace,
     Composition,
     Result,
@@ -21,7 +22,24 @@ pub enum PCHook<C: Composition> {
     EndSuccess,
     EndFailure(&'static str),
     Intrinsic(fn(state: &mut GAState<C>) -> super::Result<()>),
+    /// An intrinsic that may need to split the path before it can run.
+    ///
+    /// If the returned constraints are empty the intrinsic ran to completion.
+    /// Otherwise the state is left untouched and one path is explored per
+    /// constraint, each of which re-enters the intrinsic.
+    ForkingIntrinsic(fn(state: &mut GAState<C>) -> super::Result<Vec<C::SmtExpression>>),
     Suppress,
+    /// Skips the function, returning a new unconstrained value of the given
+    /// number of bits, see [`GAState::stub_return`].
+    StubReturn(u32),
+}
+
+impl<C: Composition> PCHook<C> {
+    /// Skips the function and returns a new unconstrained `bits` wide value,
+    /// useful for opaque library calls such as `rand`.
+    pub const fn stub_return(bits: u32) -> Self {
+        Self::StubReturn(bits)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -82,6 +100,25 @@ pub struct HookContainer<C: Composition> {
 
     pub(crate) fp_register_read_hook: HashMap<String, FpRegisterReadHook<C>>,
     pub(crate) fp_register_write_hook: HashMap<String, FpRegisterWriteHook<C>>,
+
+    /// The region `[low, high)` that the stack is allowed to occupy.
+    pub(crate) stack_region: Option<(u64, u64)>,
+
+    /// Regions `[low, high)` with restricted access, later regions take
+    /// precedence.
+    pub(crate) memory_permissions: Vec<((u64, u64), Permissions)>,
+
+    /// Memory mapped peripheral regions `[low, high)`.
+    pub(crate) mmio_regions: Vec<(u64, u64)>,
+
+    /// Number of reads from peripheral regions, used to name the read values.
+    pub(crate) mmio_reads: usize,
+
+    pub(crate) register_watchpoints: HashMap<String, Vec<Watchpoint<C>>>,
+
+    /// PC hooks that are installed at the address of a symbol once the symbol
+    /// map is known, see [`HookContainer::add_named`].
+    pub(crate) named_pc_hooks: Vec<(String, PCHook<C>)>,
 }
 
 pub type FlagReadHook<C> = fn(state: &mut GAState<C>) -> super::Result<<C as Composition>::SmtExpression>;
@@ -105,6 +142,27 @@ pub type TemporalHook<C> = fn(&mut GAState<C>) -> ResultOrTerminate<()>;
 
 pub type Precondition<C> = fn(state: &mut GAState<C>) -> super::ResultOrTerminate<()>;
 
+/// Returns a boolean expression that is true when the value written to a
+/// watched register should trigger the watchpoint.
+pub type WatchpointPredicate<C> = fn(state: &GAState<C>, value: &<C as Composition>::SmtExpression) -> <C as Composition>::SmtExpression;
+
+/// Invoked when a write to a watched register may satisfy the predicate.
+pub type WatchpointCallback<C> = fn(state: &mut GAState<C>, register: &str, value: &<C as Composition>::SmtExpression) -> WatchpointAction;
+
+/// What to do with the path after a watchpoint has triggered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchpointAction {
+    Continue,
+    /// Ends the path as a failure with the given reason.
+    Fail(&'static str),
+}
+
+#[derive(Debug, Clone)]
+pub struct Watchpoint<C: Composition> {
+    pub predicate: WatchpointPredicate<C>,
+    pub callback: WatchpointCallback<C>,
+}
+
 impl<C: Composition> HookContainer<C> {
     /// Adds all the hooks contained in another state container.
     pub fn add_all(&mut self, other: PrioriHookContainer<C>) {
@@ -346,6 +404,30 @@ impl<C: Composition> HookContainer<C> {
         Ok(())
     }
 
+    /// Adds a PC hook at the start of the function or symbol `name`.
+    ///
+    /// The name is resolved once the symbol map of the binary is known, see
+    /// [`HookContainer::resolve_named`].
+    pub fn add_named(&mut self, name: &str, hook: PCHook<C>) -> &mut Self {
+        self.named_pc_hooks.push((name.to_owned(), hook));
+        self
+    }
+
+    /// Installs all hooks added with [`HookContainer::add_named`] at the
+    /// addresses of their symbols.
+    ///
+    /// Returns an error naming the first symbol that is not in `map`.
+    pub fn resolve_named(&mut self, map: &SubProgramMap) -> Result<()> {
+        for (name, hook) in std::mem::take(&mut self.named_pc_hooks) {
+            let Some(program) = map.get_by_name(&name) else {
+                return Err(crate::GAError::ProjectError(crate::project::ProjectError::UnresolvedSymbol(name))).context("While resolving named hooks");
+            };
+            trace!("[{name}]: Adding hook for subprogram {:?}", program);
+            self.add_pc_hook(program.bounds.0 & ((u64::MAX >> 1) << 1), hook);
+        }
+        Ok(())
+    }
+
     #[must_use]
     pub fn could_possibly_be_read_hook(&self) -> Vec<&MemoryRangeReadHook<C>> {
         todo!("We need to generate both paths, if address is symbolic")
@@ -579,22 +661,131 @@ impl<C: Composition> HookContainer<C> {
             flag_write_hook: HashMap::new(),
             pc_preconditions: HashMap::new(),
             pc_preconditions_one_shots: HashMap::new(),
+            stack_region: None,
+            memory_permissions: Vec::new(),
+            mmio_regions: Vec::new(),
+            mmio_reads: 0,
+            register_watchpoints: HashMap::new(),
+            named_pc_hooks: Vec::new(),
+        }
+    }
+
+    /// Sets the region `[low, high)` that the stack is allowed to occupy.
+    ///
+    /// Any path that may set the stack pointer outside of `[low, high]`, or
+    /// that may write outside of the region relative to the stack pointer,
+    /// will end as a failure. The stack pointer equals `high` when the stack
+    /// is empty.
+    pub const fn set_stack_region(&mut self, low: u64, high: u64) -> &mut Self {
+        self.stack_region = Some((low, high));
+        self
+    }
+
+    /// Restricts the accesses permitted to the region `[low, high)`.
+    ///
+    /// Any path that accesses the region in a way that is not permitted will
+    /// end as a failure, this includes accesses through symbolic addresses that
+    /// may fall in the region. Memory outside of all registered regions may be
+    /// accessed freely. If regions overlap the one registered last takes
+    /// precedence.
+    pub fn set_memory_permissions(&mut self, low: u64, high: u64, permissions: Permissions) -> &mut Self {
+        self.memory_permissions.push(((low, high), permissions));
+        self
+    }
+
+    /// Returns true if a `bits` wide `access` at `address` is permitted.
+    #[must_use]
+    pub fn access_permitted(&self, address: u64, bits: u32, access: Access) -> bool {
+        if self.memory_permissions.is_empty() {
+            return true;
+        }
+        (address..address + u64::from(bits.div_ceil(BITS_IN_BYTE)).max(1)).all(|byte| {
+            self.memory_permissions
+                .iter()
+                .rev()
+                .find(|((low, high), _)| (*low..*high).contains(&byte))
+                .is_none_or(|(_, permissions)| permissions.permits(access))
+        })
+    }
+
+    /// Returns true if a `bits` wide `access` at the symbolic `address` is
+    /// permitted for every value the address may take under `constraints`.
+    pub fn symbolic_access_permitted(&self, constraints: &C::SMT, address: &C::SmtExpression, bits: u32, access: Access) -> std::result::Result<bool, SolverError> {
+        if self.memory_permissions.iter().all(|(_, permissions)| permissions.permits(access)) {
+            return Ok(true);
+        }
+        let size = address.size();
+        let mut violations = Vec::new();
+        for offset in 0..u64::from(bits.div_ceil(BITS_IN_BYTE)).max(1) {
+            let byte = address.add(&constraints.from_u64(offset, size));
+            // Regions registered later take precedence, so a region only
+            // denies the bytes that none of the later regions contain.
+            let mut overridden: Option<C::SmtExpression> = None;
+            for ((low, high), permissions) in self.memory_permissions.iter().rev() {
+                let inside = byte.ugte(&constraints.from_u64(*low, size)).and(&byte.ult(&constraints.from_u64(*high, size)));
+                if !permissions.permits(access) {
+                    violations.push(match &overridden {
+                        Some(overridden) => inside.and(&overridden.not()),
+                        None => inside.clone(),
+                    });
+                }
+                overridden = Some(match overridden {
+                    Some(overridden) => overridden.or(&inside),
+                    None => inside,
+                });
+            }
+        }
+        match violations.into_iter().reduce(|violation, other| violation.or(&other)) {
+            Some(violation) => Ok(!constraints.is_sat_with_constraint(&violation)?),
+            None => Ok(true),
         }
     }
 
-    pub const fn reader<'a>(&'a mut self, memory: &'a mut C::Memory, memory_filter: &'a mut C::MemoryFilter) -> Reader<'a, C> {
+    /// Marks the region `[low, high)` as memory mapped peripherals.
+    ///
+    /// Every read from a constant address in the region returns a fresh
+    /// unconstrained value named after the address, and writes to the region
+    /// are dropped.
+    pub fn add_mmio_region(&mut self, low: u64, high: u64) -> &mut Self {
+        self.mmio_regions.push((low, high));
+        self
+    }
+
+    /// Returns true if `address` lies in a memory mapped peripheral region.
+    #[must_use]
+    pub fn is_mmio(&self, address: u64) -> bool {
+        self.mmio_regions.iter().any(|(low, high)| (*low..*high).contains(&address))
+    }
+
+    /// Adds a watchpoint on `register`.
+    ///
+    /// Whenever the executor writes a value to the register that may satisfy
+    /// `predicate` the `callback` is invoked. Multiple watchpoints can be added
+    /// to the same register.
+    pub fn add_register_watchpoint(&mut self, register: &(impl ToString + ?Sized), predicate: WatchpointPredicate<C>, callback: WatchpointCallback<C>) -> &mut Self {
+        self.register_watchpoints.entry(register.to_string()).or_default().push(Watchpoint { predicate, callback });
+        self
+    }
+
+    pub fn get_register_watchpoints(&self, register: &str) -> Option<&[Watchpoint<C>]> {
+        self.register_watchpoints.get(register).map(Vec::as_slice)
+    }
+
+    pub const fn reader<'a>(&'a mut self, memory: &'a mut C::Memory, memory_filter: &'a mut C::MemoryFilter, constraints: &'a C::SMT) -> Reader<'a, C> {
         Reader {
             memory,
             container: self,
             memory_filter,
+            constraints,
         }
     }
 
-    pub const fn writer<'a>(&'a mut self, memory: &'a mut C::Memory, memory_filter: &'a mut C::MemoryFilter) -> Writer<'a, C> {
+    pub const fn writer<'a>(&'a mut self, memory: &'a mut C::Memory, memory_filter: &'a mut C::MemoryFilter, constraints: &'a C::SMT) -> Writer<'a, C> {
         Writer {
             memory,
             container: self,
             memory_filter,
+            constraints,
         }
     }
 
@@ -611,7 +802,7 @@ pub enum ResultOrHook<A: Sized, B: Sized> {
     Result(A),
     Hook(B),
     Hooks(Vec<B>),
-    EndFailure(String),
+    EndFailure(FailureReason),
 }
 
 impl<C: Composition> HookContainer<C> {
@@ -674,6 +865,7 @@ impl<C: Composition> HookContainer<C> {
     }
 
     pub fn add_rust_hooks(&mut self, map: &SubProgramMap) {
+        self.add_memory_intrinsics(map);
         let _ = self.add_pc_hook_regex(map, r"^panic.*", &PCHook::EndFailure("panic"));
         let _ = self.add_pc_hook_regex(map, r"^panic_cold_explicit$", &PCHook::EndFailure("explicit panic"));
         let _ = self.add_pc_hook_regex(
@@ -692,6 +884,20 @@ impl<C: Composition> HookContainer<C> {
         );
     }
 
+    /// Replaces `memcpy` and `memset` with intrinsics that copy or fill the
+    /// whole region at once.
+    ///
+    /// Symbolic lengths are split in to one path per possible length, at most
+    /// [`MAX_INTRINSIC_LENGTHS`] of them. Lengths above
+    /// [`MAX_MEMORY_OPERATION_LENGTH`](super::state::MAX_MEMORY_OPERATION_LENGTH)
+    /// are rejected.
+    pub fn add_memory_intrinsics(&mut self, map: &SubProgramMap) {
+        let _ = self.add_pc_hook_regex(map, r"^memcpy$", &PCHook::ForkingIntrinsic(memcpy));
+        let _ = self.add_pc_hook_regex(map, r"^__aeabi_memcpy[48]?$", &PCHook::ForkingIntrinsic(memcpy));
+        let _ = self.add_pc_hook_regex(map, r"^memset$", &PCHook::ForkingIntrinsic(memset));
+        let _ = self.add_pc_hook_regex(map, r"^__aeabi_memset[48]?$", &PCHook::ForkingIntrinsic(aeabi_memset));
+    }
+
     pub fn default(map: &SubProgramMap) -> Result<Self> {
         let mut ret = Self::new();
         // intrinsic functions
@@ -728,6 +934,104 @@ impl<C: Composition> HookContainer<C> {
     }
 }
 
+/// The maximum number of concrete lengths a symbolic length passed to a
+/// memory intrinsic is split in to.
+pub const MAX_INTRINSIC_LENGTHS: u32 = 16;
+
+/// The length argument of a memory intrinsic.
+enum Length<C: Composition> {
+    /// The length has a single possible value.
+    Concrete(u64),
+    /// The constraints to split the path on, one per possible length.
+    Fork(Vec<C::SmtExpression>),
+}
+
+fn resolve_length<C: Composition>(state: &GAState<C>, length: &C::SmtExpression) -> Result<Length<C>> {
+    if let Some(length) = length.get_constant() {
+        return Ok(Length::Concrete(length));
+    }
+
+    let lengths = state
+        .constraints
+        .solutions_iter(length, MAX_INTRINSIC_LENGTHS + 1)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if lengths.len() > MAX_INTRINSIC_LENGTHS as usize {
+        return Err(SolverError::TooManySolutions).context("While resolving the length of a memory intrinsic");
+    }
+    match lengths.as_slice() {
+        [] => Err(SolverError::Unsat).context("While resolving the length of a memory intrinsic"),
+        [concrete] => Ok(Length::Concrete(concrete.get_constant().expect("Solutions to be constant"))),
+        _ => Ok(Length::Fork(lengths.iter().map(|concrete| length._eq(concrete)).collect())),
+    }
+}
+
+/// Turns a failure inside an intrinsic in to an error, as intrinsics can not
+/// end the path themselves.
+fn intrinsic_result<V>(result: ResultOrTerminate<V>, context: &'static str) -> Result<V> {
+    match result {
+        ResultOrTerminate::Result(result) => result.context(context),
+        ResultOrTerminate::Failure(reason) => Err(anyhow::anyhow!("{reason}")).context(context),
+    }
+}
+
+fn argument<C: Composition>(state: &mut GAState<C>, n: usize) -> Result<C::SmtExpression> {
+    intrinsic_result(state.argument(n), "While reading an intrinsic argument")
+}
+
+/// Jumps back to where the intrinsic was called from.
+fn return_from_intrinsic<C: Composition>(state: &mut GAState<C>) -> Result<()> {
+    let ra_name = state.architecture.get_register_name(InterfaceRegister::ReturnAddress);
+    let ra = state.get_register(ra_name)?;
+    let pc_name = state.architecture.get_register_name(InterfaceRegister::ProgramCounter);
+    state.set_register(pc_name, ra)
+}
+
+/// `memcpy(destination, source, length)`
+pub(crate) fn memcpy<C: Composition>(state: &mut GAState<C>) -> Result<Vec<C::SmtExpression>> {
+    let destination = argument(state, 0)?;
+    let source = argument(state, 1)?;
+    let length = argument(state, 2)?;
+    let length = match resolve_length(state, &length)? {
+        Length::Concrete(length) => length,
+        Length::Fork(constraints) => return Ok(constraints),
+    };
+    trace!("memcpy of {length} bytes from {source:?} to {destination:?}");
+    intrinsic_result(state.copy_memory(&destination, &source, length), "While executing memcpy")?;
+
+    return_from_intrinsic(state)?;
+    Ok(Vec::new())
+}
+
+/// Fills `length` bytes at `destination` with the least significant byte of
+/// `value`.
+fn fill<C: Composition>(state: &mut GAState<C>, destination: &C::SmtExpression, value: &C::SmtExpression, length: &C::SmtExpression) -> Result<Vec<C::SmtExpression>> {
+    let length = match resolve_length(state, length)? {
+        Length::Concrete(length) => length,
+        Length::Fork(constraints) => return Ok(constraints),
+    };
+    trace!("memset of {length} bytes at {destination:?}");
+    intrinsic_result(state.fill_memory(destination, value, length), "While executing memset")?;
+
+    return_from_intrinsic(state)?;
+    Ok(Vec::new())
+}
+
+/// `memset(destination, value, length)`
+pub(crate) fn memset<C: Composition>(state: &mut GAState<C>) -> Result<Vec<C::SmtExpression>> {
+    let destination = argument(state, 0)?;
+    let value = argument(state, 1)?;
+    let length = argument(state, 2)?;
+    fill(state, &destination, &value, &length)
+}
+
+/// `__aeabi_memset(destination, length, value)`, note the argument order.
+pub(crate) fn aeabi_memset<C: Composition>(state: &mut GAState<C>) -> Result<Vec<C::SmtExpression>> {
+    let destination = argument(state, 0)?;
+    let length = argument(state, 1)?;
+    let value = argument(state, 2)?;
+    fill(state, &destination, &value, &length)
+}
+
 impl<C: Composition> Default for PrioriHookContainer<C> {
     fn default() -> Self {
         Self::new()
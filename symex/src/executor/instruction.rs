@@ -16,6 +16,13 @@ pub enum CycleCount<C: Composition> {
     Function(fn(state: &mut GAState<C>) -> usize),
 }
 
+/// Errors raised when building an [`Instruction`].
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum InstructionError {
+    #[error("Instruction size must be a non zero multiple of 8 bits, got {0} bits")]
+    InvalidSize(u32),
+}
+
 /// Represents a general assembly instruction.
 #[derive(Debug, Clone)]
 pub struct Instruction<C: Composition> {
@@ -45,3 +52,106 @@ impl<C: Composition> CycleCount<C> {
         }
     }
 }
+
+impl<C: Composition> Instruction<C> {
+    /// Returns a builder for an instruction, see [`InstructionBuilder`].
+    pub const fn builder() -> InstructionBuilder<C> {
+        InstructionBuilder {
+            instruction_size: 0,
+            operations: Vec::new(),
+            max_cycle: CycleCount::Value(1),
+            memory_access: false,
+        }
+    }
+}
+
+/// Builds an [`Instruction`] and validates its fields.
+///
+/// Unless set, the instruction takes a single cycle and does not access
+/// memory. The instruction size must always be set.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct InstructionBuilder<C: Composition> {
+    instruction_size: u32,
+    operations: Vec<Operation>,
+    max_cycle: CycleCount<C>,
+    memory_access: bool,
+}
+
+impl<C: Composition> InstructionBuilder<C> {
+    /// Sets the size of the original machine instruction in number of bits.
+    pub const fn instruction_size(mut self, bits: u32) -> Self {
+        self.instruction_size = bits;
+        self
+    }
+
+    /// Appends an operation to the instruction.
+    pub fn operation(mut self, operation: Operation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    /// Appends all of `operations` to the instruction.
+    pub fn operations(mut self, operations: impl IntoIterator<Item = Operation>) -> Self {
+        self.operations.extend(operations);
+        self
+    }
+
+    /// Sets the maximum number of cycles the instruction takes.
+    pub const fn max_cycle(mut self, max_cycle: CycleCount<C>) -> Self {
+        self.max_cycle = max_cycle;
+        self
+    }
+
+    /// Sets whether or not the instruction accesses memory.
+    pub const fn memory_access(mut self, memory_access: bool) -> Self {
+        self.memory_access = memory_access;
+        self
+    }
+
+    /// Builds the instruction.
+    ///
+    /// Fails if the instruction size is zero or not a multiple of 8 bits.
+    pub fn build(self) -> Result<Instruction<C>, InstructionError> {
+        if self.instruction_size == 0 || self.instruction_size % 8 != 0 {
+            return Err(InstructionError::InvalidSize(self.instruction_size));
+        }
+        Ok(Instruction {
+            instruction_size: self.instruction_size,
+            operations: self.operations,
+            max_cycle: self.max_cycle,
+            memory_access: self.memory_access,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use general_assembly::{operand::Operand, operation::Operation};
+
+    use super::{Instruction, InstructionError};
+    use crate::defaults::bitwuzla::DefaultCompositionNoLogger;
+
+    #[test]
+    fn test_builder() {
+        let instruction = Instruction::<DefaultCompositionNoLogger>::builder()
+            .instruction_size(16)
+            .operation(Operation::Nop)
+            .operations([Operation::Nop, Operation::SetZFlag(Operand::Register("R0".to_owned()))])
+            .memory_access(true)
+            .build()
+            .unwrap();
+        assert_eq!(instruction.instruction_size, 16);
+        assert_eq!(instruction.operations.len(), 3);
+        assert!(instruction.memory_access);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_size() {
+        let error = Instruction::<DefaultCompositionNoLogger>::builder().operation(Operation::Nop).build().unwrap_err();
+        assert_eq!(error, InstructionError::InvalidSize(0));
+
+        let error = Instruction::<DefaultCompositionNoLogger>::builder().instruction_size(12).build().unwrap_err();
+        assert_eq!(error, InstructionError::InvalidSize(12));
+    }
+}
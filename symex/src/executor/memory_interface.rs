@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 
 use anyhow::Context;
 use general_assembly::extension::ieee754::{OperandType, RoundingMode};
@@ -18,22 +18,83 @@ use crate::{
             RegisterWriteHook,
             ResultOrHook,
         },
+        FailureReason,
         ResultOrTerminate,
     },
     smt::{Lambda, MemoryError, ProgramMemory, SmtExpr, SmtMap, SmtSolver},
     Composition,
+    GAError,
 };
 
+/// The kind of a memory access.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Access {
+    Read,
+    Write,
+    /// Fetching an instruction.
+    Execute,
+}
+
+impl Display for Access {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read => write!(f, "Read"),
+            Self::Write => write!(f, "Write"),
+            Self::Execute => write!(f, "Execute"),
+        }
+    }
+}
+
+/// The accesses permitted to a region of memory.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Permissions {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl Permissions {
+    pub const READ_EXECUTE: Self = Self {
+        read: true,
+        write: false,
+        execute: true,
+    };
+    pub const READ_ONLY: Self = Self {
+        read: true,
+        write: false,
+        execute: false,
+    };
+    pub const READ_WRITE: Self = Self {
+        read: true,
+        write: true,
+        execute: false,
+    };
+
+    /// Returns true if `access` is permitted.
+    #[must_use]
+    pub const fn permits(&self, access: Access) -> bool {
+        match access {
+            Access::Read => self.read,
+            Access::Write => self.write,
+            Access::Execute => self.execute,
+        }
+    }
+}
+
 pub struct Reader<'a, C: Composition> {
     pub memory: &'a mut C::Memory,
     pub memory_filter: &'a mut C::MemoryFilter,
     pub container: &'a mut HookContainer<C>,
+    /// The constraints of the path, used to check symbolic accesses.
+    pub constraints: &'a C::SMT,
 }
 
 pub struct Writer<'a, C: Composition> {
     pub memory: &'a mut C::Memory,
     pub memory_filter: &'a mut C::MemoryFilter,
     pub container: &'a mut HookContainer<C>,
+    /// The constraints of the path, used to check symbolic accesses.
+    pub constraints: &'a C::SMT,
 }
 
 /// A simple memory filter that only buckets the memory accesses in to the
@@ -90,13 +151,29 @@ pub trait MemoryFilter<C: Composition>: Debug + Clone {
 }
 
 impl<C: Composition> Reader<'_, C> {
+    /// Returns a fresh unconstrained value if `caddr` lies in a memory mapped
+    /// peripheral region.
+    fn read_mmio(&mut self, caddr: u64, size: u32) -> Option<C::SmtExpression> {
+        if !self.container.is_mmio(caddr) {
+            return None;
+        }
+        let name = format!("mmio_{caddr:#x}_{}", self.container.mmio_reads);
+        self.container.mmio_reads += 1;
+        Some(self.memory.unconstrained(&name, size))
+    }
+
     #[allow(clippy::if_same_then_else)]
     pub fn read_memory(&mut self, addr: &C::SmtExpression, size: u32) -> ResultOrHook<anyhow::Result<C::SmtExpression>, MemoryReadHook<C>> {
         let caddr = addr.get_constant();
         // TODO: Run hooks if symbol could be containend in them....
         if caddr.is_none() {
             if !self.memory_filter.read_memory_permitted(addr, size) {
-                return ResultOrHook::EndFailure("Tried to write a non permitted symbolic address".to_owned());
+                return ResultOrHook::EndFailure(FailureReason::Custom("Tried to write a non permitted symbolic address".to_owned()));
+            }
+            match self.container.symbolic_access_permitted(self.constraints, addr, size, Access::Read) {
+                Ok(true) => {}
+                Ok(false) => return ResultOrHook::EndFailure(FailureReason::Custom(MemoryError::SymbolicPermissionViolation(Access::Read).to_string())),
+                Err(e) => return ResultOrHook::Result(Err(e).context("While checking the permissions of a symbolic read")),
             }
             return match self.memory.get(addr, size) {
                 ResultOrTerminate::Result(r) => ResultOrHook::Result(r.context("While reading from a non- constant address")),
@@ -106,7 +183,10 @@ impl<C: Composition> Reader<'_, C> {
 
         let caddr = caddr.unwrap();
         if !self.memory_filter.read_memory_permitted_const(caddr, size) {
-            return ResultOrHook::EndFailure(format!("Tried to write a non premitted constant address {caddr:#x}"));
+            return ResultOrHook::EndFailure(FailureReason::Custom(format!("Tried to write a non premitted constant address {caddr:#x}")));
+        }
+        if !self.container.access_permitted(caddr, size, Access::Read) {
+            return ResultOrHook::EndFailure(FailureReason::Custom(MemoryError::PermissionViolation(Access::Read, caddr).to_string()));
         }
 
         if let Some(hook) = self.container.single_memory_read_hook.get(&caddr) {
@@ -132,6 +212,9 @@ impl<C: Composition> Reader<'_, C> {
         if ret.peek().is_some() {
             return ResultOrHook::Hooks(ret.collect());
         }
+        if let Some(value) = self.read_mmio(caddr, size) {
+            return ResultOrHook::Result(Ok(value));
+        }
         let result = match self.memory.get_from_const_address(caddr, size) {
             ResultOrTerminate::Failure(f) => return ResultOrHook::EndFailure(f),
             ResultOrTerminate::Result(r) => r.context("While reading from a static address"),
@@ -141,6 +224,9 @@ impl<C: Composition> Reader<'_, C> {
 
     #[allow(clippy::if_same_then_else)]
     pub fn read_memory_constant(&mut self, caddr: u64, size: u32) -> ResultOrHook<anyhow::Result<C::SmtExpression>, MemoryReadHook<C>> {
+        if !self.container.access_permitted(caddr, size, Access::Read) {
+            return ResultOrHook::EndFailure(FailureReason::Custom(MemoryError::PermissionViolation(Access::Read, caddr).to_string()));
+        }
         if let Some(hook) = self.container.single_memory_read_hook.get(&caddr) {
             debug!("Address {caddr} had a hook : {:?}", hook);
             let mut return_value = self
@@ -164,6 +250,9 @@ impl<C: Composition> Reader<'_, C> {
         if return_value.peek().is_some() {
             return ResultOrHook::Hooks(return_value.collect());
         }
+        if let Some(value) = self.read_mmio(caddr, size) {
+            return ResultOrHook::Result(Ok(value));
+        }
         let result = match self.memory.get_from_const_address(caddr, size) {
             ResultOrTerminate::Failure(f) => return ResultOrHook::EndFailure(f),
             ResultOrTerminate::Result(r) => r.context("While reading from a static address"),
@@ -173,7 +262,7 @@ impl<C: Composition> Reader<'_, C> {
 
     pub fn read_register(&mut self, id: &String) -> ResultOrHook<std::result::Result<C::SmtExpression, MemoryError>, RegisterReadHook<C>> {
         if !self.memory_filter.read_register_permitted(id) {
-            return ResultOrHook::EndFailure(format!("Tried to read from a non permitted register {id}"));
+            return ResultOrHook::EndFailure(FailureReason::Custom(format!("Tried to read from a non permitted register {id}")));
         }
         if let Some(hook) = self.container.register_read_hook.get(id) {
             return ResultOrHook::Hook(*hook);
@@ -191,7 +280,7 @@ impl<C: Composition> Reader<'_, C> {
         signed: bool,
     ) -> ResultOrHook<std::result::Result<C::SmtFPExpression, MemoryError>, FpRegisterReadHook<C>> {
         if !self.memory_filter.read_register_permitted(id) {
-            return ResultOrHook::EndFailure(format!("Tried to read from a non permitted register {id}"));
+            return ResultOrHook::EndFailure(FailureReason::Custom(format!("Tried to read from a non permitted register {id}")));
         }
         if let Some(hook) = self.container.fp_register_read_hook.get(id) {
             return ResultOrHook::Hook(*hook);
@@ -219,14 +308,32 @@ impl<C: Composition> Writer<'_, C> {
         let caddr = addr.get_constant();
         if caddr.is_none() {
             if !self.memory_filter.write_memory_permitted(addr, value.size()) {
-                return ResultOrHook::EndFailure("Tried to write a non permitted symbolic address".to_owned());
+                return ResultOrHook::EndFailure(FailureReason::Custom("Tried to write a non permitted symbolic address".to_owned()));
+            }
+            match self.container.symbolic_access_permitted(self.constraints, addr, value.size(), Access::Write) {
+                Ok(true) => {}
+                Ok(false) => {
+                    return ResultOrHook::EndFailure(FailureReason::Custom(format!(
+                        "{} {}",
+                        GAError::WritingToStaticMemoryProhibited,
+                        MemoryError::SymbolicPermissionViolation(Access::Write)
+                    )))
+                }
+                Err(e) => return ResultOrHook::Result(Err(MemoryError::from(e))),
             }
             return ResultOrHook::Result(self.memory.set(addr, value));
         }
 
         let caddr = caddr.unwrap();
         if !self.memory_filter.write_memory_permitted_const(caddr, value.size()) {
-            return ResultOrHook::EndFailure(format!("Tried to write a non permitted address {caddr:#x}"));
+            return ResultOrHook::EndFailure(FailureReason::Custom(format!("Tried to write a non permitted address {caddr:#x}")));
+        }
+        if !self.container.access_permitted(caddr, value.size(), Access::Write) {
+            return ResultOrHook::EndFailure(FailureReason::Custom(format!(
+                "{} {}",
+                GAError::WritingToStaticMemoryProhibited,
+                MemoryError::PermissionViolation(Access::Write, caddr)
+            )));
         }
 
         if let Some(hook) = self.container.single_memory_write_hook.get(&caddr) {
@@ -251,12 +358,23 @@ impl<C: Composition> Writer<'_, C> {
         if ret.peek().is_some() {
             return ResultOrHook::Hooks(ret.collect());
         }
+        if self.container.is_mmio(caddr) {
+            debug!("Dropped a write to MMIO address {caddr:#x}");
+            return ResultOrHook::Result(Ok(()));
+        }
         ResultOrHook::Result(self.memory.set_to_const_address(caddr, value))
     }
 
     pub fn write_memory_constant(&mut self, caddr: u64, value: C::SmtExpression) -> ResultOrHook<std::result::Result<(), MemoryError>, MemoryWriteHook<C>> {
         if !self.memory_filter.write_memory_permitted_const(caddr, value.size()) {
-            return ResultOrHook::EndFailure(format!("Tried to write a non permitted address {caddr:#x}"));
+            return ResultOrHook::EndFailure(FailureReason::Custom(format!("Tried to write a non permitted address {caddr:#x}")));
+        }
+        if !self.container.access_permitted(caddr, value.size(), Access::Write) {
+            return ResultOrHook::EndFailure(FailureReason::Custom(format!(
+                "{} {}",
+                GAError::WritingToStaticMemoryProhibited,
+                MemoryError::PermissionViolation(Access::Write, caddr)
+            )));
         }
 
         if let Some(hook) = self.container.single_memory_write_hook.get(&caddr) {
@@ -281,12 +399,16 @@ impl<C: Composition> Writer<'_, C> {
         if ret.peek().is_some() {
             return ResultOrHook::Hooks(ret.collect());
         }
+        if self.container.is_mmio(caddr) {
+            debug!("Dropped a write to MMIO address {caddr:#x}");
+            return ResultOrHook::Result(Ok(()));
+        }
         ResultOrHook::Result(self.memory.set_to_const_address(caddr, value))
     }
 
     pub fn write_register(&mut self, id: &str, value: &C::SmtExpression) -> ResultOrHook<std::result::Result<(), MemoryError>, RegisterWriteHook<C>> {
         if !self.memory_filter.write_register_permitted(id) {
-            return ResultOrHook::EndFailure(format!("Tried to write a non permitted register {id}"));
+            return ResultOrHook::EndFailure(FailureReason::Custom(format!("Tried to write a non permitted register {id}")));
         }
         if let Some(hook) = self.container.register_write_hook.get(id) {
             return ResultOrHook::Hook(*hook);
@@ -303,7 +425,7 @@ impl<C: Composition> Writer<'_, C> {
         signed: bool,
     ) -> ResultOrHook<std::result::Result<(), MemoryError>, FpRegisterWriteHook<C>> {
         if !self.memory_filter.write_register_permitted(id) {
-            return ResultOrHook::EndFailure(format!("Tried to write a non permitted register {id}"));
+            return ResultOrHook::EndFailure(FailureReason::Custom(format!("Tried to write a non permitted register {id}")));
         }
         if let Some(hook) = self.container.fp_register_write_hook.get(id) {
             return ResultOrHook::Hook(*hook);
@@ -1,6 +1,9 @@
 //! General assembly executor
 
-use std::{collections::VecDeque, fmt::Display};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Display,
+};
 
 use anyhow::Context as _;
 use general_assembly::{
@@ -8,10 +11,11 @@ use general_assembly::{
     prelude::{DataWord, Operand, Operation},
     shift::Shift,
 };
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use hooks::PCHook;
 use instruction::Instruction;
-use state::{ContinueInsideInstruction, GAState, HookOrInstruction};
+use state::{ContinueInsideInstruction, ForkStrategy, GAState, HookOrInstruction};
+use taint::TaintLocation;
 pub(crate) use util::add_with_carry;
 use vm::VM;
 
@@ -25,17 +29,23 @@ use crate::{
     trace,
     warn,
     Composition,
+    GAError,
     Result,
 };
 
+pub mod coverage;
 mod extension;
 pub mod hooks;
 pub mod instruction;
 pub mod memory_interface;
+pub mod replay;
 pub mod state;
+pub mod taint;
 pub(crate) mod util;
 pub mod vm;
 
+pub use extension::ieee754::FpState;
+
 pub struct GAExecutor<'vm, C: Composition> {
     pub vm: &'vm mut VM<C>,
     pub state: GAState<C>,
@@ -43,16 +53,87 @@ pub struct GAExecutor<'vm, C: Composition> {
     util: UtilityCloures<C>,
     context: Context<C>,
     current_operation_index: usize,
+    /// Set if the path was set aside to let other paths catch up, see
+    /// [`PathSelector::should_suspend`].
+    suspended: bool,
 }
 
 #[derive(Clone)]
 pub enum PathResult<C: Composition> {
-    Success(Option<C::SmtExpression>),
-    Failure(&'static str),
+    /// The path completed, along with the values of all named inputs that lead
+    /// to it and the number of cycles it took.
+    Success(Option<C::SmtExpression>, BTreeMap<String, u64>, u64),
+    Failure(FailureReason),
     AssumptionUnsat,
     Suppress,
 }
 
+impl<C: Composition> Display for PathResult<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Success(Some(value), ..) => write!(f, "Success ({value:?})"),
+            Self::Success(None, ..) => write!(f, "Success"),
+            Self::Failure(reason) => write!(f, "Failure: {reason}"),
+            Self::AssumptionUnsat => write!(f, "Unsatisfiable assumption"),
+            Self::Suppress => write!(f, "Suppressed"),
+        }
+    }
+}
+
+/// Why a path ended in a failure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FailureReason {
+    /// None of the possible continuations of the path were satisfiable.
+    Unsat,
+    /// The program aborted, either through an abort operation or by reaching a
+    /// failure hook such as a panic handler.
+    Abort(String),
+    /// The stack pointer left the configured stack region at the given
+    /// location.
+    StackOverflow(String),
+    /// A division by zero at the given location.
+    DivByZero(String),
+    /// A value that was never initialized was used as `value` at `location`.
+    UninitializedRead {
+        value: &'static str,
+        location: String,
+    },
+    /// The program counter was set to a symbolic value with more solutions
+    /// than the solution limit at the given location.
+    SymbolicPc(String),
+    /// A register watchpoint ended the path with `reason` at `location`.
+    Watchpoint {
+        reason: &'static str,
+        location: String,
+    },
+    Custom(String),
+}
+
+impl FailureReason {
+    /// Appends `location` to reasons that carry a message.
+    pub(crate) fn at(self, location: &str) -> Self {
+        match self {
+            Self::Custom(message) => Self::Custom(format!("{message} @ {location}")),
+            reason => reason,
+        }
+    }
+}
+
+impl Display for FailureReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsat => write!(f, "No continuation of the path was satisfiable"),
+            Self::Abort(reason) => write!(f, "Aborted: {reason}"),
+            Self::StackOverflow(location) => write!(f, "Stack overflow @ {location}"),
+            Self::DivByZero(location) => write!(f, "Division by zero @ {location}"),
+            Self::UninitializedRead { value, location } => write!(f, "{} @ {location}", crate::smt::MemoryError::UninitializedRead(value)),
+            Self::SymbolicPc(location) => write!(f, "{} @ {location}", GAError::NonDeterministicPC),
+            Self::Watchpoint { reason, location } => write!(f, "{reason} @ {location}"),
+            Self::Custom(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
 pub enum Continue {
     This,
     Next,
@@ -75,7 +156,7 @@ pub(crate) struct AddWithCarryResult<E: SmtExpr> {
 
 pub enum ResultOrTerminate<V> {
     Result(anyhow::Result<V>),
-    Failure(String),
+    Failure(FailureReason),
 }
 
 //#[cfg(test)]
@@ -130,7 +211,7 @@ macro_rules! extract {
                         unreachable!("Invalid precondition");
                     }
                 },
-                ResultOrTerminate::Failure(e) => return Ok(Some(PathResult::Failure(e.to_string().leak()))),
+                ResultOrTerminate::Failure(e) => return Ok(Some(PathResult::Failure(e))),
             }
         }
     };
@@ -147,7 +228,7 @@ macro_rules! extract {
                         unreachable!("Invalid precondition");
                     }
                 },
-                ResultOrTerminate::Failure(e) => return Ok(PathResult::Failure(e.to_string().leak())),
+                ResultOrTerminate::Failure(e) => return Ok(PathResult::Failure(e)),
             }
         }
     };
@@ -210,6 +291,11 @@ pub struct Context<C: Composition> {
     locals: HashMap<String, C::SmtExpression>,
     /// Representation of the per-instruction local floating point variables.
     fp_locals: HashMap<String, <C::SMT as SmtSolver>::FpExpression>,
+    /// The locals holding a value derived from the stack pointer.
+    stack_locals: HashSet<String>,
+    /// Set when the current operation reads the stack pointer or a value
+    /// derived from it.
+    reads_stack: bool,
     /// The operations to execute.
     ///
     /// After an instruction completes it will pop that layer of the queue.
@@ -221,6 +307,8 @@ impl<C: Composition> Context<C> {
         Self {
             locals: HashMap::new(),
             fp_locals: HashMap::new(),
+            stack_locals: HashSet::new(),
+            reads_stack: false,
             execution_queue: VecDeque::new(),
         }
     }
@@ -228,6 +316,7 @@ impl<C: Composition> Context<C> {
     fn clear(&mut self) {
         self.locals = HashMap::new();
         self.fp_locals = HashMap::new();
+        self.stack_locals = HashSet::new();
     }
 
     /// Marker that denotes that the forked instruction should resume execution
@@ -254,16 +343,27 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
             project,
             current_operation_index: 0,
             context: Context::new(),
+            suspended: false,
         }
     }
 
+    /// Replaces the state with `state`, to continue with another path.
+    pub(crate) fn restart(&mut self, state: GAState<C>) {
+        self.util = UtilityCloures::new(&state, self.project.get_word_size());
+        self.state = state;
+        self.current_operation_index = 0;
+        self.context = Context::new();
+        self.suspended = false;
+    }
+
     pub fn resume_execution(&mut self, logger: &mut C::Logger) -> Result<PathResult<C>> {
         let possible_continue = self.state.continue_in_instruction.clone();
 
         if let Some(i) = possible_continue {
             match self.continue_executing_instruction(&i, logger) {
-                ResultOrTerminate::Failure(f) => return Ok(PathResult::Failure(f.leak())),
-                ResultOrTerminate::Result(r) => r?,
+                ResultOrTerminate::Failure(f) => return Ok(PathResult::Failure(f)),
+                ResultOrTerminate::Result(Err(e)) => return self.suppress_or_error(e, logger),
+                ResultOrTerminate::Result(Ok(())) => {}
             }
             let i = i.clone();
             self.state.continue_in_instruction = None;
@@ -274,6 +374,13 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
         let mut instruction_counter = 0;
         loop {
             instruction_counter += 1;
+            // A resumed path always makes progress before it is suspended again.
+            let pc = self.state.memory.get_pc().ok().and_then(|pc| pc.get_constant());
+            if instruction_counter > 1 && pc.is_some_and(|pc| self.vm.paths.should_suspend(pc)) {
+                debug!("Suspending path");
+                self.suspended = true;
+                return Ok(PathResult::Suppress);
+            }
             self.state.architecture.pre_instruction_loading_hook()(&mut self.state);
             let instruction = match extract!(Result(self.state.get_next_instruction(logger)), context: "While executing instruction {instruction_counter} in a resumed context @ {}",self.state.debug_string_address(self.state.last_pc))
             {
@@ -288,16 +395,20 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
                         continue;
                     }
+                    PCHook::StubReturn(bits) => {
+                        debug!("Skipping stubbed function");
+                        self.state.stub_return(bits)?;
+                        continue;
+                    }
                     PCHook::EndSuccess => {
                         debug!("Symbolic execution ended successfully");
                         self.state.increment_cycle_count();
-                        return Ok(PathResult::Success(None));
+                        return Ok(PathResult::Success(None, self.state.named_inputs()?, self.state.get_cycle_count()));
                     }
                     PCHook::EndFailure(reason) => {
                         debug!("Symbolic execution ended unsuccessfully");
-                        let data = reason;
                         self.state.increment_cycle_count();
-                        return Ok(PathResult::Failure(data));
+                        return Ok(PathResult::Failure(FailureReason::Abort(reason.to_owned())));
                     }
                     PCHook::Suppress => {
                         debug!("Suppressing path");
@@ -313,6 +424,11 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                         self.state.last_instruction = None;
                         continue;
                     }
+                    PCHook::ForkingIntrinsic(f) => {
+                        debug!("Running forking intrinsic hook.");
+                        self.run_forking_intrinsic(f, logger)?;
+                        continue;
+                    }
                 },
             };
             //logger.update_delimiter(self.state.last_pc);
@@ -322,10 +438,17 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
             trace!("executing instruction: {:?}", instruction);
             match self.execute_instruction(&instruction, logger) {
-                ResultOrTerminate::Failure(f) => return Ok(PathResult::Failure(f.leak())),
-                ResultOrTerminate::Result(Err(e)) => return Err(e),
+                ResultOrTerminate::Failure(f) => return Ok(PathResult::Failure(f)),
+                ResultOrTerminate::Result(Err(e)) => return self.suppress_or_error(e, logger),
                 ResultOrTerminate::Result(Ok(_)) => {}
             }
+            if let Some(max_instructions) = self.state.max_instructions {
+                if self.state.instruction_count() > max_instructions {
+                    debug!("Suppressing path");
+                    logger.warn(format!("Suppressing path as it exceeds the budget of {max_instructions} instructions"));
+                    return Ok(PathResult::Suppress);
+                }
+            }
 
             self.state.set_last_instruction(instruction);
             self.state.architecture.post_instruction_execution_hook()(&mut self.state);
@@ -339,8 +462,9 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
         if let Some(i) = possible_continue {
             match self.continue_executing_instruction(&i, logger) {
-                ResultOrTerminate::Failure(f) => return Ok(Some(PathResult::Failure(f.leak()))),
-                ResultOrTerminate::Result(r) => r?,
+                ResultOrTerminate::Failure(f) => return Ok(Some(PathResult::Failure(f))),
+                ResultOrTerminate::Result(Err(e)) => return self.suppress_or_error(e, logger).map(Some),
+                ResultOrTerminate::Result(Ok(())) => {}
             }
             self.state.continue_in_instruction = None;
             self.state.set_last_instruction(i.instruction);
@@ -353,8 +477,9 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
         if let Some(i) = possible_continue {
             match self.continue_executing_instruction(&i, logger) {
-                ResultOrTerminate::Failure(f) => return Ok(Some(PathResult::Failure(f.leak()))),
-                ResultOrTerminate::Result(r) => r?,
+                ResultOrTerminate::Failure(f) => return Ok(Some(PathResult::Failure(f))),
+                ResultOrTerminate::Result(Err(e)) => return self.suppress_or_error(e, logger).map(Some),
+                ResultOrTerminate::Result(Ok(())) => {}
             }
             self.state.continue_in_instruction = None;
             self.state.set_last_instruction(i.instruction);
@@ -374,16 +499,20 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
                         continue;
                     }
+                    PCHook::StubReturn(bits) => {
+                        debug!("Skipping stubbed function");
+                        self.state.stub_return(bits)?;
+                        continue;
+                    }
                     PCHook::EndSuccess => {
                         debug!("Symbolic execution ended successfully");
                         self.state.increment_cycle_count();
-                        return Ok(Some(PathResult::Success(None)));
+                        return Ok(Some(PathResult::Success(None, self.state.named_inputs()?, self.state.get_cycle_count())));
                     }
                     PCHook::EndFailure(reason) => {
                         debug!("Symbolic execution ended unsuccessfully");
-                        let data = reason;
                         self.state.increment_cycle_count();
-                        return Ok(Some(PathResult::Failure(data)));
+                        return Ok(Some(PathResult::Failure(FailureReason::Abort(reason.to_owned()))));
                     }
                     PCHook::Suppress => {
                         logger.warn("Suppressing path");
@@ -397,6 +526,11 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                         self.state.last_instruction = None;
                         continue;
                     }
+                    PCHook::ForkingIntrinsic(f) => {
+                        debug!("Running forking intrinsic hook.");
+                        self.run_forking_intrinsic(f, logger)?;
+                        continue;
+                    }
                 },
             };
             // Add cycles to cycle count
@@ -404,14 +538,36 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
             trace!("executing instruction: {:?}", instruction);
             match self.execute_instruction(&instruction, logger) {
-                ResultOrTerminate::Failure(f) => return Ok(Some(PathResult::Failure(f.leak()))),
-                ResultOrTerminate::Result(Err(e)) => return Err(e),
+                ResultOrTerminate::Failure(f) => return Ok(Some(PathResult::Failure(f))),
+                ResultOrTerminate::Result(Err(e)) => return self.suppress_or_error(e, logger).map(Some),
                 ResultOrTerminate::Result(Ok(true)) => steps -= 1,
                 ResultOrTerminate::Result(Ok(false)) => steps -= 1,
             }
 
             self.state.set_last_instruction(instruction);
             self.state.architecture.post_instruction_execution_hook()(&mut self.state);
+
+            // Checked after the step so a resumed path always makes progress.
+            let pc = self.state.memory.get_pc().ok().and_then(|pc| pc.get_constant());
+            if pc.is_some_and(|pc| self.vm.paths.should_suspend(pc)) {
+                debug!("Suspending path");
+                self.suspended = true;
+                return Ok(Some(PathResult::Suppress));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Steps one instruction at a time until `predicate` holds for the
+    /// current state or the path ends.
+    ///
+    /// The predicate is checked before every step, returns `None` if it held
+    /// and the result of the path otherwise.
+    pub fn run_until(&mut self, predicate: impl Fn(&GAState<C>) -> bool, logger: &mut C::Logger) -> Result<Option<PathResult<C>>> {
+        while !predicate(&self.state) {
+            if let Some(result) = self.step(1, logger)? {
+                return Ok(Some(result));
+            }
         }
         Ok(None)
     }
@@ -421,8 +577,9 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
         if let Some(i) = possible_continue {
             match self.continue_executing_instruction(&i, logger) {
-                ResultOrTerminate::Failure(f) => return Ok(PathResult::Failure(f.leak())),
-                ResultOrTerminate::Result(r) => r?,
+                ResultOrTerminate::Failure(f) => return Ok(PathResult::Failure(f)),
+                ResultOrTerminate::Result(Err(e)) => return self.suppress_or_error(e, logger),
+                ResultOrTerminate::Result(Ok(())) => {}
             }
             self.state.continue_in_instruction = None;
             self.state.set_last_instruction(i.instruction);
@@ -443,16 +600,20 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
                         continue;
                     }
+                    PCHook::StubReturn(bits) => {
+                        debug!("Skipping stubbed function");
+                        self.state.stub_return(bits)?;
+                        continue;
+                    }
                     PCHook::EndSuccess => {
                         debug!("Symbolic execution ended successfully");
                         self.state.increment_cycle_count();
-                        return Ok(PathResult::Success(None));
+                        return Ok(PathResult::Success(None, self.state.named_inputs()?, self.state.get_cycle_count()));
                     }
                     PCHook::EndFailure(reason) => {
                         debug!("Symbolic execution ended unsuccessfully");
-                        let data = reason;
                         self.state.increment_cycle_count();
-                        return Ok(PathResult::Failure(data));
+                        return Ok(PathResult::Failure(FailureReason::Abort(reason.to_owned())));
                     }
                     PCHook::Suppress => {
                         logger.warn("Suppressing path");
@@ -470,6 +631,11 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                         self.state.last_instruction = None;
                         continue;
                     }
+                    PCHook::ForkingIntrinsic(f) => {
+                        trace!("Running forking intrinsic hook @ {pc:#x}");
+                        self.run_forking_intrinsic(f, logger)?;
+                        continue;
+                    }
                 },
             };
             //logger.update_delimiter(self.state.last_pc);
@@ -480,8 +646,8 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
             trace!("executing instruction: {:?}", instruction);
             match self.execute_instruction(&instruction, logger) {
-                ResultOrTerminate::Failure(f) => return Ok(PathResult::Failure(f.leak())),
-                ResultOrTerminate::Result(Err(e)) => return Err(e),
+                ResultOrTerminate::Failure(f) => return Ok(PathResult::Failure(f)),
+                ResultOrTerminate::Result(Err(e)) => return self.suppress_or_error(e, logger),
                 ResultOrTerminate::Result(Ok(_)) => {}
             }
 
@@ -493,6 +659,23 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
     // Fork execution. Will create a new path with `constraint`.
     fn fork(&mut self, constraint: C::SmtExpression, logger: &C::Logger, operation: &Continue, msg: &'static str) {
+        // A replay follows a single path.
+        if self.state.replay.is_some() {
+            return;
+        }
+        let pc = self.state.last_pc & ((u64::MAX >> 1) << 1);
+        // The constraint is kept for the rest of the new path, keep it small.
+        let constraint = constraint.simplify();
+        // The new path is re-derived from the start of this path when explored,
+        // unless the path created symbols that can not be re-created.
+        if let Some(origin) = self.state.origin.clone().filter(|origin| {
+            self.state.fork_strategy == ForkStrategy::Incremental && C::Memory::SEEDS_SYMBOLS && self.state.memory.unnamed_symbols() == origin.state.memory.unnamed_symbols()
+        }) {
+            let symbols = self.state.memory.named_variables().map(|(name, value)| (name.to_owned(), value.clone())).collect();
+            self.vm.paths.save_path(Path::deferred(origin, symbols, constraint, pc));
+            return;
+        }
+
         let forked_state = match operation {
             Continue::This => {
                 let mut clone = self.state.clone();
@@ -518,15 +701,83 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
             }
         };
 
-        let pc = self.state.last_pc & ((u64::MAX >> 1) << 1);
         let mut new_logger = logger.fork();
         new_logger.warn(format!("{}: {msg}", self.state.debug_string_fork()));
-        let path = Path::new(forked_state, Some(constraint), pc, new_logger);
+        let constraint = (constraint.get_constant_bool() != Some(true)).then_some(constraint);
+        let path = Path::new(forked_state, constraint, pc, new_logger);
 
         self.vm.paths.save_path(path);
     }
 
+    /// Suppresses the path if `error` was caused by exceeding the
+    /// [`SolutionLimits`](state::SolutionLimits) and suppression was requested,
+    /// or by the solver failing internally, otherwise returns the error.
+    fn suppress_or_error(&self, error: anyhow::Error, logger: &mut C::Logger) -> Result<PathResult<C>> {
+        if matches!(error.downcast_ref::<GAError>(), Some(GAError::AssumptionUnsat)) {
+            return Ok(PathResult::AssumptionUnsat);
+        }
+        if matches!(error.downcast_ref::<SolverError>(), Some(SolverError::Internal(_))) {
+            logger.warn(format!("Suppressing path as the solver failed internally: {error:#}"));
+            return Ok(PathResult::Suppress);
+        }
+        if self.state.solution_limits.suppress_exceeding && matches!(error.downcast_ref::<SolverError>(), Some(SolverError::TooManySolutions)) {
+            logger.warn(format!("Suppressing path as it exceeds the solution limits: {error:#}"));
+            return Ok(PathResult::Suppress);
+        }
+        Err(error)
+    }
+
+    /// Runs a [`PCHook::ForkingIntrinsic`].
+    ///
+    /// If the intrinsic requests a split, a new path is created for all but the
+    /// first constraint. The current path continues under the first constraint
+    /// and re-enters the intrinsic.
+    fn run_forking_intrinsic(&mut self, intrinsic: fn(&mut GAState<C>) -> Result<Vec<C::SmtExpression>>, logger: &C::Logger) -> Result<()> {
+        self.state.fix_replay_inputs()?;
+        let mut constraints = intrinsic(&mut self.state)?.into_iter();
+        let Some(first) = constraints.next() else {
+            // Set last instruction to empty to not count instruction twice
+            self.state.last_instruction = None;
+            return Ok(());
+        };
+
+        let pc = self.state.last_pc & ((u64::MAX >> 1) << 1);
+        for constraint in constraints.map(SmtExpr::simplify) {
+            // A replay follows a single path.
+            if self.state.replay.is_some() {
+                break;
+            }
+            // While re-deriving an incrementally forked path the intrinsic is
+            // re-entered under the constraints of that path.
+            if self.state.fork_strategy == ForkStrategy::Incremental && !self.state.constraints.is_sat_with_constraint(&constraint)? {
+                continue;
+            }
+            let mut new_logger = logger.fork();
+            new_logger.warn(format!("{}: Forking due to non concrete argument to intrinsic", self.state.debug_string_fork()));
+            let path = Path::new(self.state.clone(), Some(constraint), pc, new_logger);
+            self.vm.paths.save_path(path);
+        }
+        self.state.assert(&first);
+        Ok(())
+    }
+
+    /// Resolves the number of bytes a [`Operation::Memcpy`] or
+    /// [`Operation::Memset`] operates on, forking the path once per possible
+    /// length if it is symbolic.
+    fn memory_operation_length(&mut self, length: &Operand, logger: &C::Logger) -> ResultOrTerminate<u64> {
+        let length = extract!(Ok(self.get_operand_value(length, logger)));
+        let length = extract!(Ok(self.fork_for_all(&length, logger)));
+        match length.get_constant() {
+            Some(length) => ResultOrTerminate::Result(Ok(length)),
+            None => ResultOrTerminate::Result(Err(anyhow::anyhow!("A {} bit length does not fit in 64 bits", length.size()))),
+        }
+    }
+
     /// Creates smt expression from a dataword.
+    ///
+    /// The expression holds the value of the dataword, not its representation
+    /// in memory. The byte order of the project is applied by the memory when
+    /// the value is stored or loaded.
     pub(crate) fn get_dexpr_from_dataword(&self, data: DataWord) -> C::SmtExpression {
         match data {
             DataWord::Word64(v) => self.state.memory.from_u64(v, 64),
@@ -540,19 +791,7 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
     /// Retrieves a smt expression representing value stored at `address` in
     /// memory.
     fn get_memory(&mut self, addr: C::SmtExpression, bits: u32) -> ResultOrTerminate<C::SmtExpression> {
-        // trace!("Getting memory addr: {:?}", address);
-        // let addr = self.state.memory.from_u64(address, self.project.get_ptr_size());
-        ResultOrTerminate::Result(match self.state.reader().read_memory(&addr, bits) {
-            hooks::ResultOrHook::Hook(hook) => hook(&mut self.state, addr),
-            hooks::ResultOrHook::Hooks(hooks) => {
-                if hooks.len() == 1 {
-                    return ResultOrTerminate::Result(hooks[0](&mut self.state, addr));
-                }
-                todo!("Handle multiple hooks.");
-            }
-            hooks::ResultOrHook::Result(result) => result,
-            hooks::ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(format!("{e} @ {}", self.state.debug_string())),
-        })
+        self.state.read_memory(addr, bits)
     }
 
     /// Retrieves a smt expression representing value stored at `address` in
@@ -561,31 +800,21 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
         let sym_addr = self.state.memory.from_u64(addr, self.project.get_ptr_size());
         ResultOrTerminate::Result(match self.state.reader().read_memory_constant(addr, bits) {
             hooks::ResultOrHook::Hook(hook) => hook(&mut self.state, sym_addr),
-            hooks::ResultOrHook::Hooks(hooks) => {
-                if hooks.len() == 1 {
-                    return ResultOrTerminate::Result(hooks[0](&mut self.state, sym_addr));
-                }
-                todo!("Handle multiple hooks.");
-            }
+            // Every hook is run in turn, the one registered for the exact
+            // address comes last and provides the value.
+            hooks::ResultOrHook::Hooks(hooks) => hooks
+                .into_iter()
+                .try_fold(None, |_, hook| hook(&mut self.state, sym_addr.clone()).map(Some))
+                .and_then(|value| value.context("No memory read hook was run")),
             hooks::ResultOrHook::Result(result) => result,
-            hooks::ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(format!("{e} @ {}", self.state.debug_string())),
+            hooks::ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(e.at(&self.state.debug_string())),
         })
     }
 
     #[allow(dead_code)]
     /// Sets the memory at `address` to `data`.
     fn set_memory(&mut self, data: C::SmtExpression, addr: C::SmtExpression, bits: u32) -> ResultOrTerminate<()> {
-        ResultOrTerminate::Result(match self.state.writer().write_memory(&addr, data.resize_unsigned(bits)) {
-            hooks::ResultOrHook::Hook(hook) => hook(&mut self.state, data, addr),
-            hooks::ResultOrHook::Hooks(hooks) => {
-                if hooks.len() == 1 {
-                    return ResultOrTerminate::Result(hooks[0](&mut self.state, data, addr));
-                }
-                todo!("Handle multiple hooks (write).");
-            }
-            hooks::ResultOrHook::Result(result) => result.map_err(Into::into),
-            hooks::ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(format!("{e} @ {}", self.state.debug_string())),
-        })
+        self.state.write_memory(addr, data, bits)
     }
 
     /// Sets the memory at `address` to `data`.
@@ -593,21 +822,21 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
         let sym_addr = self.state.memory.from_u64(addr, self.project.get_ptr_size());
         ResultOrTerminate::Result(match self.state.writer().write_memory_constant(addr, data.resize_unsigned(bits)) {
             hooks::ResultOrHook::Hook(hook) => hook(&mut self.state, data, sym_addr),
-            hooks::ResultOrHook::Hooks(hooks) => {
-                if hooks.len() == 1 {
-                    return ResultOrTerminate::Result(hooks[0](&mut self.state, data, sym_addr));
-                }
-                todo!("Handle multiple hooks (write).");
-            }
+            hooks::ResultOrHook::Hooks(hooks) => hooks.into_iter().try_for_each(|hook| hook(&mut self.state, data.clone(), sym_addr.clone())),
             hooks::ResultOrHook::Result(result) => result.map_err(Into::into),
-            hooks::ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(format!("{e} @ {}", self.state.debug_string())),
+            hooks::ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(e.at(&self.state.debug_string())),
         })
     }
 
     /// Get the smt expression for a operand.
     pub(crate) fn get_operand_value(&mut self, operand: &Operand, logger: &C::Logger) -> ResultOrTerminate<C::SmtExpression> {
         let ret = match operand {
-            Operand::Register(name) => self.state.get_register(name),
+            Operand::Register(name) => {
+                if name == self.state.architecture.get_register_name(InterfaceRegister::StackPointer) {
+                    self.context.reads_stack = true;
+                }
+                self.state.get_register(name)
+            }
             Operand::Immediate(v) => Ok(self.get_dexpr_from_dataword(v.to_owned())),
             Operand::Address(address, width) => {
                 let address = self.get_dexpr_from_dataword(*address);
@@ -615,7 +844,10 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                     Some(addr) => self.state.memory.from_u64(addr, self.state.memory.get_ptr_size()),
                     None => address,
                 };
-                let res = extract!(Ok(self.get_memory(address, *width)));
+                let res = extract!(Ok(self.get_memory(address.clone(), *width)));
+                if let (true, Some(address)) = (self.state.taint.enabled(), address.get_constant()) {
+                    self.state.taint_read(&TaintLocation::memory(address, *width).collect::<Vec<_>>(), &res);
+                }
                 Ok(res)
             }
             Operand::AddressWithOffset {
@@ -623,12 +855,28 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 offset_reg: _,
                 width: _,
             } => todo!(),
-            Operand::Local(k) => Ok((self.context.locals.get(k).expect("Local was used before it was declared!")).to_owned()),
+            Operand::Local(k) => {
+                let value = (self.context.locals.get(k).expect("Local was used before it was declared!")).to_owned();
+                if self.context.stack_locals.contains(k) {
+                    self.context.reads_stack = true;
+                }
+                if self.state.taint.enabled() {
+                    self.state.taint_read(&[TaintLocation::Local(k.to_owned())], &value);
+                }
+                Ok(value)
+            }
             Operand::AddressInLocal(local_name, width) => {
                 let address = self.context.locals.get(local_name).expect("Local was used before it was declared!").to_owned();
+                if let Some(value) = extract!(Ok(self.read_table(&address, *width))) {
+                    return ResultOrTerminate::Result(Ok(value));
+                }
                 let address = match extract!(Ok(self.resolve_address(&address, logger, false))) {
                     Some(addr) => {
-                        return self.get_memory_constant(addr, *width);
+                        let res = extract!(Ok(self.get_memory_constant(addr, *width)));
+                        if self.state.taint.enabled() {
+                            self.state.taint_read(&TaintLocation::memory(addr, *width).collect::<Vec<_>>(), &res);
+                        }
+                        return ResultOrTerminate::Result(Ok(res));
                     }
                     None => address,
                 };
@@ -645,7 +893,12 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
     }
 
     pub(crate) fn fork_for_all(&mut self, source: &C::SmtExpression, logger: &C::Logger) -> ResultOrTerminate<C::SmtExpression> {
-        let exprs = match self.state.constraints.get_values(source, 255) {
+        // A concrete value has exactly one solution.
+        if let Some(value) = source.try_concrete() {
+            return ResultOrTerminate::Result(Ok(self.state.memory.from_u64(value, source.size())));
+        }
+        let max_solutions = self.state.solution_limits.max_fork_solutions;
+        let exprs = match self.state.constraints.get_values(source, max_solutions) {
             Ok(val) => val,
             Err(err) => {
                 warn!("Too many solutions");
@@ -655,10 +908,8 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
         let exprs = match exprs {
             crate::smt::Solutions::Exactly(a) => a,
-            // NOTE: We should likely not break here but allow for a configurable number
-            // paths.
             crate::smt::Solutions::AtLeast(_) => {
-                warn!("Number of solutions exceeds 255.");
+                warn!("Number of solutions exceeds {max_solutions}.");
 
                 return ResultOrTerminate::Result(Err(SolverError::TooManySolutions.into()));
             }
@@ -681,7 +932,7 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
         // assert first address and return concrete
         let concrete_expr = exprs[0].clone();
-        self.state.constraints.assert(&source._eq(&concrete_expr));
+        self.state.assert(&source._eq(&concrete_expr));
         ResultOrTerminate::Result(Ok(concrete_expr))
     }
 
@@ -690,28 +941,54 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
         match operand {
             Operand::Register(v) => {
                 let value = if v == self.state.architecture.get_register_name(InterfaceRegister::ProgramCounter) {
-                    extract!(Ok(self.fork_for_all(&value, logger)))
-                    // return ResultOrTerminate::Result(Ok(()));
+                    if self.state.memory.is_poisoned(&value) {
+                        return ResultOrTerminate::Failure(FailureReason::UninitializedRead {
+                            value: "the program counter",
+                            location: self.state.debug_string(),
+                        });
+                    }
+                    match self.fork_for_all(&value, logger) {
+                        ResultOrTerminate::Result(Err(e)) if matches!(e.downcast_ref::<SolverError>(), Some(SolverError::TooManySolutions)) => {
+                            return ResultOrTerminate::Failure(FailureReason::SymbolicPc(self.state.debug_string()));
+                        }
+                        result => extract!(Ok(result)),
+                    }
                 } else {
                     value
                 };
 
+                if extract!(Ok(self.state.register_write_overflows_stack(v, &value))) {
+                    return ResultOrTerminate::Failure(FailureReason::StackOverflow(self.state.debug_string()));
+                }
+
                 trace!("Setting register {} to {:?}", v, value);
-                let _ = extract!(self.state.set_register(v, value).into());
+                let _ = extract!(self.state.set_register(v, value.clone()).into());
+                if let Some(reason) = extract!(Ok(self.state.check_register_watchpoints(v, &value))) {
+                    return ResultOrTerminate::Failure(FailureReason::Watchpoint {
+                        reason,
+                        location: self.state.debug_string(),
+                    });
+                }
             }
             Operand::Immediate(_) => panic!(), // Not prohibited change to error later
             Operand::AddressInLocal(local_name, width) => {
                 let address = extract!(Ok(self.get_operand_value(&Operand::Local(local_name.to_owned()), logger)));
+                if self.context.stack_locals.contains(local_name) && extract!(Ok(self.state.stack_write_overflows_stack(&address, *width))) {
+                    return ResultOrTerminate::Failure(FailureReason::StackOverflow(self.state.debug_string()));
+                }
                 let Some(address) = extract!(Ok(self.resolve_address(&address, logger, true))) else {
+                    if self.state.taint.enabled() {
+                        self.state.taint.write_unresolved_memory();
+                    }
                     return self.set_memory(value, address, *width);
                 };
+                self.taint_write_memory(address, *width);
                 extract!(Ok(self.set_memory_constant(value, address, *width)));
             }
             Operand::Address(address, width) => {
-                let address = self.get_dexpr_from_dataword(*address);
-                let Some(address) = extract!(Ok(self.resolve_address(&address, logger, true))) else {
-                    todo!();
-                };
+                // The address of an address operand is always concrete.
+                let address = u64::from(*address);
+                self.taint_write_memory(address, *width);
                 extract!(Ok(self.set_memory_constant(value, address, *width)));
             }
             Operand::AddressWithOffset {
@@ -720,19 +997,134 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 width: _,
             } => todo!(),
             Operand::Local(k) => {
+                if self.state.taint.enabled() {
+                    self.state.taint.write(TaintLocation::Local(k.to_owned()));
+                }
+                if self.context.reads_stack {
+                    self.context.stack_locals.insert(k.to_owned());
+                } else {
+                    self.context.stack_locals.remove(k);
+                }
                 self.context.locals.insert(k.to_owned(), value);
             }
             Operand::Flag(f) => {
-                extract!(Ok(self.state.set_flag(f, &value.resize_unsigned(1).simplify())));
+                let width = self.state.flag_width(f);
+                extract!(Ok(self.state.set_flag(f, &value.resize_unsigned(width).simplify())));
             }
         }
         ResultOrTerminate::Result(Ok(()))
     }
 
+    /// Labels the bytes written by a `bits` wide write to `address` with the
+    /// labels of the current operation.
+    fn taint_write_memory(&mut self, address: u64, bits: u32) {
+        if !self.state.taint.enabled() {
+            return;
+        }
+        for location in TaintLocation::memory(address, bits) {
+            self.state.taint.write(location);
+        }
+    }
+
+    /// Reads `bits` from a symbolic `address` that can only point in to a table
+    /// in read only program memory, such as a jump table.
+    ///
+    /// The value is selected from the concrete table entries, so a jump through
+    /// the table forks only over the targets the index can select. Returns
+    /// `None` if table reads are disabled, which is the default, if the address
+    /// is concrete, if the possible addresses are not of the form
+    /// `base + index * stride`, if any entry is outside of read only memory or
+    /// if there are more than
+    /// [`max_table_entries`](state::SolutionLimits::max_table_entries)
+    /// entries.
+    fn read_table(&mut self, address: &C::SmtExpression, bits: u32) -> ResultOrTerminate<Option<C::SmtExpression>> {
+        let max_entries = self.state.solution_limits.max_table_entries;
+        if max_entries == 0 || address.try_concrete().is_some() {
+            return ResultOrTerminate::Result(Ok(None));
+        }
+
+        // Solutions are fetched lazily, the first address outside of read only
+        // memory ends the search.
+        let entry_bytes = u64::from(bits.div_ceil(8)).max(1);
+        let mut entries = Vec::new();
+        for solution in self.state.constraints.solutions_iter(address, max_entries + 1) {
+            let entry = match solution {
+                Ok(entry) => entry,
+                Err(err) => return ResultOrTerminate::Result(Err(err.into())),
+            };
+            let Some(entry_address) = entry.get_constant() else {
+                return ResultOrTerminate::Result(Ok(None));
+            };
+            if entries.len() == max_entries as usize || !self.in_read_only_memory(entry_address, entry_bytes) {
+                return ResultOrTerminate::Result(Ok(None));
+            }
+            entries.push((entry, entry_address));
+        }
+
+        // A single entry is resolved as any other address.
+        if entries.len() < 2 {
+            return ResultOrTerminate::Result(Ok(None));
+        }
+
+        // The entries have to be evenly spaced and must not overlap.
+        entries.sort_unstable_by_key(|(_, entry_address)| *entry_address);
+        let stride = entries[1].1 - entries[0].1;
+        if stride < entry_bytes || entries.windows(2).any(|pair| pair[1].1 - pair[0].1 != stride) {
+            return ResultOrTerminate::Result(Ok(None));
+        }
+
+        debug!("Reading {} table entries with a stride of {stride} at {:?}", entries.len(), address);
+        let (_, last_address) = entries.pop().expect("There are at least two entries");
+        let mut value = extract!(Ok(self.get_memory_constant(last_address, bits)));
+        for (entry, entry_address) in entries.into_iter().rev() {
+            let entry_value = extract!(Ok(self.get_memory_constant(entry_address, bits)));
+            value = address._eq(&entry).ite(&entry_value, &value);
+        }
+        ResultOrTerminate::Result(Ok(Some(value)))
+    }
+
+    /// Returns true if all `bytes` bytes starting at `address` are in read only
+    /// program memory.
+    fn in_read_only_memory(&self, address: u64, bytes: u64) -> bool {
+        let Some(end) = address.checked_add(bytes) else {
+            return false;
+        };
+        self.project.read_only_regions().any(|(start, region_end)| start <= address && end <= region_end)
+    }
+
+    /// Ends the path if `divisor` is zero, forking off a path that ends if it
+    /// may be zero.
+    fn check_divisor(&mut self, divisor: &C::SmtExpression, logger: &C::Logger) -> ResultOrTerminate<()> {
+        let zero = divisor._eq(&self.state.memory.from_u64(0, divisor.size()));
+        let (zero_possible, non_zero_possible) = match zero.get_constant_bool() {
+            Some(zero) => (zero, !zero),
+            None => (
+                extract!(Ok(self.state.constraints.is_sat_with_constraint(&zero).map_err(Into::into)), context: "While checking if the divisor may be zero"),
+                extract!(Ok(self.state.constraints.is_sat_with_constraint(&zero.not()).map_err(Into::into)), context: "While checking if the divisor may be non zero"),
+            ),
+        };
+        match (zero_possible, non_zero_possible) {
+            (true, false) => ResultOrTerminate::Failure(FailureReason::DivByZero(self.state.debug_string())),
+            (false, true) => ResultOrTerminate::Result(Ok(())),
+            (true, true) => {
+                self.fork(zero.clone(), logger, &Continue::This, "Forking as the divisor may be zero");
+                self.state.assert(&zero.not());
+                ResultOrTerminate::Result(Ok(()))
+            }
+            (false, false) => ResultOrTerminate::Failure(FailureReason::Unsat),
+        }
+    }
+
     fn resolve_address(&mut self, address: &C::SmtExpression, logger: &C::Logger, _write: bool) -> ResultOrTerminate<Option<u64>> {
         debug!("Resolving address {:?} as constant", address);
-        let ret = match &address.get_constant() {
-            Some(addr) => Result::Ok(Some(*addr)),
+        if self.state.memory.is_poisoned(address) {
+            return ResultOrTerminate::Failure(FailureReason::UninitializedRead {
+                value: "an address",
+                location: self.state.debug_string(),
+            });
+        }
+        let ret = match address.try_concrete() {
+            Some(addr) => Result::Ok(Some(addr)),
             None => {
                 debug!("Address {:?} non deterministic!", address);
 
@@ -769,16 +1161,19 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                         for addr in &bucket_idecies[1..] {
                             let constraint = potential_bucket_idx._eq(addr);
                             self.fork(constraint, logger, &Continue::This, "Forking due to non concrete address while resolving address");
-                            self.state.constraints.assert(&potential_bucket_idx._ne(addr));
+                            self.state.assert(&potential_bucket_idx._ne(addr));
                         }
 
                         // assert first address and return concrete
                         let concrete_bucket = &bucket_idecies[0];
-                        self.state.constraints.assert(&potential_bucket_idx._eq(concrete_bucket));
+                        self.state.assert(&potential_bucket_idx._eq(concrete_bucket));
                     }
                 }
 
-                let addresses = match self.state.constraints.get_values(address, 10) {
+                // Solutions are fetched lazily, a unique address costs no more than confirming
+                // that there is no second one.
+                let max_addresses = self.state.solution_limits.max_address_solutions;
+                let addresses = match self.state.constraints.solutions_iter(address, max_addresses + 1).collect::<Result<Vec<_>, _>>() {
                     Ok(val) => val,
                     Err(err) => {
                         warn!("Too many solutions");
@@ -786,16 +1181,14 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                     }
                 };
 
-                let addresses = match addresses {
-                    crate::smt::Solutions::Exactly(a) => a,
-                    // NOTE: We should likely not break here but allow for a configurable number
-                    // paths.
-                    crate::smt::Solutions::AtLeast(_) => {
-                        warn!("Number of solutions exceeds 2.");
-
-                        return ResultOrTerminate::Result(Ok(None));
+                if addresses.len() > max_addresses as usize {
+                    warn!("Number of solutions exceeds {max_addresses}.");
+                    if self.state.solution_limits.suppress_exceeding {
+                        return ResultOrTerminate::Result(Err(SolverError::TooManySolutions.into()));
                     }
-                };
+
+                    return ResultOrTerminate::Result(Ok(None));
+                }
 
                 if addresses.len() == 1 {
                     return ResultOrTerminate::Result(Ok(Some(addresses[0].get_constant().unwrap())));
@@ -810,12 +1203,12 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 for addr in &addresses[1..] {
                     let constraint = address._eq(addr);
                     self.fork(constraint, logger, &Continue::This, "Forking due to non concrete address while resolving address");
-                    self.state.constraints.assert(&address._ne(addr));
+                    self.state.assert(&address._ne(addr));
                 }
 
                 // assert first address and return concrete
                 let concrete_address = &addresses[0];
-                self.state.constraints.assert(&address._eq(concrete_address));
+                self.state.assert(&address._eq(concrete_address));
                 Ok(Some(concrete_address.get_constant().unwrap()))
             }
         };
@@ -864,7 +1257,14 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
         // update last pc
         let pc_name = self.state.architecture.get_register_name(InterfaceRegister::ProgramCounter);
         let old_pc = extract!(Ok(self.state.get_register(&pc_name)));
-        self.state.last_pc = old_pc.get_constant().unwrap();
+        // A symbolic PC cannot be resolved to a single instruction, end the path
+        // rather than guessing.
+        let Some(last_pc) = old_pc.get_constant() else {
+            return ResultOrTerminate::Failure(FailureReason::SymbolicPc(self.state.debug_string()));
+        };
+        self.state.last_pc = last_pc;
+        extract!(Ok(self.state.fix_replay_inputs()));
+        extract!(Ok(self.state.record_return(last_pc)));
 
         // Always increment pc before executing the operations
         extract!(Ok(self.state.set_register(
@@ -883,7 +1283,7 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
         self.state.current_instruction = Some(i.to_owned());
 
         // check if we should actually execute the instruction
-        let should_run = match self.state.get_next_instruction_condition_expression() {
+        let should_run = match extract!(Ok(self.state.get_next_instruction_condition_expression())) {
             Some(c) => match c.get_constant_bool() {
                 Some(constant_c) => constant_c,
                 None => {
@@ -892,7 +1292,7 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
 
                     if true_possible && false_possible {
                         self.fork(c.not(), logger, &Continue::This, "Forking due to conditional execution, both options are possible");
-                        self.state.constraints.assert(&c);
+                        self.state.assert(&c);
                     }
 
                     if !true_possible && !false_possible {
@@ -928,8 +1328,18 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                     }
                     continue;
                 }
+                // Nops have no effect, step past them without copying them or touching the
+                // queue. Any condition was already consumed for the instruction as a whole.
+                if let Some(Operation::Nop) = operation {
+                    let (counter, _) = self.context.execution_queue.back_mut().expect("Check is invalid");
+                    *counter += 1;
+                    continue;
+                }
                 let idx = self.context.execution_queue.len();
                 let operation = operation.expect("Invalid checks.").clone();
+                if let Some(replay) = &mut self.state.replay {
+                    replay.steps.push((self.state.last_pc, operation.clone()));
+                }
 
                 extract!(Ok(self.execute_operation(&operation, logger)));
                 // If an instruction pushed to the execution queue we do not want to increment
@@ -939,6 +1349,7 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                     *counter += 1;
                 }
             }
+            extract!(Ok(self.state.record_call(last_pc + u64::from(i.instruction_size / 8))));
         }
         self.state.memory.dispatch_temporal_hooks();
 
@@ -951,6 +1362,10 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
     pub(crate) fn execute_operation(&mut self, operation: &Operation, logger: &mut C::Logger) -> ResultOrTerminate<()> {
         let pc = self.state.memory.get_pc().unwrap().get_constant().unwrap();
         trace!("PC: {:#x} -> Executing operation: {:?}", pc, operation);
+        if self.state.taint.enabled() {
+            self.state.taint.begin_operation();
+        }
+        self.context.reads_stack = false;
         match operation {
             Operation::Nop => (), // nop so do nothing
             Operation::Move { destination, source } => {
@@ -997,21 +1412,78 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 };
                 extract!(Ok(self.set_operand_value(destination, result, logger)));
             }
+            Operation::SaturatingAdd {
+                destination,
+                operand1,
+                operand2,
+                signed,
+                bits,
+            }
+            | Operation::SaturatingSub {
+                destination,
+                operand1,
+                operand2,
+                signed,
+                bits,
+            } => {
+                let op1 = extract!(Ok(self.get_operand_value(operand1, logger))).resize_unsigned(*bits);
+                let op2 = extract!(Ok(self.get_operand_value(operand2, logger))).resize_unsigned(*bits);
+                let result = match (operation, signed) {
+                    (Operation::SaturatingAdd { .. }, true) => op1.sadds(&op2),
+                    (Operation::SaturatingAdd { .. }, false) => op1.uadds(&op2),
+                    (_, true) => op1.ssubs(&op2),
+                    (_, false) => op1.usubs(&op2),
+                };
+                let word_size = self.project.get_word_size();
+                let result = match (*bits < word_size, signed) {
+                    (true, true) => result.sign_ext(word_size),
+                    (true, false) => result.zero_ext(word_size),
+                    (false, _) => result,
+                };
+                extract!(Ok(self.set_operand_value(destination, result, logger)));
+            }
             Operation::Mul { destination, operand1, operand2 } => {
                 let op1 = extract!(Ok(self.get_operand_value(operand1, logger)),context: "While getting operand for Mul");
                 let op2 = extract!(Ok(self.get_operand_value(operand2, logger)));
                 let result = op1.mul(&op2);
                 extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result for Mul");
             }
+            Operation::MulChecked {
+                destination,
+                operand1,
+                operand2,
+                signed,
+                overflow_flag,
+            } => {
+                let op1 = extract!(Ok(self.get_operand_value(operand1, logger)),context: "While getting operand for MulChecked");
+                let op2 = extract!(Ok(self.get_operand_value(operand2, logger)));
+                let width = op1.size();
+                let (op1, op2) = match signed {
+                    true => (op1.sign_ext(2 * width), op2.sign_ext(2 * width)),
+                    false => (op1.zero_ext(2 * width), op2.zero_ext(2 * width)),
+                };
+                let product = op1.mul(&op2);
+                let result = product.slice(0, width - 1);
+                // The product fits if extending the low half gives back the full product.
+                let extended = match signed {
+                    true => result.sign_ext(2 * width),
+                    false => result.zero_ext(2 * width),
+                };
+                let overflow = product._ne(&extended);
+                extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result for MulChecked");
+                extract!(Ok(self.set_operand_value(overflow_flag, overflow, logger)));
+            }
             Operation::UDiv { destination, operand1, operand2 } => {
                 let op1 = extract!(Ok(self.get_operand_value(operand1, logger)),context: "While getting operand for UDiv");
                 let op2 = extract!(Ok(self.get_operand_value(operand2, logger)));
+                extract!(Ok(self.check_divisor(&op2, logger)), context: "While checking the divisor for UDiv");
                 let result = op1.udiv(&op2);
                 extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result for UDiv");
             }
             Operation::SDiv { destination, operand1, operand2 } => {
                 let op1 = extract!(Ok(self.get_operand_value(operand1, logger)),context: "While getting operand for SDiv");
                 let op2 = extract!(Ok(self.get_operand_value(operand2, logger)));
+                extract!(Ok(self.check_divisor(&op2, logger)), context: "While checking the divisor for SDiv");
                 let result = op1.sdiv(&op2);
                 extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result for SDiv");
             }
@@ -1048,29 +1520,16 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 let shift_amount = extract!(Ok(self.get_operand_value(shift_n, logger)));
                 let result = match shift_t {
                     Shift::Lsl | Shift::Lsr => value.shift(&shift_amount, shift_t.clone()),
-                    Shift::Asr => {
-                        let smount = shift_amount.get_constant().expect("I am sorry") as u32;
-                        let ext = value.sign_ext(smount + value.size());
-                        let result = ext.slice(smount, smount + value.size() - 1);
-                        let _cout = ext.slice(smount - 1, smount - 1);
-                        result
-                    }
+                    // Shifting by the width or more fills the result with the sign bit.
+                    Shift::Asr => value.shift(&shift_amount.resize_unsigned(value.size()), Shift::Asr),
+                    // Always rotates one step, the carry flag is shifted in as the new top bit.
                     Shift::Rrx => {
-                        let ret = value
-                            .and(&shift_amount.sub(&self.state.memory.from_u64(1, 32)))
-                            .shift(&self.state.memory.from_u64(1, 32), Shift::Lsr)
-                            .simplify();
-                        ret.or(&self
-                            .state
-                            // Set the carry bit right above the last bit
-                            .get_flag("C")
-                            .unwrap()
-                            .shift(&shift_amount.add(&self.state.memory.from_u64(1, 32)), Shift::Lsl))
-                    }
-                    Shift::Ror => {
-                        let word_size = self.state.memory.from_u64(self.project.get_word_size() as u64, self.project.get_word_size());
-                        value.shift(&shift_amount, Shift::Lsr).or(&value.shift(&word_size.sub(&shift_amount), Shift::Lsr))
+                        let carry = extract!(Ok(self.state.get_flag("C"))).resize_unsigned(value.size());
+                        let top_bit = self.state.memory.from_u64(u64::from(value.size() - 1), value.size());
+                        let one = self.state.memory.from_u64(1, value.size());
+                        value.shift(&one, Shift::Lsr).or(&carry.shift(&top_bit, Shift::Lsl))
                     }
+                    Shift::Ror => value.rotate_right(&shift_amount.resize_unsigned(value.size())),
                 };
                 extract!(Ok(self.set_operand_value(destination, result, logger)));
             }
@@ -1092,11 +1551,21 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 let result = value.shift(&shift_amount, Shift::Asr);
                 extract!(Ok(self.set_operand_value(destination, result, logger)));
             }
-            Operation::Sror { destination, operand, shift } => {
-                let word_size = self.state.memory.from_u64(self.project.get_word_size() as u64, self.project.get_word_size());
+            Operation::Sror {
+                destination,
+                operand,
+                shift: amount,
+            }
+            | Operation::RotateRight { destination, operand, amount } => {
+                let value = extract!(Ok(self.get_operand_value(operand, logger)));
+                let amount = extract!(Ok(self.get_operand_value(amount, logger)));
+                let result = value.rotate_right(&amount.resize_unsigned(value.size()));
+                extract!(Ok(self.set_operand_value(destination, result, logger)));
+            }
+            Operation::RotateLeft { destination, operand, amount } => {
                 let value = extract!(Ok(self.get_operand_value(operand, logger)));
-                let shift = extract!(Ok(self.get_operand_value(shift, logger))).srem(&word_size);
-                let result = value.shift(&shift, Shift::Lsr).or(&value.shift(&word_size.sub(&shift), Shift::Lsl));
+                let amount = extract!(Ok(self.get_operand_value(amount, logger)));
+                let result = value.rotate_left(&amount.resize_unsigned(value.size()));
                 extract!(Ok(self.set_operand_value(destination, result, logger)));
             }
             Operation::ConditionalJump { destination, condition } => {
@@ -1122,7 +1591,7 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 let destination: C::SmtExpression = extract!(Ok(match (true_possible, false_possible) {
                     (true, true) => {
                         self.fork(c.not(), logger, &Continue::This, "Forking paths due to conditional branch");
-                        self.state.constraints.assert(&c);
+                        self.state.assert(&c);
                         self.state.set_has_jumped();
                         let dest_value = extract!(Ok(self.get_operand_value(destination, logger)));
                         let dest_value = extract!(Ok(self.fork_for_all(&dest_value, logger)));
@@ -1252,6 +1721,22 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 let result = add_with_carry(&op1, &op2, &carry, self.project.get_word_size()).result;
                 extract!(Ok(self.set_operand_value(destination, result, logger)));
             }
+            Operation::AddWithCarry {
+                destination,
+                carry_out_flag,
+                overflow_flag,
+                operand1,
+                operand2,
+                carry_in,
+            } => {
+                let op1 = extract!(Ok(self.get_operand_value(operand1, logger)));
+                let op2 = extract!(Ok(self.get_operand_value(operand2, logger)));
+                let carry_in = extract!(Ok(self.get_operand_value(carry_in, logger)));
+                let AddWithCarryResult { carry_out, overflow, result } = add_with_carry(&op1, &op2, &carry_in, self.project.get_word_size());
+                extract!(Ok(self.set_operand_value(destination, result, logger)));
+                extract!(Ok(self.set_operand_value(carry_out_flag, carry_out, logger)));
+                extract!(Ok(self.set_operand_value(overflow_flag, overflow, logger)));
+            }
             // These need to be tested are way to complex to be trusted
             Operation::SetCFlagShiftLeft { operand, shift } => {
                 let op = extract!(Ok(self.get_operand_value(operand, logger))).zero_ext(1 + self.project.get_word_size());
@@ -1298,12 +1783,12 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
             }
             Operation::CountOnes { destination, operand } => {
                 let operand = extract!(Ok(self.get_operand_value(operand, logger)),context: "While getting operand for count ones");
-                let result = self.util.count_ones.apply(operand);
+                let result = operand.popcount();
                 extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result for count ones");
             }
             Operation::CountZeroes { destination, operand } => {
                 let operand = extract!(Ok(self.get_operand_value(operand, logger)),context: "While getting operand for count zeros");
-                let result = self.util.count_zeroes.apply(operand);
+                let result = operand.not().popcount();
                 extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result of count zeros");
             }
             Operation::CountLeadingOnes { destination, operand } => {
@@ -1316,6 +1801,24 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 let result = self.util.count_leading_zeroes.apply(operand);
                 extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result of leading zeros");
             }
+            Operation::CountTrailingOnes { destination, operand } => {
+                let operand = extract!(Ok(self.get_operand_value(operand, logger)),context: "While getting operand for count trailing ones");
+                let result = self.util.count_trailing_ones.apply(operand);
+                extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result of trailing ones");
+            }
+            Operation::CountTrailingZeroes { destination, operand } => {
+                let operand = extract!(Ok(self.get_operand_value(operand, logger)),context: "While getting operand for count trailing zeros");
+                let result = self.util.count_trailing_zeroes.apply(operand);
+                extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result of trailing zeros");
+            }
+            Operation::BitReverse { destination, operand, bits } => {
+                let operand = extract!(Ok(self.get_operand_value(operand, logger)),context: "While getting operand for bit reverse");
+                let result = match *bits == operand.size() && *bits == self.project.get_word_size() {
+                    true => self.util.reverse_bits.apply(operand),
+                    false => util::reverse_bits(&operand.resize_unsigned(*bits), *bits).resize_unsigned(operand.size()),
+                };
+                extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result of bit reverse");
+            }
             Operation::BitFieldExtract {
                 destination,
                 operand,
@@ -1326,9 +1829,62 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                 trace!("Running bitfieldextract");
                 let operand = extract!(Ok(self.get_operand_value(operand, logger)),context: "While getting operand  of bit field extract operation");
                 trace!("Got operand {operand:?}");
-                let operand = operand.slice(*start_bit, *stop_bit);
+                let operand = operand.extract(*stop_bit, *start_bit);
                 extract!(Ok(self.set_operand_value(destination, operand, logger)),context: "While setting result of bit field extract operation");
             }
+            Operation::SetBit { destination, operand, bit, value } => {
+                let operand = extract!(Ok(self.get_operand_value(operand, logger)),context: "While getting operand of set bit operation");
+                let bit = extract!(Ok(self.get_operand_value(bit, logger)),context: "While getting bit index of set bit operation");
+                let value = extract!(Ok(self.get_operand_value(value, logger)),context: "While getting value of set bit operation").slice(0, 0);
+                let width = operand.size();
+                let result = match bit.get_constant() {
+                    Some(bit) if bit < u64::from(width) => {
+                        let bit = bit as u32;
+                        let mut result = value;
+                        if bit < width - 1 {
+                            result = operand.slice(bit + 1, width - 1).concat(&result);
+                        }
+                        if bit > 0 {
+                            result = result.concat(&operand.slice(0, bit - 1));
+                        }
+                        result
+                    }
+                    Some(_) => operand,
+                    None => {
+                        let bit = bit.resize_unsigned(width);
+                        let mask = self.state.memory.from_u64(1, width).shift(&bit, Shift::Lsl);
+                        let value = value.zero_ext(width).shift(&bit, Shift::Lsl);
+                        operand.and(&mask.not()).or(&value)
+                    }
+                };
+                extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result of set bit operation");
+            }
+            Operation::GetBit { destination, operand, bit } => {
+                let operand = extract!(Ok(self.get_operand_value(operand, logger)),context: "While getting operand of get bit operation");
+                let bit = extract!(Ok(self.get_operand_value(bit, logger)),context: "While getting bit index of get bit operation");
+                let width = operand.size();
+                let result = match bit.get_constant() {
+                    Some(bit) if bit < u64::from(width) => operand.slice(bit as u32, bit as u32).zero_ext(width),
+                    Some(_) => self.state.memory.from_u64(0, width),
+                    None => {
+                        let one = self.state.memory.from_u64(1, width);
+                        operand.shift(&bit.resize_unsigned(width), Shift::Lsr).and(&one)
+                    }
+                };
+                extract!(Ok(self.set_operand_value(destination, result, logger)),context: "While setting result of get bit operation");
+            }
+            Operation::Memcpy { destination, source, length } => {
+                let destination = extract!(Ok(self.get_operand_value(destination, logger)),context: "While getting destination of memcpy operation");
+                let source = extract!(Ok(self.get_operand_value(source, logger)),context: "While getting source of memcpy operation");
+                let length = extract!(Ok(self.memory_operation_length(length, logger)),context: "While resolving length of memcpy operation");
+                extract!(Ok(self.state.copy_memory(&destination, &source, length)),context: "While executing memcpy operation");
+            }
+            Operation::Memset { destination, value, length } => {
+                let destination = extract!(Ok(self.get_operand_value(destination, logger)),context: "While getting destination of memset operation");
+                let value = extract!(Ok(self.get_operand_value(value, logger)),context: "While getting value of memset operation");
+                let length = extract!(Ok(self.memory_operation_length(length, logger)),context: "While resolving length of memset operation");
+                extract!(Ok(self.state.fill_memory(&destination, &value, length)),context: "While executing memset operation");
+            }
             Operation::Compare { lhs, rhs, operation, destination } => {
                 let lhs = extract!(Ok(self.get_operand_value(lhs, logger)),context: "While getting lhs of compare operation");
                 let rhs = extract!(Ok(self.get_operand_value(rhs, logger)),context: "While getting rhs of compare operation");
@@ -1377,15 +1933,56 @@ impl<'vm, C: Composition> GAExecutor<'vm, C> {
                             &Continue::This,
                             "Forking as both paths in ITE instruction is possible",
                         );
-                        self.state.constraints.assert(&result._eq(&self.state.memory.from_bool(true)));
+                        self.state.assert(&result._eq(&self.state.memory.from_bool(true)));
                         for operation in then {
                             extract!(Ok(self.execute_operation(operation, logger)),context: "While running then block in ITE");
                         }
                     }
-                    _ => return ResultOrTerminate::Failure("None of the ITE paths were possible".to_string()),
+                    _ => return ResultOrTerminate::Failure(FailureReason::Unsat),
+                }
+            }
+            Operation::While {
+                compute_condition,
+                condition,
+                operations,
+            } => loop {
+                for operation in compute_condition {
+                    extract!(Ok(self.execute_operation(operation, logger)),context: "While computing condition for while loop");
+                }
+                let result = extract!(Ok(self.get_operand_value(condition, logger)),context: "While resolving condition variable for while loop");
+                debug!("While loop with condition : {result:?}");
+                let true_possible = extract!(Ok(self.state.constraints.is_sat_with_constraint(&result).map_err(Into::into)),context: "While resolving true possible in while loop");
+                let false_possible =
+                    extract!(Ok(self.state.constraints.is_sat_with_constraint(&result.not()).map_err(Into::into)),context: "While resolving false possible in while loop");
+                match (true_possible, false_possible) {
+                    (true, false) => {}
+                    (false, true) => break,
+                    (true, true) => {
+                        // The forked path re-evaluates the loop with the current locals and exits it.
+                        self.fork(
+                            result._eq(&self.state.memory.from_bool(false)),
+                            logger,
+                            &Continue::This,
+                            "Forking as both exiting and iterating the while loop is possible",
+                        );
+                        self.state.assert(&result._eq(&self.state.memory.from_bool(true)));
+                    }
+                    _ => return ResultOrTerminate::Failure(FailureReason::Unsat),
+                }
+                for operation in operations {
+                    extract!(Ok(self.execute_operation(operation, logger)),context: "While running body of while loop");
+                }
+            },
+            Operation::Assume { condition } => {
+                let condition = extract!(Ok(self.get_operand_value(condition, logger)), context: "While resolving the condition of an assumption");
+                let condition = condition._eq(&self.state.memory.from_bool(true));
+                let possible = extract!(Ok(self.state.constraints.is_sat_with_constraint(&condition).map_err(Into::into)), context: "While checking an assumption");
+                if !possible {
+                    return ResultOrTerminate::Result(Err(GAError::AssumptionUnsat.into()));
                 }
+                self.state.assert(&condition);
             }
-            Operation::Abort { error } => return ResultOrTerminate::Failure(error.to_string()),
+            Operation::Abort { error } => return ResultOrTerminate::Failure(FailureReason::Abort(error.to_string())),
             Operation::Ieee754(inner) => return self.execute_ieee754(inner.clone(), logger),
             #[allow(dead_code, unused)]
             Operation::Log { operand, meta, level } => {
@@ -1415,23 +2012,33 @@ mod test {
         condition::Condition,
         operand::{DataWord, Operand},
         operation::Operation,
+        shift::Shift,
     };
     use hashbrown::HashMap;
     use transpiler::pseudo;
 
-    use super::{state::GAState, vm::VM};
+    use super::{
+        replay::{Model, Replay},
+        state::{GAState, SolutionLimits},
+        taint::TaintLocation,
+        vm::VM,
+        FailureReason,
+        PathResult,
+        ResultOrTerminate,
+    };
     use crate::{
         arch::{arm::v6::ArmV6M, Architecture, NoArchitectureOverride},
         defaults::bitwuzla::{DefaultComposition, DefaultCompositionNoLogger},
         executor::{
-            hooks::HookContainer,
+            hooks::{HookContainer, WatchpointAction},
             instruction::{CycleCount, Instruction},
+            memory_interface::Permissions,
             util::{add_with_carry, UtilityCloures},
             GAExecutor,
         },
         logging::NoLogger,
         path_selection::PathSelector,
-        project::Project,
+        project::{dwarf_helper::SubProgram, Project},
         smt::{
             bitwuzla::{expr::BitwuzlaExpr, Bitwuzla},
             Lambda,
@@ -1439,8 +2046,10 @@ mod test {
             SmtExpr,
             SmtMap,
             SmtSolver,
+            Solutions,
         },
         Endianness,
+        GAError,
         WordSize,
     };
 
@@ -1448,7 +2057,6 @@ mod test {
     fn test_count_ones_concrete() {
         let ctx = crate::smt::bitwuzla::Bitwuzla::new();
         let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
-        let word_size = project.get_word_size();
         let state = GAState::<DefaultComposition>::create_test_state(
             project,
             ctx.clone(),
@@ -1459,23 +2067,53 @@ mod test {
             (),
             crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
         );
-        let util = UtilityCloures::new(&state, word_size);
         let num1 = state.memory.from_u64(1, 32);
         let num32 = state.memory.from_u64(32, 32);
         let numff = state.memory.from_u64(0xff, 32);
-        let result = Lambda::apply(&util.count_ones, num1);
+        let result = num1.popcount();
         assert_eq!(result.get_constant().unwrap(), 1);
-        let result: BitwuzlaExpr = Lambda::apply(&util.count_ones, num32);
+        let result: BitwuzlaExpr = num32.popcount();
         assert_eq!(result.get_constant().unwrap(), 1);
-        let result: BitwuzlaExpr = Lambda::apply(&util.count_ones, numff);
+        let result: BitwuzlaExpr = numff.popcount();
         assert_eq!(result.get_constant().unwrap(), 8);
     }
 
+    #[test]
+    fn test_popcount() {
+        let ctx = Bitwuzla::new();
+        let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
+        let state = GAState::<DefaultComposition>::create_test_state(
+            project,
+            ctx.clone(),
+            ctx.clone(),
+            0,
+            0,
+            HookContainer::new(),
+            (),
+            crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
+        );
+        for (value, expected) in [(0, 0), (1, 1), (32, 1), (0xff, 8), (0x8000_0001, 2), (0xffff_ffff, 32)] {
+            let value = state.memory.from_u64(value, 32);
+            assert_eq!(value.popcount().get_constant(), Some(expected));
+        }
+
+        // Agrees with summing every bit for every input.
+        let any_u32 = ctx.unconstrained(32, "any1");
+        let sum = (0..32).fold(ctx.from_u64(0, 32), |sum, bit| sum.add(&any_u32.slice(bit, bit).resize_unsigned(32)));
+        assert!(!ctx.is_sat_with_constraint(&any_u32.popcount()._ne(&sum)).unwrap());
+
+        // Other widths are counted at their own width.
+        let byte = state.memory.from_u64(0b1011_0110, 8);
+        assert_eq!(byte.popcount().size(), 8);
+        assert_eq!(byte.popcount().get_constant(), Some(5));
+        let bit = state.memory.from_u64(1, 1);
+        assert_eq!(bit.popcount().get_constant(), Some(1));
+    }
+
     #[test]
     fn test_count_ones_symbolic() {
         let ctx = Bitwuzla::new();
         let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
-        let word_size = project.get_word_size();
         let state = GAState::<DefaultComposition>::create_test_state(
             project,
             ctx.clone(),
@@ -1486,12 +2124,11 @@ mod test {
             (),
             crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
         );
-        let util = UtilityCloures::new(&state, word_size);
         let any_u32 = ctx.unconstrained(32, "any1");
         let num_0x100 = ctx.from_u64(0x100, 32);
         let num_8 = ctx.from_u64(8, 32);
         ctx.assert(&any_u32.ult(&num_0x100));
-        let result = Lambda::apply(&util.count_ones, any_u32);
+        let result = any_u32.popcount();
         let result_below_or_equal_8 = result.ulte(&num_8);
         let result_above_8 = result.ugt(&num_8);
         let can_be_below_or_equal_8 = ctx.is_sat_with_constraint(&result_below_or_equal_8).unwrap();
@@ -1504,7 +2141,6 @@ mod test {
     fn test_count_zeroes_concrete() {
         let ctx = Bitwuzla::new();
         let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
-        let word_size = project.get_word_size();
         let state = GAState::<DefaultComposition>::create_test_state(
             project,
             ctx.clone(),
@@ -1515,15 +2151,14 @@ mod test {
             (),
             crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
         );
-        let util = UtilityCloures::new(&state, word_size);
         let num1 = state.memory.from_u64(!1, 32);
         let num32 = state.memory.from_u64(!32, 32);
         let numff = state.memory.from_u64(!0xff, 32);
-        let result = Lambda::apply(&util.count_zeroes, num1);
+        let result = num1.not().popcount();
         assert_eq!(result.get_constant().unwrap(), 1);
-        let result = Lambda::apply(&util.count_zeroes, num32);
+        let result = num32.not().popcount();
         assert_eq!(result.get_constant().unwrap(), 1);
-        let result = Lambda::apply(&util.count_zeroes, numff);
+        let result = numff.not().popcount();
         assert_eq!(result.get_constant().unwrap(), 8);
     }
 
@@ -1580,11 +2215,13 @@ mod test {
         let result = Lambda::apply(&util.count_leading_zeroes, input);
         assert_eq!(result.get_constant().unwrap(), 3);
     }
+
     #[test]
-    fn test_add_with_carry() {
-        let ctx = crate::smt::bitwuzla::Bitwuzla::new();
-        let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
-        let state = GAState::<DefaultCompositionNoLogger>::create_test_state(
+    fn test_count_trailing_ones_concrete() {
+        let ctx = Bitwuzla::new();
+        let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit8, Endianness::Little, HashMap::new()));
+        let word_size = project.get_word_size();
+        let state = GAState::<DefaultComposition>::create_test_state(
             project,
             ctx.clone(),
             ctx,
@@ -1594,8 +2231,110 @@ mod test {
             (),
             crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
         );
-        let one_bool = state.memory.from_bool(true);
-        let zero_bool = state.memory.from_bool(false);
+        let util = UtilityCloures::new(&state, word_size);
+        let input = state.memory.from_u64(0b0000_0001, 8);
+        let result = Lambda::apply(&util.count_trailing_ones, input);
+        assert_eq!(result.get_constant().unwrap(), 1);
+        let input = state.memory.from_u64(0b0000_0011, 8);
+        let result = Lambda::apply(&util.count_trailing_ones, input);
+        assert_eq!(result.get_constant().unwrap(), 2);
+        let input = state.memory.from_u64(0b1100_0111, 8);
+        let result = Lambda::apply(&util.count_trailing_ones, input);
+        assert_eq!(result.get_constant().unwrap(), 3);
+        let input = state.memory.from_u64(0b1111_1111, 8);
+        let result = Lambda::apply(&util.count_trailing_ones, input);
+        assert_eq!(result.get_constant().unwrap(), 8);
+    }
+
+    #[test]
+    fn test_count_trailing_zeroes_concrete() {
+        let ctx = Bitwuzla::new();
+        let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit8, Endianness::Little, HashMap::new()));
+        let word_size = project.get_word_size();
+        let state = GAState::<DefaultComposition>::create_test_state(
+            project,
+            ctx.clone(),
+            ctx,
+            0,
+            0,
+            HookContainer::new(),
+            (),
+            crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
+        );
+        let util = UtilityCloures::new(&state, word_size);
+        let input = state.memory.from_u64(0b0000_0010, 8);
+        let result = Lambda::apply(&util.count_trailing_zeroes, input);
+        assert_eq!(result.get_constant().unwrap(), 1);
+        let input = state.memory.from_u64(0b1000_0100, 8);
+        let result = Lambda::apply(&util.count_trailing_zeroes, input);
+        assert_eq!(result.get_constant().unwrap(), 2);
+        let input = state.memory.from_u64(0b1110_1000, 8);
+        let result = Lambda::apply(&util.count_trailing_zeroes, input);
+        assert_eq!(result.get_constant().unwrap(), 3);
+        let input = state.memory.from_u64(0, 8);
+        let result = Lambda::apply(&util.count_trailing_zeroes, input);
+        assert_eq!(result.get_constant().unwrap(), 8);
+    }
+
+    #[test]
+    fn test_reverse_bits_concrete() {
+        let ctx = Bitwuzla::new();
+        let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
+        let word_size = project.get_word_size();
+        let state = GAState::<DefaultComposition>::create_test_state(
+            project,
+            ctx.clone(),
+            ctx,
+            0,
+            0,
+            HookContainer::new(),
+            (),
+            crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
+        );
+        let util = UtilityCloures::new(&state, word_size);
+        let input = state.memory.from_u64(0x8000_0001, 32);
+        let result = Lambda::apply(&util.reverse_bits, input);
+        assert_eq!(result.get_constant().unwrap(), 0x8000_0001);
+        let input = state.memory.from_u64(0x1, 32);
+        let result = Lambda::apply(&util.reverse_bits, input);
+        assert_eq!(result.get_constant().unwrap(), 0x8000_0000);
+        let input = state.memory.from_u64(0x1234_5678, 32);
+        let result = Lambda::apply(&util.reverse_bits, input);
+        assert_eq!(result.get_constant().unwrap(), 0x1E6A_2C48);
+    }
+
+    #[test]
+    fn test_bit_reverse_operation() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let r0 = Operand::Register("R0".to_owned());
+
+        let operation = Operation::BitReverse {
+            destination: r0.clone(),
+            operand: Operand::Immediate(DataWord::Word32(0b1100_0001)),
+            bits: 8,
+        };
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap();
+        assert_eq!(result.get_constant().unwrap(), 0b1000_0011);
+    }
+    #[test]
+    fn test_add_with_carry() {
+        let ctx = crate::smt::bitwuzla::Bitwuzla::new();
+        let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
+        let state = GAState::<DefaultCompositionNoLogger>::create_test_state(
+            project,
+            ctx.clone(),
+            ctx,
+            0,
+            0,
+            HookContainer::new(),
+            (),
+            crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
+        );
+        let one_bool = state.memory.from_bool(true);
+        let zero_bool = state.memory.from_bool(false);
         let zero = state.memory.from_u64(0, 32);
         let num42 = state.memory.from_u64(42, 32);
         let num16 = state.memory.from_u64(16, 32);
@@ -1668,11 +2407,47 @@ mod test {
         VM::new_test_vm(project, state, NoLogger)
     }
 
+    #[test]
+    fn test_big_endian_immediate_round_trip() {
+        let ctx = crate::smt::bitwuzla::Bitwuzla::new();
+        let project = Arc::new(Project::manual_project(vec![0; 8], 0x1000, 0x1008, WordSize::Bit32, Endianness::Big, HashMap::new()));
+        let state = GAState::<DefaultCompositionNoLogger>::create_test_state(
+            project.clone(),
+            ctx.clone(),
+            ctx,
+            0,
+            0,
+            HookContainer::new(),
+            (),
+            crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
+        );
+        let mut vm = VM::new_test_vm(project.clone(), state, NoLogger);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        // Both program memory and RAM store the most significant byte first.
+        for address in [0x1000, 0x2000_0000] {
+            let word = Operand::Address(DataWord::Word32(address), 32);
+            let operation = Operation::Move {
+                destination: word.clone(),
+                source: Operand::Immediate(DataWord::Word32(0x0102_0304)),
+            };
+            executor.execute_operation(&operation, &mut NoLogger).unwrap();
+
+            let value = executor.get_operand_value(&word, &mut NoLogger).unwrap();
+            assert_eq!(value.get_constant(), Some(0x0102_0304));
+            for (offset, expected) in [0x01, 0x02, 0x03, 0x04].into_iter().enumerate() {
+                let byte = Operand::Address(DataWord::Word32(address + offset as u32), 8);
+                let value = executor.get_operand_value(&byte, &mut NoLogger).unwrap();
+                assert_eq!(value.get_constant(), Some(expected));
+            }
+        }
+    }
+
     #[test]
     fn test_move() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // move imm into reg
@@ -1726,7 +2501,7 @@ mod test {
     fn test_add() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let r0 = Operand::Register("R0".to_owned());
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -1783,7 +2558,7 @@ mod test {
     fn test_adc() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
         let imm_12 = Operand::Immediate(DataWord::Word32(12));
@@ -1833,11 +2608,78 @@ mod test {
         assert_eq!(result, 55);
     }
 
+    #[test]
+    fn test_add_with_carry_operation() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        let r0 = Operand::Register("R0".to_owned());
+        let r1 = Operand::Register("R1".to_owned());
+        let values = [0, 1, 12, 42, i32::MAX as u32, i32::MIN as u32, u32::MAX - 1, u32::MAX];
+
+        for op1 in values {
+            for op2 in values {
+                for carry in [false, true] {
+                    let operand1 = Operand::Immediate(DataWord::Word32(op1));
+                    let operand2 = Operand::Immediate(DataWord::Word32(op2));
+                    let carry_in = executor.state.memory.from_bool(carry);
+
+                    // Reference, the separate operations.
+                    executor.state.set_flag("C", &carry_in).unwrap();
+                    let operations = [
+                        Operation::Adc {
+                            destination: r0.clone(),
+                            operand1: operand1.clone(),
+                            operand2: operand2.clone(),
+                        },
+                        Operation::SetVFlag {
+                            operand1: operand1.clone(),
+                            operand2: operand2.clone(),
+                            sub: false,
+                            carry: true,
+                        },
+                        Operation::SetCFlag {
+                            operand1: operand1.clone(),
+                            operand2: operand2.clone(),
+                            sub: false,
+                            carry: true,
+                        },
+                    ];
+                    for operation in &operations {
+                        executor.execute_operation(operation, &mut NoLogger).ok();
+                    }
+                    let expected_result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+                    let expected_c = executor.state.get_flag("C").unwrap().get_constant_bool().unwrap();
+                    let expected_v = executor.state.get_flag("V").unwrap().get_constant_bool().unwrap();
+
+                    executor.state.set_flag("C", &carry_in).unwrap();
+                    let operation = Operation::AddWithCarry {
+                        destination: r1.clone(),
+                        carry_out_flag: Operand::Flag("C".to_owned()),
+                        overflow_flag: Operand::Flag("V".to_owned()),
+                        operand1,
+                        operand2,
+                        carry_in: Operand::Flag("C".to_owned()),
+                    };
+                    executor.execute_operation(&operation, &mut NoLogger).ok();
+                    let result = executor.get_operand_value(&r1, &mut NoLogger).unwrap().get_constant().unwrap();
+                    let c = executor.state.get_flag("C").unwrap().get_constant_bool().unwrap();
+                    let v = executor.state.get_flag("V").unwrap().get_constant_bool().unwrap();
+
+                    assert_eq!(result, expected_result, "{op1:#x} + {op2:#x} + {carry}");
+                    assert_eq!(c, expected_c, "carry of {op1:#x} + {op2:#x} + {carry}");
+                    assert_eq!(v, expected_v, "overflow of {op1:#x} + {op2:#x} + {carry}");
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_sub() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let r0 = Operand::Register("R0".to_owned());
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -1894,7 +2736,7 @@ mod test {
     fn test_mul() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let r0 = Operand::Register("R0".to_owned());
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -1947,11 +2789,41 @@ mod test {
         assert_eq!(r0_value, 672);
     }
 
+    #[test]
+    fn test_mul_checked() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        let r0 = Operand::Register("R0".to_owned());
+        let mut mul_checked = |op1: u32, op2: u32, signed: bool| {
+            let operation = Operation::MulChecked {
+                destination: r0.clone(),
+                operand1: Operand::Immediate(DataWord::Word32(op1)),
+                operand2: Operand::Immediate(DataWord::Word32(op2)),
+                signed,
+                overflow_flag: Operand::Flag("V".to_owned()),
+            };
+            executor.execute_operation(&operation, &mut NoLogger).unwrap();
+            let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+            let overflow = executor.state.get_flag("V").unwrap().get_constant_bool().unwrap();
+            (result, overflow)
+        };
+
+        assert_eq!(mul_checked(0xffff_ffff, 2, false), (0xffff_fffe, true));
+        assert_eq!(mul_checked(42, 16, false), (672, false));
+
+        // -1 * 2 fits when the operands are signed.
+        assert_eq!(mul_checked(0xffff_ffff, 2, true), (0xffff_fffe, false));
+        assert_eq!(mul_checked(0x4000_0000, 2, true), (0x8000_0000, true));
+        assert_eq!(mul_checked(0x4000_0000, 2, false), (0x8000_0000, false));
+    }
+
     #[test]
     fn test_set_v_flag() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
         let imm_12 = Operand::Immediate(DataWord::Word32(12));
@@ -1999,7 +2871,7 @@ mod test {
     fn test_conditional_execution() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let imm_0 = Operand::Immediate(DataWord::Word32(0));
         let imm_1 = Operand::Immediate(DataWord::Word32(1));
         let r0 = Operand::Register("R0".to_owned());
@@ -2047,11 +2919,218 @@ mod test {
         assert_eq!(r0_value, 1);
     }
 
+    #[test]
+    fn test_conditional_execution_fork() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project.clone());
+        let imm_0 = Operand::Immediate(DataWord::Word32(0));
+        let imm_1 = Operand::Immediate(DataWord::Word32(1));
+        let registers = ["R0", "R1", "R2", "R3", "R4"].map(|name| Operand::Register(name.to_owned()));
+
+        let z = executor.state.memory.unconstrained("z", 1);
+        executor.state.set_flag("Z", &z).unwrap();
+
+        // ITETE followed by one instruction outside of the block.
+        let mut program = vec![Instruction {
+            instruction_size: 16,
+            operations: vec![Operation::ConditionalExecution {
+                conditions: vec![Condition::EQ, Condition::NE, Condition::EQ, Condition::NE],
+            }],
+            max_cycle: CycleCount::Value(0),
+            memory_access: false,
+        }];
+        for register in &registers {
+            executor
+                .execute_instruction(
+                    &Instruction {
+                        instruction_size: 16,
+                        operations: vec![Operation::Move {
+                            destination: register.clone(),
+                            source: imm_0.clone(),
+                        }],
+                        max_cycle: CycleCount::Value(0),
+                        memory_access: false,
+                    },
+                    &mut NoLogger,
+                )
+                .unwrap();
+            program.push(Instruction {
+                instruction_size: 16,
+                operations: vec![Operation::Move {
+                    destination: register.clone(),
+                    source: imm_1.clone(),
+                }],
+                max_cycle: CycleCount::Value(0),
+                memory_access: false,
+            });
+        }
+
+        for p in &program {
+            executor.execute_instruction(p, &mut NoLogger).unwrap();
+        }
+        assert!(executor.state.instruction_conditions.is_empty());
+        let values = registers
+            .iter()
+            .map(|register| executor.get_operand_value(register, &mut NoLogger).unwrap().get_constant().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, [1, 0, 1, 0, 1]);
+
+        // The path forked on the first instruction in the block, the remaining
+        // conditions must still be pending on the forked path.
+        let Some(path) = executor.vm.paths.get_path() else {
+            panic!("Incorrect number of paths detected");
+        };
+        let mut executor = GAExecutor::from_state(path.state.materialize(), executor.vm, project);
+        for constraint in path.constraints.clone() {
+            executor.state.constraints.assert(&constraint);
+        }
+        assert_eq!(executor.state.instruction_conditions.len(), 3);
+
+        for p in &program[2..] {
+            executor.execute_instruction(p, &mut NoLogger).unwrap();
+        }
+        assert!(executor.state.instruction_conditions.is_empty());
+        let values = registers
+            .iter()
+            .map(|register| executor.get_operand_value(register, &mut NoLogger).unwrap().get_constant().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, [0, 1, 0, 1, 1]);
+        assert!(executor.vm.paths.get_path().is_none());
+    }
+
+    /// Runs `program` on the current path and on every path forked while
+    /// running it.
+    ///
+    /// Returns the values of `registers` at the end of each path, followed by
+    /// whether `condition` holds on that path, in the order the paths were
+    /// explored.
+    fn explore_program(
+        mut executor: GAExecutor<'_, DefaultCompositionNoLogger>,
+        project: &Arc<Project<Bitwuzla>>,
+        program: &[Instruction<DefaultCompositionNoLogger>],
+        registers: &[Operand],
+        condition: &Condition,
+    ) -> Vec<Vec<u64>> {
+        let pc = |executor: &mut GAExecutor<'_, DefaultCompositionNoLogger>| executor.state.get_register("PC").unwrap().get_constant().unwrap();
+        let start = pc(&mut executor);
+        let mut results = Vec::new();
+        loop {
+            // Forked paths resume after the instruction they forked on.
+            let next = usize::try_from((pc(&mut executor) - start) / 2).unwrap();
+            for instruction in &program[next..] {
+                executor.execute_instruction(instruction, &mut NoLogger).unwrap();
+            }
+            assert!(executor.state.instruction_conditions.is_empty());
+
+            let mut values = registers
+                .iter()
+                .map(|register| executor.get_operand_value(register, &mut NoLogger).unwrap().get_constant().unwrap())
+                .collect::<Vec<_>>();
+            let condition = executor.state.get_expr(condition).unwrap();
+            let holds = executor.state.constraints.is_sat_with_constraint(&condition).unwrap();
+            let fails = executor.state.constraints.is_sat_with_constraint(&condition.not()).unwrap();
+            assert_ne!(holds, fails, "The condition should be decided by the path");
+            values.push(u64::from(holds));
+            results.push(values);
+
+            let Some(path) = executor.vm.paths.get_path() else {
+                return results;
+            };
+            executor = GAExecutor::from_state(path.state.materialize(), executor.vm, project.clone());
+            for constraint in path.constraints {
+                executor.state.constraints.assert(&constraint);
+            }
+        }
+    }
+
+    /// A 16 bit instruction that moves `source` to `destination`.
+    fn move_instruction(destination: Operand, source: Operand) -> Instruction<DefaultCompositionNoLogger> {
+        Instruction {
+            instruction_size: 16,
+            operations: vec![Operation::Move { destination, source }],
+            max_cycle: CycleCount::Value(0),
+            memory_access: false,
+        }
+    }
+
+    #[test]
+    fn test_conditional_execution_symbolic_n_and_z() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project.clone());
+        let imm_1 = Operand::Immediate(DataWord::Word32(1));
+        let names = ["R0", "R1", "R2", "R3", "R4"];
+        for name in names {
+            let zero = executor.state.memory.from_u64(0, 32);
+            executor.state.set_register(name, zero).unwrap();
+        }
+        let registers = names.map(|name| Operand::Register(name.to_owned()));
+        for flag in ["N", "Z", "V"] {
+            let value = executor.state.memory.unconstrained(flag, 1);
+            executor.state.set_flag(flag, &value).unwrap();
+        }
+
+        // ITETE GT followed by one instruction outside of the block, GT reads
+        // both the N and the Z flag.
+        let mut program = vec![Instruction {
+            instruction_size: 16,
+            operations: vec![Operation::ConditionalExecution {
+                conditions: vec![Condition::GT, Condition::LE, Condition::GT, Condition::LE],
+            }],
+            max_cycle: CycleCount::Value(0),
+            memory_access: false,
+        }];
+        program.extend(registers.iter().map(|register| move_instruction(register.clone(), imm_1.clone())));
+
+        let results = explore_program(executor, &project, &program, &registers, &Condition::GT);
+        assert_eq!(results, [vec![1, 0, 1, 0, 1, 1], vec![0, 1, 0, 1, 1, 0]]);
+    }
+
+    #[test]
+    fn test_conditional_execution_flags_set_in_block() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project.clone());
+        let imm_1 = Operand::Immediate(DataWord::Word32(1));
+        let names = ["R0", "R1", "R2"];
+        for name in names {
+            let zero = executor.state.memory.from_u64(0, 32);
+            executor.state.set_register(name, zero).unwrap();
+        }
+        let registers = names.map(|name| Operand::Register(name.to_owned()));
+        let z = executor.state.memory.unconstrained("z", 1);
+        executor.state.set_flag("Z", &z).unwrap();
+        let new_z = executor.state.memory.unconstrained("new_z", 32);
+        executor.state.set_register("R5", new_z).unwrap();
+
+        // ITTT EQ where the second instruction in the block overwrites Z, the
+        // third instruction must read the new flag and only on paths where the
+        // second instruction ran.
+        let program = [
+            Instruction {
+                instruction_size: 16,
+                operations: vec![Operation::ConditionalExecution {
+                    conditions: vec![Condition::EQ, Condition::EQ, Condition::EQ],
+                }],
+                max_cycle: CycleCount::Value(0),
+                memory_access: false,
+            },
+            move_instruction(registers[0].clone(), imm_1.clone()),
+            move_instruction(Operand::Flag("Z".to_owned()), Operand::Register("R5".to_owned())),
+            move_instruction(registers[1].clone(), imm_1.clone()),
+            move_instruction(registers[2].clone(), imm_1),
+        ];
+
+        let results = explore_program(executor, &project, &program, &registers, &Condition::EQ);
+        assert_eq!(results, [vec![1, 1, 1, 1], vec![1, 0, 1, 0], vec![0, 0, 1, 0]]);
+    }
+
     #[test]
     fn test_ite() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project.clone());
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project.clone());
         let imm_0 = Operand::Immediate(DataWord::Word32(0));
         let imm_1 = Operand::Immediate(DataWord::Word32(1));
         let imm_2 = Operand::Immediate(DataWord::Word32(2));
@@ -2101,7 +3180,7 @@ mod test {
 
         if let Some(path) = executor.vm.paths.get_path() {
             let mut path = path;
-            let mut executor = GAExecutor::from_state(path.state, executor.vm, project);
+            let mut executor = GAExecutor::from_state(path.state.materialize(), executor.vm, project);
             assert!(executor.state.constraints.ctx == executor.state.memory.ram.ctx);
 
             for constraint in path.constraints.clone() {
@@ -2122,4 +3201,1768 @@ mod test {
 
         assert!(executor.vm.paths.get_path().is_none());
     }
+
+    #[test]
+    fn test_ternary() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project.clone());
+        let imm_0 = Operand::Immediate(DataWord::Word32(0));
+        let r0 = Operand::Register("R0".to_owned());
+        let r1 = Operand::Register("R1".to_owned());
+        let program = vec![Instruction {
+            instruction_size: 32,
+            operations: pseudo!([
+                r0:u32;
+                r1:u32;
+                imm_0:u32;
+
+                let is_zero = r1 == imm_0;
+                r0 = is_zero ? 1u32 : 2u32;
+            ]),
+            max_cycle: CycleCount::Value(0),
+            memory_access: false,
+        }];
+
+        for p in &program {
+            executor.execute_instruction(p, &mut crate::logging::NoLogger).unwrap();
+        }
+
+        let r0_value = executor.get_operand_value(&r0, &mut NoLogger).ok().unwrap().get_constant().unwrap();
+        assert_eq!(r0_value, 1);
+
+        let Some(mut path) = executor.vm.paths.get_path() else {
+            panic!("Incorrect number of paths detected");
+        };
+        let mut executor = GAExecutor::from_state(path.state.materialize(), executor.vm, project);
+        for constraint in path.constraints.clone() {
+            executor.state.constraints.assert(&constraint);
+        }
+
+        executor.resume_execution_test(0, &mut path.logger).unwrap();
+
+        let r0_value = executor.get_operand_value(&r0, &mut NoLogger).ok().unwrap().get_constant().unwrap();
+        assert_eq!(r0_value, 2);
+        assert!(executor.vm.paths.get_path().is_none());
+    }
+
+    #[test]
+    fn test_cycle_budget() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        let fast = Instruction {
+            instruction_size: 16,
+            operations: vec![Operation::Nop],
+            max_cycle: CycleCount::Value(2),
+            memory_access: false,
+        };
+        let slow = Instruction {
+            instruction_size: 16,
+            operations: vec![Operation::Nop],
+            max_cycle: CycleCount::Value(10),
+            memory_access: false,
+        };
+
+        // Fast branch, returns after a single instruction.
+        executor.execute_instruction(&fast, &mut NoLogger).unwrap();
+        executor.state.set_last_instruction(fast.clone());
+        executor.state.increment_cycle_count();
+        assert_eq!(executor.state.get_cycle_count(), 2);
+        assert_eq!(executor.state.cycle_bound_witness(5).unwrap(), None);
+
+        // Slow branch, takes the long way before returning.
+        executor.state.set_cycle_count(0);
+        for instruction in [&fast, &slow] {
+            executor.execute_instruction(instruction, &mut NoLogger).unwrap();
+            executor.state.set_last_instruction(instruction.clone());
+            executor.state.increment_cycle_count();
+        }
+        assert_eq!(executor.state.get_cycle_count(), 12);
+        assert!(executor.state.cycle_bound_witness(5).unwrap().is_some());
+        assert_eq!(executor.state.cycle_bound_witness(12).unwrap(), None);
+    }
+
+    #[test]
+    fn test_cycle_cost_load_exceeds_move() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        // mov r0, r1
+        let mov = <ArmV6M as Architecture<NoArchitectureOverride>>::translate(&[0x08, 0x46, 0x00, 0x00], &mut executor.state).unwrap();
+        // ldr r0, [r1]
+        let ldr = <ArmV6M as Architecture<NoArchitectureOverride>>::translate(&[0x08, 0x68, 0x00, 0x00], &mut executor.state).unwrap();
+
+        let ram = executor.state.memory.from_u64(0x2000_0000, 32);
+        executor.state.memory.set_register("LastAddr", ram).unwrap();
+
+        let cost = executor.state.architecture.cycle_cost();
+        let mov_cost = cost(&mov, &mut executor.state);
+        let ldr_cost = cost(&ldr, &mut executor.state);
+        assert_eq!(mov_cost, 1);
+        assert!(ldr_cost > mov_cost);
+
+        for instruction in [mov, ldr] {
+            executor.state.set_last_instruction(instruction);
+            executor.state.increment_cycle_count();
+        }
+        assert_eq!(executor.state.get_cycle_count(), mov_cost + ldr_cost);
+    }
+
+    #[test]
+    fn test_armv6m_memory_and_branch_penalties() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let cost = executor.state.architecture.cycle_cost();
+
+        // ldr r0, [r1]
+        let ldr = <ArmV6M as Architecture<NoArchitectureOverride>>::translate(&[0x08, 0x68, 0x00, 0x00], &mut executor.state).unwrap();
+        // push {r4, lr}
+        let push = <ArmV6M as Architecture<NoArchitectureOverride>>::translate(&[0x10, 0xb5, 0x00, 0x00], &mut executor.state).unwrap();
+        // bx lr
+        let bx = <ArmV6M as Architecture<NoArchitectureOverride>>::translate(&[0x70, 0x47, 0x00, 0x00], &mut executor.state).unwrap();
+
+        // Loads from the single-cycle I/O port have no wait states.
+        let sio = executor.state.memory.from_u64(0xd000_0004, 32);
+        executor.state.memory.set_register("LastAddr", sio).unwrap();
+        assert_eq!(cost(&ldr, &mut executor.state), 1);
+
+        // The push does not set the accessed address, the earlier I/O port
+        // address must not remove its wait state.
+        assert_eq!(cost(&push, &mut executor.state), 3);
+        assert_eq!(cost(&ldr, &mut executor.state), 2);
+
+        // Branches pay for refilling the pipeline.
+        assert_eq!(cost(&bx, &mut executor.state), 2);
+    }
+
+    #[test]
+    fn test_symbolic_pc_does_not_panic() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        let pc = executor.state.memory.unconstrained("pc", 32);
+        let err = executor.state.set_register("PC", pc).unwrap_err();
+        assert!(matches!(err.downcast_ref::<GAError>(), Some(GAError::NonDeterministicPC)));
+
+        // bx r1, with too many possible targets to fork for each of them.
+        let target = executor.state.memory.unconstrained("target", 32);
+        executor.state.set_register("R1", target).unwrap();
+        let bx = <ArmV6M as Architecture<NoArchitectureOverride>>::translate(&[0x08, 0x47, 0x00, 0x00], &mut executor.state).unwrap();
+        let result = executor.execute_instruction(&bx, &mut NoLogger);
+        assert!(matches!(result, ResultOrTerminate::Failure(FailureReason::SymbolicPc(_))));
+    }
+
+    #[test]
+    fn test_div_by_zero() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project.clone());
+        let r0 = Operand::Register("R0".to_owned());
+        let r1 = Operand::Register("R1".to_owned());
+        let divide = |operation| Instruction {
+            instruction_size: 32,
+            operations: vec![operation],
+            max_cycle: CycleCount::Value(0),
+            memory_access: false,
+        };
+
+        let udiv = divide(Operation::UDiv {
+            destination: r0.clone(),
+            operand1: Operand::Immediate(DataWord::Word32(7)),
+            operand2: Operand::Immediate(DataWord::Word32(0)),
+        });
+        let result = executor.execute_instruction(&udiv, &mut NoLogger);
+        assert!(matches!(result, ResultOrTerminate::Failure(FailureReason::DivByZero(_))));
+
+        // A divisor that may be zero continues as non zero and forks off a path where
+        // it is zero.
+        let divisor = executor.state.memory.unconstrained("divisor", 32);
+        executor.state.set_register("R1", divisor.clone()).unwrap();
+        let sdiv = divide(Operation::SDiv {
+            destination: r0,
+            operand1: Operand::Immediate(DataWord::Word32(7)),
+            operand2: r1,
+        });
+        assert!(matches!(executor.execute_instruction(&sdiv, &mut NoLogger), ResultOrTerminate::Result(Ok(_))));
+        let zero = executor.state.memory.from_u64(0, 32);
+        assert!(!executor.state.constraints.is_sat_with_constraint(&divisor._eq(&zero)).unwrap());
+
+        let Some(mut path) = executor.vm.paths.get_path() else {
+            panic!("No path was forked for a zero divisor");
+        };
+        let mut executor = GAExecutor::from_state(path.state.materialize(), executor.vm, project);
+        for constraint in path.constraints.clone() {
+            executor.state.constraints.assert(&constraint);
+        }
+        let result = executor.resume_execution_test(0, &mut path.logger).unwrap();
+        assert!(matches!(result, PathResult::Failure(FailureReason::DivByZero(_))));
+    }
+
+    #[test]
+    fn test_stack_overflow() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let sp = Operand::Register("SP".to_owned());
+
+        // No stack region configured, anything goes.
+        let operation = Operation::Move {
+            destination: sp.clone(),
+            source: Operand::Immediate(DataWord::Word32(0x10)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+
+        executor.state.hooks.set_stack_region(0x100, 0x200);
+
+        // Within the region.
+        let operation = Operation::Move {
+            destination: sp.clone(),
+            source: Operand::Immediate(DataWord::Word32(0x100)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let sp_value = executor.get_operand_value(&sp, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(sp_value, 0x100);
+
+        // Decrementing below the region.
+        let operation = Operation::Sub {
+            destination: sp.clone(),
+            operand1: sp.clone(),
+            operand2: Operand::Immediate(DataWord::Word32(4)),
+        };
+        match executor.execute_operation(&operation, &mut NoLogger) {
+            super::ResultOrTerminate::Failure(f) => assert!(matches!(f, FailureReason::StackOverflow(_))),
+            super::ResultOrTerminate::Result(_) => panic!("Stack overflow was not detected"),
+        }
+
+        // The stack is empty when SP points one past the region.
+        let operation = Operation::Move {
+            destination: sp.clone(),
+            source: Operand::Immediate(DataWord::Word32(0x200)),
+        };
+        assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), super::ResultOrTerminate::Result(Ok(_))));
+
+        // Popping more than was pushed leaves the region.
+        let operation = Operation::Add {
+            destination: sp.clone(),
+            operand1: sp,
+            operand2: Operand::Immediate(DataWord::Word32(4)),
+        };
+        match executor.execute_operation(&operation, &mut NoLogger) {
+            super::ResultOrTerminate::Failure(f) => assert!(matches!(f, FailureReason::StackOverflow(_))),
+            super::ResultOrTerminate::Result(_) => panic!("Leaving the top of the stack region was not detected"),
+        }
+
+        // str r1, [sp, #-4] without moving SP.
+        let store_below_sp = |executor: &mut GAExecutor<'_, DefaultCompositionNoLogger>| {
+            let operation = Operation::Sub {
+                destination: Operand::Local("address".to_owned()),
+                operand1: Operand::Register("SP".to_owned()),
+                operand2: Operand::Immediate(DataWord::Word32(4)),
+            };
+            assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), super::ResultOrTerminate::Result(Ok(_))));
+            let operation = Operation::Move {
+                destination: Operand::AddressInLocal("address".to_owned(), 32),
+                source: Operand::Register("R1".to_owned()),
+            };
+            executor.execute_operation(&operation, &mut NoLogger)
+        };
+        let sp_value = executor.state.memory.from_u64(0x104, 32);
+        executor.state.set_register("SP", sp_value).unwrap();
+        assert!(matches!(store_below_sp(&mut executor), super::ResultOrTerminate::Result(Ok(_))));
+        let sp_value = executor.state.memory.from_u64(0x100, 32);
+        executor.state.set_register("SP", sp_value).unwrap();
+        match store_below_sp(&mut executor) {
+            super::ResultOrTerminate::Failure(f) => assert!(matches!(f, FailureReason::StackOverflow(_))),
+            super::ResultOrTerminate::Result(_) => panic!("Writing below the stack region was not detected"),
+        }
+
+        // Writes that are not relative to SP are not checked.
+        let operation = Operation::Move {
+            destination: Operand::Local("address".to_owned()),
+            source: Operand::Immediate(DataWord::Word32(0x10)),
+        };
+        assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), super::ResultOrTerminate::Result(Ok(_))));
+        let operation = Operation::Move {
+            destination: Operand::AddressInLocal("address".to_owned(), 32),
+            source: Operand::Register("R1".to_owned()),
+        };
+        assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), super::ResultOrTerminate::Result(Ok(_))));
+
+        // A symbolic SP is checked with the solver.
+        let any_sp = executor.state.memory.unconstrained("sp", 32);
+        assert!(executor.state.register_write_overflows_stack("SP", &any_sp).unwrap());
+        let low = executor.state.memory.from_u64(0x100, 32);
+        let high = executor.state.memory.from_u64(0x200, 32);
+        executor.state.constraints.assert(&any_sp.ugte(&low).and(&any_sp.ulte(&high)));
+        assert!(!executor.state.register_write_overflows_stack("SP", &any_sp).unwrap());
+    }
+
+    #[test]
+    fn test_uninitialized_read() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.memory.set_poison_tracking(true);
+
+        // Initialized memory may be used as an address.
+        let operation = Operation::Move {
+            destination: Operand::Address(DataWord::Word32(0x100), 32),
+            source: Operand::Immediate(DataWord::Word32(0x200)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let operation = Operation::Move {
+            destination: Operand::Local("ptr".to_owned()),
+            source: Operand::Address(DataWord::Word32(0x100), 32),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let operation = Operation::Move {
+            destination: Operand::Local("value".to_owned()),
+            source: Operand::AddressInLocal("ptr".to_owned(), 32),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+
+        // Uninitialized memory may not.
+        let operation = Operation::Move {
+            destination: Operand::Local("ptr".to_owned()),
+            source: Operand::Address(DataWord::Word32(0x300), 32),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let operation = Operation::Move {
+            destination: Operand::Local("value".to_owned()),
+            source: Operand::AddressInLocal("ptr".to_owned(), 32),
+        };
+        match executor.execute_operation(&operation, &mut NoLogger) {
+            super::ResultOrTerminate::Failure(f) => assert!(matches!(f, FailureReason::UninitializedRead { value: "an address", .. })),
+            super::ResultOrTerminate::Result(_) => panic!("Uninitialized read was not detected"),
+        }
+    }
+
+    #[test]
+    fn test_memory_diff() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let store = |address: u32, value: u32, width: u32| Operation::Move {
+            destination: Operand::Address(DataWord::Word32(address), width),
+            source: Operand::Immediate(DataWord::Word32(value)),
+        };
+
+        executor.execute_operation(&store(0x100, 0x2a, 32), &mut NoLogger).unwrap();
+        // Nothing can be compared before a snapshot is taken.
+        assert!(matches!(executor.state.diff(), ResultOrTerminate::Result(Err(_))));
+        executor.state.snapshot().unwrap();
+
+        // Writing the same value again is not a change.
+        executor.execute_operation(&store(0x100, 0x2a, 32), &mut NoLogger).unwrap();
+        executor.execute_operation(&store(0x104, 0x55, 8), &mut NoLogger).unwrap();
+
+        let diff = executor.state.diff().unwrap();
+        assert_eq!(diff.changed.len(), 1);
+        let (address, before, after) = &diff.changed[0];
+        assert_eq!(*address, 0x104);
+        assert_eq!(before.get_constant(), None);
+        assert_eq!(after.get_constant(), Some(0x55));
+        assert!(diff.symbolic_writes.is_empty());
+
+        // Writes to symbolic addresses are reported as they are.
+        symbolic_pointer(&mut executor, &[0x200, 0x204]);
+        let value = executor.state.memory.from_u64(0x66, 8);
+        let address = executor.state.get_register("R0").unwrap();
+        executor.state.memory.set(&address, value).unwrap();
+        let diff = executor.state.diff().unwrap();
+        assert_eq!(diff.symbolic_writes.len(), 1);
+    }
+
+    #[test]
+    fn test_memory_permissions() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.hooks.set_memory_permissions(0x1000, 0x2000, Permissions::READ_EXECUTE);
+        executor.state.hooks.set_memory_permissions(0x2000_0000, 0x2000_1000, Permissions::READ_WRITE);
+
+        // Data may be read and written.
+        let operation = Operation::Move {
+            destination: Operand::Address(DataWord::Word32(0x2000_0000), 32),
+            source: Operand::Immediate(DataWord::Word32(0x4770)),
+        };
+        assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), ResultOrTerminate::Result(Ok(()))));
+        let operation = Operation::Move {
+            destination: Operand::Local("value".to_owned()),
+            source: Operand::Address(DataWord::Word32(0x2000_0000), 32),
+        };
+        assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), ResultOrTerminate::Result(Ok(()))));
+
+        // Text may not be written.
+        let operation = Operation::Move {
+            destination: Operand::Address(DataWord::Word32(0x1ffe), 32),
+            source: Operand::Immediate(DataWord::Word32(0)),
+        };
+        match executor.execute_operation(&operation, &mut NoLogger) {
+            ResultOrTerminate::Failure(f) => assert!(f.to_string().starts_with("Writing to static memory not permitted. Write access to 0x1ffe")),
+            ResultOrTerminate::Result(_) => panic!("Write to read only memory was not detected"),
+        }
+
+        // Data may not be executed.
+        executor.state.memory.set_pc(0x2000_0000).unwrap();
+        match executor.state.get_next_instruction(&mut NoLogger) {
+            ResultOrTerminate::Failure(f) => assert!(f.to_string().starts_with("Execute access to 0x20000000 is not permitted.")),
+            ResultOrTerminate::Result(_) => panic!("Execution of data was not detected"),
+        }
+    }
+
+    #[test]
+    fn test_symbolic_memory_permissions() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.hooks.set_memory_permissions(0x1000, 0x2000, Permissions::READ_EXECUTE);
+        executor.state.hooks.set_memory_permissions(0x2000_0000, 0x2000_1000, Permissions::READ_WRITE);
+
+        // An address that can only point in to data may be written.
+        let address = executor.state.memory.unconstrained("address", 32);
+        let low = executor.state.memory.from_u64(0x2000_0000, 32);
+        let high = executor.state.memory.from_u64(0x2000_0100, 32);
+        executor.state.constraints.assert(&address.ugte(&low).and(&address.ult(&high)));
+        let value = executor.state.memory.from_u64(0, 32);
+        assert!(matches!(executor.state.write_memory(address, value.clone(), 32), ResultOrTerminate::Result(Ok(()))));
+
+        // An address that may point in to text may be read but not written.
+        let address = executor.state.memory.unconstrained("any_address", 32);
+        assert!(matches!(executor.state.read_memory(address.clone(), 32), ResultOrTerminate::Result(Ok(_))));
+        match executor.state.write_memory(address, value, 32) {
+            ResultOrTerminate::Failure(f) => assert!(f.to_string().contains("Write access through a symbolic address may not be permitted.")),
+            ResultOrTerminate::Result(_) => panic!("Symbolic write to read only memory was not detected"),
+        }
+    }
+
+    #[test]
+    fn test_execute_permission_covers_whole_instruction() {
+        let vm_with_text_end = |text_end: u64| {
+            let ctx = crate::smt::bitwuzla::Bitwuzla::new();
+            // bl, a 32 bit instruction.
+            let project = Arc::new(Project::manual_project(
+                vec![0x00, 0xf0, 0x00, 0xf8],
+                0x1000,
+                0x1004,
+                WordSize::Bit32,
+                Endianness::Little,
+                HashMap::new(),
+            ));
+            let mut hooks = HookContainer::new();
+            hooks.set_memory_permissions(0x1000, 0x1004, Permissions::READ_ONLY);
+            hooks.set_memory_permissions(0x1000, text_end, Permissions::READ_EXECUTE);
+            let state = GAState::<DefaultCompositionNoLogger>::create_test_state(
+                project.clone(),
+                ctx.clone(),
+                ctx,
+                0x1000,
+                0,
+                hooks,
+                (),
+                crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
+            );
+            VM::new_test_vm(project, state, NoLogger)
+        };
+
+        let mut vm = vm_with_text_end(0x1004);
+        let mut state = vm.paths.get_path().unwrap().state.materialize();
+        state.memory.set_pc(0x1000).unwrap();
+        assert!(matches!(state.get_next_instruction(&mut NoLogger), ResultOrTerminate::Result(Ok(_))));
+
+        // Only the first half of the instruction may be executed.
+        let mut vm = vm_with_text_end(0x1002);
+        let mut state = vm.paths.get_path().unwrap().state.materialize();
+        state.memory.set_pc(0x1000).unwrap();
+        match state.get_next_instruction(&mut NoLogger) {
+            ResultOrTerminate::Failure(f) => assert!(f.to_string().starts_with("Execute access to 0x1000 is not permitted.")),
+            ResultOrTerminate::Result(_) => panic!("Executing the second half of the instruction was not detected"),
+        }
+    }
+
+    #[test]
+    fn test_multiple_memory_hooks() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor
+            .state
+            .hooks
+            .add_range_memory_write_hook((0x100, 0x200), |state, value, _address| state.set_register("R2", value))
+            .add_memory_write_hook(0x100, |state, value, _address| state.set_register("R3", value))
+            .add_range_memory_read_hook((0x100, 0x200), |state, _address| {
+                let value = state.memory.from_u64(1, 32);
+                state.set_register("R4", value.clone())?;
+                Ok(value)
+            })
+            .add_memory_read_hook(0x100, |state, _address| Ok(state.memory.from_u64(2, 32)));
+
+        // Both write hooks are run.
+        let address = executor.state.memory.from_u64(0x100, 32);
+        let value = executor.state.memory.from_u64(5, 32);
+        assert!(matches!(executor.state.write_memory(address.clone(), value, 32), ResultOrTerminate::Result(Ok(()))));
+        assert_eq!(executor.state.get_register("R2").unwrap().get_constant(), Some(5));
+        assert_eq!(executor.state.get_register("R3").unwrap().get_constant(), Some(5));
+
+        // Both read hooks are run, the hook for the exact address provides the value.
+        let ResultOrTerminate::Result(Ok(value)) = executor.state.read_memory(address, 32) else {
+            panic!("Reading through multiple hooks failed");
+        };
+        assert_eq!(value.get_constant(), Some(2));
+        assert_eq!(executor.state.get_register("R4").unwrap().get_constant(), Some(1));
+    }
+
+    #[test]
+    fn test_mmio_region() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.hooks.add_mmio_region(0x4000_0000, 0x4000_1000);
+
+        // Writes are dropped.
+        let operation = Operation::Move {
+            destination: Operand::Address(DataWord::Word32(0x4000_0000), 32),
+            source: Operand::Immediate(DataWord::Word32(1)),
+        };
+        assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), ResultOrTerminate::Result(Ok(()))));
+
+        for local in ["first", "second"] {
+            let operation = Operation::Move {
+                destination: Operand::Local(local.to_owned()),
+                source: Operand::Address(DataWord::Word32(0x4000_0000), 32),
+            };
+            assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), ResultOrTerminate::Result(Ok(()))));
+        }
+
+        let first = executor.context.locals.get("first").unwrap().clone();
+        let second = executor.context.locals.get("second").unwrap().clone();
+        assert!(first.get_constant().is_none());
+        assert!(executor.state.constraints.is_sat_with_constraint(&first._ne(&second)).unwrap());
+    }
+
+    #[test]
+    fn test_taint_propagation() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.taint.set_enabled(true);
+
+        let input = executor.state.memory.unconstrained("input", 32);
+        executor.state.set_register("R0", input).unwrap();
+        let three = executor.state.memory.from_u64(3, 32);
+        executor.state.set_register("R2", three).unwrap();
+
+        let operation = Operation::Add {
+            destination: Operand::Register("R1".to_owned()),
+            operand1: Operand::Register("R0".to_owned()),
+            operand2: Operand::Immediate(DataWord::Word32(1)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let operation = Operation::Move {
+            destination: Operand::Address(DataWord::Word32(0x100), 32),
+            source: Operand::Register("R1".to_owned()),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let operation = Operation::Move {
+            destination: Operand::Address(DataWord::Word32(0x104), 32),
+            source: Operand::Register("R2".to_owned()),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+
+        let taint = &executor.state.taint;
+        assert!(taint.is_tainted_by(&TaintLocation::Register("R1".to_owned()), "input"));
+        for location in TaintLocation::memory(0x100, 32) {
+            assert!(taint.is_tainted_by(&location, "input"));
+        }
+        for location in TaintLocation::memory(0x104, 32) {
+            assert!(taint.labels(&location).is_none());
+        }
+
+        // Overwriting the value with a constant clears the taint.
+        let operation = Operation::Move {
+            destination: Operand::Register("R1".to_owned()),
+            source: Operand::Immediate(DataWord::Word32(0)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        assert!(!executor.state.taint.is_tainted_by(&TaintLocation::Register("R1".to_owned()), "input"));
+    }
+
+    #[test]
+    fn test_saturating_add() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let r0 = Operand::Register("R0".to_owned());
+
+        // i16::MAX + 1 saturates to i16::MAX.
+        let operation = Operation::SaturatingAdd {
+            destination: r0.clone(),
+            operand1: Operand::Immediate(DataWord::Word32(i16::MAX as u32)),
+            operand2: Operand::Immediate(DataWord::Word32(1)),
+            signed: true,
+            bits: 16,
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, i16::MAX as u64);
+
+        // i16::MIN + -1 saturates to i16::MIN.
+        let operation = Operation::SaturatingAdd {
+            destination: r0.clone(),
+            operand1: Operand::Immediate(DataWord::Word32(i16::MIN as i32 as u32)),
+            operand2: Operand::Immediate(DataWord::Word32(-1i32 as u32)),
+            signed: true,
+            bits: 16,
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, i16::MIN as i32 as u32 as u64);
+
+        // u16::MAX + 1 saturates to u16::MAX.
+        let operation = Operation::SaturatingAdd {
+            destination: r0.clone(),
+            operand1: Operand::Immediate(DataWord::Word32(u16::MAX as u32)),
+            operand2: Operand::Immediate(DataWord::Word32(1)),
+            signed: false,
+            bits: 16,
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, u16::MAX as u64);
+
+        // No saturation.
+        let operation = Operation::SaturatingAdd {
+            destination: r0.clone(),
+            operand1: Operand::Immediate(DataWord::Word32(40)),
+            operand2: Operand::Immediate(DataWord::Word32(2)),
+            signed: true,
+            bits: 16,
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, 42);
+    }
+
+    #[test]
+    fn test_saturating_sub() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let r0 = Operand::Register("R0".to_owned());
+
+        // i16::MIN - 1 saturates to i16::MIN.
+        let operation = Operation::SaturatingSub {
+            destination: r0.clone(),
+            operand1: Operand::Immediate(DataWord::Word32(i16::MIN as i32 as u32)),
+            operand2: Operand::Immediate(DataWord::Word32(1)),
+            signed: true,
+            bits: 16,
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, i16::MIN as i32 as u32 as u64);
+
+        // i16::MAX - -1 saturates to i16::MAX.
+        let operation = Operation::SaturatingSub {
+            destination: r0.clone(),
+            operand1: Operand::Immediate(DataWord::Word32(i16::MAX as u32)),
+            operand2: Operand::Immediate(DataWord::Word32(-1i32 as u32)),
+            signed: true,
+            bits: 16,
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, i16::MAX as u64);
+
+        // 0 - 1 saturates to 0.
+        let operation = Operation::SaturatingSub {
+            destination: r0.clone(),
+            operand1: Operand::Immediate(DataWord::Word32(0)),
+            operand2: Operand::Immediate(DataWord::Word32(1)),
+            signed: false,
+            bits: 16,
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, 0);
+    }
+
+    #[test]
+    fn test_while() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let r0 = Operand::Register("R0".to_owned());
+        let n = Operand::Immediate(DataWord::Word32(10));
+
+        let operations = pseudo!([
+            r0:u32;
+            n:u32;
+            let sum:u32 = 0u32;
+            let i:u32 = 0u32;
+            while(i < n) {
+                i = i + 1u32;
+                sum = sum + i;
+            }
+            r0 = sum;
+        ]);
+        for operation in &operations {
+            executor.execute_operation(operation, &mut NoLogger).ok();
+        }
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, 55);
+    }
+
+    #[test]
+    fn test_for_range() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let r0 = Operand::Register("R0".to_owned());
+        let r1 = Operand::Register("R1".to_owned());
+
+        let operations = pseudo!([
+            r0:u32;
+            r1:u32;
+            let count:u32 = 0u32;
+            let sum:u32 = 0u32;
+            for i in 0..7u32 {
+                count = count + 1u32;
+                sum = sum + i;
+            }
+            r0 = count;
+            r1 = sum;
+        ]);
+        for operation in &operations {
+            executor.execute_operation(operation, &mut NoLogger).ok();
+        }
+        let count = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(count, 7);
+        let sum = executor.get_operand_value(&r1, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(sum, 21);
+    }
+
+    #[test]
+    fn test_signed_shift() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let r0 = Operand::Register("R0".to_owned());
+        let r1 = Operand::Register("R1".to_owned());
+        let r2 = Operand::Register("R2".to_owned());
+        let signed = Operand::Immediate(DataWord::Word32(0x8000_0000));
+        let unsigned = Operand::Immediate(DataWord::Word32(0x8000_0000));
+
+        // `>>` is logical regardless of signedness, `asr` preserves the sign.
+        let operations = pseudo!([
+            r0:i32;
+            r1:u32;
+            r2:i32;
+            signed:i32;
+            unsigned:u32;
+            r0 = signed asr 4u32;
+            r1 = unsigned >> 4u32;
+            r2 = signed >> 4u32;
+        ]);
+        for operation in &operations {
+            executor.execute_operation(operation, &mut NoLogger).ok();
+        }
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, 0xf800_0000);
+        let result = executor.get_operand_value(&r1, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, 0x0800_0000);
+        let result = executor.get_operand_value(&r2, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, 0x0800_0000);
+    }
+    #[test]
+    fn test_constant_folding_equivalence() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let registers = ["R0", "R1", "R2", "R3", "R4", "R5"].map(|name| Operand::Register(name.to_owned()));
+        let [r0, r1, r2, r3, r4, r5] = registers.clone();
+
+        // Operations on literals are folded by `pseudo!`.
+        let folded = pseudo!([
+            r0:u32;
+            r1:u32;
+            r2:u32;
+            r3:i32;
+            r4:i32;
+            r5:i32;
+            r0 = 0xf0u32 << 28u32;
+            r1 = 2u32 - 5u32;
+            r2 = 7u32 / 2u32;
+            r3 = 2i32 - 5i32;
+            r4 = -7i32 / 2i32;
+            r5 = -16i32 >> 28i32;
+        ]);
+        let imm = |value: i32| Operand::Immediate(DataWord::Word32(value.cast_unsigned()));
+        let unfolded = [
+            Operation::Sl {
+                destination: r0,
+                operand: imm(0xf0),
+                shift: imm(28),
+            },
+            Operation::Sub {
+                destination: r1,
+                operand1: imm(2),
+                operand2: imm(5),
+            },
+            Operation::UDiv {
+                destination: r2,
+                operand1: imm(7),
+                operand2: imm(2),
+            },
+            Operation::Sub {
+                destination: r3,
+                operand1: imm(2),
+                operand2: imm(5),
+            },
+            Operation::SDiv {
+                destination: r4,
+                operand1: imm(-7),
+                operand2: imm(2),
+            },
+            Operation::Srl {
+                destination: r5,
+                operand: imm(-16),
+                shift: imm(28),
+            },
+        ];
+        assert!(folded.iter().all(|operation| matches!(operation, Operation::Move { .. })));
+
+        let mut run = |operations: &[Operation]| {
+            for operation in operations {
+                executor.execute_operation(operation, &mut NoLogger).ok();
+            }
+            registers
+                .iter()
+                .map(|register| executor.get_operand_value(register, &mut NoLogger).unwrap().get_constant().unwrap())
+                .collect::<Vec<_>>()
+        };
+        let folded = run(&folded);
+        assert_eq!(folded, run(&unfolded));
+        let minus_three = u64::from((-3i32).cast_unsigned());
+        assert_eq!(folded, vec![0, minus_three, 3, minus_three, minus_three, 15]);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let r0 = Operand::Register("R0".to_owned());
+        let r1 = Operand::Register("R1".to_owned());
+        let table = Operand::Immediate(DataWord::Word32(0x100));
+
+        for (address, value) in [(0x100, 3), (0x104, 5), (0x108, 7), (0x10c, 11)] {
+            let operation = Operation::Move {
+                destination: Operand::Address(DataWord::Word32(address), 32),
+                source: Operand::Immediate(DataWord::Word32(value)),
+            };
+            executor.execute_operation(&operation, &mut NoLogger).ok();
+        }
+
+        let operations = pseudo!([
+            r0:u32;
+            r1:u32;
+            let sum:u32 = 0u32;
+            for i in 0..4u32 {
+                sum = sum + table[i];
+            }
+            r0 = sum;
+            r1 = table[2u32];
+        ]);
+        for operation in &operations {
+            executor.execute_operation(operation, &mut NoLogger).ok();
+        }
+        let result = executor.get_operand_value(&r0, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, 26);
+        let result = executor.get_operand_value(&r1, &mut NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(result, 7);
+    }
+
+    #[test]
+    fn test_memcpy_intrinsic() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        for (address, value) in [(0x100, 0x1122_3344), (0x104, 0x5566_7788)] {
+            let operation = Operation::Move {
+                destination: Operand::Address(DataWord::Word32(address), 32),
+                source: Operand::Immediate(DataWord::Word32(value)),
+            };
+            executor.execute_operation(&operation, &mut NoLogger).ok();
+        }
+        for (register, value) in [("R0", 0x200), ("R1", 0x100), ("R2", 8), ("LR", 0x1000)] {
+            let value = executor.state.memory.from_u64(value, 32);
+            executor.state.set_register(register, value).unwrap();
+        }
+
+        executor.run_forking_intrinsic(super::hooks::memcpy, &NoLogger).unwrap();
+
+        for (address, value) in [(0x200, 0x1122_3344), (0x204, 0x5566_7788)] {
+            let result = executor.get_operand_value(&Operand::Address(DataWord::Word32(address), 32), &mut NoLogger).unwrap();
+            assert_eq!(result.get_constant().unwrap(), value);
+        }
+        let pc = executor.state.get_register("PC").unwrap().get_constant().unwrap();
+        assert_eq!(pc, 0x1000);
+    }
+
+    #[test]
+    fn test_memset_intrinsic_symbolic_length() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        let operation = Operation::Move {
+            destination: Operand::Address(DataWord::Word32(0x300), 32),
+            source: Operand::Immediate(DataWord::Word32(0)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        for (register, value) in [("R0", 0x300), ("R1", 0xab), ("LR", 0x1000)] {
+            let value = executor.state.memory.from_u64(value, 32);
+            executor.state.set_register(register, value).unwrap();
+        }
+        let length = executor.state.memory.unconstrained("length", 32);
+        let two = executor.state.memory.from_u64(2, 32);
+        let four = executor.state.memory.from_u64(4, 32);
+        executor.state.constraints.assert(&length._eq(&two).or(&length._eq(&four)));
+        executor.state.set_register("R2", length).unwrap();
+
+        // The first run splits the path on the possible lengths.
+        executor.run_forking_intrinsic(super::hooks::memset, &NoLogger).unwrap();
+        assert_eq!(executor.vm.paths.waiting_paths(), 1);
+
+        // The second run fills memory using the length of this path.
+        executor.run_forking_intrinsic(super::hooks::memset, &NoLogger).unwrap();
+        let result = executor.get_operand_value(&Operand::Address(DataWord::Word32(0x300), 32), &mut NoLogger).unwrap();
+        assert!([0xabab, 0xabab_abab].contains(&result.get_constant().unwrap()));
+    }
+
+    #[test]
+    fn test_memcpy_operation() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        for (address, value) in [(0x100, 0x1122_3344), (0x104, 0x5566_7788), (0x208, 0x99aa_bbcc)] {
+            let operation = Operation::Move {
+                destination: Operand::Address(DataWord::Word32(address), 32),
+                source: Operand::Immediate(DataWord::Word32(value)),
+            };
+            executor.execute_operation(&operation, &mut NoLogger).ok();
+        }
+
+        let operation = Operation::Memcpy {
+            destination: Operand::Immediate(DataWord::Word32(0x200)),
+            source: Operand::Immediate(DataWord::Word32(0x100)),
+            length: Operand::Immediate(DataWord::Word32(8)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+
+        // Memory after the copied region is left untouched.
+        for (address, value) in [(0x200, 0x1122_3344), (0x204, 0x5566_7788), (0x208, 0x99aa_bbcc)] {
+            let result = executor.get_operand_value(&Operand::Address(DataWord::Word32(address), 32), &mut NoLogger).unwrap();
+            assert_eq!(result.get_constant().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_memset_operation() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        let operation = Operation::Move {
+            destination: Operand::Address(DataWord::Word32(0x300), 32),
+            source: Operand::Immediate(DataWord::Word32(0)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+
+        let operation = Operation::Memset {
+            destination: Operand::Immediate(DataWord::Word32(0x300)),
+            value: Operand::Immediate(DataWord::Word32(0x12ab)),
+            length: Operand::Immediate(DataWord::Word32(3)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        let result = executor.get_operand_value(&Operand::Address(DataWord::Word32(0x300), 32), &mut NoLogger).unwrap();
+        assert_eq!(result.get_constant().unwrap(), 0x00ab_abab);
+
+        // A symbolic length splits the path on the possible lengths.
+        let length = executor.state.memory.unconstrained("length", 32);
+        let one = executor.state.memory.from_u64(1, 32);
+        let four = executor.state.memory.from_u64(4, 32);
+        executor.state.constraints.assert(&length._eq(&one).or(&length._eq(&four)));
+        executor.state.set_register("R2", length).unwrap();
+        let operation = Operation::Memset {
+            destination: Operand::Immediate(DataWord::Word32(0x300)),
+            value: Operand::Immediate(DataWord::Word32(0xcd)),
+            length: Operand::Register("R2".to_owned()),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        assert_eq!(executor.vm.paths.waiting_paths(), 1);
+        let result = executor.get_operand_value(&Operand::Address(DataWord::Word32(0x300), 32), &mut NoLogger).unwrap();
+        assert!([0x00ab_abcd, 0xcdcd_cdcd].contains(&result.get_constant().unwrap()));
+    }
+
+    #[test]
+    fn test_stack_arguments() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        for (register, value) in [("R3", 0x33), ("SP", 0x400)] {
+            let value = executor.state.memory.from_u64(value, 32);
+            executor.state.set_register(register, value).unwrap();
+        }
+        for (address, value) in [(0x400, 0x44), (0x404, 0x55)] {
+            let operation = Operation::Move {
+                destination: Operand::Address(DataWord::Word32(address), 32),
+                source: Operand::Immediate(DataWord::Word32(value)),
+            };
+            executor.execute_operation(&operation, &mut NoLogger).ok();
+        }
+
+        // Arguments past the argument registers are read from the stack.
+        for (n, value) in [(3, 0x33), (4, 0x44), (5, 0x55)] {
+            assert_eq!(executor.state.argument(n).unwrap().get_constant().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_memory_operation_length_limit() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let length = super::state::MAX_MEMORY_OPERATION_LENGTH + 1;
+
+        let operation = Operation::Memset {
+            destination: Operand::Immediate(DataWord::Word32(0x300)),
+            value: Operand::Immediate(DataWord::Word32(0)),
+            length: Operand::Immediate(DataWord::Word32(u32::try_from(length).unwrap())),
+        };
+        match executor.execute_operation(&operation, &mut NoLogger) {
+            ResultOrTerminate::Result(Err(error)) => assert!(matches!(error.downcast_ref::<GAError>(), Some(GAError::MemoryOperationTooLong(len)) if *len == length)),
+            _ => panic!("Expected the length to be rejected"),
+        }
+
+        // The intrinsics share the same limit.
+        for (register, value) in [("R0", 0x300), ("R1", 0x100), ("R2", length), ("LR", 0x1000)] {
+            let value = executor.state.memory.from_u64(value, 32);
+            executor.state.set_register(register, value).unwrap();
+        }
+        let error = executor.run_forking_intrinsic(super::hooks::memcpy, &NoLogger).unwrap_err();
+        assert!(matches!(error.downcast_ref::<GAError>(), Some(GAError::MemoryOperationTooLong(len)) if *len == length));
+    }
+
+    fn symbolic_read_instruction() -> Instruction<DefaultCompositionNoLogger> {
+        Instruction {
+            instruction_size: 32,
+            operations: vec![
+                Operation::Move {
+                    destination: Operand::Local("ptr".to_owned()),
+                    source: Operand::Register("R0".to_owned()),
+                },
+                Operation::Move {
+                    destination: Operand::Register("R1".to_owned()),
+                    source: Operand::AddressInLocal("ptr".to_owned(), 32),
+                },
+            ],
+            max_cycle: CycleCount::Value(0),
+            memory_access: true,
+        }
+    }
+
+    /// Sets R0 to a symbolic pointer that may point to any of `addresses`.
+    fn symbolic_pointer(executor: &mut GAExecutor<'_, DefaultCompositionNoLogger>, addresses: &[u64]) {
+        let ptr = executor.state.memory.unconstrained("ptr", 32);
+        let constraint = addresses
+            .iter()
+            .map(|address| ptr._eq(&executor.state.memory.from_u64(*address, 32)))
+            .reduce(|acc, el| acc.or(&el))
+            .unwrap();
+        executor.state.constraints.assert(&constraint);
+        executor.state.set_register("R0", ptr).unwrap();
+    }
+
+    #[test]
+    fn test_symbolic_address_write() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project.clone());
+        symbolic_pointer(&mut executor, &[0x100, 0x104]);
+        let instruction = Instruction {
+            instruction_size: 32,
+            operations: vec![
+                Operation::Move {
+                    destination: Operand::Local("ptr".to_owned()),
+                    source: Operand::Register("R0".to_owned()),
+                },
+                Operation::Move {
+                    destination: Operand::AddressInLocal("ptr".to_owned(), 32),
+                    source: Operand::Immediate(DataWord::Word32(0x2a)),
+                },
+            ],
+            max_cycle: CycleCount::Value(0),
+            memory_access: true,
+        };
+        fn written(executor: &mut GAExecutor<'_, DefaultCompositionNoLogger>) -> [bool; 2] {
+            [0x100, 0x104].map(|address| {
+                executor
+                    .get_operand_value(&Operand::Address(DataWord::Word32(address), 32), &mut NoLogger)
+                    .unwrap()
+                    .get_constant()
+                    == Some(0x2a)
+            })
+        }
+
+        // The write is forked over both targets, each path writes to one of them.
+        executor.execute_instruction(&instruction, &mut NoLogger).unwrap();
+        assert_eq!(executor.vm.paths.waiting_paths(), 1);
+        let [first, second] = written(&mut executor);
+        assert_ne!(first, second);
+
+        let path = vm.paths.get_path().unwrap();
+        let mut executor = GAExecutor::from_state(path.state.materialize(), &mut vm, project);
+        for constraint in &path.constraints {
+            executor.state.assert(constraint);
+        }
+        let instruction = executor.state.continue_in_instruction.take().unwrap();
+        assert!(matches!(
+            executor.continue_executing_instruction(&instruction, &mut NoLogger),
+            ResultOrTerminate::Result(Ok(()))
+        ));
+        assert_eq!(written(&mut executor), [second, first]);
+    }
+
+    #[test]
+    fn test_address_solution_limit() {
+        let limits = SolutionLimits {
+            max_address_solutions: 2,
+            max_fork_solutions: 2,
+            max_table_entries: 0,
+            suppress_exceeding: true,
+        };
+
+        // Within the bound the path is forked once per extra address.
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.solution_limits = limits;
+        symbolic_pointer(&mut executor, &[0x100, 0x104]);
+        executor.execute_instruction(&symbolic_read_instruction(), &mut NoLogger).unwrap();
+        assert_eq!(executor.vm.paths.waiting_paths(), 1);
+
+        // Exceeding the bound suppresses the path.
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.solution_limits = limits;
+        symbolic_pointer(&mut executor, &[0x100, 0x104, 0x108]);
+        let ResultOrTerminate::Result(Err(error)) = executor.execute_instruction(&symbolic_read_instruction(), &mut NoLogger) else {
+            panic!("Exceeding the bound was not detected");
+        };
+        assert!(matches!(executor.suppress_or_error(error, &mut NoLogger), Ok(PathResult::Suppress)));
+        assert_eq!(executor.vm.paths.waiting_paths(), 0);
+    }
+
+    #[test]
+    fn test_address_solution_limit_without_suppression() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.solution_limits.max_address_solutions = 2;
+        symbolic_pointer(&mut executor, &[0x100, 0x104, 0x108]);
+
+        // The address is kept symbolic rather than forked on.
+        executor.execute_instruction(&symbolic_read_instruction(), &mut NoLogger).unwrap();
+        assert_eq!(executor.vm.paths.waiting_paths(), 0);
+    }
+
+    #[test]
+    fn test_taint_unresolved_address_write() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.taint.set_enabled(true);
+        executor.state.solution_limits.max_address_solutions = 2;
+        symbolic_pointer(&mut executor, &[0x100, 0x104, 0x108]);
+        let input = executor.state.memory.unconstrained("input", 32);
+        executor.state.set_register("R1", input).unwrap();
+
+        let instruction = Instruction {
+            instruction_size: 32,
+            operations: vec![
+                Operation::Move {
+                    destination: Operand::Local("ptr".to_owned()),
+                    source: Operand::Register("R0".to_owned()),
+                },
+                Operation::Move {
+                    destination: Operand::AddressInLocal("ptr".to_owned(), 32),
+                    source: Operand::Register("R1".to_owned()),
+                },
+            ],
+            max_cycle: CycleCount::Value(0),
+            memory_access: true,
+        };
+        executor.execute_instruction(&instruction, &mut NoLogger).unwrap();
+        assert_eq!(executor.vm.paths.waiting_paths(), 0);
+
+        // The write may have hit any byte, reading any of them is tainted.
+        for location in TaintLocation::memory(0x108, 32) {
+            assert!(executor.state.taint.is_tainted_by(&location, "input"));
+        }
+        let operation = Operation::Move {
+            destination: Operand::Register("R2".to_owned()),
+            source: Operand::Address(DataWord::Word32(0x108), 32),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).ok();
+        assert!(executor.state.taint.is_tainted_by(&TaintLocation::Register("R2".to_owned()), "input"));
+    }
+
+    fn table_vm(targets: &[u32], read_only: bool) -> VM<DefaultCompositionNoLogger> {
+        let table = targets.iter().flat_map(|target| target.to_le_bytes()).collect();
+        let end = 0x1000 + 4 * targets.len() as u64;
+        let ctx = crate::smt::bitwuzla::Bitwuzla::new();
+        let project = Arc::new(if read_only {
+            Project::manual_read_only_project(table, 0x1000, end, WordSize::Bit32, Endianness::Little)
+        } else {
+            Project::manual_project(table, 0x1000, end, WordSize::Bit32, Endianness::Little, HashMap::new())
+        });
+        let state = GAState::<DefaultCompositionNoLogger>::create_test_state(
+            project.clone(),
+            ctx.clone(),
+            ctx,
+            0,
+            0,
+            HookContainer::new(),
+            (),
+            crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
+        );
+        VM::new_test_vm(project, state, NoLogger)
+    }
+
+    #[test]
+    fn test_jump_table_forks_over_entries() {
+        let targets = [0x2000_u32, 0x2010, 0x2020, 0x2030];
+        let mut vm = table_vm(&targets, true);
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.solution_limits.max_table_entries = 8;
+        // Too few address solutions to resolve the table by forking on the address.
+        executor.state.solution_limits.max_address_solutions = 2;
+
+        let index = executor.state.memory.unconstrained("index", 32);
+        let entries = executor.state.memory.from_u64(targets.len() as u64, 32);
+        executor.state.constraints.assert(&index.ult(&entries));
+        let entry_size = executor.state.memory.from_u64(4, 32);
+        let base = executor.state.memory.from_u64(0x1000, 32);
+        let entry = base.add(&index.mul(&entry_size));
+        executor.state.set_register("R0", entry).unwrap();
+
+        let instruction = Instruction {
+            instruction_size: 32,
+            operations: vec![
+                Operation::Move {
+                    destination: Operand::Local("entry".to_owned()),
+                    source: Operand::Register("R0".to_owned()),
+                },
+                Operation::Move {
+                    destination: Operand::Register("PC".to_owned()),
+                    source: Operand::AddressInLocal("entry".to_owned(), 32),
+                },
+            ],
+            max_cycle: CycleCount::Value(0),
+            memory_access: true,
+        };
+        executor.execute_instruction(&instruction, &mut NoLogger).unwrap();
+
+        // One path per table entry.
+        assert_eq!(executor.vm.paths.waiting_paths(), targets.len() - 1);
+        let pc = executor.state.get_register("PC").unwrap().get_constant().unwrap();
+        assert!(targets.iter().any(|target| u64::from(*target) == pc));
+    }
+
+    #[test]
+    fn test_table_read_requirements() {
+        let targets = [0x2000_u32, 0x2010, 0x2020, 0x2030];
+        let table_entry = |executor: &mut GAExecutor<'_, DefaultCompositionNoLogger>, indices: &[u64]| {
+            let index = executor.state.memory.unconstrained("index", 32);
+            let mut valid = executor.state.memory.from_bool(false);
+            for i in indices {
+                valid = valid.or(&index._eq(&executor.state.memory.from_u64(*i, 32)));
+            }
+            executor.state.constraints.assert(&valid);
+            let base = executor.state.memory.from_u64(0x1000, 32);
+            base.add(&index.mul(&executor.state.memory.from_u64(4, 32)))
+        };
+
+        let mut vm = table_vm(&targets, true);
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let entry = table_entry(&mut executor, &[0, 1, 2, 3]);
+
+        // Table reads are opt in.
+        assert!(executor.read_table(&entry, 32).unwrap().is_none());
+        executor.state.solution_limits.max_table_entries = 8;
+        assert!(executor.read_table(&entry, 32).unwrap().is_some());
+
+        // Unevenly spaced entries are not a table.
+        let mut vm = table_vm(&targets, true);
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.solution_limits.max_table_entries = 8;
+        let entry = table_entry(&mut executor, &[0, 1, 3]);
+        assert!(executor.read_table(&entry, 32).unwrap().is_none());
+
+        // Writable memory may have changed since the table was built.
+        let mut vm = table_vm(&targets, false);
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.solution_limits.max_table_entries = 8;
+        let entry = table_entry(&mut executor, &[0, 1, 2, 3]);
+        assert!(executor.read_table(&entry, 32).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_fork_solution_limit() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.solution_limits.max_fork_solutions = 2;
+        let value = executor.state.memory.unconstrained("value", 32);
+        let bound = executor.state.memory.from_u64(3, 32);
+        executor.state.constraints.assert(&value.ult(&bound));
+
+        let ResultOrTerminate::Result(Err(error)) = executor.fork_for_all(&value, &NoLogger) else {
+            panic!("Exceeding the bound was not detected");
+        };
+        // Without suppression the error is kept.
+        assert!(executor.suppress_or_error(error, &mut NoLogger).is_err());
+    }
+
+    #[test]
+    fn test_model_from_state() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.entry_subprogram = Some(SubProgram {
+            name: "entry".to_owned(),
+            bounds: (0, 0),
+            file: None,
+            call_file: None,
+        });
+        let a = executor.state.memory.unconstrained("a", 32);
+        let b = executor.state.memory.unconstrained("b", 32);
+        let ten = executor.state.memory.from_u64(10, 32);
+        executor.state.constraints.assert(&a.add(&b)._eq(&ten));
+        executor.state.constraints.assert(&a.ugt(&b));
+
+        let model = Model::from_state(&executor.state).unwrap();
+        assert_eq!(model.entry(), "entry");
+        let (a_value, b_value) = (model.get("a").unwrap(), model.get("b").unwrap());
+        assert_eq!(a_value.wrapping_add(b_value) & 0xffff_ffff, 10);
+        assert!(a_value > b_value);
+
+        // The solver state is left untouched.
+        let a_value = executor.state.memory.from_u64(a_value, 32);
+        assert!(executor.state.constraints.is_sat_with_constraint(&a._ne(&a_value)).unwrap());
+    }
+
+    #[test]
+    fn test_concretize() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let input = executor.state.memory.unconstrained("input", 32);
+        let seven = executor.state.memory.from_u64(7, 32);
+        executor.state.constraints.assert(&input.mul(&seven)._eq(&executor.state.memory.from_u64(42, 32)));
+        executor.state.constraints.assert(&input.ult(&seven));
+
+        assert_eq!(executor.state.concretize("input"), Some(6));
+        assert_eq!(executor.state.concretize("missing"), None);
+        assert_eq!(executor.state.named_inputs().unwrap().get("input"), Some(&6));
+    }
+
+    #[test]
+    fn test_replay_disables_forking() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let model = Model::new("entry".to_owned(), [("value".to_owned(), 2)].into_iter().collect());
+        executor.state.replay = Some(Replay::new(model));
+
+        // Inputs in the model are fixed.
+        let value = executor.state.memory.unconstrained("value", 32);
+        executor.state.fix_replay_inputs().unwrap();
+        let ResultOrTerminate::Result(Ok(result)) = executor.fork_for_all(&value, &NoLogger) else {
+            panic!("Fixed input could not be resolved");
+        };
+        assert_eq!(result.get_constant().unwrap(), 2);
+
+        // Inputs outside of the model are resolved without creating new paths.
+        let other = executor.state.memory.unconstrained("other", 32);
+        let bound = executor.state.memory.from_u64(3, 32);
+        executor.state.constraints.assert(&other.ult(&bound));
+        let ResultOrTerminate::Result(Ok(_)) = executor.fork_for_all(&other, &NoLogger) else {
+            panic!("Input could not be resolved");
+        };
+        assert_eq!(executor.vm.paths.waiting_paths(), 0);
+    }
+
+    #[test]
+    fn test_register_watchpoint() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.hooks.add_register_watchpoint(
+            "R0",
+            |state, value| value._eq(&state.memory.from_u64(0xdead_beef, 32)),
+            |state, _register, _value| {
+                let one = state.memory.from_u64(1, 32);
+                state.set_register("WATCHED", one).unwrap();
+                WatchpointAction::Continue
+            },
+        );
+        let r0 = Operand::Register("R0".to_owned());
+
+        // Writes that cannot satisfy the predicate are ignored.
+        let operation = Operation::Move {
+            destination: r0.clone(),
+            source: Operand::Immediate(DataWord::Word32(42)),
+        };
+        assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), ResultOrTerminate::Result(Ok(()))));
+        let watched = executor.state.get_register("WATCHED").unwrap();
+        assert!(watched.get_constant().is_none());
+
+        let operation = Operation::Move {
+            destination: r0.clone(),
+            source: Operand::Immediate(DataWord::Word32(0xdead_beef)),
+        };
+        assert!(matches!(executor.execute_operation(&operation, &mut NoLogger), ResultOrTerminate::Result(Ok(()))));
+        assert_eq!(executor.state.get_register("WATCHED").unwrap().get_constant().unwrap(), 1);
+
+        // Symbolic values that may satisfy the predicate trigger the watchpoint as well.
+        executor.state.hooks.add_register_watchpoint(
+            "R0",
+            |state, value| value._eq(&state.memory.from_u64(0xdead_beef, 32)),
+            |_state, _register, _value| WatchpointAction::Fail("R0 was set to 0xdeadbeef"),
+        );
+        let any = executor.state.memory.unconstrained("any", 32);
+        executor.state.set_register("R1", any).unwrap();
+        let operation = Operation::Move {
+            destination: r0,
+            source: Operand::Register("R1".to_owned()),
+        };
+        let ResultOrTerminate::Failure(reason) = executor.execute_operation(&operation, &mut NoLogger) else {
+            panic!("Watchpoint did not end the path");
+        };
+        assert!(matches!(
+            reason,
+            FailureReason::Watchpoint {
+                reason: "R0 was set to 0xdeadbeef",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_register_dump() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        let value = executor.state.memory.from_u64(0x2a, 32);
+        executor.state.set_register("R0", value).unwrap();
+        let input = executor.state.memory.unconstrained("input", 32);
+        executor.state.set_register("R1", input).unwrap();
+
+        let dump = executor.state.register_dump();
+        assert!(dump.contains("R0: 0x2a"));
+        assert!(dump.contains("R1: input"));
+    }
+
+    #[test]
+    fn test_multi_bit_flag() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.declare_flag("GE", 4).unwrap();
+        assert_eq!(executor.state.get_flag("GE").unwrap().size(), 4);
+
+        let operation = Operation::Move {
+            destination: Operand::Flag("GE".to_owned()),
+            source: Operand::Immediate(DataWord::Word32(0b1011)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        let ge = executor.get_operand_value(&Operand::Flag("GE".to_owned()), &mut NoLogger).unwrap();
+        assert_eq!(ge.get_constant(), Some(0b1011));
+
+        // Flags that are not declared are still a single bit.
+        let operation = Operation::Move {
+            destination: Operand::Flag("C".to_owned()),
+            source: Operand::Immediate(DataWord::Word32(0b10)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        assert_eq!(executor.state.get_flag("C").unwrap().get_constant(), Some(0));
+    }
+
+    #[test]
+    fn test_asr_symbolic_amount() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let amount = executor.state.memory.unconstrained("amount", 32);
+        let low = executor.state.memory.from_u64(1, 32);
+        let high = executor.state.memory.from_u64(4, 32);
+        executor.state.constraints.assert(&amount.ugte(&low));
+        executor.state.constraints.assert(&amount.ulte(&high));
+        executor.state.set_register("R1", amount).unwrap();
+
+        let operation = Operation::Shift {
+            destination: Operand::Register("R2".to_owned()),
+            operand: Operand::Immediate(DataWord::Word32(0x8000_0010)),
+            shift_n: Operand::Register("R1".to_owned()),
+            shift_t: Shift::Asr,
+        };
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+
+        let result = executor.state.get_register("R2").unwrap();
+        let Solutions::Exactly(values) = executor.state.constraints.get_values(&result, 5).unwrap() else {
+            panic!("The result has more solutions than shift amounts");
+        };
+        let mut values = values.iter().map(|value| value.get_constant().unwrap()).collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![0xc000_0008, 0xe000_0004, 0xf000_0002, 0xf800_0001]);
+    }
+
+    #[test]
+    fn test_set_and_get_bit() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let bit = executor.state.memory.unconstrained("bit", 32);
+        let three = executor.state.memory.from_u64(3, 32);
+        executor.state.constraints.assert(&bit._eq(&three));
+        executor.state.set_register("R1", bit).unwrap();
+
+        let destination = Operand::Register("R2".to_owned());
+        for bit in [Operand::Immediate(DataWord::Word32(3)), Operand::Register("R1".to_owned())] {
+            let mut execute = |operation: Operation| {
+                executor.execute_operation(&operation, &mut NoLogger).unwrap();
+                let result = executor.state.get_register("R2").unwrap();
+                let Solutions::Exactly(values) = executor.state.constraints.get_values(&result, 2).unwrap() else {
+                    panic!("The bit index is fixed so the result should be as well");
+                };
+                values.iter().map(|value| value.get_constant().unwrap()).collect::<Vec<_>>()
+            };
+
+            let set_bit = |operand: u32, value: u32| Operation::SetBit {
+                destination: destination.clone(),
+                operand: Operand::Immediate(DataWord::Word32(operand)),
+                bit: bit.clone(),
+                value: Operand::Immediate(DataWord::Word32(value)),
+            };
+            assert_eq!(execute(set_bit(0xf0, 1)), [0xf8]);
+            assert_eq!(execute(set_bit(0xffff_ffff, 0)), [0xffff_fff7]);
+            // Only the least significant bit of the value is used.
+            assert_eq!(execute(set_bit(0xf0, 2)), [0xf0]);
+
+            let get_bit = |operand: u32| Operation::GetBit {
+                destination: destination.clone(),
+                operand: Operand::Immediate(DataWord::Word32(operand)),
+                bit: bit.clone(),
+            };
+            assert_eq!(execute(get_bit(0xf8)), [1]);
+            assert_eq!(execute(get_bit(0xfff7)), [0]);
+        }
+    }
+
+    #[test]
+    fn test_rotate_symbolic_amount() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let amount = executor.state.memory.unconstrained("amount", 32);
+        executor.state.set_register("R1", amount.clone()).unwrap();
+
+        let value = 0x8000_0013_u32;
+        let operand = Operand::Immediate(DataWord::Word32(value));
+        let destination = Operand::Register("R2".to_owned());
+        let amount_operand = Operand::Register("R1".to_owned());
+        let rotations: [(Operation, fn(u32, u32) -> u32); 4] = [
+            (
+                Operation::RotateLeft {
+                    destination: destination.clone(),
+                    operand: operand.clone(),
+                    amount: amount_operand.clone(),
+                },
+                u32::rotate_left,
+            ),
+            (
+                Operation::RotateRight {
+                    destination: destination.clone(),
+                    operand: operand.clone(),
+                    amount: amount_operand.clone(),
+                },
+                u32::rotate_right,
+            ),
+            (
+                Operation::Sror {
+                    destination: destination.clone(),
+                    operand: operand.clone(),
+                    shift: amount_operand.clone(),
+                },
+                u32::rotate_right,
+            ),
+            (
+                Operation::Shift {
+                    destination: destination.clone(),
+                    operand: operand.clone(),
+                    shift_n: amount_operand.clone(),
+                    shift_t: Shift::Ror,
+                },
+                u32::rotate_right,
+            ),
+        ];
+        for (operation, reference) in rotations {
+            executor.execute_operation(&operation, &mut NoLogger).unwrap();
+            let result = executor.state.get_register("R2").unwrap();
+
+            // Amounts of the width or more wrap around.
+            for steps in 0..40 {
+                let fixed = amount._eq(&executor.state.memory.from_u64(u64::from(steps), 32));
+                let expected = executor.state.memory.from_u64(u64::from(reference(value, steps)), 32);
+                let differs = fixed.and(&result._ne(&expected));
+                assert!(
+                    !executor.state.constraints.is_sat_with_constraint(&differs).unwrap(),
+                    "{operation:?} differs for {steps} steps"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rrx() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let operation = Operation::Shift {
+            destination: Operand::Register("R2".to_owned()),
+            operand: Operand::Immediate(DataWord::Word32(0x0000_0003)),
+            shift_n: Operand::Immediate(DataWord::Word32(1)),
+            shift_t: Shift::Rrx,
+        };
+
+        for (carry, expected) in [(true, 0x8000_0001), (false, 0x0000_0001)] {
+            let carry = executor.state.memory.from_bool(carry);
+            executor.state.set_flag("C", &carry).unwrap();
+            executor.execute_operation(&operation, &mut NoLogger).unwrap();
+            let result = executor.state.get_register("R2").unwrap();
+            assert_eq!(result.get_constant(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_nops_are_skipped() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.replay = Some(Replay::new(Model::new("entry".to_owned(), std::iter::empty().collect())));
+        let r0 = Operand::Register("R0".to_owned());
+
+        let mut operations = vec![Operation::Nop; 1000];
+        operations.push(Operation::Move {
+            destination: r0.clone(),
+            source: Operand::Immediate(DataWord::Word32(1)),
+        });
+        operations.extend(vec![Operation::Nop; 1000]);
+        let program = [
+            Instruction {
+                instruction_size: 32,
+                operations: vec![Operation::SetZFlag(Operand::Immediate(DataWord::Word32(0)))],
+                max_cycle: CycleCount::Value(0),
+                memory_access: false,
+            },
+            Instruction {
+                instruction_size: 32,
+                operations: vec![Operation::ConditionalExecution { conditions: vec![Condition::NE] }],
+                max_cycle: CycleCount::Value(0),
+                memory_access: false,
+            },
+            // Skipped by the condition, even though it only contains nops.
+            Instruction {
+                instruction_size: 32,
+                operations: vec![Operation::Nop; 1000],
+                max_cycle: CycleCount::Value(0),
+                memory_access: false,
+            },
+            Instruction {
+                instruction_size: 32,
+                operations,
+                max_cycle: CycleCount::Value(0),
+                memory_access: false,
+            },
+        ];
+
+        for p in &program {
+            executor.execute_instruction(p, &mut NoLogger).unwrap();
+        }
+
+        let r0_value = executor.get_operand_value(&r0, &mut NoLogger).ok().unwrap().get_constant().unwrap();
+        assert_eq!(r0_value, 1);
+        let steps = &executor.state.replay.as_ref().unwrap().steps;
+        assert_eq!(steps.len(), 3);
+        assert!(!steps.iter().any(|(_, operation)| matches!(operation, Operation::Nop)));
+    }
+
+    #[test]
+    fn test_concrete_address_skips_solver() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let base = executor.state.memory.from_u64(0x2000_0000, 32);
+        let offset = executor.state.memory.from_u64(4, 32);
+        let address = base.add(&offset);
+
+        let sat_calls = executor.state.constraints.statistics().sat_calls;
+        assert_eq!(executor.resolve_address(&address, &NoLogger, false).unwrap(), Some(0x2000_0004));
+        assert_eq!(executor.fork_for_all(&address, &NoLogger).unwrap().get_constant(), Some(0x2000_0004));
+        assert_eq!(executor.state.constraints.statistics().sat_calls, sat_calls);
+    }
+
+    #[test]
+    fn test_instruction_count() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let r0 = Operand::Register("R0".to_owned());
+
+        let program: Vec<_> = (0..5)
+            .map(|value| Instruction {
+                instruction_size: 32,
+                operations: vec![Operation::Add {
+                    destination: r0.clone(),
+                    operand1: r0.clone(),
+                    operand2: Operand::Immediate(DataWord::Word32(value)),
+                }],
+                max_cycle: CycleCount::Value(1),
+                memory_access: false,
+            })
+            .collect();
+
+        assert_eq!(executor.state.instruction_count(), 0);
+        for p in &program {
+            executor.execute_instruction(p, &mut NoLogger).unwrap();
+        }
+        assert_eq!(executor.state.instruction_count(), program.len());
+    }
 }
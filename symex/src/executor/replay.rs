@@ -0,0 +1,208 @@
+//! Concrete replay of a previously explored path.
+//!
+//! A [`Model`] is extracted from the final state of a path and can then be
+//! passed to [`SymexArbiter::replay`](crate::manager::SymexArbiter::replay)
+//! which re-executes the entry function with all named inputs fixed to the
+//! values in the model.
+//!
+//! A [`TestVector`] pairs such a model with the values the outputs of the
+//! function held at the end of the path, see
+//! [`SymexArbiter::generate_test_vectors`](crate::manager::SymexArbiter::generate_test_vectors).
+
+use std::collections::{BTreeMap, HashSet};
+
+use general_assembly::operation::Operation;
+
+use super::{state::GAState, PathResult};
+use crate::{
+    smt::{SmtExpr, SmtMap, SmtSolver, Solutions, SolverError},
+    Composition,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ReplayError {
+    #[error("The path has no entry function to replay from")]
+    NoEntryFunction,
+
+    #[error("The model does not satisfy the path constraints @ {0:#x}")]
+    Infeasible(u64),
+
+    #[error("The replay did not produce a result")]
+    NoResult,
+}
+
+/// Concrete values for all named inputs of a path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Model {
+    entry: String,
+    values: BTreeMap<String, u64>,
+}
+
+impl Model {
+    #[must_use]
+    pub const fn new(entry: String, values: BTreeMap<String, u64>) -> Self {
+        Self { entry, values }
+    }
+
+    /// Extracts a model from the final state of a path.
+    ///
+    /// This must be called before the next path is explored as the solver
+    /// only holds the constraints of the most recently completed path.
+    pub fn from_state<C: Composition>(state: &GAState<C>) -> crate::Result<Self> {
+        let Some(entry) = state.entry_subprogram.as_ref().map(|function| function.name.clone()) else {
+            return Err(ReplayError::NoEntryFunction.into());
+        };
+        Ok(Self {
+            entry,
+            values: state.named_inputs()?,
+        })
+    }
+
+    pub(super) fn solve<S: SmtSolver>(ctx: &S, variables: Vec<(String, S::Expression)>) -> Result<BTreeMap<String, u64>, SolverError> {
+        let mut values = BTreeMap::new();
+        for (name, variable) in variables {
+            let value = match variable.get_constant() {
+                Some(_) => variable.clone(),
+                None => match ctx.get_values(&variable, 1)? {
+                    Solutions::Exactly(values) | Solutions::AtLeast(values) => values.into_iter().next().ok_or(SolverError::Unsat)?,
+                },
+            };
+            // Values wider than 64 bits cannot be represented in the model.
+            let Some(concrete) = value.get_constant() else {
+                continue;
+            };
+            ctx.assert(&variable._eq(&value));
+            values.insert(name, concrete);
+        }
+        Ok(values)
+    }
+
+    /// Returns the name of the function the path started in.
+    #[must_use]
+    pub fn entry(&self) -> &str {
+        &self.entry
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.values.get(name).copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.values.iter().map(|(name, value)| (name.as_str(), *value))
+    }
+}
+
+/// Concrete inputs for a path along with the outputs they produce.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestVector {
+    /// The inputs that lead down the path.
+    pub inputs: Model,
+    /// The value of each output register at the end of the path under
+    /// [`Self::inputs`]. Outputs wider than 64 bits are omitted.
+    pub outputs: BTreeMap<String, u64>,
+}
+
+impl TestVector {
+    /// Extracts a test vector from the final state of a path, reading the
+    /// registers in `outputs`.
+    ///
+    /// As for [`Model::from_state`] this must be called before the next path
+    /// is explored.
+    pub fn from_state<C: Composition>(state: &mut GAState<C>, outputs: &[&str]) -> crate::Result<Self> {
+        let Some(entry) = state.entry_subprogram.as_ref().map(|function| function.name.clone()) else {
+            return Err(ReplayError::NoEntryFunction.into());
+        };
+        let variables = state.memory.named_variables().map(|(name, value)| (name.to_owned(), value.clone())).collect::<Vec<_>>();
+
+        // The outputs are read with the inputs fixed so that they are
+        // consistent with the model.
+        state.constraints.push();
+        let vector = Self::solve(state, variables, outputs);
+        state.constraints.pop();
+        let (inputs, outputs) = vector?;
+        Ok(Self {
+            inputs: Model::new(entry, inputs),
+            outputs,
+        })
+    }
+
+    fn solve<C: Composition>(
+        state: &mut GAState<C>,
+        variables: Vec<(String, C::SmtExpression)>,
+        outputs: &[&str],
+    ) -> crate::Result<(BTreeMap<String, u64>, BTreeMap<String, u64>)> {
+        let inputs = Model::solve(&state.constraints, variables)?;
+        let mut values = BTreeMap::new();
+        for register in outputs {
+            let value = state.get_register(*register)?;
+            let value = match value.get_constant() {
+                Some(value) => Some(value),
+                None => match state.constraints.get_values(&value, 1)? {
+                    Solutions::Exactly(values) | Solutions::AtLeast(values) => values.first().and_then(SmtExpr::get_constant),
+                },
+            };
+            if let Some(value) = value {
+                values.insert((*register).to_owned(), value);
+            }
+        }
+        Ok((inputs, values))
+    }
+}
+
+/// The operations executed while replaying a [`Model`].
+#[derive(Clone)]
+pub struct ConcreteTrace<C: Composition> {
+    /// Every executed operation, in order, along with the address of the
+    /// instruction it belongs to.
+    pub steps: Vec<(u64, Operation)>,
+    /// How the replayed path ended.
+    pub result: PathResult<C>,
+}
+
+/// Replay bookkeeping carried by a [`GAState`].
+///
+/// While a state is replaying no new paths are created.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    model: Model,
+    fixed: HashSet<String>,
+    pub(crate) steps: Vec<(u64, Operation)>,
+}
+
+impl Replay {
+    #[must_use]
+    pub fn new(model: Model) -> Self {
+        Self {
+            model,
+            fixed: HashSet::new(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Constrains every named variable in `state` that has not yet been fixed
+    /// to the value in the model.
+    pub(crate) fn fix_inputs<C: Composition>(&mut self, state: &mut GAState<C>) -> crate::Result<()> {
+        let mut fixed = Vec::new();
+        for (name, variable) in state.memory.named_variables() {
+            if self.fixed.contains(name) {
+                continue;
+            }
+            let Some(value) = self.model.get(name) else {
+                continue;
+            };
+            let value = state.memory.from_u64(value, variable.size());
+            fixed.push(variable._eq(&value));
+            self.fixed.insert(name.to_owned());
+        }
+
+        // The inputs are part of the path condition, a resumed path keeps them.
+        for constraint in &fixed {
+            state.assert(constraint);
+        }
+        if !fixed.is_empty() && !state.constraints.is_sat()? {
+            return Err(ReplayError::Infeasible(state.last_pc).into());
+        }
+        Ok(())
+    }
+}
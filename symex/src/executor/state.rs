@@ -1,29 +1,39 @@
 //! Holds the state in general assembly execution.
 
-use std::collections::VecDeque;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    rc::Rc,
+};
 
 use anyhow::Context as _;
 use general_assembly::prelude::Condition;
 
 use super::{
+    coverage::Coverage,
     extension::ieee754::FpState,
-    hooks::{HookContainer, PCHook, ResultOrHook},
+    hooks::{HookContainer, PCHook, ResultOrHook, WatchpointAction},
     instruction::Instruction,
+    replay::{Model, Replay},
+    taint::{Taint, TaintLocation},
+    FailureReason,
     ResultOrTerminate,
 };
 use crate::{
     arch::{InterfaceRegister, SupportedArchitecture, TryAsMut},
     debug,
-    executor::memory_interface::{MemoryFilter, Reader, Writer},
+    executor::memory_interface::{Access, MemoryFilter, Reader, Writer},
     extract,
     logging::Logger,
+    memory::BITS_IN_BYTE,
+    path_selection::{Origin, PathSelector},
     project::{
         self,
         dwarf_helper::{CallStack, DebugData, LineMap, SubProgram, DAP},
         ProjectError,
     },
-    smt::{ProgramMemory, SmtExpr, SmtMap, SmtSolver},
+    smt::{ProgramMemory, SmtExpr, SmtMap, SmtSolver, Solutions},
     trace,
+    warn,
     Composition,
     GAError,
     Result,
@@ -40,6 +50,108 @@ pub struct ContinueInsideInstruction<C: Composition> {
     pub context: crate::executor::Context<C>,
 }
 
+/// Bounds on the number of paths created when a symbolic value has to be made
+/// concrete.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolutionLimits {
+    /// The maximum number of concrete addresses a symbolic address is
+    /// resolved to.
+    pub max_address_solutions: u32,
+    /// The maximum number of concrete values a symbolic value that must be
+    /// concrete, such as a jump target, is forked in to.
+    pub max_fork_solutions: u32,
+    /// The maximum number of entries a symbolic read from read only program
+    /// memory, such as a jump table lookup, is resolved over before the
+    /// address is treated as any other symbolic address. A limit of zero, the
+    /// default, disables table reads.
+    pub max_table_entries: u32,
+    /// Whether or not paths exceeding the limits are suppressed with a warning
+    /// instead of ending the analysis with an error.
+    pub suppress_exceeding: bool,
+}
+
+impl Default for SolutionLimits {
+    fn default() -> Self {
+        Self {
+            max_address_solutions: 10,
+            max_fork_solutions: 255,
+            max_table_entries: 0,
+            suppress_exceeding: false,
+        }
+    }
+}
+
+/// How new paths are created when execution can continue in more than one
+/// way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ForkStrategy {
+    /// Every new path holds a full copy of the state at the point where it
+    /// was created.
+    #[default]
+    Clone,
+    /// New paths only hold the branch constraint, their state is re-derived
+    /// by executing from the start of the path they were forked from once
+    /// they are explored.
+    ///
+    /// This trades execution time for memory. The start of every path that
+    /// holds a complete state is kept as the origin of the paths forked from
+    /// it, so a path that forks only once holds as many states as when
+    /// cloning. Exploring three independent branches depth first, the waiting
+    /// paths hold three states when cloning and one shared origin when
+    /// forking incrementally, see
+    /// [`Runner::number_of_queued_states`](crate::manager::Runner::number_of_queued_states).
+    ///
+    /// The full state is cloned instead if the memory model does not support
+    /// [`SmtMap::seed_symbols`] or if the path created unnamed symbols, see
+    /// [`SmtMap::unnamed_symbols`], as those can not be re-created.
+    Incremental,
+}
+
+/// The value an argument register of the entry function starts with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArgumentValue {
+    /// The argument has a fixed value.
+    Concrete(u64),
+    /// The argument is any value in `[low, high)`, compared unsigned.
+    Range { low: u64, high: u64 },
+    /// The argument is any value, reported under the given name.
+    Symbolic(String),
+}
+
+/// A function call on the shadow call stack, see [`GAState::call_stack`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CallFrame {
+    /// The address of the called function.
+    pub function: u64,
+    /// The address execution continues at once the function returns.
+    pub return_address: u64,
+    /// The stack pointer at the time of the call.
+    pub stack_pointer: u64,
+}
+
+/// The changes to the memory of a path since a snapshot, see
+/// [`GAState::diff`].
+#[derive(Clone, Debug)]
+pub struct MemoryDiff<E> {
+    /// The address, the value in the snapshot and the current value of every
+    /// changed byte, ordered by address.
+    pub changed: Vec<(u64, E, E)>,
+    /// The address and value of every write to a symbolic address since the
+    /// snapshot. These can not be compared byte by byte.
+    pub symbolic_writes: Vec<(E, E)>,
+}
+
+/// The maximum number of bytes a single memory copy or fill operates on, see
+/// [`GAState::copy_memory`] and [`GAState::fill_memory`].
+pub const MAX_MEMORY_OPERATION_LENGTH: u64 = 0x1_0000;
+
+fn check_memory_operation_length(length: u64) -> Result<()> {
+    if length > MAX_MEMORY_OPERATION_LENGTH {
+        return Err(GAError::MemoryOperationTooLong(length).into());
+    }
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub struct GAState<C: Composition> {
     pub memory: C::Memory,
@@ -61,6 +173,30 @@ pub struct GAState<C: Composition> {
     pub line_lookup: LineMap,
     pub entry_subprogram: Option<SubProgram>,
     pub memory_filter: C::MemoryFilter,
+    pub solution_limits: SolutionLimits,
+    pub coverage: Coverage,
+    /// The inputs each value is derived from, only tracked if enabled.
+    pub taint: Taint,
+    /// Set while concretely replaying a path, disables forking.
+    pub replay: Option<Replay>,
+    pub fork_strategy: ForkStrategy,
+    /// The number of instructions the path may execute before it is
+    /// suppressed, see [`GAState::instruction_count`].
+    pub max_instructions: Option<usize>,
+    /// Every constraint asserted on the current path, see
+    /// [`GAState::assert`].
+    ///
+    /// Only recorded if the path selector uses it, see
+    /// [`PathSelector::USES_PATH_CONDITION`].
+    pub path_condition: Vec<C::SmtExpression>,
+    /// The payload produced by [`Composition::path_payload`] once the path
+    /// has completed.
+    pub path_payload: Option<C::PathPayload>,
+    /// The start of the current path, set when forking incrementally.
+    pub(crate) origin: Option<Rc<Origin<C>>>,
+    call_stack: Vec<CallFrame>,
+    /// The width of flags that are wider than a single bit.
+    flag_widths: BTreeMap<String, u32>,
     debug_data: Option<DebugData>,
 }
 
@@ -98,6 +234,8 @@ impl<C: Composition> GAState<C> {
         memory_filter.define_regions(ctx, &project);
 
         let mut memory = C::Memory::new(ctx.clone(), project, endianness, initial_sp, &state, &architecture)?;
+        memory.set_poison_tracking(C::TRACK_UNINITIALIZED_READS);
+        memory.set_merge_tracking(C::MERGE_PATHS);
         let pc_expr = ctx.from_u64(pc_reg, ptr_size);
         memory.set_register("PC", pc_expr)?;
 
@@ -123,24 +261,96 @@ impl<C: Composition> GAState<C> {
             instruction_conditions: VecDeque::new(),
             any_counter: 0,
             architecture,
-            fp_state: FpState::new(),
+            fp_state: FpState::with_rounding_mode(C::ROUNDING_MODE),
             instruction_had_condition: false,
             line_lookup,
             debug_data: Some(debug_data),
             entry_subprogram,
             memory_filter,
+            solution_limits: SolutionLimits::default(),
+            coverage: Coverage::new(),
+            taint: Taint::new(C::TRACK_TAINT),
+            replay: None,
+            fork_strategy: ForkStrategy::Clone,
+            max_instructions: None,
+            path_condition: Vec::new(),
+            path_payload: None,
+            origin: None,
+            call_stack: vec![CallFrame {
+                function: start_address & ((u64::MAX >> 1) << 1),
+                return_address: end_address,
+                stack_pointer: sp_reg,
+            }],
+            flag_widths: BTreeMap::new(),
         };
 
         ret.architecture.initiate_state()(&mut ret);
         Ok(ret)
     }
 
+    /// Fixes any new named inputs to the values in the replayed model.
+    pub(crate) fn fix_replay_inputs(&mut self) -> Result<()> {
+        let Some(mut replay) = self.replay.take() else {
+            return Ok(());
+        };
+        let result = replay.fix_inputs(self);
+        self.replay = Some(replay);
+        result
+    }
+
     pub fn label_new_symbolic(&mut self, start: &str) -> String {
         let ret = format!("{start}_{}", self.any_counter);
         self.any_counter += 1;
         ret
     }
 
+    /// Initializes the argument registers according to `arguments`.
+    ///
+    /// Returns the constraint that has to hold for the ranged arguments, if
+    /// any.
+    pub fn initialize_arguments(&mut self, arguments: &[(String, ArgumentValue)]) -> Result<Option<C::SmtExpression>> {
+        let word_size = self.memory.get_word_size();
+        let mut constraint: Option<C::SmtExpression> = None;
+        for (register, value) in arguments {
+            match value {
+                ArgumentValue::Concrete(value) => {
+                    let value = self.memory.from_u64(*value, word_size);
+                    self.set_register(register, value)?;
+                }
+                ArgumentValue::Range { low, high } => {
+                    let value = self.get_register(register)?;
+                    let in_range = value.ugte(&self.memory.from_u64(*low, word_size)).and(&value.ult(&self.memory.from_u64(*high, word_size)));
+                    constraint = Some(match constraint {
+                        Some(constraint) => constraint.and(&in_range),
+                        None => in_range,
+                    });
+                }
+                ArgumentValue::Symbolic(name) => {
+                    let value = self.memory.unconstrained(name, word_size);
+                    self.set_register(register, value)?;
+                }
+            }
+        }
+        Ok(constraint)
+    }
+
+    /// Returns from the current function without executing it.
+    ///
+    /// The return value register is set to a new unconstrained value of `bits`
+    /// bits, zero extended or truncated to the register size, and execution
+    /// continues at the return address.
+    pub fn stub_return(&mut self, bits: u32) -> Result<()> {
+        let name = self.label_new_symbolic("return");
+        let value = self.memory.unconstrained(&name, bits).resize_unsigned(self.memory.get_word_size());
+        let ret_name = self.architecture.get_register_name(InterfaceRegister::ReturnValue);
+        self.set_register(&ret_name, value)?;
+
+        let ra_name = self.architecture.get_register_name(InterfaceRegister::ReturnAddress);
+        let ra = self.get_register(&ra_name)?;
+        let pc_name = self.architecture.get_register_name(InterfaceRegister::ProgramCounter);
+        self.set_register(&pc_name, ra)
+    }
+
     pub const fn reset_has_jumped(&mut self) {
         self.has_jumped = false;
     }
@@ -161,9 +371,18 @@ impl<C: Composition> GAState<C> {
         self.instruction_counter += 1;
     }
 
+    /// Returns the number of instructions executed on this path, including
+    /// the ones executed before it was forked.
+    #[must_use]
+    pub const fn instruction_count(&self) -> usize {
+        self.instruction_counter
+    }
+
     /// Gets the current instruction count
+    #[must_use]
+    #[deprecated(since = "0.2.0", note = "use `GAState::instruction_count` instead")]
     pub const fn get_instruction_count(&self) -> usize {
-        self.instruction_counter
+        self.instruction_count()
     }
 
     /// Gets the last instruction that was executed.
@@ -186,6 +405,141 @@ impl<C: Composition> GAState<C> {
         self.memory.set_cycle_count(value);
     }
 
+    /// Asserts `constraint` on the current path.
+    pub fn assert(&mut self, constraint: &C::SmtExpression) {
+        self.constraints.assert(constraint);
+        if <C::PathSelector as PathSelector<C>>::USES_PATH_CONDITION {
+            self.path_condition.push(constraint.clone());
+        }
+    }
+
+    /// Combines `self` and `other`, which resume at the same address, in to a
+    /// single state.
+    ///
+    /// Values that differ are selected by `condition`, the condition under
+    /// which `self` is reached. The path condition of the combined state is
+    /// that either state is reached. The user state is taken from `self`.
+    ///
+    /// Returns `None` if either state resumes inside an instruction or a
+    /// conditional block, or if the memories can not be combined.
+    #[must_use]
+    pub fn merge(&self, condition: &C::SmtExpression, other: &Self, other_condition: &C::SmtExpression) -> Option<Self> {
+        let mid_instruction = |state: &Self| state.continue_in_instruction.is_some() || state.get_in_conditional_block();
+        if mid_instruction(self) || mid_instruction(other) || self.call_stack != other.call_stack {
+            return None;
+        }
+        let memory = self.memory.merge(&other.memory, condition)?;
+
+        let mut merged = self.clone();
+        merged.memory = memory;
+        merged.path_condition = vec![condition.or(other_condition)];
+        merged.any_counter = self.any_counter.max(other.any_counter);
+        merged.instruction_counter = self.instruction_counter.max(other.instruction_counter);
+        merged.taint.merge(&other.taint);
+        Some(merged)
+    }
+
+    /// Returns the functions currently being executed, outermost first.
+    ///
+    /// A call is recorded when a jump sets the return address register to the
+    /// address of the following instruction. The call returns when execution
+    /// reaches that address with the stack pointer restored.
+    #[must_use]
+    pub fn call_stack(&self) -> &[CallFrame] {
+        &self.call_stack
+    }
+
+    /// Records a call if the instruction preceding `next` jumped to a function
+    /// with `next` as the return address.
+    pub(crate) fn record_call(&mut self, next: u64) -> Result<()> {
+        let mask = (u64::MAX >> 1) << 1;
+        let pc_name = self.architecture.get_register_name(InterfaceRegister::ProgramCounter);
+        let Some(pc) = self.get_register(&pc_name)?.get_constant() else {
+            return Ok(());
+        };
+        if pc & mask == next & mask {
+            return Ok(());
+        }
+        let ra_name = self.architecture.get_register_name(InterfaceRegister::ReturnAddress);
+        if self.get_register(&ra_name)?.get_constant().map(|ra| ra & mask) != Some(next & mask) {
+            return Ok(());
+        }
+        let Some(stack_pointer) = self.get_register("SP")?.get_constant() else {
+            return Ok(());
+        };
+        self.call_stack.push(CallFrame {
+            function: pc & mask,
+            return_address: next & mask,
+            stack_pointer,
+        });
+        Ok(())
+    }
+
+    /// Records a return if `pc` is the return address of the innermost call.
+    pub(crate) fn record_return(&mut self, pc: u64) -> Result<()> {
+        let Some(frame) = self.call_stack.last().copied() else {
+            return Ok(());
+        };
+        if frame.return_address != pc & ((u64::MAX >> 1) << 1) {
+            return Ok(());
+        }
+        if self.get_register("SP")?.get_constant() == Some(frame.stack_pointer) {
+            self.call_stack.pop();
+        }
+        Ok(())
+    }
+
+    /// Returns a value for the named symbolic input `name` that satisfies the
+    /// path constraints.
+    ///
+    /// Returns `None` if there is no such input, if the constraints are not
+    /// satisfiable or if the value is wider than 64 bits.
+    #[must_use]
+    pub fn concretize(&self, name: &str) -> Option<u64> {
+        let (_, variable) = self.memory.named_variables().find(|(variable, _)| *variable == name)?;
+        if let Some(value) = variable.get_constant() {
+            return Some(value);
+        }
+        match self.constraints.get_values(variable, 1).ok()? {
+            Solutions::Exactly(values) | Solutions::AtLeast(values) => values.first()?.get_constant(),
+        }
+    }
+
+    /// Returns values for all named symbolic inputs that together satisfy the
+    /// path constraints.
+    ///
+    /// Inputs wider than 64 bits are omitted.
+    pub fn named_inputs(&self) -> Result<BTreeMap<String, u64>> {
+        let variables = self.memory.named_variables().map(|(name, value)| (name.to_owned(), value.clone())).collect::<Vec<_>>();
+
+        // Values are fixed one at a time to ensure that they are consistent with each
+        // other.
+        self.constraints.push();
+        let values = Model::solve(&self.constraints, variables);
+        self.constraints.pop();
+        Ok(values?)
+    }
+
+    /// Returns values of the named inputs under which the current path takes
+    /// more than `max_cycles` cycles, or `None` if no inputs do.
+    ///
+    /// The bound is checked by the solver together with the path constraints.
+    pub fn cycle_bound_witness(&mut self, max_cycles: u64) -> Result<Option<BTreeMap<String, u64>>> {
+        let cycles = self.get_cycle_count();
+        let exceeds = self.memory.from_u64(cycles, 64).ugt(&self.memory.from_u64(max_cycles, 64));
+        let variables = self.memory.named_variables().map(|(name, value)| (name.to_owned(), value.clone())).collect::<Vec<_>>();
+
+        self.constraints.push();
+        self.constraints.assert(&exceeds);
+        let witness = match self.constraints.is_sat() {
+            Ok(true) => Model::solve(&self.constraints, variables).map(Some),
+            Ok(false) => Ok(None),
+            Err(error) => Err(error),
+        };
+        self.constraints.pop();
+        Ok(witness?)
+    }
+
     /// Increment the cycle counter with the cycle count of the last
     /// instruction.
     pub fn increment_cycle_count(&mut self) {
@@ -194,15 +548,15 @@ impl<C: Composition> GAState<C> {
             return;
         }
 
-        let cycles = match &self.last_instruction {
-            Some(i) => match i.max_cycle {
-                super::instruction::CycleCount::Value(v) => v,
-                super::instruction::CycleCount::Function(f) => f(self),
-            },
-            None => 0,
+        // The instruction is moved out while it is counted to pass it by
+        // reference alongside the state.
+        let Some(instruction) = self.last_instruction.take() else {
+            return;
         };
-        trace!("Incrementing cycles: {}, for {:?}", cycles, self.last_instruction);
-        self.memory.increment_cycle_count(cycles as u64);
+        let cycles = self.architecture.cycle_cost()(&instruction, self);
+        trace!("Incrementing cycles: {}, for {:?}", cycles, instruction);
+        self.last_instruction = Some(instruction);
+        self.memory.increment_cycle_count(cycles);
     }
 
     /// Update the last instruction that was executed.
@@ -216,17 +570,21 @@ impl<C: Composition> GAState<C> {
         }
     }
 
-    pub fn replace_instruction_conditions(&mut self, conditions: &Vec<Condition>) {
+    /// Starts a new conditional block, discarding any conditions left over
+    /// from a previous block.
+    pub fn replace_instruction_conditions(&mut self, conditions: &[Condition]) {
         self.instruction_conditions.clear();
-        for condition in conditions {
-            self.instruction_conditions.push_back(condition.to_owned());
-        }
+        self.instruction_conditions.extend(conditions.iter().cloned());
     }
 
-    pub fn get_next_instruction_condition_expression(&mut self) -> Option<C::SmtExpression> {
-        // TODO add error handling
+    /// Consumes the condition for the next instruction, if any.
+    ///
+    /// Conditions are consumed in the order they were added, one per
+    /// instruction, so the flags are read when the instruction executes and
+    /// not when the block was started.
+    pub fn get_next_instruction_condition_expression(&mut self) -> Result<Option<C::SmtExpression>> {
         self.instruction_had_condition = !self.instruction_conditions.is_empty();
-        self.instruction_conditions.pop_front().map(|condition| self.get_expr(&condition).unwrap())
+        self.instruction_conditions.pop_front().map(|condition| self.get_expr(&condition)).transpose()
     }
 
     /// Create a state used for testing.
@@ -256,7 +614,9 @@ impl<C: Composition> GAState<C> {
         let mut memory_filter = C::MemoryFilter::new();
         memory_filter.define_regions(&mut ctx, &project);
 
-        let memory = C::Memory::new(ctx, project, end, initial_sp, &state, &architecture).unwrap();
+        let mut memory = C::Memory::new(ctx, project, end, initial_sp, &state, &architecture).unwrap();
+        memory.set_poison_tracking(C::TRACK_UNINITIALIZED_READS);
+        memory.set_merge_tracking(C::MERGE_PATHS);
         let mut registers = HashMap::new();
         let pc_expr = memory.from_u64(pc_reg, 32);
         registers.insert("PC".to_owned(), pc_expr);
@@ -279,12 +639,23 @@ impl<C: Composition> GAState<C> {
             instruction_conditions: VecDeque::new(),
             any_counter: 0,
             architecture,
-            fp_state: FpState::new(),
+            fp_state: FpState::with_rounding_mode(C::ROUNDING_MODE),
             instruction_had_condition: false,
             line_lookup: LineMap::empty(),
             debug_data: None,
             entry_subprogram: None,
             memory_filter,
+            solution_limits: SolutionLimits::default(),
+            coverage: Coverage::new(),
+            taint: Taint::new(C::TRACK_TAINT),
+            replay: None,
+            fork_strategy: ForkStrategy::Clone,
+            max_instructions: None,
+            path_condition: Vec::new(),
+            path_payload: None,
+            origin: None,
+            call_stack: Vec::new(),
+            flag_widths: BTreeMap::new(),
         };
         ret.architecture.initiate_state()(&mut ret);
 
@@ -296,12 +667,14 @@ impl<C: Composition> GAState<C> {
         // crude solution should probably change
         if &register.to_string() == "PC" {
             return self
-                .hooks
-                .writer(&mut self.memory, &mut self.memory_filter)
+                .writer()
                 .write_pc(expr.get_constant().ok_or(GAError::NonDeterministicPC)? as u32)
                 .map_err(|e| GAError::SmtMemoryError(e).into());
         }
-        match self.hooks.writer(&mut self.memory, &mut self.memory_filter).write_register(&register.to_string(), &expr) {
+        if self.taint.enabled() {
+            self.taint.write(TaintLocation::Register(register.to_string()));
+        }
+        match self.writer().write_register(&register.to_string(), &expr) {
             ResultOrHook::Hook(hook) => hook(self, expr)?,
             ResultOrHook::Hooks(hooks) => {
                 for hook in hooks {
@@ -314,28 +687,108 @@ impl<C: Composition> GAState<C> {
         Ok(())
     }
 
+    /// Runs the watchpoints on `register` for a newly written `value`.
+    ///
+    /// Returns the failure reason if any of the triggered watchpoints requested
+    /// that the path should end.
+    pub fn check_register_watchpoints(&mut self, register: &str, value: &C::SmtExpression) -> Result<Option<&'static str>> {
+        let Some(watchpoints) = self.hooks.get_register_watchpoints(register).map(<[_]>::to_vec) else {
+            return Ok(None);
+        };
+        for watchpoint in watchpoints {
+            let condition = (watchpoint.predicate)(self, value);
+            if !self.may_hold(&condition)? {
+                continue;
+            }
+            if let WatchpointAction::Fail(reason) = (watchpoint.callback)(self, register, value) {
+                return Ok(Some(reason));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns true if `condition` holds for some assignment of the symbols.
+    fn may_hold(&self, condition: &C::SmtExpression) -> Result<bool> {
+        Ok(match condition.get_constant_bool() {
+            Some(holds) => holds,
+            None => self.constraints.is_sat_with_constraint(condition)?,
+        })
+    }
+
+    /// Checks if writing `value` to `register` may move the stack pointer out
+    /// of the configured stack region.
+    ///
+    /// The stack occupies `[low, high)` and grows downwards, so the stack
+    /// pointer may take any value in `[low, high]`, it points to `high` when
+    /// the stack is empty. Always returns false if no stack region has been
+    /// configured.
+    pub fn register_write_overflows_stack(&mut self, register: &str, value: &C::SmtExpression) -> Result<bool> {
+        let Some((low, high)) = self.hooks.stack_region else {
+            return Ok(false);
+        };
+        if register != self.architecture.get_register_name(InterfaceRegister::StackPointer) {
+            return Ok(false);
+        }
+        let (low_sp, high_sp) = (self.memory.from_u64(low, value.size()), self.memory.from_u64(high, value.size()));
+        let outside = value.ult(&low_sp).or(&value.ugt(&high_sp));
+        if !self.may_hold(&outside)? {
+            return Ok(false);
+        }
+        let pc = self.memory.get_pc().ok().and_then(|pc| pc.get_constant()).unwrap_or_default();
+        match value.get_constant() {
+            Some(sp) => warn!("Stack overflow @ PC {pc:#x}, SP {sp:#x} is outside of [{low:#x}, {high:#x}]"),
+            None => warn!("Stack overflow @ PC {pc:#x}, SP may be outside of [{low:#x}, {high:#x}]"),
+        }
+        Ok(true)
+    }
+
+    /// Checks if a `bits` wide write to the stack pointer relative `address`
+    /// may write outside of the configured stack region `[low, high)`.
+    ///
+    /// Always returns false if no stack region has been configured.
+    pub fn stack_write_overflows_stack(&mut self, address: &C::SmtExpression, bits: u32) -> Result<bool> {
+        let Some((low, high)) = self.hooks.stack_region else {
+            return Ok(false);
+        };
+        let last = high.saturating_sub(u64::from(bits.div_ceil(BITS_IN_BYTE)).max(1));
+        let (first, last) = (self.memory.from_u64(low, address.size()), self.memory.from_u64(last, address.size()));
+        let outside = address.ult(&first).or(&address.ugt(&last));
+        if !self.may_hold(&outside)? {
+            return Ok(false);
+        }
+        let pc = self.memory.get_pc().ok().and_then(|pc| pc.get_constant()).unwrap_or_default();
+        match address.get_constant() {
+            Some(address) => warn!("Stack overflow @ PC {pc:#x}, write to {address:#x} is outside of [{low:#x}, {high:#x})"),
+            None => warn!("Stack overflow @ PC {pc:#x}, write may be outside of [{low:#x}, {high:#x})"),
+        }
+        Ok(true)
+    }
+
     /// Get the value stored at a register.
     pub fn get_register(&mut self, register: &(impl ToString + ?Sized)) -> Result<C::SmtExpression> {
         // crude solution should probably change
         if &register.to_string() == "PC" {
-            return self
-                .hooks
-                .reader(&mut self.memory, &mut self.memory_filter)
-                .read_pc()
-                .map_err(|e| GAError::SmtMemoryError(e).into());
+            return self.reader().read_pc().map_err(|e| GAError::SmtMemoryError(e).into());
         }
-        match self.hooks.reader(&mut self.memory, &mut self.memory_filter).read_register(&register.to_string()) {
-            ResultOrHook::Hook(hook) => hook(self),
+        let value = match self.reader().read_register(&register.to_string()) {
+            ResultOrHook::Hook(hook) => hook(self)?,
             ResultOrHook::Hooks(_hooks) => todo!("Handle multiple hooks on read"),
-            ResultOrHook::Result(Err(e)) => Err(GAError::SmtMemoryError(e).into()),
-            ResultOrHook::Result(o) => Ok(o?),
+            ResultOrHook::Result(Err(e)) => return Err(GAError::SmtMemoryError(e).into()),
+            ResultOrHook::Result(Ok(value)) => value,
             ResultOrHook::EndFailure(_) => todo!("Handle end failure from register."),
+        };
+        if self.taint.enabled() {
+            self.taint_read(&[TaintLocation::Register(register.to_string())], &value);
         }
+        Ok(value)
     }
 
     /// Set the value of a flag.
     pub fn set_flag(&mut self, flag: &(impl ToString + ?Sized), expr: &C::SmtExpression) -> Result<()> {
-        match self.hooks.writer(&mut self.memory, &mut self.memory_filter).write_flag(&flag.to_string(), expr) {
+        if self.taint.enabled() {
+            self.taint.write(TaintLocation::Flag(flag.to_string()));
+        }
+        match self.writer().write_flag(&flag.to_string(), expr) {
             ResultOrHook::Hook(hook) => hook(self, expr.clone())?,
             ResultOrHook::Hooks(hooks) => {
                 for hook in hooks {
@@ -351,56 +804,102 @@ impl<C: Composition> GAState<C> {
 
     /// Get the value of a flag.
     pub fn get_flag(&mut self, flag: &(impl ToString + ?Sized)) -> Result<C::SmtExpression> {
-        match self.hooks.reader(&mut self.memory, &mut self.memory_filter).read_flag(&flag.to_string()) {
-            ResultOrHook::Hook(hook) => hook(self),
+        let value = match self.reader().read_flag(&flag.to_string()) {
+            ResultOrHook::Hook(hook) => hook(self)?,
             ResultOrHook::Hooks(_hooks) => todo!("Handle multiple hooks on read"),
-            ResultOrHook::Result(Err(e)) => Err(GAError::SmtMemoryError(e).into()),
-            ResultOrHook::Result(o) => Ok(o?),
+            ResultOrHook::Result(Err(e)) => return Err(GAError::SmtMemoryError(e).into()),
+            ResultOrHook::Result(Ok(value)) => value,
             ResultOrHook::EndFailure(_) => todo!("Handle end failure from register."),
+        };
+        if self.taint.enabled() {
+            self.taint_read(&[TaintLocation::Flag(flag.to_string())], &value);
+        }
+        Ok(value)
+    }
+
+    /// Declares `flag` as a `width` bits wide field, such as the `GE` bits on
+    /// ARM, flags that are not declared are a single bit.
+    ///
+    /// The flag is set to an unconstrained value of the declared width.
+    pub fn declare_flag(&mut self, flag: &(impl ToString + ?Sized), width: u32) -> Result<()> {
+        let flag = flag.to_string();
+        let value = self.memory.unconstrained(&flag, width);
+        self.memory.set_flag(&flag, value).map_err(GAError::SmtMemoryError)?;
+        self.flag_widths.insert(flag, width);
+        Ok(())
+    }
+
+    /// Returns the width of `flag` in bits, see [`GAState::declare_flag`].
+    #[must_use]
+    pub fn flag_width(&self, flag: &str) -> u32 {
+        self.flag_widths.get(flag).copied().unwrap_or(1)
+    }
+
+    /// Adds the labels of `locations` to the labels of the current operation.
+    ///
+    /// If none of the locations have been labeled and `value` is one of the
+    /// named inputs, the locations are labeled with that input first.
+    pub(crate) fn taint_read(&mut self, locations: &[TaintLocation], value: &C::SmtExpression) {
+        if !self.taint.enabled() {
+            return;
+        }
+        if value.get_constant().is_none() && locations.iter().all(|location| self.taint.is_unlabeled(location)) {
+            let inputs = self
+                .memory
+                .named_variables()
+                .filter(|(_, variable)| variable.size() == value.size() && variable._eq(value).simplify().get_constant_bool() == Some(true))
+                .map(|(name, _)| name.to_owned())
+                .collect::<Vec<_>>();
+            for (location, name) in locations.iter().flat_map(|location| inputs.iter().map(move |name| (location, name))) {
+                self.taint.mark(location.clone(), name);
+            }
+        }
+        for location in locations {
+            self.taint.read(location);
         }
     }
 
     /// Get the expression for a condition based on the current flag values.
     pub fn get_expr(&mut self, condition: &Condition) -> Result<C::SmtExpression> {
         Ok(match condition {
-            Condition::EQ => self.get_flag("Z").unwrap(),
-            Condition::NE => self.get_flag("Z").unwrap().not(),
-            Condition::CS => self.get_flag("C").unwrap(),
-            Condition::CC => self.get_flag("C").unwrap().not(),
-            Condition::MI => self.get_flag("N").unwrap(),
-            Condition::PL => self.get_flag("N").unwrap().not(),
-            Condition::VS => self.get_flag("V").unwrap(),
-            Condition::VC => self.get_flag("V").unwrap().not(),
+            Condition::EQ => self.get_flag("Z")?,
+            Condition::NE => self.get_flag("Z")?.not(),
+            Condition::CS => self.get_flag("C")?,
+            Condition::CC => self.get_flag("C")?.not(),
+            Condition::MI => self.get_flag("N")?,
+            Condition::PL => self.get_flag("N")?.not(),
+            Condition::VS => self.get_flag("V")?,
+            Condition::VC => self.get_flag("V")?.not(),
             Condition::HI => {
-                let c = self.get_flag("C").unwrap();
-                let nz = self.get_flag("Z").unwrap().not();
+                let c = self.get_flag("C")?;
+                let nz = self.get_flag("Z")?.not();
                 c.and(&nz)
             }
             Condition::LS => {
-                let nc = self.get_flag("C").unwrap().not();
-                let z = self.get_flag("Z").unwrap();
+                let nc = self.get_flag("C")?.not();
+                let z = self.get_flag("Z")?;
                 nc.or(&z)
             }
             Condition::GE => {
-                let n = self.get_flag("N").unwrap();
-                let v = self.get_flag("V").unwrap();
+                let n = self.get_flag("N")?;
+                let v = self.get_flag("V")?;
                 n._eq(&v)
             }
             Condition::LT => {
-                let n = self.get_flag("N").unwrap();
-                let v = self.get_flag("V").unwrap();
+                let n = self.get_flag("N")?;
+                let v = self.get_flag("V")?;
                 n._ne(&v)
             }
             Condition::GT => {
-                let z = self.get_flag("Z").unwrap();
-                let n = self.get_flag("N").unwrap();
-                let v = self.get_flag("V").unwrap();
+                let z = self.get_flag("Z")?;
+                let n = self.get_flag("N")?;
+                let v = self.get_flag("V")?;
                 z.not().and(&n._eq(&v))
             }
             Condition::LE => {
-                let z = self.get_flag("Z").unwrap();
-                let n = self.get_flag("N").unwrap();
-                let v = self.get_flag("V").unwrap();
+                let z = self.get_flag("Z")?;
+                let n = self.get_flag("N")?;
+                let v = self.get_flag("V")?;
                 z.or(&n._ne(&v))
             }
             Condition::None => self.memory.from_bool(true),
@@ -433,18 +932,35 @@ impl<C: Composition> GAState<C> {
                 ResultOrHook::EndFailure(_) => todo!("Handle out of bounds reads for program memory reads"),
             }
         };
-        ResultOrTerminate::Result(Ok(HookOrInstruction::Instruction({
-            //println!("PC {pc:#x}");
-            extract!(Ok(ResultOrTerminate::Result(
-                match self.instruction_from_array_ptr(&extract!(Ok(ResultOrTerminate::Result(match self.memory.get_from_instruction_memory(pc.into()) {
-                    Ok(val) => Ok(val),
-                    Err(e) => Err(e).context("While reading instruction"),
-                })))) {
-                    Ok(val) => Ok(val),
-                    Err(e) => Err(e).context("While reading instruction"),
-                }
+        // The instruction size is not known until the instruction has been
+        // decoded, so the first byte is checked before decoding and the whole
+        // instruction after.
+        let permission_violation = |state: &mut Self| -> ResultOrTerminate<HookOrInstruction<C>> {
+            ResultOrTerminate::Failure(FailureReason::Custom(format!(
+                "{} @ {}",
+                crate::smt::MemoryError::PermissionViolation(Access::Execute, pc.into()),
+                state.debug_string()
             )))
-        })))
+        };
+        if !self.hooks.access_permitted(pc.into(), BITS_IN_BYTE, Access::Execute) {
+            return permission_violation(self);
+        }
+        let instruction = extract!(Ok(ResultOrTerminate::Result(
+            match self.instruction_from_array_ptr(&extract!(Ok(ResultOrTerminate::Result(match self.memory.get_from_instruction_memory(pc.into()) {
+                Ok(val) => Ok(val),
+                Err(e) => Err(e).context("While reading instruction"),
+            })))) {
+                Ok(val) => Ok(val),
+                Err(e) => Err(e).context("While reading instruction"),
+            }
+        )));
+        if !self.hooks.access_permitted(pc.into(), instruction.instruction_size, Access::Execute) {
+            return permission_violation(self);
+        }
+        if C::RECORD_COVERAGE {
+            self.coverage.record(pc.into());
+        }
+        ResultOrTerminate::Result(Ok(HookOrInstruction::Instruction(instruction)))
     }
 
     #[doc(hidden)]
@@ -466,16 +982,151 @@ impl<C: Composition> GAState<C> {
         Ok(self.memory.set(address, value)?)
     }
 
+    /// Reads `bits` bits at `address` through the memory hooks and the memory
+    /// filter.
+    pub fn read_memory(&mut self, address: C::SmtExpression, bits: u32) -> ResultOrTerminate<C::SmtExpression> {
+        ResultOrTerminate::Result(match self.reader().read_memory(&address, bits) {
+            ResultOrHook::Hook(hook) => hook(self, address),
+            // Every hook is run in turn, the one registered for the exact
+            // address comes last and provides the value.
+            ResultOrHook::Hooks(hooks) => hooks
+                .into_iter()
+                .try_fold(None, |_, hook| hook(self, address.clone()).map(Some))
+                .and_then(|value| value.context("No memory read hook was run")),
+            ResultOrHook::Result(result) => result,
+            ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(e.at(&self.debug_string())),
+        })
+    }
+
+    /// Writes the `bits` least significant bits of `value` to `address`
+    /// through the memory hooks and the memory filter.
+    pub fn write_memory(&mut self, address: C::SmtExpression, value: C::SmtExpression, bits: u32) -> ResultOrTerminate<()> {
+        ResultOrTerminate::Result(match self.writer().write_memory(&address, value.resize_unsigned(bits)) {
+            ResultOrHook::Hook(hook) => hook(self, value, address),
+            ResultOrHook::Hooks(hooks) => hooks.into_iter().try_for_each(|hook| hook(self, value.clone(), address.clone())),
+            ResultOrHook::Result(result) => result.map_err(Into::into),
+            ResultOrHook::EndFailure(e) => return ResultOrTerminate::Failure(e.at(&self.debug_string())),
+        })
+    }
+
+    /// Copies `length` bytes from `source` to `destination`.
+    ///
+    /// The whole source is read before anything is written, so overlapping
+    /// regions behave like `memmove`. Lengths above
+    /// [`MAX_MEMORY_OPERATION_LENGTH`] are rejected.
+    pub fn copy_memory(&mut self, destination: &C::SmtExpression, source: &C::SmtExpression, length: u64) -> ResultOrTerminate<()> {
+        extract!(Ok(check_memory_operation_length(length)));
+        let ptr_size = self.memory.get_ptr_size();
+        let mut bytes = Vec::new();
+        for offset in 0..length {
+            let address = source.add(&self.memory.from_u64(offset, ptr_size));
+            bytes.push(extract!(Ok(self.read_memory(address, BITS_IN_BYTE)), context: "While reading the source of a memory copy"));
+        }
+        for (offset, byte) in (0..).zip(bytes) {
+            let address = destination.add(&self.memory.from_u64(offset, ptr_size));
+            extract!(Ok(self.write_memory(address, byte, BITS_IN_BYTE)), context: "While writing the destination of a memory copy");
+        }
+        ResultOrTerminate::Result(Ok(()))
+    }
+
+    /// Fills `length` bytes at `destination` with the least significant byte
+    /// of `value`. Lengths above [`MAX_MEMORY_OPERATION_LENGTH`] are rejected.
+    pub fn fill_memory(&mut self, destination: &C::SmtExpression, value: &C::SmtExpression, length: u64) -> ResultOrTerminate<()> {
+        extract!(Ok(check_memory_operation_length(length)));
+        let ptr_size = self.memory.get_ptr_size();
+        let byte = value.resize_unsigned(BITS_IN_BYTE);
+        for offset in 0..length {
+            let address = destination.add(&self.memory.from_u64(offset, ptr_size));
+            extract!(Ok(self.write_memory(address, byte.clone(), BITS_IN_BYTE)), context: "While writing the destination of a memory fill");
+        }
+        ResultOrTerminate::Result(Ok(()))
+    }
+
+    /// Reads the `n`:th word sized function argument.
+    ///
+    /// Arguments past the ones passed in registers are read from the stack,
+    /// where the caller stores them starting at the stack pointer.
+    pub fn argument(&mut self, n: usize) -> ResultOrTerminate<C::SmtExpression> {
+        let registers = self.architecture.argument_registers();
+        if let Some(name) = registers.get(n) {
+            return ResultOrTerminate::Result(self.get_register(name));
+        }
+
+        let word_size = self.memory.get_word_size();
+        let ptr_size = self.memory.get_ptr_size();
+        let offset = (n - registers.len()) as u64 * u64::from(word_size / BITS_IN_BYTE);
+        let sp = extract!(Ok(self.get_register("SP")), context: "While locating stack arguments");
+        let address = sp.add(&self.memory.from_u64(offset, ptr_size));
+        self.read_memory(address, word_size)
+    }
+
+    /// Takes a snapshot of the memory that can later be compared to the
+    /// current memory with [`GAState::diff`], replacing any earlier snapshot.
+    ///
+    /// Only the previous value of each byte written after the snapshot is
+    /// kept. Fails if the memory model does not support
+    /// [`SmtMap::start_write_log`].
+    pub fn snapshot(&mut self) -> Result<()> {
+        if !<C::Memory as SmtMap>::LOGS_WRITES {
+            return Err(GAError::SnapshotsUnsupported.into());
+        }
+        self.memory.start_write_log();
+        Ok(())
+    }
+
+    /// Lists the changes to memory since the last [`GAState::snapshot`].
+    ///
+    /// Bytes written at constant addresses are listed if they differ from
+    /// their value in the snapshot. Values with a single solution under the
+    /// current constraints are concretized.
+    pub fn diff(&mut self) -> ResultOrTerminate<MemoryDiff<C::SmtExpression>> {
+        let Some(log) = self.memory.write_log() else {
+            return ResultOrTerminate::Result(Err(GAError::NoSnapshot.into()));
+        };
+        let mut previous = log.previous.iter().map(|(address, before)| (*address, before.clone())).collect::<Vec<_>>();
+        let symbolic_writes = log.symbolic_writes.clone();
+        previous.sort_unstable_by_key(|(address, _)| *address);
+
+        let mut changed = Vec::new();
+        for (address, before) in previous {
+            let before = self.concretize_value(before);
+            let after = self.concretize_value(extract!(Ok(self.memory.get_from_const_address(address, BITS_IN_BYTE))));
+            let differs = match (before.get_constant(), after.get_constant()) {
+                (Some(before), Some(after)) => before != after,
+                _ => match self.constraints.is_sat_with_constraint(&before._ne(&after)) {
+                    Ok(differs) => differs,
+                    Err(err) => return ResultOrTerminate::Result(Err(err.into())),
+                },
+            };
+            if differs {
+                changed.push((address, before, after));
+            }
+        }
+        ResultOrTerminate::Result(Ok(MemoryDiff { changed, symbolic_writes }))
+    }
+
+    /// Returns the only solution of `value` if there is one, otherwise the
+    /// value itself.
+    fn concretize_value(&self, value: C::SmtExpression) -> C::SmtExpression {
+        if value.get_constant().is_some() {
+            return value;
+        }
+        match self.constraints.get_values(&value, 1) {
+            Ok(Solutions::Exactly(mut solutions)) if solutions.len() == 1 => solutions.remove(0),
+            _ => value,
+        }
+    }
+
     pub fn instruction_from_array_ptr(&mut self, data: &[u8]) -> project::Result<Instruction<C>> {
         self.architecture.translate()(data, self).map_err(Into::into)
     }
 
     pub const fn reader(&mut self) -> Reader<'_, C> {
-        self.hooks.reader(&mut self.memory, &mut self.memory_filter)
+        self.hooks.reader(&mut self.memory, &mut self.memory_filter, &self.constraints)
     }
 
     pub const fn writer(&mut self) -> Writer<'_, C> {
-        self.hooks.writer(&mut self.memory, &mut self.memory_filter)
+        self.hooks.writer(&mut self.memory, &mut self.memory_filter, &self.constraints)
     }
 
     pub fn debug_string_new_pc(&self) -> String {
@@ -505,7 +1156,7 @@ impl<C: Composition> GAState<C> {
             Some(line) => format!("PC : {pc:#x} -> {line} ({name})"),
             None => format!("PC: {pc:#x} ({name})"),
         };
-        ret
+        self.with_registers(ret)
     }
 
     pub fn debug_string_fork(&mut self) -> String {
@@ -516,7 +1167,44 @@ impl<C: Composition> GAState<C> {
             None => format!("PC: {pc:#x}"),
         };
 
-        ret
+        self.with_registers(ret)
+    }
+
+    /// Returns the value of every register, sorted by name.
+    ///
+    /// Concrete values are shown in hex. Symbolic values are shown as the name
+    /// of the input they are, or as the expression they are derived from if
+    /// it is short enough to read.
+    pub fn register_dump(&mut self) -> String {
+        let mut registers = self.memory.get_registers().into_iter().collect::<Vec<_>>();
+        registers.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        registers
+            .iter()
+            .map(|(name, value)| {
+                let value = match (value.get_constant(), value.get_identifier()) {
+                    (Some(value), _) => format!("{value:#x}"),
+                    (None, Some(identifier)) => identifier,
+                    (None, None) => {
+                        let expression = format!("{value:?}");
+                        match expression.len() > 50 {
+                            true => "Large symbolic expression".to_string(),
+                            false => expression,
+                        }
+                    }
+                };
+                format!("{name}: {value}")
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Appends the [`register_dump`](Self::register_dump) to `location` if
+    /// [`Composition::LOG_REGISTERS`] is set.
+    fn with_registers(&mut self, location: String) -> String {
+        match C::LOG_REGISTERS {
+            true => format!("{location} [{}]", self.register_dump()),
+            false => location,
+        }
     }
 
     pub fn get_back_trace(&mut self, constraints: &[C::SmtExpression]) -> Option<CallStack> {
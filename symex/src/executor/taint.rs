@@ -0,0 +1,118 @@
+//! Tracks which named symbolic inputs a value is derived from.
+//!
+//! Every value written by an operation is labeled with the union of the labels
+//! of all values that operation read. Only explicit data flow is tracked, a
+//! value that is selected by a tainted condition is not itself tainted.
+
+use std::collections::{BTreeSet, HashMap};
+
+/// A location that can hold a labeled value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TaintLocation {
+    Register(String),
+    Flag(String),
+    Local(String),
+    /// A single byte in memory.
+    Address(u64),
+}
+
+impl TaintLocation {
+    /// Returns the byte locations covered by a `bits` wide access at
+    /// `address`.
+    pub fn memory(address: u64, bits: u32) -> impl Iterator<Item = Self> {
+        (address..address + u64::from(bits.div_ceil(8))).map(Self::Address)
+    }
+}
+
+/// Taint labels for all locations in a state.
+#[derive(Clone, Debug, Default)]
+pub struct Taint {
+    enabled: bool,
+    labels: HashMap<TaintLocation, BTreeSet<String>>,
+    /// Labels of every value written through an address that could not be
+    /// resolved, any byte in memory may hold such a value.
+    unresolved_writes: BTreeSet<String>,
+    /// Labels of every value read by the operation that is currently executing.
+    pending: BTreeSet<String>,
+}
+
+impl Taint {
+    #[must_use]
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, ..Self::default() }
+    }
+
+    #[must_use]
+    pub const fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub const fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Marks the value at `location` as derived from the input `name`.
+    pub fn mark(&mut self, location: TaintLocation, name: &str) {
+        self.labels.entry(location).or_default().insert(name.to_owned());
+    }
+
+    /// Returns the names of all inputs the value at `location` is derived from.
+    #[must_use]
+    pub fn labels(&self, location: &TaintLocation) -> Option<&BTreeSet<String>> {
+        self.labels.get(location)
+    }
+
+    /// Returns true if the value at `location` is derived from the input
+    /// `name`.
+    #[must_use]
+    pub fn is_tainted_by(&self, location: &TaintLocation, name: &str) -> bool {
+        self.labels(location).is_some_and(|labels| labels.contains(name)) || (matches!(location, TaintLocation::Address(_)) && self.unresolved_writes.contains(name))
+    }
+
+    /// Adds the labels of `other` to the labels of `self`.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        for (location, labels) in &other.labels {
+            self.labels.entry(location.clone()).or_default().extend(labels.iter().cloned());
+        }
+        self.unresolved_writes.extend(other.unresolved_writes.iter().cloned());
+    }
+
+    /// Returns true if `location` has no labels.
+    pub(crate) fn is_unlabeled(&self, location: &TaintLocation) -> bool {
+        !self.labels.contains_key(location)
+    }
+
+    /// Clears the labels collected by the previous operation.
+    pub(crate) fn begin_operation(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Adds the labels of `location` to the labels of the current operation.
+    pub(crate) fn read(&mut self, location: &TaintLocation) {
+        if let Some(labels) = self.labels.get(location) {
+            self.pending.extend(labels.iter().cloned());
+        }
+        if matches!(location, TaintLocation::Address(_)) {
+            self.pending.extend(self.unresolved_writes.iter().cloned());
+        }
+    }
+
+    /// Labels `location` with the labels of every value read by the current
+    /// operation.
+    pub(crate) fn write(&mut self, location: TaintLocation) {
+        if self.pending.is_empty() {
+            self.labels.remove(&location);
+        } else {
+            self.labels.insert(location, self.pending.clone());
+        }
+    }
+
+    /// Adds the labels of every value read by the current operation to all of
+    /// memory, as the address written to could not be resolved.
+    ///
+    /// The labels can not be cleared by later writes as it is not known which
+    /// bytes were written.
+    pub(crate) fn write_unresolved_memory(&mut self) {
+        self.unresolved_writes.extend(self.pending.iter().cloned());
+    }
+}
@@ -6,34 +6,38 @@ use crate::{
     Composition,
 };
 
-fn count_ones<C: Composition>(input: &C::SmtExpression, ctx: &GAState<C>, word_size: u32) -> C::SmtExpression {
+fn count_leading_ones<C: Composition>(input: &C::SmtExpression, ctx: &GAState<C>, word_size: u32) -> C::SmtExpression {
     let mut count = ctx.memory.from_u64(0, word_size);
+    let mut stop_count_mask = ctx.memory.from_u64(1, word_size);
     let mask = ctx.memory.from_u64(1, word_size);
-    for n in 0..word_size {
+    for n in (0..word_size).rev() {
         let symbolic_n = ctx.memory.from_u64(n as u64, word_size);
-        let to_add = input.shift(&symbolic_n, Shift::Lsr).and(&mask);
+        let to_add = input.shift(&symbolic_n, Shift::Lsr).and(&mask).and(&stop_count_mask);
+        stop_count_mask = to_add.clone();
         count = count.add(&to_add);
     }
     count
 }
 
-fn count_zeroes<C: Composition>(input: &C::SmtExpression, ctx: &GAState<C>, word_size: u32) -> C::SmtExpression {
+fn count_leading_zeroes<C: Composition>(input: &C::SmtExpression, ctx: &GAState<C>, word_size: u32) -> C::SmtExpression {
     let input = input.not();
     let mut count = ctx.memory.from_u64(0, word_size);
+    let mut stop_count_mask = ctx.memory.from_u64(1, word_size);
     let mask = ctx.memory.from_u64(1, word_size);
-    for n in 0..word_size {
+    for n in (0..word_size).rev() {
         let symbolic_n = ctx.memory.from_u64(n as u64, word_size);
-        let to_add = input.shift(&symbolic_n, Shift::Lsr).and(&mask);
+        let to_add = input.shift(&symbolic_n, Shift::Lsr).and(&mask).and(&stop_count_mask);
+        stop_count_mask = to_add.clone();
         count = count.add(&to_add);
     }
     count
 }
 
-fn count_leading_ones<C: Composition>(input: &C::SmtExpression, ctx: &GAState<C>, word_size: u32) -> C::SmtExpression {
+fn count_trailing_ones<C: Composition>(input: &C::SmtExpression, ctx: &GAState<C>, word_size: u32) -> C::SmtExpression {
     let mut count = ctx.memory.from_u64(0, word_size);
     let mut stop_count_mask = ctx.memory.from_u64(1, word_size);
     let mask = ctx.memory.from_u64(1, word_size);
-    for n in (0..word_size).rev() {
+    for n in 0..word_size {
         let symbolic_n = ctx.memory.from_u64(n as u64, word_size);
         let to_add = input.shift(&symbolic_n, Shift::Lsr).and(&mask).and(&stop_count_mask);
         stop_count_mask = to_add.clone();
@@ -42,12 +46,12 @@ fn count_leading_ones<C: Composition>(input: &C::SmtExpression, ctx: &GAState<C>
     count
 }
 
-fn count_leading_zeroes<C: Composition>(input: &C::SmtExpression, ctx: &GAState<C>, word_size: u32) -> C::SmtExpression {
+fn count_trailing_zeroes<C: Composition>(input: &C::SmtExpression, ctx: &GAState<C>, word_size: u32) -> C::SmtExpression {
     let input = input.not();
     let mut count = ctx.memory.from_u64(0, word_size);
     let mut stop_count_mask = ctx.memory.from_u64(1, word_size);
     let mask = ctx.memory.from_u64(1, word_size);
-    for n in (0..word_size).rev() {
+    for n in 0..word_size {
         let symbolic_n = ctx.memory.from_u64(n as u64, word_size);
         let to_add = input.shift(&symbolic_n, Shift::Lsr).and(&mask).and(&stop_count_mask);
         stop_count_mask = to_add.clone();
@@ -56,6 +60,13 @@ fn count_leading_zeroes<C: Composition>(input: &C::SmtExpression, ctx: &GAState<
     count
 }
 
+/// Reverses the order of the `bits` least significant bits of `input`.
+///
+/// The result is `bits` wide.
+pub fn reverse_bits<E: SmtExpr>(input: &E, bits: u32) -> E {
+    (1..bits).fold(input.extract(0, 0), |result, n| result.concat(&input.extract(n, n)))
+}
+
 /// Does an add with carry and returns result, carry out and overflow like a
 /// hardware adder.
 pub fn add_with_carry<E: SmtExpr>(op1: &E, op2: &E, carry_in: &E, word_size: u32) -> AddWithCarryResult<E> {
@@ -76,8 +87,9 @@ pub fn add_with_carry<E: SmtExpr>(op1: &E, op2: &E, carry_in: &E, word_size: u32
 pub struct UtilityCloures<C: Composition> {
     pub count_leading_zeroes: <C::SMT as SmtSolver>::UnaryLambda,
     pub count_leading_ones: <C::SMT as SmtSolver>::UnaryLambda,
-    pub count_zeroes: <C::SMT as SmtSolver>::UnaryLambda,
-    pub count_ones: <C::SMT as SmtSolver>::UnaryLambda,
+    pub count_trailing_zeroes: <C::SMT as SmtSolver>::UnaryLambda,
+    pub count_trailing_ones: <C::SMT as SmtSolver>::UnaryLambda,
+    pub reverse_bits: <C::SMT as SmtSolver>::UnaryLambda,
 }
 
 impl<C: Composition> UtilityCloures<C> {
@@ -86,8 +98,9 @@ impl<C: Composition> UtilityCloures<C> {
         Self {
             count_leading_zeroes: <C::SMT as SmtSolver>::UnaryLambda::new(&mut solver, ctx.memory.get_word_size(), |a| count_leading_zeroes(&a, ctx, word_size)),
             count_leading_ones: <C::SMT as SmtSolver>::UnaryLambda::new(&mut solver, ctx.memory.get_word_size(), |a| count_leading_ones(&a, ctx, word_size)),
-            count_zeroes: <C::SMT as SmtSolver>::UnaryLambda::new(&mut solver, ctx.memory.get_word_size(), |a| count_zeroes(&a, ctx, word_size)),
-            count_ones: <C::SMT as SmtSolver>::UnaryLambda::new(&mut solver, ctx.memory.get_word_size(), |a| count_ones(&a, ctx, word_size)),
+            count_trailing_zeroes: <C::SMT as SmtSolver>::UnaryLambda::new(&mut solver, ctx.memory.get_word_size(), |a| count_trailing_zeroes(&a, ctx, word_size)),
+            count_trailing_ones: <C::SMT as SmtSolver>::UnaryLambda::new(&mut solver, ctx.memory.get_word_size(), |a| count_trailing_ones(&a, ctx, word_size)),
+            reverse_bits: <C::SMT as SmtSolver>::UnaryLambda::new(&mut solver, ctx.memory.get_word_size(), |a| reverse_bits(&a, word_size)),
         }
     }
 }
@@ -1,9 +1,19 @@
 //! Describes the VM for general assembly
 
-use super::{hooks::HookContainer, state::GAState, GAExecutor, PathResult};
+use std::rc::Rc;
+
+use general_assembly::extension::ieee754::RoundingMode;
+
+use super::{
+    coverage::Coverage,
+    hooks::HookContainer,
+    state::{ArgumentValue, ForkStrategy, GAState, SolutionLimits},
+    GAExecutor,
+    PathResult,
+};
 use crate::{
     arch::SupportedArchitecture,
-    path_selection::{Path, PathSelector},
+    path_selection::{Origin, Path, PathSelector, PathState},
     project::dwarf_helper::{DebugData, LineMap, SubProgram},
     smt::{SmtMap, SmtSolver},
     trace,
@@ -32,6 +42,12 @@ impl<C: Composition> VM<C> {
         logger: C::Logger,
         line_map: LineMap,
         debug_data: DebugData,
+        solution_limits: SolutionLimits,
+        fork_strategy: ForkStrategy,
+        max_instructions: Option<usize>,
+        coverage: Coverage,
+        rounding_mode: RoundingMode,
+        arguments: &[(String, ArgumentValue)],
     ) -> Result<Self> {
         let mut vm = Self {
             project: project.clone(),
@@ -52,8 +68,14 @@ impl<C: Composition> VM<C> {
             Some(function.clone()),
         )?;
         state.memory.set_pc(function.bounds.0 as u32)?;
+        state.solution_limits = solution_limits;
+        state.fork_strategy = fork_strategy;
+        state.max_instructions = max_instructions;
+        state.coverage = coverage;
+        state.fp_state.rounding_mode = rounding_mode;
+        let constraint = state.initialize_arguments(arguments)?;
 
-        vm.paths.save_path(Path::new(state, None, 0, logger));
+        vm.paths.save_path(Path::new(state, constraint, 0, logger));
 
         Ok(vm)
     }
@@ -99,15 +121,22 @@ impl<C: Composition> VM<C> {
     #[allow(clippy::type_complexity)]
     pub fn run(&mut self) -> Result<Option<(PathResult<C>, GAState<C>, Vec<C::SmtExpression>, u64, C::Logger)>> {
         trace!("VM::run");
-        if let Some(mut path) = self.paths.get_path() {
+        while let Some(mut path) = self.paths.get_path() {
             trace!("VM running path {path:?}");
-            let mut executor = GAExecutor::from_state(path.state, self, self.project.clone());
+            let state = Self::start_state(path.state, &path.logger);
+            let mut executor = GAExecutor::from_state(state, self, self.project.clone());
 
             for constraint in path.constraints.clone() {
-                executor.state.constraints.assert(&constraint);
+                executor.state.assert(&constraint);
             }
 
             let result = executor.resume_execution(&mut path.logger)?;
+            if executor.suspended {
+                let state = executor.state;
+                let pc = state.last_pc;
+                self.paths.save_path(Path::new(state, None, pc, path.logger));
+                continue;
+            }
             return Ok(Some((result, executor.state, path.constraints, path.pc, path.logger)));
         }
         trace!("No more paths!");
@@ -118,10 +147,11 @@ impl<C: Composition> VM<C> {
         if let Some(mut path) = self.paths.get_path() {
             trace!("VM running path {path:?}");
             let project = self.project.clone();
-            let mut executor = GAExecutor::from_state(path.state.clone(), self, self.project.clone());
+            let state = Self::start_state(path.state.clone(), &path.logger);
+            let mut executor = GAExecutor::from_state(state, self, self.project.clone());
 
             for constraint in path.constraints.clone() {
-                executor.state.constraints.assert(&constraint);
+                executor.state.assert(&constraint);
             }
 
             let _result = executor.resume_execution_stepper(&mut path.logger)?;
@@ -130,6 +160,24 @@ impl<C: Composition> VM<C> {
         trace!("No more paths!");
         Ok(None)
     }
+
+    /// Returns the state to start executing `path` from.
+    ///
+    /// When forking incrementally the start of every materialized path is
+    /// recorded so that paths forked from it can be re-derived.
+    fn start_state(path: PathState<C>, logger: &C::Logger) -> GAState<C> {
+        match path {
+            PathState::Materialized(mut state) if state.fork_strategy == ForkStrategy::Incremental => {
+                state.origin = None;
+                state.origin = Some(Rc::new(Origin {
+                    state: state.clone(),
+                    logger: logger.clone(),
+                }));
+                state
+            }
+            path => path.materialize(),
+        }
+    }
 }
 
 pub struct SymexStepper<'vm, C: Composition> {
@@ -143,13 +191,57 @@ pub struct SymexStepper<'vm, C: Composition> {
 impl<'vm, C: Composition> SymexStepper<'vm, C> {
     /// Returns none if the path did not terminate.
     #[allow(clippy::type_complexity)]
+    ///
+    /// A path that is suspended, see [`PathSelector::should_suspend`], is set
+    /// aside and stepping continues with the next path on the following call.
     pub fn step(&mut self, steps: usize) -> Result<Option<(PathResult<C>, &GAState<C>, &Vec<C::SmtExpression>, u64, &C::Logger)>> {
-        match self.executor.step(steps, &mut self.path.logger)? {
+        let mut result = self.executor.step(steps, &mut self.path.logger)?;
+        if self.executor.suspended {
+            result = self.resume_next_path()?;
+        }
+        match result {
             Some(result) => Ok(Some((result, &self.executor.state, &self.path.constraints, self.path.pc, &self.path.logger))),
             None => Ok(None),
         }
     }
 
+    /// Steps until `predicate` holds for the current state or the path ends.
+    ///
+    /// Returns the result of the path if it ended along with the state it
+    /// stopped in. Suspended paths are set aside as in [`Self::step`].
+    pub fn run_until(&mut self, predicate: impl Fn(&GAState<C>) -> bool) -> Result<(Option<PathResult<C>>, &GAState<C>)> {
+        loop {
+            let mut result = self.executor.run_until(&predicate, &mut self.path.logger)?;
+            if self.executor.suspended {
+                result = self.resume_next_path()?;
+                if result.is_none() {
+                    continue;
+                }
+            }
+            return Ok((result, &self.executor.state));
+        }
+    }
+
+    /// Sets the suspended path aside and continues with the next waiting
+    /// path, which it may have been merged in to.
+    ///
+    /// Returns the result of the next path if it ended while being resumed.
+    fn resume_next_path(&mut self) -> Result<Option<PathResult<C>>> {
+        let state = self.executor.state.clone();
+        let pc = state.last_pc;
+        self.executor.vm.paths.save_path(Path::new(state, None, pc, self.path.logger.clone()));
+
+        let mut path = self.executor.vm.paths.get_path().expect("A path to wait for the suspended path");
+        trace!("Stepper running path {path:?}");
+        self.executor.restart(VM::<C>::start_state(path.state.clone(), &path.logger));
+        for constraint in path.constraints.clone() {
+            self.executor.state.assert(&constraint);
+        }
+        let result = self.executor.resume_execution_stepper(&mut path.logger)?;
+        self.path = path;
+        Ok(result)
+    }
+
     pub const fn executor(&mut self) -> &mut GAExecutor<'vm, C> {
         &mut self.executor
     }
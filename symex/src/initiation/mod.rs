@@ -13,12 +13,14 @@ use crate::{
     manager::SymexArbiter,
     project::{
         dwarf_helper::{line_program, DebugData, LineMap, SubProgramMap},
+        validate_layout,
         Project,
         ProjectError,
     },
-    smt::{SmtMap, SmtSolver},
+    smt::{SmtMap, SmtSolver, SolverConfig},
     Composition,
     Endianness,
+    WordSize,
 };
 
 mod sealed {
@@ -33,11 +35,15 @@ use sealed::{ArchOverride, BinaryLoadingDone, SmtSolverConfigured};
 /// SMT solver has been configured.
 pub struct SmtConfigured<Smt: SmtSolver> {
     smt: Smt,
+    config: SolverConfig,
 }
 
 impl<Smt: SmtSolver> Clone for SmtConfigured<Smt> {
     fn clone(&self) -> Self {
-        Self { smt: Smt::new() }
+        Self {
+            smt: Smt::with_config(&self.config),
+            config: self.config,
+        }
     }
 }
 
@@ -74,6 +80,16 @@ impl Clone for BinaryLoaded<'static> {
     }
 }
 
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+/// Headerless binary loaded.
+pub struct RawLoaded {
+    data: Vec<u8>,
+    base_addr: u64,
+    entry: u64,
+    symtab: HashMap<String, u64>,
+}
+
 #[doc(hidden)]
 #[derive(Debug, Clone)]
 /// Binary not loaded.
@@ -94,6 +110,8 @@ pub struct SymexConstructor<'str, Override: ArchOverride, Smt: SmtSolverConfigur
     override_arch: Override,
     smt: Smt,
     binary_file: Binary,
+    /// Word size and endianness overriding the ones in the file header.
+    layout: Option<(WordSize, Endianness)>,
 }
 
 impl<'str> SymexConstructor<'str, NoArchOverride, SmtNotConfigured, BinaryNotLoaded> {
@@ -104,6 +122,7 @@ impl<'str> SymexConstructor<'str, NoArchOverride, SmtNotConfigured, BinaryNotLoa
             override_arch: NoArchOverride,
             smt: SmtNotConfigured,
             binary_file: BinaryNotLoaded,
+            layout: None,
         }
     }
 }
@@ -115,25 +134,57 @@ impl<'str, S: SmtSolverConfigured, B: BinaryLoadingDone> SymexConstructor<'str,
             override_arch: a.into(),
             smt: self.smt,
             binary_file: self.binary_file,
+            layout: self.layout,
         }
     }
 }
 
+impl<A: ArchOverride, S: SmtSolverConfigured, B: BinaryLoadingDone> SymexConstructor<'_, A, S, B> {
+    /// Overrides the word size and endianness inferred from the binary.
+    pub fn override_layout(mut self, word_size: WordSize, endianness: Endianness) -> crate::Result<Self> {
+        validate_layout(word_size, endianness)?;
+        self.layout = Some((word_size, endianness));
+        Ok(self)
+    }
+}
+
 impl<'str, A: ArchOverride, B: BinaryLoadingDone> SymexConstructor<'str, A, SmtNotConfigured, B> {
+    /// Configures the SMT solver with the default [`SolverConfig`], using one
+    /// thread per available core.
     pub fn configure_smt<S: SmtSolver>(self) -> SymexConstructor<'str, A, SmtConfigured<S>, B> {
+        self.configure_smt_with(SolverConfig::default())
+    }
+
+    /// Configures the SMT solver with the thread count and rewrite level in
+    /// `config`.
+    pub fn configure_smt_with<S: SmtSolver>(self, config: SolverConfig) -> SymexConstructor<'str, A, SmtConfigured<S>, B> {
         SymexConstructor {
             file: self.file,
             override_arch: self.override_arch,
-            smt: SmtConfigured::<S> { smt: S::new() },
+            smt: SmtConfigured::<S> {
+                smt: S::with_config(&config),
+                config,
+            },
             binary_file: self.binary_file,
+            layout: self.layout,
         }
     }
+
+    /// Configures the SMT solver to only use a single thread.
+    ///
+    /// Multi threaded solving may return different models between runs, use
+    /// this together with [`SymexArbiter::set_seed`](crate::manager::SymexArbiter::set_seed)
+    /// when runs need to be reproducible.
+    pub fn configure_smt_single_threaded<S: SmtSolver>(self) -> SymexConstructor<'str, A, SmtConfigured<S>, B> {
+        self.configure_smt_with(SolverConfig::single_threaded())
+    }
 }
 
 impl<'str, A: ArchOverride, S: SmtSolverConfigured> SymexConstructor<'str, A, S, BinaryNotLoaded> {
     pub fn load_binary(self) -> crate::Result<SymexConstructor<'str, A, S, BinaryLoaded<'static>>> {
         let file = std::fs::read(self.file).map_err(|e| crate::GAError::CouldNotOpenFile(e.to_string()))?;
         let data = &(*file.leak());
+        // Detects the binary format, ELF, Mach-O and PE files are supported.
         let obj_file = match object::File::parse(data) {
             Ok(x) => x,
             Err(e) => {
@@ -145,6 +196,7 @@ impl<'str, A: ArchOverride, S: SmtSolverConfigured> SymexConstructor<'str, A, S,
                 return Err(crate::GAError::ProjectError(ProjectError::UnableToParseElf(ret.display().to_string())))?;
             }
         };
+        debug!("Loaded {:?} binary", obj_file.format());
         Ok(SymexConstructor {
             file: self.file,
             override_arch: self.override_arch,
@@ -153,10 +205,59 @@ impl<'str, A: ArchOverride, S: SmtSolverConfigured> SymexConstructor<'str, A, S,
                 object: obj_file,
                 path: self.file.to_string(),
             },
+            layout: self.layout,
         })
     }
 }
 
+impl<'str, A: ArchOverride, S: SmtSolverConfigured> SymexConstructor<'str, A, S, BinaryNotLoaded> {
+    /// Loads a headerless binary, such as a flash dump, in to a single
+    /// executable region starting at `base_addr`.
+    ///
+    /// As there is no header the architecture can not be discovered and has
+    /// to be set using [`SymexConstructor::with_architecture`].
+    pub fn load_raw(self, base_addr: u64, entry: u64) -> crate::Result<SymexConstructor<'str, A, S, RawLoaded>> {
+        let data = std::fs::read(self.file).map_err(|e| crate::GAError::CouldNotOpenFile(e.to_string()))?;
+        debug!("Loaded {} bytes of raw binary at {base_addr:#x}", data.len());
+        Ok(SymexConstructor {
+            file: self.file,
+            override_arch: self.override_arch,
+            smt: self.smt,
+            binary_file: RawLoaded {
+                data,
+                base_addr,
+                entry,
+                symtab: HashMap::new(),
+            },
+            layout: self.layout,
+        })
+    }
+}
+
+impl<'str, A: ArchOverride, S: SmtSolverConfigured> SymexConstructor<'str, A, S, RawLoaded> {
+    /// Adds symbols to the raw binary, e.g. from a linker map.
+    ///
+    /// At least `_stack_start` is required to run the binary.
+    pub fn with_symbols(mut self, symtab: HashMap<String, u64>) -> Self {
+        self.binary_file.symtab.extend(symtab);
+        self
+    }
+}
+
+impl<'str, S: SmtSolverConfigured> SymexConstructor<'str, NoArchOverride, S, RawLoaded> {
+    /// Sets the architecture of a raw binary, this replaces
+    /// [`SymexConstructor::discover`] for binaries without a header.
+    pub fn with_architecture(self, arch: SupportedArchitecture<NoArchitectureOverride>) -> SymexConstructor<'str, SupportedArchitecture<NoArchitectureOverride>, S, RawLoaded> {
+        SymexConstructor {
+            file: self.file,
+            override_arch: arch,
+            smt: self.smt,
+            binary_file: self.binary_file,
+            layout: self.layout,
+        }
+    }
+}
+
 impl<'str, S: SmtSolverConfigured> SymexConstructor<'str, NoArchOverride, S, BinaryLoaded<'static>> {
     pub fn discover(self) -> crate::Result<SymexConstructor<'str, SupportedArchitecture<NoArchitectureOverride>, S, BinaryLoaded<'static>>> {
         let arch = SupportedArchitecture::discover(&self.binary_file.object)?;
@@ -166,6 +267,7 @@ impl<'str, S: SmtSolverConfigured> SymexConstructor<'str, NoArchOverride, S, Bin
             override_arch: arch,
             smt: self.smt,
             binary_file: self.binary_file,
+            layout: self.layout,
         })
     }
 }
@@ -202,22 +304,26 @@ impl<S: SmtSolver, Override: ArchitectureOverride> SymexConstructor<'_, Supporte
             Endianness::Big => gimli::RunTimeEndian::Big,
         };
 
-        let debug_info = binary.section_by_name(".debug_info").unwrap();
-        let debug_info = DebugInfo::new(debug_info.data().unwrap(), gimli_endian);
-
-        let debug_abbrev = binary.section_by_name(".debug_abbrev").unwrap();
-        let debug_abbrev = DebugAbbrev::new(debug_abbrev.data().unwrap(), gimli_endian);
+        // Mach-O and PE binaries are often shipped without debug info, in that case only the symbol
+        // table is used.
+        let section = |name: &str| binary.section_by_name(name).and_then(|section| section.data().ok()).unwrap_or_default();
 
-        let debug_str = binary.section_by_name(".debug_str").unwrap();
-        let debug_str = DebugStr::new(debug_str.data().unwrap(), gimli_endian);
-        let debug_line = DebugLine::new(binary.section_by_name(".debug_line").unwrap().data().unwrap(), gimli_endian);
+        let debug_info = DebugInfo::new(section(".debug_info"), gimli_endian);
+        let debug_abbrev = DebugAbbrev::new(section(".debug_abbrev"), gimli_endian);
+        let debug_str = DebugStr::new(section(".debug_str"), gimli_endian);
+        let debug_line = DebugLine::new(section(".debug_line"), gimli_endian);
 
         let mut map = SubProgramMap::new(&debug_info, &debug_abbrev, &debug_str, &debug_line);
         map.insert_symtab(symtab);
         let mut hooks = HookContainer::default(&map)?;
         self.override_arch.add_hooks(&mut hooks, &mut map);
+        hooks.resolve_named(&map)?;
 
-        let project = std::sync::Arc::new(Project::from_binary(&mut smt, &binary, map.clone())?);
+        let mut project = Project::from_binary(&mut smt, &binary, map.clone())?;
+        if let Some((word_size, endianness)) = self.layout {
+            project.override_layout(word_size, endianness)?;
+        }
+        let project = std::sync::Arc::new(project);
         let line_map = line_program(&binary, gimli_endian).unwrap_or(LineMap::empty());
         let debug_data = DebugData::new(Box::leak(Box::new(binary)), gimli_endian).expect("Debug data to be created");
 
@@ -235,6 +341,45 @@ impl<S: SmtSolver, Override: ArchitectureOverride> SymexConstructor<'_, Supporte
     }
 }
 
+impl<S: SmtSolver, Override: ArchitectureOverride> SymexConstructor<'_, SupportedArchitecture<Override>, SmtConfigured<S>, RawLoaded> {
+    pub fn compose<C, StateCreator: FnOnce() -> C::StateContainer, LoggingCreator: FnOnce(&SubProgramMap) -> C::Logger>(
+        self,
+        user_state_composer: StateCreator,
+        logger: LoggingCreator,
+    ) -> crate::Result<SymexArbiter<C>>
+    where
+        C::Memory: SmtMap<ProgramMemory = std::sync::Arc<Project<C::SMT>>>,
+        C: Composition<SMT = S, ArchitectureOverride = Override>,
+    {
+        let RawLoaded { data, base_addr, entry, symtab } = self.binary_file;
+        let mut smt = self.smt.smt;
+
+        // There is no debug info, only the symbols that were given explicitly.
+        let mut map = SubProgramMap::default();
+        map.insert_symtab(symtab);
+        let mut hooks = HookContainer::default(&map)?;
+        self.override_arch.add_hooks(&mut hooks, &mut map);
+        hooks.resolve_named(&map)?;
+
+        let mut project = Project::from_raw(&mut smt, data, base_addr, entry, map.clone());
+        if let Some((word_size, endianness)) = self.layout {
+            project.override_layout(word_size, endianness)?;
+        }
+
+        Ok(SymexArbiter::<C>::new(
+            logger(&map),
+            std::sync::Arc::new(project),
+            smt,
+            user_state_composer(),
+            hooks,
+            map,
+            self.override_arch,
+            LineMap::empty(),
+            DebugData::empty(),
+        ))
+    }
+}
+
 impl Display for NoArchOverride {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Not overriding architecture")
@@ -250,3 +395,551 @@ impl<S: SmtSolver> SmtSolverConfigured for SmtConfigured<S> {}
 
 impl BinaryLoadingDone for BinaryNotLoaded {}
 impl BinaryLoadingDone for BinaryLoaded<'static> {}
+impl BinaryLoadingDone for RawLoaded {}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::{
+        ops::ControlFlow,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use hashbrown::HashMap;
+
+    use super::SymexConstructor;
+    use crate::{
+        arch::{arm::v6::ArmV6M, Architecture, NoArchitectureOverride, SupportedArchitecture},
+        defaults::{bitwuzla::DefaultComposition, logger::SimplePathLogger},
+        executor::{
+            hooks::{LangagueHooks, PCHook},
+            memory_interface::MemoryBucketingFilter,
+            state::{ArgumentValue, ForkStrategy, GAState},
+            FailureReason,
+            PathResult,
+        },
+        manager::SymexArbiter,
+        path_selection::{DFSPathSelection, MergingSelector, PathSelector},
+        project::Project,
+        smt::{
+            bitwuzla::{expr::BitwuzlaExpr, fpexpr::FpExpr, memory::BitwuzlaMemory, Bitwuzla},
+            SmtExpr,
+            SmtMap,
+            SmtSolver,
+            Solutions,
+        },
+        Composition,
+    };
+
+    /// Attaches the value of `R2` to every successful path.
+    #[derive(Clone, Debug)]
+    struct PayloadComposition {}
+
+    impl Composition for PayloadComposition {
+        type ArchitectureOverride = NoArchitectureOverride;
+        type Logger = SimplePathLogger;
+        type Memory = BitwuzlaMemory<()>;
+        type MemoryFilter = MemoryBucketingFilter<Self>;
+        type PathPayload = u64;
+        type PathSelector = DFSPathSelection<Self>;
+        type ProgramMemory = std::sync::Arc<Project<Self::SMT>>;
+        type SMT = Bitwuzla;
+        type SmtExpression = BitwuzlaExpr;
+        type SmtFPExpression = FpExpr;
+        type StateContainer = ();
+
+        fn path_payload(state: &mut GAState<Self>, result: &PathResult<Self>) -> Option<u64> {
+            if !matches!(result, PathResult::Success(..)) {
+                return None;
+            }
+            state.get_register("R2").ok()?.get_constant()
+        }
+    }
+
+    /// Merges the paths of branches that reconverge.
+    #[derive(Clone, Debug)]
+    struct MergingComposition {}
+
+    impl Composition for MergingComposition {
+        type ArchitectureOverride = NoArchitectureOverride;
+        type Logger = SimplePathLogger;
+        type Memory = BitwuzlaMemory<()>;
+        type MemoryFilter = MemoryBucketingFilter<Self>;
+        type PathPayload = ();
+        type PathSelector = MergingSelector<Self>;
+        type ProgramMemory = std::sync::Arc<Project<Self::SMT>>;
+        type SMT = Bitwuzla;
+        type SmtExpression = BitwuzlaExpr;
+        type SmtFPExpression = FpExpr;
+        type StateContainer = ();
+
+        const MERGE_PATHS: bool = true;
+    }
+
+    /// A function with three independent branches, computing a different value
+    /// in `R2` on each path.
+    ///
+    /// ```text
+    /// movs r2, #0
+    /// cmp r0, #0; beq 1f; adds r2, #1
+    /// 1: cmp r1, #0; beq 2f; adds r2, #2
+    /// 2: cmp r3, #0; beq 3f; adds r2, #4
+    /// 3: bx lr
+    /// ```
+    const THREE_BRANCHES: [u8; 22] = [
+        0x00, 0x22, 0x00, 0x28, 0x00, 0xd0, 0x01, 0x32, 0x00, 0x29, 0x00, 0xd0, 0x02, 0x32, 0x00, 0x2b, 0x00, 0xd0, 0x04, 0x32, 0x70, 0x47,
+    ];
+
+    /// Loads the raw Armv6-M `program` at `0x1000` with the stack starting at
+    /// `0x2000_1000` and `symbols` added to the symbol table.
+    ///
+    /// The program is written to a temporary file unique to the call, which is
+    /// removed once it has been read.
+    pub(crate) fn raw_executor<C>(program: &[u8], symbols: &[(&str, u64)]) -> SymexArbiter<C>
+    where
+        C: Composition<SMT = Bitwuzla, ArchitectureOverride = NoArchitectureOverride, Logger = SimplePathLogger, StateContainer = ()>,
+        C::Memory: SmtMap<ProgramMemory = std::sync::Arc<Project<Bitwuzla>>>,
+    {
+        static PROGRAMS: AtomicUsize = AtomicUsize::new(0);
+        let id = PROGRAMS.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("symex_test_{}_{id}.bin", std::process::id()));
+        std::fs::write(&path, program).unwrap();
+        let file = path.display().to_string();
+        let loaded = SymexConstructor::new(&file).load_raw(0x1000, 0x1001);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut symtab = HashMap::new();
+        symtab.insert("_stack_start".to_string(), 0x2000_1000);
+        symtab.extend(symbols.iter().map(|(name, address)| ((*name).to_string(), *address)));
+        loaded
+            .unwrap()
+            .with_symbols(symtab)
+            .with_architecture(SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()))
+            .configure_smt::<Bitwuzla>()
+            .compose::<C, _, _>(|| (), SimplePathLogger::from_sub_programs)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_load_raw() {
+        // movs r0, #42; bx lr
+        let mut executor = raw_executor::<DefaultComposition>(&[0x2a, 0x20, 0x70, 0x47], &[]);
+
+        let mut runner = executor.run_from_pc(0x1000, &LangagueHooks::None).unwrap();
+        let mut stepper = runner.stepper().unwrap().expect("A path to step through");
+        assert!(stepper.step(1).unwrap().is_none());
+        let r0 = stepper.executor().state.get_register("R0").unwrap();
+        assert_eq!(r0.get_constant(), Some(42));
+    }
+
+    #[test]
+    fn test_run_until() {
+        // movs r0, #1; movs r1, #2; movs r2, #3; bx lr
+        let mut executor = raw_executor::<DefaultComposition>(&[0x01, 0x20, 0x02, 0x21, 0x03, 0x22, 0x70, 0x47], &[]);
+
+        let mut runner = executor.run_from_pc(0x1000, &LangagueHooks::None).unwrap();
+        let mut stepper = runner.stepper().unwrap().expect("A path to step through");
+        let at = |target: u64| move |state: &GAState<DefaultComposition>| state.memory.get_pc().unwrap().get_constant().map(|pc| pc & !1) == Some(target);
+
+        let (result, _) = stepper.run_until(at(0x1004)).unwrap();
+        assert!(result.is_none());
+        let state = &mut stepper.executor().state;
+        assert_eq!(state.get_register("R0").unwrap().get_constant(), Some(1));
+        assert_eq!(state.get_register("R1").unwrap().get_constant(), Some(2));
+        assert_eq!(state.get_register("R2").unwrap().get_constant(), None);
+
+        // The target is never reached again so the path runs to completion.
+        let (result, _) = stepper.run_until(at(0x1000)).unwrap();
+        assert!(matches!(result, Some(PathResult::Success(..))));
+    }
+
+    #[test]
+    fn test_enumerate() {
+        // movs r1, #3; ands r1, r0
+        // cmp r2, #0; beq 1f; movs r1, #0
+        // 1: bx lr
+        let mut executor = raw_executor::<DefaultComposition>(&[0x03, 0x21, 0x01, 0x40, 0x00, 0x2a, 0x00, 0xd0, 0x00, 0x21, 0x70, 0x47], &[]);
+
+        // Both paths can produce 0, it should only be reported once.
+        let values = executor.run_from_pc(0x1000, &LangagueHooks::None).unwrap().enumerate("R1", 4).unwrap();
+        assert!(matches!(values, Solutions::Exactly(values) if values == [0, 1, 2, 3]));
+
+        let values = executor.run_from_pc(0x1000, &LangagueHooks::None).unwrap().enumerate("R1", 2).unwrap();
+        assert!(matches!(values, Solutions::AtLeast(values) if values.len() == 2));
+    }
+
+    #[test]
+    fn test_named_hook() {
+        // bl callee; bx lr; nop
+        // callee: movs r0, #1; bx lr
+        let mut executor = raw_executor::<DefaultComposition>(&[0x00, 0xf0, 0x02, 0xf8, 0x70, 0x47, 0x00, 0xbf, 0x01, 0x20, 0x70, 0x47], &[("callee", 0x1009)]);
+        executor.add_hooks(|hooks, _| {
+            hooks.add_named("callee", PCHook::EndFailure("entered callee"));
+        });
+
+        let results = executor
+            .run_from_pc(0x1000, &LangagueHooks::None)
+            .unwrap()
+            .map(|result| result.unwrap().2)
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(&results[0], PathResult::Failure(FailureReason::Abort(reason)) if reason == "entered callee"));
+
+        executor.add_hooks(|hooks, _| {
+            hooks.add_named("missing", PCHook::EndSuccess);
+        });
+        let Err(error) = executor.run_from_pc(0x1000, &LangagueHooks::None) else {
+            panic!("Unresolved hook names should be reported");
+        };
+        assert!(format!("{error:#}").contains("missing"));
+    }
+
+    #[test]
+    fn test_stub_return() {
+        // push {lr}; bl callee; pop {pc}
+        // callee: movs r0, #1; bx lr
+        let mut executor = raw_executor::<DefaultComposition>(&[0x00, 0xb5, 0x00, 0xf0, 0x01, 0xf8, 0x00, 0xbd, 0x01, 0x20, 0x70, 0x47], &[("callee", 0x1009)]);
+        executor.add_hooks(|hooks, _| {
+            hooks.add_named("callee", PCHook::stub_return(32));
+        });
+
+        let results = executor
+            .run_from_pc(0x1000, &LangagueHooks::None)
+            .unwrap()
+            .map(|result| {
+                let (mut state, _, result) = result.unwrap();
+                assert!(matches!(result, PathResult::Success(..)));
+                state.get_register("R0").unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        // The body would have returned 1, the stub returns any value.
+        assert_eq!(results[0].get_constant(), None);
+    }
+
+    #[test]
+    fn test_call_stack() {
+        // entry: push {lr}; bl f1; pop {pc}; nop
+        // f1: push {lr}; bl f2; pop {pc}; nop
+        // f2: nop; bx lr
+        let program = [
+            0x00, 0xb5, 0x00, 0xf0, 0x02, 0xf8, 0x00, 0xbd, 0x00, 0xbf, 0x00, 0xb5, 0x00, 0xf0, 0x02, 0xf8, 0x00, 0xbd, 0x00, 0xbf, 0x00, 0xbf, 0x70, 0x47,
+        ];
+        let mut executor = raw_executor::<DefaultComposition>(&program, &[("entry", 0x1001), ("f1", 0x100b), ("f2", 0x1015)]);
+        executor.add_hooks(|hooks, _| {
+            hooks.add_pc_hook(0x1016, PCHook::EndFailure("failed in f2"));
+        });
+
+        let results = executor.run_from_pc(0x1000, &LangagueHooks::None).unwrap().map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        let (state, logger, result) = &results[0];
+        assert!(matches!(result, PathResult::Failure(FailureReason::Abort(_))));
+        let functions = state.call_stack().iter().map(|frame| (frame.function, frame.return_address)).collect::<Vec<_>>();
+        assert_eq!(functions, [(0x1000, 0xffff_fffe), (0x100a, 0x1006), (0x1014, 0x1010)]);
+
+        let log = logger.to_string();
+        assert!(log.contains("#0 f2 @ 0x1014"));
+        assert!(log.contains("#1 f1 @ 0x1010"));
+        assert!(log.contains("#2 entry @ 0x1006"));
+    }
+
+    #[test]
+    fn test_argument_values() {
+        // cmp r0, #10; bcs 1f; bx lr
+        // 1: bx lr
+        let mut executor = raw_executor::<DefaultComposition>(&[0x0a, 0x28, 0x00, 0xd2, 0x70, 0x47, 0x70, 0x47], &[("entry", 0x1001)]);
+        executor.set_argument("R0", ArgumentValue::Range { low: 0, high: 10 });
+
+        // The branch for values of 10 and above is infeasible.
+        assert_eq!(executor.run("entry", &LangagueHooks::None).unwrap().count(), 1);
+        let values = executor.enumerate("entry", "R0", 20, &LangagueHooks::None).unwrap();
+        assert!(matches!(values, Solutions::Exactly(values) if values == (0..10).collect::<Vec<_>>()));
+
+        executor.set_argument("R0", ArgumentValue::Concrete(3));
+        let values = executor.enumerate("entry", "R0", 20, &LangagueHooks::None).unwrap();
+        assert!(matches!(values, Solutions::Exactly(values) if values == [3]));
+    }
+
+    #[test]
+    fn test_generate_test_vectors() {
+        // movs r2, #0
+        // cmp r0, #0; beq 1f; adds r2, #1
+        // 1: bx lr
+        let mut executor = raw_executor::<DefaultComposition>(&[0x00, 0x22, 0x00, 0x28, 0x00, 0xd0, 0x01, 0x32, 0x70, 0x47], &[("entry", 0x1001)]);
+
+        let vectors = executor.generate_test_vectors("entry", &["R2"], &LangagueHooks::None).unwrap();
+        assert_eq!(vectors.len(), 2);
+        assert_ne!(vectors[0].inputs, vectors[1].inputs);
+        for vector in &vectors {
+            assert_eq!(vector.inputs.entry(), "entry");
+            let expected = u64::from(vector.inputs.get("R0").unwrap() != 0);
+            assert_eq!(vector.outputs.get("R2"), Some(&expected));
+        }
+    }
+
+    #[test]
+    fn test_path_payload() {
+        // movs r2, #0
+        // cmp r0, #0; beq 1f; adds r2, #1
+        // 1: bx lr
+        let mut executor = raw_executor::<PayloadComposition>(&[0x00, 0x22, 0x00, 0x28, 0x00, 0xd0, 0x01, 0x32, 0x70, 0x47], &[("entry", 0x1001)]);
+
+        let mut payloads = executor
+            .run("entry", &LangagueHooks::None)
+            .unwrap()
+            .map(|result| result.unwrap().0.path_payload.expect("Every path to succeed"))
+            .collect::<Vec<_>>();
+        payloads.sort_unstable();
+        assert_eq!(payloads, [0, 1]);
+    }
+
+    #[test]
+    fn test_instruction_budget() {
+        // movs r0, #0
+        // 1: adds r0, #1; cmp r0, #255; bne 1b
+        // bx lr
+        let mut executor = raw_executor::<DefaultComposition>(&[0x00, 0x20, 0x01, 0x30, 0xff, 0x28, 0xfc, 0xd1, 0x70, 0x47], &[("entry", 0x1001)]);
+
+        let results = executor.run("entry", &LangagueHooks::None).unwrap().map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        let (state, log, result) = &results[0];
+        assert!(matches!(result, PathResult::Success(..)));
+        assert_eq!(state.instruction_count(), 1 + 255 * 3 + 1);
+        // The count is reported next to the cycle count of the path.
+        assert!(log.to_string().contains(&format!("over {} instructions", 1 + 255 * 3 + 1)));
+
+        // The loop is cut short and the path is suppressed.
+        executor.set_max_instructions_per_path(100);
+        assert_eq!(executor.run("entry", &LangagueHooks::None).unwrap().count(), 0);
+    }
+
+    /// Explores all paths of a function with three independent branches and
+    /// returns the value it computes on each path.
+    fn explore_branches(strategy: ForkStrategy) -> Vec<u64> {
+        let mut executor = raw_executor::<DefaultComposition>(&THREE_BRANCHES, &[]);
+        executor.set_fork_strategy(strategy);
+
+        let mut results = executor
+            .run_from_pc(0x1000, &LangagueHooks::None)
+            .unwrap()
+            .map(|result| {
+                let (mut state, _, result) = result.unwrap();
+                assert!(matches!(result, PathResult::Success(..)));
+                state.get_register("R2").unwrap().get_constant().unwrap()
+            })
+            .collect::<Vec<_>>();
+        results.sort_unstable();
+        results
+    }
+
+    #[test]
+    fn test_run_with_stops_early() {
+        // movs r2, #0
+        // cmp r0, #0; beq 1f; adds r2, #1
+        // 1: cmp r1, #0; beq 2f; adds r2, #2
+        // 2: bx lr
+        let mut executor = raw_executor::<DefaultComposition>(
+            &[0x00, 0x22, 0x00, 0x28, 0x00, 0xd0, 0x01, 0x32, 0x00, 0x29, 0x00, 0xd0, 0x02, 0x32, 0x70, 0x47],
+            &[("entry", 0x1001)],
+        );
+
+        let mut paths = 0;
+        let flow = executor
+            .run_with("entry", &LangagueHooks::None, |_, _, result| {
+                assert!(matches!(result, PathResult::Success(..)));
+                paths += 1;
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+        assert_eq!(flow, ControlFlow::Continue(()));
+        assert_eq!(paths, 4);
+
+        // No further paths are explored once the callback breaks.
+        let mut paths = 0;
+        let flow = executor
+            .run_with("entry", &LangagueHooks::None, |_, _, _| {
+                paths += 1;
+                ControlFlow::Break(())
+            })
+            .unwrap();
+        assert_eq!(flow, ControlFlow::Break(()));
+        assert_eq!(paths, 1);
+    }
+
+    #[test]
+    fn test_max_paths() {
+        let mut executor = raw_executor::<DefaultComposition>(&THREE_BRANCHES, &[]);
+        executor.set_max_paths(3);
+
+        let mut runner = executor.run_from_pc(0x1000, &LangagueHooks::None).unwrap();
+        let paths = runner.by_ref().map(|result| result.unwrap()).count();
+        assert_eq!(paths, 3);
+        assert!(runner.truncated());
+        assert_eq!(runner.number_of_queued_paths(), 0);
+
+        // The solver is left in a state where all paths can be explored again.
+        executor.set_max_paths(8);
+        let mut runner = executor.run_from_pc(0x1000, &LangagueHooks::None).unwrap();
+        let mut results = runner
+            .by_ref()
+            .map(|result| {
+                let (mut state, _, result) = result.unwrap();
+                assert!(matches!(result, PathResult::Success(..)));
+                state.get_register("R2").unwrap().get_constant().unwrap()
+            })
+            .collect::<Vec<_>>();
+        results.sort_unstable();
+        assert_eq!(results, (0..8).collect::<Vec<_>>());
+        assert!(!runner.truncated());
+    }
+
+    #[test]
+    fn test_fork_strategies_agree() {
+        let expected = (0..8).collect::<Vec<_>>();
+        assert_eq!(explore_branches(ForkStrategy::Clone), expected);
+        assert_eq!(explore_branches(ForkStrategy::Incremental), expected);
+    }
+
+    #[test]
+    fn test_incremental_forks_hold_fewer_states() {
+        let peak_queued_states = |strategy| {
+            let mut executor = raw_executor::<DefaultComposition>(&THREE_BRANCHES, &[]);
+            executor.set_fork_strategy(strategy);
+            let mut runner = executor.run_from_pc(0x1000, &LangagueHooks::None).unwrap();
+            let mut peak = runner.number_of_queued_states();
+            while let Some(result) = runner.next() {
+                result.unwrap();
+                peak = peak.max(runner.number_of_queued_states());
+            }
+            peak
+        };
+
+        // The first path forks once at every branch, the waiting paths share
+        // its start when forking incrementally.
+        assert_eq!(peak_queued_states(ForkStrategy::Clone), 3);
+        assert_eq!(peak_queued_states(ForkStrategy::Incremental), 1);
+    }
+
+    #[test]
+    fn test_incremental_forks_on_unnamed_symbols() {
+        // push {lr}; movs r2, #0; bl callee
+        // cmp r0, #0; beq 1f; adds r2, #1
+        // 1: cmp r0, #1; beq 2f; adds r2, #2
+        // 2: pop {pc}
+        // callee: bx lr
+        let program = [
+            0x00, 0xb5, 0x00, 0x22, 0x00, 0xf0, 0x07, 0xf8, 0x00, 0x28, 0x00, 0xd0, 0x01, 0x32, 0x01, 0x28, 0x00, 0xd0, 0x02, 0x32, 0x00, 0xbd, 0x70, 0x47,
+        ];
+        let explore = |strategy| {
+            let mut executor = raw_executor::<DefaultComposition>(&program, &[("callee", 0x1017)]);
+            executor.set_fork_strategy(strategy);
+            // The callee returns a symbol that is created anew when re-deriving
+            // the state.
+            executor.add_hooks(|hooks, _| {
+                hooks.add_named(
+                    "callee",
+                    PCHook::Intrinsic(|state: &mut GAState<DefaultComposition>| {
+                        let value = state.memory.unconstrained_unnamed(32);
+                        state.set_register("R0", value)?;
+                        let lr = state.get_register("LR")?;
+                        state.set_register("PC", lr)?;
+                        Ok(())
+                    }),
+                );
+            });
+            let mut results = executor
+                .run_from_pc(0x1000, &LangagueHooks::None)
+                .unwrap()
+                .map(|result| {
+                    let (mut state, _, result) = result.unwrap();
+                    assert!(matches!(result, PathResult::Success(..)));
+                    state.get_register("R2").unwrap().get_constant().unwrap()
+                })
+                .collect::<Vec<_>>();
+            results.sort_unstable();
+            results
+        };
+
+        assert_eq!(explore(ForkStrategy::Clone), [1, 2, 3]);
+        assert_eq!(explore(ForkStrategy::Incremental), [1, 2, 3]);
+    }
+
+    /// An `if`/`else` where both sides store to the same stack slot, which is
+    /// loaded after they join.
+    ///
+    /// ```text
+    /// cmp r0, #0; beq 1f
+    /// movs r2, #1; str r2, [sp]; b 2f
+    /// 1: movs r2, #2; str r2, [sp]
+    /// 2: ldr r3, [sp]; bx lr
+    /// ```
+    const DIAMOND_WITH_STORES: [u8; 18] = [0x00, 0x28, 0x02, 0xd0, 0x01, 0x22, 0x00, 0x92, 0x01, 0xe0, 0x02, 0x22, 0x00, 0x92, 0x00, 0x9b, 0x70, 0x47];
+
+    /// Returns the only value `R3` can hold in `state` when `R0` is `r0`.
+    fn r3_when(state: &mut GAState<MergingComposition>, r0: u64) -> Option<u64> {
+        let condition = state.get_register("R0").unwrap()._eq(&state.memory.from_u64(r0, 32));
+        let r3 = state.get_register("R3").unwrap();
+        state.constraints.push();
+        state.constraints.assert(&condition);
+        let values = state.constraints.get_values(&r3, 2);
+        state.constraints.pop();
+        match values.ok()? {
+            Solutions::Exactly(values) if values.len() == 1 => values[0].get_constant(),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_diamond_with_stores_merges_to_one_path() {
+        let mut executor = raw_executor::<MergingComposition>(&DIAMOND_WITH_STORES, &[]);
+
+        let mut results = executor
+            .run_from_pc(0x1000, &LangagueHooks::None)
+            .unwrap()
+            .map(|result| result.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        let (state, _, result) = &mut results[0];
+        assert!(matches!(result, PathResult::Success(..)));
+        assert_eq!(r3_when(state, 0), Some(2));
+        assert_eq!(r3_when(state, 1), Some(1));
+    }
+
+    #[test]
+    fn test_stepper_merges_diamond_with_stores() {
+        let mut executor = raw_executor::<MergingComposition>(&DIAMOND_WITH_STORES, &[]);
+
+        let mut runner = executor.run_from_pc(0x1000, &LangagueHooks::None).unwrap();
+        let mut stepper = runner.stepper().unwrap().expect("A path to step through");
+        let (result, _) = stepper.run_until(|_| false).unwrap();
+        assert!(matches!(result, Some(PathResult::Success(..))));
+        let state = &mut stepper.executor().state;
+        assert_eq!(r3_when(state, 0), Some(2));
+        assert_eq!(r3_when(state, 1), Some(1));
+        assert_eq!(stepper.executor().vm.paths.waiting_paths(), 0);
+    }
+
+    #[test]
+    fn test_cycle_bound_witness_reports_slow_branch() {
+        // cmp r0, #0; beq 1f; bx lr
+        // 1: nop; nop; nop; nop; nop; nop; bx lr
+        let mut executor = raw_executor::<DefaultComposition>(
+            &[
+                0x00, 0x28, 0x00, 0xd0, 0x70, 0x47, 0x00, 0xbf, 0x00, 0xbf, 0x00, 0xbf, 0x00, 0xbf, 0x00, 0xbf, 0x00, 0xbf, 0x70, 0x47,
+            ],
+            &[],
+        );
+
+        let witnesses = executor
+            .run_from_pc(0x1000, &LangagueHooks::None)
+            .unwrap()
+            .filter_map(|result| {
+                let (mut state, _, result) = result.unwrap();
+                assert!(matches!(result, PathResult::Success(..)));
+                state.cycle_bound_witness(8).unwrap()
+            })
+            .collect::<Vec<_>>();
+        // Only the slow branch, taken when r0 is zero, exceeds the bound.
+        assert_eq!(witnesses.len(), 1);
+        assert_eq!(witnesses[0].get("R0"), Some(&0));
+    }
+}
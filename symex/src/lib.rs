@@ -38,13 +38,15 @@
 use std::fmt::Debug;
 
 use arch::{ArchError, ArchitectureOverride};
-use executor::memory_interface::MemoryFilter;
+use executor::{memory_interface::MemoryFilter, state::GAState, PathResult};
+use general_assembly::extension::ieee754::RoundingMode;
 use logging::Logger;
 use memory::MemoryError;
 use path_selection::PathSelector;
 use project::ProjectError;
 use smt::{ProgramMemory, SmtExpr, SmtFPExpr, SmtMap, SmtSolver, SolverError};
 
+pub mod analysis;
 pub mod arch;
 pub mod defaults;
 pub mod executor;
@@ -55,6 +57,7 @@ pub mod memory;
 pub mod path_selection;
 pub mod project;
 pub mod smt;
+pub use analysis::{AnalysisOptions, AnalysisSummary, Analyzer};
 pub use general_assembly;
 
 pub type Result<T> = std::result::Result<T, anyhow::Error>;
@@ -81,7 +84,51 @@ pub trait Composition: Clone + Debug {
 
     type PathSelector: PathSelector<Self>;
 
+    /// User data attached to every completed path, see
+    /// [`Self::path_payload`].
+    type PathPayload: Clone + Debug;
+
     type MemoryFilter: MemoryFilter<Self>;
+
+    /// If true, reads from memory that has never been written are tracked and
+    /// any path where such values are used as an address or as the program
+    /// counter ends in a failure.
+    const TRACK_UNINITIALIZED_READS: bool = false;
+
+    /// If true, the address of every executed instruction is recorded so that
+    /// an instruction coverage report can be produced after the analysis.
+    const RECORD_COVERAGE: bool = true;
+
+    /// If true, every value written by an operation is labeled with the named
+    /// inputs it is derived from, see [`Taint`](crate::executor::taint::Taint).
+    const TRACK_TAINT: bool = false;
+
+    /// The rounding mode used by floating point operations that do not
+    /// request a specific one.
+    const ROUNDING_MODE: RoundingMode = RoundingMode::TiesTowardZero;
+
+    /// If true, [`MergingSelector`](crate::path_selection::MergingSelector)
+    /// merges paths that reach the same address. This reduces the number of
+    /// paths explored after branches that reconverge, but makes the
+    /// expressions of the merged path larger and suspends paths that are
+    /// ahead of others.
+    const MERGE_PATHS: bool = false;
+
+    /// If true, the locations reported when a path forks or fails include the
+    /// value of every register, see
+    /// [`GAState::register_dump`](crate::executor::state::GAState::register_dump).
+    const LOG_REGISTERS: bool = false;
+
+    /// Called when a path completes, before it is returned from the
+    /// [`Runner`](crate::manager::Runner).
+    ///
+    /// The returned payload is stored in
+    /// [`GAState::path_payload`] of the final state, making it possible to
+    /// attach domain specific data such as which assertion failed to the
+    /// result. Suppressed paths are never passed to this function.
+    fn path_payload(_state: &mut GAState<Self>, _result: &PathResult<Self>) -> Option<Self::PathPayload> {
+        None
+    }
 }
 
 /// Helper to mask fields from a type.
@@ -155,6 +202,18 @@ pub enum GAError {
 
     #[error("Invalid floating point rounding mode requested.")]
     InvalidRoundingMode,
+
+    #[error("The path does not satisfy an assumption.")]
+    AssumptionUnsat,
+
+    #[error("A memory copy or fill of {0} bytes exceeds the limit of {max} bytes.", max = executor::state::MAX_MEMORY_OPERATION_LENGTH)]
+    MemoryOperationTooLong(u64),
+
+    #[error("The memory model does not support snapshots.")]
+    SnapshotsUnsupported,
+
+    #[error("No memory snapshot has been taken.")]
+    NoSnapshot,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
@@ -166,7 +225,7 @@ pub enum InternalError {
     InvalidErrorCombination,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WordSize {
     Bit64,
     Bit32,
@@ -174,7 +233,7 @@ pub enum WordSize {
     Bit8,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[must_use]
 pub enum Endianness {
     Little,
@@ -88,6 +88,10 @@ pub trait Logger: Clone + core::fmt::Debug {
     fn new<C: Composition>(state: &SymexArbiter<C>) -> Self;
 
     fn record_backtrace(&mut self, bt: Option<CallStack>);
+
+    /// Records the shadow call stack of a failing path, see
+    /// [`GAState::call_stack`].
+    fn record_call_stack<C: Composition>(&mut self, _state: &GAState<C>) {}
 }
 
 impl Logger for NoLogger {
@@ -125,6 +129,124 @@ impl Logger for NoLogger {
     fn record_backtrace(&mut self, _bt: Option<CallStack>) {}
 }
 
+/// Forwards everything that is logged to two loggers, e.g. to keep a human
+/// readable log and a machine readable log of the same run.
+///
+/// Both loggers have to identify regions in the same way, the current region
+/// is taken from the first logger. A [`CallStack`] can not be copied so
+/// backtraces are only recorded by the first logger.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct TeeLogger<L1, L2> {
+    pub first: L1,
+    pub second: L2,
+}
+
+impl<L1, L2> TeeLogger<L1, L2> {
+    pub const fn new(first: L1, second: L2) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns the two inner loggers.
+    #[must_use]
+    pub fn into_inner(self) -> (L1, L2) {
+        (self.first, self.second)
+    }
+}
+
+impl<L1, L2> Logger for TeeLogger<L1, L2>
+where
+    L1: Logger,
+    L1::RegionIdentifier: Clone,
+    L1::RegionDelimiter: Clone,
+    L2: Logger<RegionIdentifier = L1::RegionIdentifier, RegionDelimiter = L1::RegionDelimiter>,
+{
+    type RegionDelimiter = L1::RegionDelimiter;
+    type RegionIdentifier = L1::RegionIdentifier;
+
+    fn assume<T: ToString>(&mut self, assumption: T) {
+        let assumption = assumption.to_string();
+        self.first.assume(&assumption);
+        self.second.assume(assumption);
+    }
+
+    fn warn<T: ToString>(&mut self, warning: T) {
+        let warning = warning.to_string();
+        self.first.warn(&warning);
+        self.second.warn(warning);
+    }
+
+    fn error<T: ToString>(&mut self, error: T) {
+        let error = error.to_string();
+        self.first.error(&error);
+        self.second.error(error);
+    }
+
+    fn record_path_result<C: Composition>(&mut self, path_result: PathResult<C>) {
+        self.first.record_path_result(path_result.clone());
+        self.second.record_path_result(path_result);
+    }
+
+    fn record_final_state<C: Composition>(&mut self, state: GAState<C>) {
+        self.first.record_final_state(state.clone());
+        self.second.record_final_state(state);
+    }
+
+    fn set_path_idx(&mut self, new_path_idx: usize) {
+        self.first.set_path_idx(new_path_idx);
+        self.second.set_path_idx(new_path_idx);
+    }
+
+    fn fork(&self) -> Self {
+        Self {
+            first: self.first.fork(),
+            second: self.second.fork(),
+        }
+    }
+
+    fn add_constraints(&mut self, constraints: Vec<String>) {
+        self.first.add_constraints(constraints.clone());
+        self.second.add_constraints(constraints);
+    }
+
+    fn record_execution_time<T: ToString>(&mut self, time: T) {
+        let time = time.to_string();
+        self.first.record_execution_time(&time);
+        self.second.record_execution_time(time);
+    }
+
+    fn current_region(&self) -> Option<Self::RegionIdentifier> {
+        self.first.current_region().or_else(|| self.second.current_region())
+    }
+
+    fn register_region(&mut self, region: Self::RegionIdentifier) {
+        self.first.register_region(region.clone());
+        self.second.register_region(region);
+    }
+
+    fn update_delimiter<T: Into<Self::RegionDelimiter>, C: Composition>(&mut self, region: T, state: &mut GAState<C>) {
+        let region = region.into();
+        self.first.update_delimiter(region.clone(), state);
+        self.second.update_delimiter(region, state);
+    }
+
+    fn new<C: Composition>(state: &SymexArbiter<C>) -> Self {
+        Self {
+            first: L1::new(state),
+            second: L2::new(state),
+        }
+    }
+
+    fn record_backtrace(&mut self, bt: Option<CallStack>) {
+        self.first.record_backtrace(bt);
+    }
+
+    fn record_call_stack<C: Composition>(&mut self, state: &GAState<C>) {
+        self.first.record_call_stack(state);
+        self.second.record_call_stack(state);
+    }
+}
+
 impl From<RegionMetaData> for NoLogger {
     fn from(_value: RegionMetaData) -> Self {
         Self
@@ -157,3 +279,34 @@ impl Region for RegionMetaData {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Logger, TeeLogger};
+    use crate::{
+        defaults::logger::{SimpleLogger, SimplePathLogger},
+        project::dwarf_helper::SubProgramMap,
+    };
+
+    #[test]
+    fn test_tee_logger() {
+        let map = SubProgramMap::empty();
+        let mut logger = TeeLogger::new(SimplePathLogger::from_sub_programs(&map), SimpleLogger::from_sub_programs(&map));
+        logger.set_path_idx(0);
+        logger.warn("first warning");
+
+        // Forks log independently of the logger they were forked from.
+        let mut fork = logger.fork();
+        fork.warn("forked warning");
+
+        let (first, second) = logger.into_inner();
+        assert!(first.to_string().contains("first warning"));
+        assert!(!first.to_string().contains("forked warning"));
+        let path = second.get_latest_path().unwrap().to_string();
+        assert!(path.contains("first warning"));
+        assert!(!path.contains("forked warning"));
+
+        assert!(fork.first.to_string().contains("forked warning"));
+        assert!(fork.second.get_latest_path().unwrap().to_string().contains("forked warning"));
+    }
+}
@@ -1,21 +1,32 @@
+use std::{collections::BTreeSet, ops::ControlFlow};
+
 use anyhow::Context;
+use general_assembly::extension::ieee754::RoundingMode;
+
+use schedulability::{ResponseTime, Task, TaskSpec};
 
 use crate::{
     arch::SupportedArchitecture,
     executor::{
-        hooks::{HookContainer, LangagueHooks, PrioriHookContainer},
-        state::GAState,
+        coverage::{Coverage, CoverageReport},
+        hooks::{HookContainer, LangagueHooks, PrioriHookContainer, WatchpointCallback, WatchpointPredicate},
+        memory_interface::Permissions,
+        replay::{ConcreteTrace, Model, Replay, ReplayError, TestVector},
+        state::{ArgumentValue, ForkStrategy, GAState, SolutionLimits},
         vm::{SymexStepper, VM},
+        FpState,
         PathResult,
     },
     logging::Logger,
     path_selection::PathSelector,
     project::dwarf_helper::{DebugData, LineMap, SubProgram, SubProgramMap},
-    smt::{SmtExpr, SmtMap},
+    smt::{ProgramMemory, SmtExpr, SmtMap, SmtSolver, Solutions, Statistics},
     Composition,
     GAError,
 };
 
+pub mod schedulability;
+
 pub struct SymexArbiter<C: Composition> {
     logger: C::Logger,
     project: <C::Memory as SmtMap>::ProgramMemory,
@@ -26,6 +37,14 @@ pub struct SymexArbiter<C: Composition> {
     architecture: SupportedArchitecture<C::ArchitectureOverride>,
     line_map: LineMap,
     debug_data: DebugData,
+    solution_limits: SolutionLimits,
+    fork_strategy: ForkStrategy,
+    max_instructions_per_path: Option<usize>,
+    coverage: Coverage,
+    rounding_mode: RoundingMode,
+    arguments: Vec<(String, ArgumentValue)>,
+    seed: Option<u64>,
+    max_paths: Option<usize>,
 }
 
 impl<C: Composition> SymexArbiter<C> {
@@ -53,6 +72,14 @@ impl<C: Composition> SymexArbiter<C> {
             architecture,
             line_map,
             debug_data,
+            solution_limits: SolutionLimits::default(),
+            fork_strategy: ForkStrategy::default(),
+            max_instructions_per_path: None,
+            coverage: Coverage::new(),
+            rounding_mode: C::ROUNDING_MODE,
+            arguments: Vec::new(),
+            seed: None,
+            max_paths: None,
         }
     }
 }
@@ -63,13 +90,122 @@ impl<C: Composition> SymexArbiter<C> {
         self
     }
 
+    /// Registers the region `[low, high)` that the stack is allowed to occupy.
+    ///
+    /// If the stack pointer may be set outside of `[low, high]`, or a write
+    /// relative to the stack pointer may land outside of the region, the path
+    /// ends with a stack overflow failure. The stack pointer equals `high`
+    /// when the stack is empty.
+    pub fn add_stack_region(&mut self, low: u64, high: u64) -> &mut Self {
+        self.hooks.set_stack_region(low, high);
+        self
+    }
+
+    /// Restricts the accesses permitted to the region `[low, high)`, see
+    /// [`HookContainer::set_memory_permissions`].
+    pub fn add_memory_permissions(&mut self, low: u64, high: u64, permissions: Permissions) -> &mut Self {
+        self.hooks.set_memory_permissions(low, high, permissions);
+        self
+    }
+
+    /// Marks the region `[low, high)` as memory mapped peripherals, see
+    /// [`HookContainer::add_mmio_region`].
+    pub fn add_mmio_region(&mut self, low: u64, high: u64) -> &mut Self {
+        self.hooks.add_mmio_region(low, high);
+        self
+    }
+
+    /// Adds a watchpoint on `register`, see
+    /// [`HookContainer::add_register_watchpoint`].
+    pub fn add_register_watchpoint(&mut self, register: &(impl ToString + ?Sized), predicate: WatchpointPredicate<C>, callback: WatchpointCallback<C>) -> &mut Self {
+        self.hooks.add_register_watchpoint(register, predicate, callback);
+        self
+    }
+
+    /// Sets the bounds on how many paths are created when a symbolic value has
+    /// to be made concrete.
+    pub const fn set_solution_limits(&mut self, limits: SolutionLimits) -> &mut Self {
+        self.solution_limits = limits;
+        self
+    }
+
+    /// Sets how new paths are created when execution can continue in more
+    /// than one way, see [`ForkStrategy`].
+    pub const fn set_fork_strategy(&mut self, strategy: ForkStrategy) -> &mut Self {
+        self.fork_strategy = strategy;
+        self
+    }
+
+    /// Suppresses paths that execute more than `max_instructions`
+    /// instructions.
+    ///
+    /// The budget applies to each path separately, a forked path inherits the
+    /// instructions executed before the fork.
+    pub const fn set_max_instructions_per_path(&mut self, max_instructions: usize) -> &mut Self {
+        self.max_instructions_per_path = Some(max_instructions);
+        self
+    }
+
+    /// Sets the rounding mode used by floating point operations that do not
+    /// request a specific one, overriding [`Composition::ROUNDING_MODE`].
+    pub fn set_rounding_mode(&mut self, rounding_mode: RoundingMode) -> crate::Result<&mut Self> {
+        FpState::validate(&rounding_mode)?;
+        self.rounding_mode = rounding_mode;
+        Ok(self)
+    }
+
+    /// Sets the value `register` holds when an entry function is run with
+    /// [`Self::run`] or [`Self::run_with_hooks`], replacing any previous value
+    /// set for it.
+    ///
+    /// This is intended for the argument registers of the calling convention,
+    /// such as `R0` to `R3` on ARM and `A0` to `A7` on RISC-V. Registers
+    /// without a value are unconstrained and named after the register.
+    pub fn set_argument(&mut self, register: &(impl ToString + ?Sized), value: ArgumentValue) -> &mut Self {
+        let register = register.to_string();
+        self.arguments.retain(|(name, _)| *name != register);
+        self.arguments.push((register, value));
+        self
+    }
+
+    /// Seeds the choices the path selector makes between paths that are
+    /// equally good to explore next, see [`PathSelector::set_seed`].
+    ///
+    /// Two runs with the same seed explore the paths in the same order as long
+    /// as the solver is deterministic. Solvers that use several threads, such
+    /// as the default [`Bitwuzla`](crate::smt::bitwuzla::Bitwuzla)
+    /// configuration, may return different solutions between runs, use
+    /// [`SymexConstructor::configure_smt_single_threaded`](crate::initiation::SymexConstructor::configure_smt_single_threaded)
+    /// to avoid this.
+    pub const fn set_seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Stops exploring once `max_paths` paths have completed, the paths that
+    /// are still waiting to be explored are discarded.
+    ///
+    /// Use [`Runner::truncated`] to check if any paths were discarded.
+    pub const fn set_max_paths(&mut self, max_paths: usize) -> &mut Self {
+        self.max_paths = Some(max_paths);
+        self
+    }
+
     pub const fn get_symbol_map(&self) -> &SubProgramMap {
         &self.symbol_lookup
     }
 
+    /// Returns the hooks to run with, including the hooks for `language` and
+    /// all hooks added by name.
+    fn hooks_for_run(&self, language: &LangagueHooks) -> crate::Result<HookContainer<C>> {
+        let mut hooks = self.hooks.clone();
+        hooks.add_language_hooks(&self.symbol_lookup, language);
+        hooks.resolve_named(&self.symbol_lookup)?;
+        Ok(hooks)
+    }
+
     pub fn run_with_hooks(&mut self, function: &SubProgram, hooks: Option<PrioriHookContainer<C>>, language: &LangagueHooks) -> crate::Result<Runner<C>> {
-        let mut intermediate_hooks = self.hooks.clone();
-        intermediate_hooks.add_language_hooks(&self.symbol_lookup, language);
+        let mut intermediate_hooks = self.hooks_for_run(language)?;
         if let Some(hooks) = hooks {
             intermediate_hooks.add_all(hooks);
         }
@@ -85,16 +221,21 @@ impl<C: Composition> SymexArbiter<C> {
             self.logger.clone(),
             self.line_map.clone(),
             self.debug_data.clone(),
+            self.solution_limits,
+            self.fork_strategy,
+            self.max_instructions_per_path,
+            self.coverage.clone(),
+            self.rounding_mode.clone(),
+            &self.arguments,
         )?;
-        Ok(Runner { vm, path_idx: 0 })
+        Ok(self.runner(vm))
     }
 
     pub fn run(&mut self, function: &str, language: &LangagueHooks) -> crate::Result<Runner<C>> {
         let Some(function) = self.symbol_lookup.get_by_name(function) else {
             return Err(GAError::EntryFunctionNotFound(function.to_string()).into());
         };
-        let mut intermediate_hooks = self.hooks.clone();
-        intermediate_hooks.add_language_hooks(&self.symbol_lookup, language);
+        let intermediate_hooks = self.hooks_for_run(language)?;
         let vm = VM::new(
             self.project.clone(),
             &mut self.ctx,
@@ -106,15 +247,20 @@ impl<C: Composition> SymexArbiter<C> {
             self.logger.clone(),
             self.line_map.clone(),
             self.debug_data.clone(),
+            self.solution_limits,
+            self.fork_strategy,
+            self.max_instructions_per_path,
+            self.coverage.clone(),
+            self.rounding_mode.clone(),
+            &self.arguments,
         )?;
-        Ok(Runner { vm, path_idx: 0 })
+        Ok(self.runner(vm))
     }
 
     pub fn run_from_pc(&mut self, pc: u64, language: &LangagueHooks) -> crate::Result<Runner<C>> {
-        let mut hooks = self.hooks.clone();
-        hooks.add_language_hooks(&self.symbol_lookup, language);
+        let hooks = self.hooks_for_run(language)?;
         let ctx_clone = self.ctx.clone();
-        let state = GAState::new(
+        let mut state = GAState::new(
             &mut self.ctx,
             ctx_clone,
             self.project.clone(),
@@ -127,19 +273,23 @@ impl<C: Composition> SymexArbiter<C> {
             self.debug_data.clone(),
             None,
         )?;
+        state.solution_limits = self.solution_limits;
+        state.fork_strategy = self.fork_strategy;
+        state.max_instructions = self.max_instructions_per_path;
+        state.coverage = self.coverage.clone();
+        state.fp_state.rounding_mode = self.rounding_mode.clone();
 
         let vm = VM::new_from_state(self.project.clone(), state, self.logger.clone())?;
-        Ok(Runner { vm, path_idx: 0 })
+        Ok(self.runner(vm))
     }
 
     pub fn run_from_pc_with_hooks(&mut self, pc: u64, language: &LangagueHooks, add_hooks: Option<PrioriHookContainer<C>>) -> crate::Result<Runner<C>> {
-        let mut hooks = self.hooks.clone();
-        hooks.add_language_hooks(&self.symbol_lookup, language);
+        let mut hooks = self.hooks_for_run(language)?;
         if let Some(new_hooks) = add_hooks {
             hooks.add_all(new_hooks);
         }
         let ctx_clone = self.ctx.clone();
-        let state = GAState::new(
+        let mut state = GAState::new(
             &mut self.ctx,
             ctx_clone,
             self.project.clone(),
@@ -152,19 +302,209 @@ impl<C: Composition> SymexArbiter<C> {
             self.debug_data.clone(),
             None,
         )?;
+        state.solution_limits = self.solution_limits;
+        state.fork_strategy = self.fork_strategy;
+        state.max_instructions = self.max_instructions_per_path;
+        state.coverage = self.coverage.clone();
+        state.fp_state.rounding_mode = self.rounding_mode.clone();
 
         let vm = VM::new_from_state(self.project.clone(), state, self.logger.clone())?;
-        Ok(Runner { vm, path_idx: 0 })
+        Ok(self.runner(vm))
+    }
+
+    /// Concretely re-executes the path described by `model`.
+    ///
+    /// The entry function of the model is executed with all named inputs fixed
+    /// to the values in the model and with forking disabled. An error is
+    /// returned if the model is not feasible.
+    pub fn replay(&mut self, model: &Model, language: &LangagueHooks) -> crate::Result<ConcreteTrace<C>> {
+        let Some(function) = self.symbol_lookup.get_by_name(model.entry()).cloned() else {
+            return Err(GAError::EntryFunctionNotFound(model.entry().to_string()).into());
+        };
+        let hooks = self.hooks_for_run(language)?;
+        let ctx_clone = self.ctx.clone();
+        let mut state = GAState::new(
+            &mut self.ctx,
+            ctx_clone,
+            self.project.clone(),
+            hooks,
+            0xffff_fffe,
+            function.bounds.0 & ((u64::MAX >> 1) << 1),
+            self.state_container.clone(),
+            self.architecture.clone(),
+            self.line_map.clone(),
+            self.debug_data.clone(),
+            Some(function.clone()),
+        )?;
+        state.memory.set_pc(function.bounds.0 as u32)?;
+        state.solution_limits = self.solution_limits;
+        state.fp_state.rounding_mode = self.rounding_mode.clone();
+        state.replay = Some(Replay::new(model.clone()));
+
+        // Keep the replay constraints out of any later runs.
+        self.ctx.push();
+        let result = VM::new_from_state(self.project.clone(), state, self.logger.clone()).and_then(|mut vm| vm.run());
+        self.ctx.pop();
+
+        let Some((result, state, ..)) = result? else {
+            return Err(ReplayError::NoResult.into());
+        };
+        Ok(ConcreteTrace {
+            steps: state.replay.map(|replay| replay.steps).unwrap_or_default(),
+            result,
+        })
+    }
+
+    /// Enumerates the distinct values `register` can hold when `function`
+    /// returns, see [`Runner::enumerate`].
+    pub fn enumerate(&mut self, function: &str, register: &str, upper_bound: u32, language: &LangagueHooks) -> crate::Result<Solutions<u64>> {
+        self.run(function, language)?.enumerate(register, upper_bound)
+    }
+
+    /// Explores `function` and returns a [`TestVector`] for every path that
+    /// completes successfully.
+    ///
+    /// Each vector holds concrete values for the inputs of the path and the
+    /// values the registers in `outputs` hold once the function returns.
+    pub fn generate_test_vectors(&mut self, function: &str, outputs: &[&str], language: &LangagueHooks) -> crate::Result<Vec<TestVector>> {
+        let mut vectors = Vec::new();
+        for result in self.run(function, language)? {
+            let (mut state, _, result) = result?;
+            if !matches!(result, PathResult::Success(..)) {
+                continue;
+            }
+            // The solver only holds the constraints of the path that just completed.
+            vectors.push(TestVector::from_state(&mut state, outputs)?);
+        }
+        Ok(vectors)
+    }
+
+    /// Runs `function` and calls `on_path` for every path as it completes.
+    ///
+    /// Exploration stops as soon as `on_path` returns [`ControlFlow::Break`],
+    /// the remaining paths are never explored. Returns [`ControlFlow::Break`]
+    /// if exploration was stopped early.
+    pub fn run_with<F>(&mut self, function: &str, language: &LangagueHooks, mut on_path: F) -> crate::Result<ControlFlow<()>>
+    where
+        F: FnMut(GAState<C>, C::Logger, PathResult<C>) -> ControlFlow<()>,
+    {
+        for result in self.run(function, language)? {
+            let (state, logger, result) = result?;
+            if on_path(state, logger, result).is_break() {
+                return Ok(ControlFlow::Break(()));
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Returns the largest number of cycles taken by any path through
+    /// `function`.
+    pub fn wcet(&mut self, function: &str, language: &LangagueHooks) -> crate::Result<u64> {
+        let mut worst_case = 0;
+        for result in self.run(function, language)? {
+            let (mut state, _, _) = result?;
+            worst_case = worst_case.max(state.get_cycle_count());
+        }
+        Ok(worst_case)
+    }
+
+    /// Measures the worst case execution time of every task in `tasks` and
+    /// computes their worst case response times, see
+    /// [`schedulability::response_times`].
+    ///
+    /// Any task where [`ResponseTime::meets_deadline`] is false can miss its
+    /// deadline.
+    pub fn check_schedulability(&mut self, tasks: &[TaskSpec], language: &LangagueHooks) -> crate::Result<Vec<ResponseTime>> {
+        let tasks = tasks
+            .iter()
+            .map(|task| {
+                Ok(Task {
+                    name: task.function.clone(),
+                    priority: task.priority,
+                    wcet: self.wcet(&task.function, language)?,
+                    period: task.period,
+                    deadline: task.deadline,
+                })
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(schedulability::response_times(&tasks))
+    }
+
+    /// Returns the instruction coverage accumulated over all runs so far.
+    ///
+    /// The instructions in the program are found by linearly decoding the
+    /// executable regions of the program, as such any data embedded in those
+    /// regions, such as literal pools, is counted as instructions.
+    pub fn coverage(&mut self) -> crate::Result<CoverageReport> {
+        let regions = self.project.executable_regions().collect::<Vec<_>>();
+        let Some(&(entry, _)) = regions.first() else {
+            return Ok(self.coverage.report([]));
+        };
+        let ctx_clone = self.ctx.clone();
+        let mut state = GAState::<C>::new(
+            &mut self.ctx,
+            ctx_clone,
+            self.project.clone(),
+            self.hooks.clone(),
+            0xffff_fffe,
+            entry,
+            self.state_container.clone(),
+            self.architecture.clone(),
+            self.line_map.clone(),
+            self.debug_data.clone(),
+            None,
+        )?;
+
+        let mut instructions = Vec::new();
+        for (start, end) in regions {
+            let mut address = start;
+            while address < end {
+                let Ok(data) = state.memory.get_from_instruction_memory(address) else {
+                    break;
+                };
+                match state.instruction_from_array_ptr(&data) {
+                    Ok(instruction) => {
+                        instructions.push(address);
+                        address += u64::from(instruction.instruction_size / 8).max(2);
+                    }
+                    // Skip ahead by the smallest instruction size if the data does not decode.
+                    Err(_) => address += 2,
+                }
+            }
+        }
+        Ok(self.coverage.report(instructions))
+    }
+
+    /// Returns the solver [`Statistics`] accumulated over all runs so far.
+    pub fn statistics(&self) -> Statistics {
+        self.ctx.statistics()
     }
 
     pub fn consume(self) -> C::Logger {
         self.logger
     }
+
+    /// Creates a runner that explores the paths of `vm`.
+    fn runner(&self, mut vm: VM<C>) -> Runner<C> {
+        if let Some(seed) = self.seed {
+            vm.paths.set_seed(seed);
+        }
+        Runner {
+            vm,
+            path_idx: 0,
+            ctx: self.ctx.clone(),
+            max_paths: self.max_paths,
+            truncated: false,
+        }
+    }
 }
 
 pub struct Runner<C: Composition> {
     vm: VM<C>,
     path_idx: usize,
+    ctx: C::SMT,
+    max_paths: Option<usize>,
+    truncated: bool,
 }
 
 impl<C: Composition> Runner<C> {
@@ -172,12 +512,74 @@ impl<C: Composition> Runner<C> {
     pub fn number_of_queued_paths(&self) -> usize {
         self.vm.paths.waiting_paths()
     }
+
+    /// Returns the number of complete states held by the enqueued paths, see
+    /// [`ForkStrategy`].
+    pub fn number_of_queued_states(&self) -> usize {
+        self.vm.paths.waiting_states()
+    }
+
+    /// Returns the solver [`Statistics`] accumulated so far.
+    ///
+    /// The solver is shared with the [`SymexArbiter`] that created the runner,
+    /// so this includes any previous runs.
+    pub fn statistics(&self) -> Statistics {
+        self.ctx.statistics()
+    }
+
+    /// Returns true if paths were discarded because the limit set with
+    /// [`SymexArbiter::set_max_paths`] was reached.
+    #[must_use]
+    pub const fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Discards all paths waiting to be explored.
+    fn discard_queued_paths(&mut self) {
+        // Retrieving a path releases the solver scope it was saved in.
+        while self.vm.paths.get_path().is_some() {
+            self.truncated = true;
+        }
+    }
+}
+
+impl<C: Composition> Runner<C> {
+    /// Runs all remaining paths and collects the distinct values `register`
+    /// can hold at the end of every successful path.
+    ///
+    /// If more than `upper_bound` values exist, exploration stops and
+    /// [`Solutions::AtLeast`] is returned with `upper_bound` of them. Values
+    /// wider than 64 bits are skipped.
+    pub fn enumerate(self, register: &str, upper_bound: u32) -> crate::Result<Solutions<u64>> {
+        let mut values = BTreeSet::new();
+        for result in self {
+            let (mut state, _, result) = result?;
+            if !matches!(result, PathResult::Success(..)) {
+                continue;
+            }
+            // The solver only holds the constraints of the path that just completed.
+            let value = state.get_register(register)?;
+            let (solutions, truncated) = match state.constraints.get_values(&value, upper_bound)? {
+                Solutions::Exactly(solutions) => (solutions, false),
+                Solutions::AtLeast(solutions) => (solutions, true),
+            };
+            values.extend(solutions.iter().filter_map(SmtExpr::get_constant));
+            if truncated || values.len() > upper_bound as usize {
+                return Ok(Solutions::AtLeast(values.into_iter().take(upper_bound as usize).collect()));
+            }
+        }
+        Ok(Solutions::Exactly(values.into_iter().collect()))
+    }
 }
 
 impl<C: Composition> Iterator for Runner<C> {
     type Item = crate::Result<(GAState<C>, C::Logger, PathResult<C>)>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.max_paths.is_some_and(|max_paths| self.path_idx >= max_paths) {
+            self.discard_queued_paths();
+            return None;
+        }
         if let Some((result, mut state, conditions, pc, mut logger)) = match self.vm.run() {
             Ok(res) => res,
             Err(e) => {
@@ -206,8 +608,12 @@ impl<C: Composition> Iterator for Runner<C> {
                 return self.next();
             }
 
+            state.path_payload = C::path_payload(&mut state, &result);
             logger.record_path_result(result.clone());
-            logger.record_execution_time(cycles);
+            if matches!(result, PathResult::Failure(_)) {
+                logger.record_call_stack(&state);
+            }
+            logger.record_execution_time(format!("{cycles} cycles over {} instructions", state.instruction_count()));
             logger.record_final_state(state.clone());
             self.path_idx += 1;
             return Some(Ok((state, logger.clone(), result)));
@@ -0,0 +1,134 @@
+//! Response time analysis for fixed priority preemptive task sets.
+//!
+//! All times are measured in cycles. Blocking due to shared resources is not
+//! accounted for.
+
+use std::fmt::Display;
+
+/// A periodic task whose worst case execution time has not been measured yet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskSpec {
+    /// The function that implements the task.
+    pub function: String,
+    /// Tasks with a higher priority preempt tasks with a lower priority.
+    pub priority: u32,
+    /// Minimum time between two releases of the task.
+    pub period: u64,
+    /// Time after a release within which the task must complete.
+    pub deadline: u64,
+}
+
+/// A periodic task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Task {
+    pub name: String,
+    /// Tasks with a higher priority preempt tasks with a lower priority.
+    pub priority: u32,
+    /// Worst case execution time.
+    pub wcet: u64,
+    /// Minimum time between two releases of the task.
+    pub period: u64,
+    /// Time after a release within which the task must complete.
+    pub deadline: u64,
+}
+
+/// The worst case response time of a task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResponseTime {
+    pub task: String,
+    /// The worst case response time, or the first value found to exceed the
+    /// deadline if the task can miss it.
+    pub response_time: u64,
+    pub deadline: u64,
+}
+
+impl ResponseTime {
+    #[must_use]
+    pub const fn meets_deadline(&self) -> bool {
+        self.response_time <= self.deadline
+    }
+}
+
+impl Display for ResponseTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.meets_deadline() {
+            true => write!(f, "{}: response time {} cycles, deadline {} cycles", self.task, self.response_time, self.deadline),
+            false => write!(
+                f,
+                "{}: DEADLINE MISS, response time exceeds {} cycles, deadline {} cycles",
+                self.task, self.response_time, self.deadline
+            ),
+        }
+    }
+}
+
+/// Computes the worst case response time of every task in `tasks`.
+///
+/// Tasks with equal priority are assumed to be able to preempt each other.
+///
+/// # Panics
+///
+/// If any task has a period of zero.
+#[must_use]
+pub fn response_times(tasks: &[Task]) -> Vec<ResponseTime> {
+    tasks
+        .iter()
+        .enumerate()
+        .map(|(idx, task)| {
+            let interfering = tasks
+                .iter()
+                .enumerate()
+                .filter(|(other, t)| *other != idx && t.priority >= task.priority)
+                .map(|(_, t)| t)
+                .collect::<Vec<_>>();
+            let mut response_time = task.wcet;
+            loop {
+                let next = task.wcet + interfering.iter().map(|t| response_time.div_ceil(t.period) * t.wcet).sum::<u64>();
+                if next == response_time {
+                    break;
+                }
+                response_time = next;
+                if response_time > task.deadline {
+                    break;
+                }
+            }
+            ResponseTime {
+                task: task.name.clone(),
+                response_time,
+                deadline: task.deadline,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{response_times, Task};
+
+    fn task(name: &str, priority: u32, wcet: u64, period: u64) -> Task {
+        Task {
+            name: name.to_owned(),
+            priority,
+            wcet,
+            period,
+            deadline: period,
+        }
+    }
+
+    #[test]
+    fn test_lower_priority_misses_deadline() {
+        let result = response_times(&[task("high", 2, 2, 4), task("low", 1, 3, 6)]);
+        assert_eq!(result[0].response_time, 2);
+        assert!(result[0].meets_deadline());
+        // 3 + 2 = 5, 3 + 2 * 2 = 7 > 6.
+        assert_eq!(result[1].response_time, 7);
+        assert!(!result[1].meets_deadline());
+    }
+
+    #[test]
+    fn test_schedulable_task_set() {
+        let result = response_times(&[task("high", 2, 1, 4), task("low", 1, 2, 6)]);
+        assert_eq!(result[1].response_time, 3);
+        assert!(result.iter().all(|r| r.meets_deadline()));
+    }
+}
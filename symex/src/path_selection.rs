@@ -1,16 +1,91 @@
+use std::rc::Rc;
+
 use crate::{
     executor::state::GAState,
+    logging::Logger,
     smt::{SmtExpr, SmtMap, SmtSolver},
     Composition,
 };
 
+/// The state and logger at the start of a path.
+///
+/// Paths created with
+/// [`ForkStrategy::Incremental`](crate::executor::state::ForkStrategy::Incremental)
+/// are re-derived from this.
+#[derive(Debug)]
+pub struct Origin<C: Composition> {
+    pub(crate) state: GAState<C>,
+    pub(crate) logger: C::Logger,
+}
+
+/// The state a [`Path`] resumes from.
+#[derive(Debug, Clone)]
+pub enum PathState<C: Composition> {
+    /// A complete copy of the state at the point where the path was created.
+    Materialized(GAState<C>),
+    /// The state is re-derived by executing from the origin under the path
+    /// constraints.
+    ///
+    /// This only holds a shared reference to the origin and the named
+    /// symbols, rather than a copy of the memory, hooks and execution context.
+    Deferred {
+        /// The start of the path this path was forked from.
+        origin: Rc<Origin<C>>,
+        /// The named symbols at the point where the path was created, these
+        /// are reused when re-deriving the state.
+        symbols: Vec<(String, C::SmtExpression)>,
+    },
+}
+
+impl<C: Composition> PathState<C> {
+    /// Returns the state to start executing the path from.
+    #[must_use]
+    pub fn materialize(self) -> GAState<C> {
+        match self {
+            Self::Materialized(state) => state,
+            Self::Deferred { origin, symbols } => {
+                let mut state = origin.state.clone();
+                state.memory.seed_symbols(symbols);
+                state.origin = Some(origin);
+                state
+            }
+        }
+    }
+
+    /// Returns the solver shared by all paths.
+    #[must_use]
+    pub fn constraints(&self) -> &C::SMT {
+        match self {
+            Self::Materialized(state) => &state.constraints,
+            Self::Deferred { origin, .. } => &origin.state.constraints,
+        }
+    }
+
+    /// Returns the constraints asserted on the path before it was created.
+    #[must_use]
+    pub fn path_condition(&self) -> &[C::SmtExpression] {
+        match self {
+            Self::Materialized(state) => &state.path_condition,
+            Self::Deferred { origin, .. } => &origin.state.path_condition,
+        }
+    }
+
+    fn get_pc(&self) -> Option<u64> {
+        match self {
+            Self::Materialized(state) => state.memory.get_pc().ok()?.get_constant(),
+            // Execution resumes from the origin.
+            Self::Deferred { origin, .. } => origin.state.memory.get_pc().ok()?.get_constant(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[must_use]
 pub struct Path<C: Composition> {
     /// The state to use when resuming execution.
     ///
     /// The location in the state should be where to resume execution at.
-    pub state: GAState<C>,
+    pub state: PathState<C>,
 
     /// Constraints to add before starting execution on this path.
     pub constraints: Vec<<C::SMT as SmtSolver>::Expression>,
@@ -29,7 +104,55 @@ impl<C: Composition> Path<C> {
             None => vec![],
         };
 
-        Self { state, constraints, pc, logger }
+        Self {
+            state: PathState::Materialized(state),
+            constraints,
+            pc,
+            logger,
+        }
+    }
+
+    /// Creates a new path that is re-derived from `origin` once it is
+    /// explored.
+    pub fn deferred(origin: Rc<Origin<C>>, symbols: Vec<(String, C::SmtExpression)>, constraint: <C::SMT as SmtSolver>::Expression, pc: u64) -> Self {
+        let logger = origin.logger.fork();
+        Self {
+            state: PathState::Deferred { origin, symbols },
+            constraints: vec![constraint],
+            pc,
+            logger,
+        }
+    }
+
+    /// Combines two paths that resume at the same address in to one path,
+    /// see [`GAState::merge`].
+    ///
+    /// Returns `None` if the paths resume at different addresses or can not be
+    /// combined.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        let (PathState::Materialized(state), PathState::Materialized(other_state)) = (&self.state, &other.state) else {
+            return None;
+        };
+        if self.state.get_pc()? != other.state.get_pc()? {
+            return None;
+        }
+        let state = state.merge(&self.condition(state), other_state, &other.condition(other_state))?;
+        Some(Self {
+            state: PathState::Materialized(state),
+            constraints: vec![],
+            pc: self.pc,
+            logger: self.logger.clone(),
+        })
+    }
+
+    /// Returns the conjunction of all constraints the path is explored under.
+    fn condition(&self, state: &GAState<C>) -> C::SmtExpression {
+        state
+            .path_condition
+            .iter()
+            .chain(&self.constraints)
+            .fold(state.memory.from_bool(true), |condition, constraint| condition.and(constraint))
     }
 }
 
@@ -51,7 +174,7 @@ impl<C: Composition> PathSelector<C> for DFSPathSelection<C> {
 
     /// Add a new path to be explored.
     fn save_path(&mut self, path: Path<C>) {
-        path.state.constraints.push();
+        path.state.constraints().push();
         self.paths.push(path);
     }
 
@@ -59,7 +182,7 @@ impl<C: Composition> PathSelector<C> for DFSPathSelection<C> {
     fn get_path(&mut self) -> Option<Path<C>> {
         match self.paths.pop() {
             Some(path) => {
-                path.state.constraints.pop();
+                path.state.constraints().pop();
                 Some(path)
             }
             None => None,
@@ -67,12 +190,151 @@ impl<C: Composition> PathSelector<C> for DFSPathSelection<C> {
     }
 
     fn get_pc(&self) -> Option<u64> {
-        self.paths.last().map(|el| el.state.memory.get_pc().unwrap().get_constant().unwrap())
+        self.paths.last().and_then(|el| el.state.get_pc())
+    }
+
+    fn waiting_paths(&self) -> usize {
+        self.paths.len()
+    }
+
+    fn waiting_states(&self) -> usize {
+        held_states(&self.paths)
+    }
+}
+
+/// Explores the path that is furthest behind first, and merges paths that
+/// reach the same address if [`Composition::MERGE_PATHS`] is set.
+///
+/// A running path is suspended when another path is waiting at or before its
+/// address, this lets the paths of a branch that reconverges meet. As paths are
+/// not explored in the order they are created, the full path condition is
+/// asserted when a path is resumed.
+///
+/// Paths at the same address are explored in the order they were created,
+/// unless a seed is set in which case one of them is picked at random.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct MergingSelector<C: Composition> {
+    paths: Vec<Path<C>>,
+    /// Whether a solver scope has been opened for the running path.
+    scoped: bool,
+    /// The state of the generator used to pick between paths at the same
+    /// address, if seeded.
+    rng: Option<u64>,
+}
+
+impl<C: Composition> MergingSelector<C> {
+    /// Returns the address the path resumes at.
+    ///
+    /// A path that resumes inside of an instruction is considered to be at the
+    /// instruction it was created at.
+    fn position(path: &Path<C>) -> u64 {
+        match &path.state {
+            PathState::Materialized(state) if state.continue_in_instruction.is_some() => path.pc,
+            state => state.get_pc().unwrap_or(path.pc),
+        }
+    }
+}
+
+impl<C: Composition> PathSelector<C> for MergingSelector<C> {
+    const USES_PATH_CONDITION: bool = C::MERGE_PATHS;
+
+    /// Creates new without any stored paths.
+    fn new() -> Self {
+        Self {
+            paths: Vec::new(),
+            scoped: false,
+            rng: None,
+        }
+    }
+
+    /// Add a new path to be explored, merging it with a waiting path if
+    /// possible.
+    fn save_path(&mut self, path: Path<C>) {
+        if C::MERGE_PATHS {
+            for waiting in &mut self.paths {
+                if let Some(merged) = waiting.merge(&path) {
+                    *waiting = merged;
+                    return;
+                }
+            }
+        }
+        self.paths.push(path);
+    }
+
+    /// Retrieve the waiting path with the lowest address.
+    #[allow(clippy::cast_possible_truncation)]
+    fn get_path(&mut self) -> Option<Path<C>> {
+        let position = self.paths.iter().map(Self::position).min()?;
+        let candidates = self
+            .paths
+            .iter()
+            .enumerate()
+            .filter(|(_, path)| Self::position(path) == position)
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        let idx = match &mut self.rng {
+            Some(rng) => candidates[(next_random(rng) % candidates.len() as u64) as usize],
+            None => candidates[0],
+        };
+        let path = self.paths.remove(idx);
+
+        let solver = path.state.constraints();
+        if self.scoped {
+            solver.pop();
+        }
+        solver.push();
+        for constraint in path.state.path_condition() {
+            solver.assert(constraint);
+        }
+        self.scoped = true;
+        Some(path)
+    }
+
+    fn get_pc(&self) -> Option<u64> {
+        self.paths.iter().map(Self::position).min()
     }
 
     fn waiting_paths(&self) -> usize {
         self.paths.len()
     }
+
+    fn waiting_states(&self) -> usize {
+        held_states(&self.paths)
+    }
+
+    fn should_suspend(&self, pc: u64) -> bool {
+        C::MERGE_PATHS && self.paths.iter().any(|path| Self::position(path) <= pc)
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = Some(seed);
+    }
+}
+
+/// Returns the number of complete states held by `paths`, an origin shared by
+/// several deferred paths is only counted once.
+fn held_states<C: Composition>(paths: &[Path<C>]) -> usize {
+    let mut origins = paths
+        .iter()
+        .filter_map(|path| match &path.state {
+            PathState::Deferred { origin, .. } => Some(Rc::as_ptr(origin)),
+            PathState::Materialized(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let materialized = paths.len() - origins.len();
+    origins.sort_unstable();
+    origins.dedup();
+    materialized + origins.len()
+}
+
+/// Advances the SplitMix64 generator `state` and returns the next value.
+const fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut value = *state;
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
 }
 
 pub trait PathSelector<C: Composition> {
@@ -87,4 +349,132 @@ pub trait PathSelector<C: Composition> {
     fn get_pc(&self) -> Option<u64>;
 
     fn waiting_paths(&self) -> usize;
+
+    /// Whether the selector asserts the path condition of a path when it is
+    /// retrieved, see [`GAState::path_condition`]. The path condition is only
+    /// recorded if it is used.
+    const USES_PATH_CONDITION: bool = false;
+
+    /// Returns the number of complete states held by the waiting paths, see
+    /// [`ForkStrategy`](crate::executor::state::ForkStrategy).
+    fn waiting_states(&self) -> usize {
+        self.waiting_paths()
+    }
+
+    /// Returns true if the running path should be set aside before executing
+    /// the instruction at `pc`, to be resumed once it is retrieved again.
+    fn should_suspend(&self, _pc: u64) -> bool {
+        false
+    }
+
+    /// Seeds any choice between paths that are equally good to explore next,
+    /// two runs with the same seed explore the paths in the same order.
+    fn set_seed(&mut self, _seed: u64) {}
+}
+
+#[cfg(test)]
+#[allow(clippy::arc_with_non_send_sync)]
+mod test {
+    use std::sync::Arc;
+
+    use hashbrown::HashMap;
+
+    use super::{MergingSelector, Path, PathSelector};
+    use crate::{
+        arch::{arm::v6::ArmV6M, Architecture, NoArchitectureOverride, SupportedArchitecture},
+        executor::{hooks::HookContainer, memory_interface::MemoryBucketingFilter, state::GAState, vm::VM},
+        logging::NoLogger,
+        project::Project,
+        smt::{
+            bitwuzla::{expr::BitwuzlaExpr, fpexpr::FpExpr, memory::BitwuzlaMemory, Bitwuzla},
+            SmtMap,
+            SmtSolver,
+        },
+        Composition,
+        Endianness,
+        WordSize,
+    };
+
+    #[derive(Clone, Debug)]
+    struct MergingComposition {}
+
+    impl Composition for MergingComposition {
+        type ArchitectureOverride = NoArchitectureOverride;
+        type Logger = NoLogger;
+        type Memory = BitwuzlaMemory<()>;
+        type MemoryFilter = MemoryBucketingFilter<Self>;
+        type PathPayload = ();
+        type PathSelector = MergingSelector<Self>;
+        type ProgramMemory = Arc<Project<Self::SMT>>;
+        type SMT = Bitwuzla;
+        type SmtExpression = BitwuzlaExpr;
+        type SmtFPExpression = FpExpr;
+        type StateContainer = ();
+
+        const MERGE_PATHS: bool = true;
+    }
+
+    fn setup_vm() -> VM<MergingComposition> {
+        let ctx = Bitwuzla::new();
+        let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
+        let state = GAState::<MergingComposition>::create_test_state(
+            project.clone(),
+            ctx.clone(),
+            ctx,
+            0,
+            0,
+            HookContainer::new(),
+            (),
+            SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
+        );
+        VM::new_test_vm(project, state, NoLogger)
+    }
+
+    #[test]
+    fn test_different_registers_do_not_merge() {
+        let mut vm = setup_vm();
+        let state = vm.paths.get_path().unwrap().state.materialize();
+
+        let one = state.memory.from_u64(1, 32);
+        let two = state.memory.from_u64(2, 32);
+        let mut then_state = state.clone();
+        then_state.set_register("R0", one).unwrap();
+        then_state.memory.set_pc(8).unwrap();
+        let mut else_state = state;
+        else_state.set_register("R1", two).unwrap();
+        else_state.memory.set_pc(8).unwrap();
+
+        vm.paths.save_path(Path::new(then_state, None, 4, NoLogger));
+        vm.paths.save_path(Path::new(else_state, None, 6, NoLogger));
+        assert_eq!(vm.paths.waiting_paths(), 2);
+    }
+
+    #[test]
+    fn test_seed_decides_order_of_paths_at_same_address() {
+        // Returns the pc each path was created from, in the order they are explored.
+        fn explored(seed: Option<u64>) -> Vec<u64> {
+            let mut vm = setup_vm();
+            if let Some(seed) = seed {
+                vm.paths.set_seed(seed);
+            }
+            let state = vm.paths.get_path().unwrap().state.materialize();
+
+            let one = state.memory.from_u64(1, 32);
+            let two = state.memory.from_u64(2, 32);
+            let mut then_state = state.clone();
+            then_state.set_register("R0", one).unwrap();
+            then_state.memory.set_pc(8).unwrap();
+            let mut else_state = state;
+            else_state.set_register("R1", two).unwrap();
+            else_state.memory.set_pc(8).unwrap();
+
+            vm.paths.save_path(Path::new(then_state, None, 4, NoLogger));
+            vm.paths.save_path(Path::new(else_state, None, 6, NoLogger));
+            std::iter::from_fn(|| vm.paths.get_path()).map(|path| path.pc).collect()
+        }
+
+        assert_eq!(explored(None), vec![4, 6]);
+        assert_eq!(explored(Some(0)), explored(Some(0)));
+        assert_ne!(explored(Some(0)), explored(Some(1)));
+    }
 }
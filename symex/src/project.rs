@@ -38,14 +38,27 @@ pub enum ProjectError {
 
     #[error("Unable to find entry point: {0}")]
     InvalidSymbol(&'static str),
+
+    #[error("Unable to find symbol: {0}")]
+    UnresolvedSymbol(String),
+
+    #[error("Unsupported binary format: {0:?}")]
+    UnsupportedFileFormat(object::BinaryFormat),
+
+    #[error("Unsupported memory layout: {0:?} words in {1:?} endian byte order")]
+    UnsupportedLayout(WordSize, Endianness),
+
+    #[error("Invalid word size: {0} bits, expected one of 8, 16, 32 or 64")]
+    InvalidWordSize(u32),
 }
 
 #[must_use]
-/// Holds all data read from the ELF file.
+/// Holds all data read from the ELF, Mach-O or PE file.
 pub struct Project<S: SmtSolver> {
     segments: Segments<S>,
     word_size: WordSize,
     endianness: Endianness,
+    entry_point: u64,
     symtab: SubProgramMap,
 }
 
@@ -56,12 +69,25 @@ impl<S: SmtSolver> Project<S> {
             segments: Segments::from_single_segment(program_memory, start_addr, end_addr, false),
             word_size,
             endianness,
+            entry_point: start_addr,
+            symtab: SubProgramMap::default(),
+        }
+    }
+
+    /// Creates a project like [`Project::manual_project`] where the program
+    /// memory is read only.
+    pub fn manual_read_only_project(program_memory: Vec<u8>, start_addr: u64, end_addr: u64, word_size: WordSize, endianness: Endianness) -> Self {
+        Self {
+            segments: Segments::from_single_segment(program_memory, start_addr, end_addr, true),
+            word_size,
+            endianness,
+            entry_point: start_addr,
             symtab: SubProgramMap::default(),
         }
     }
 
     pub fn from_binary(ctx: &mut S, obj_file: &object::File<'_>, symtab: SubProgramMap) -> Result<Self> {
-        let segments = Segments::from_file(ctx, obj_file);
+        let segments = Segments::from_file(ctx, obj_file)?;
         let endianness = if obj_file.is_little_endian() { Endianness::Little } else { Endianness::Big };
 
         // Do not catch 16 or 8 bit architectures but will do for now.
@@ -71,10 +97,42 @@ impl<S: SmtSolver> Project<S> {
             segments,
             word_size,
             endianness,
+            entry_point: obj_file.entry(),
             symtab,
         })
     }
 
+    /// Creates a project from a headerless binary mapped at `base_addr`.
+    ///
+    /// As there is no header the layout defaults to 32 bit little endian, see
+    /// [`Project::override_layout`].
+    pub fn from_raw(ctx: &mut S, data: Vec<u8>, base_addr: u64, entry: u64, symtab: SubProgramMap) -> Self {
+        Self {
+            segments: Segments::from_raw(ctx, data, base_addr),
+            word_size: WordSize::Bit32,
+            endianness: Endianness::Little,
+            entry_point: entry,
+            symtab,
+        }
+    }
+
+    /// Overrides the word size and endianness inferred from the file header.
+    ///
+    /// This is needed for headerless dumps where the layout can not be
+    /// inferred.
+    pub fn override_layout(&mut self, word_size: WordSize, endianness: Endianness) -> Result<()> {
+        validate_layout(word_size, endianness)?;
+        self.word_size = word_size;
+        self.endianness = endianness;
+        Ok(())
+    }
+
+    /// Returns the entry point address declared in the file header.
+    #[must_use]
+    pub const fn entry_point(&self) -> u64 {
+        self.entry_point
+    }
+
     fn get_word_internal(&self, address: u64, width: WordSize) -> Result<DataWord> {
         Ok(match width {
             WordSize::Bit64 => match self.segments.read_raw_bytes(address, 8) {
@@ -152,6 +210,35 @@ impl<S: SmtSolver> Project<S> {
     }
 }
 
+/// Checks that the program memory can be read with the given layout.
+///
+/// Half word reads are not possible with 8 bit words, so at least 16 bit words
+/// are required.
+pub const fn validate_layout(word_size: WordSize, endianness: Endianness) -> Result<()> {
+    match word_size {
+        WordSize::Bit8 => Err(ProjectError::UnsupportedLayout(word_size, endianness)),
+        WordSize::Bit16 | WordSize::Bit32 | WordSize::Bit64 => Ok(()),
+    }
+}
+
+impl TryFrom<u32> for WordSize {
+    type Error = ProjectError;
+
+    /// Converts a width in bits to a [`WordSize`].
+    ///
+    /// Pointers are always word sized, so this is the only width a project
+    /// needs to validate.
+    fn try_from(bits: u32) -> Result<Self> {
+        match bits {
+            64 => Ok(Self::Bit64),
+            32 => Ok(Self::Bit32),
+            16 => Ok(Self::Bit16),
+            8 => Ok(Self::Bit8),
+            bits => Err(ProjectError::InvalidWordSize(bits)),
+        }
+    }
+}
+
 impl<S: SmtSolver> ProgramMemory<S::Expression> for std::sync::Arc<Project<S>> {
     fn regions(&self) -> impl Iterator<Item = (u64, u64)> {
         self.segments.sections()
@@ -209,7 +296,12 @@ impl<S: SmtSolver> ProgramMemory<S::Expression> for std::sync::Arc<Project<S>> {
         if written {
             let mut ret = ctx.new_from_u64(0, bits);
             let shift = ctx.new_from_u64(8, bits);
-            for idx in address..(address + bytes) {
+            // Assemble the value starting from the most significant byte.
+            let addresses: Box<dyn Iterator<Item = u64>> = match self.endianness {
+                Endianness::Little => Box::new((address..(address + bytes)).rev()),
+                Endianness::Big => Box::new(address..(address + bytes)),
+            };
+            for idx in addresses {
                 let inner = ctx.new_from_u64(self.segments.read_raw_bytes(idx, 1).expect("Regions to contain the data")[0] as u64, 8);
                 let read = writes.get(&idx).unwrap_or(&inner);
                 ret = ret.shift(&shift, general_assembly::shift::Shift::Lsl);
@@ -223,9 +315,18 @@ impl<S: SmtSolver> ProgramMemory<S::Expression> for std::sync::Arc<Project<S>> {
             // full word
             Ok(ctx.new_from_u64(self.get_word(address).unwrap().into(), bits))
         } else if bits % word_size == 0 {
+            let words = u64::from(bits / word_size);
+            let word_bytes = u64::from(word_size / 8);
             let mut ret = ctx.new_from_u64(0, bits);
-            for _word in 0..(bits / word_size) {
-                let new_ret = ctx.new_from_u64(self.get_word(address).unwrap().into(), word_size).resize_unsigned(bits);
+            for word in 0..words {
+                // Assemble the value starting from the most significant word.
+                let word = match self.endianness {
+                    Endianness::Little => words - 1 - word,
+                    Endianness::Big => word,
+                };
+                let new_ret = ctx
+                    .new_from_u64(self.get_word(address + word * word_bytes).unwrap().into(), word_size)
+                    .resize_unsigned(bits);
                 ret = ret.shift(&ctx.new_from_u64(word_size as u64, bits), general_assembly::shift::Shift::Lsl).or(&new_ret);
             }
             Ok(ret)
@@ -247,7 +348,7 @@ impl<S: SmtSolver> ProgramMemory<S::Expression> for std::sync::Arc<Project<S>> {
 
     fn set<Expr: SmtExpr, Ctx: Context<Expr = Expr>>(
         &self,
-        mut address: u64,
+        address: u64,
         mut dataword: Expr,
         writes: &mut HashMap<u64, Expr>,
         ctx: &mut Ctx,
@@ -257,37 +358,20 @@ impl<S: SmtSolver> ProgramMemory<S::Expression> for std::sync::Arc<Project<S>> {
             1 => {
                 let _ = writes.insert(address, dataword);
             }
-            2 => {
-                for _ in 0..2 {
-                    let old_v = dataword.clone();
-                    dataword = dataword.shift(&ctx.new_from_u64(8, dataword.size()), general_assembly::shift::Shift::Lsr);
-                    let v2 = old_v
-                        .sub(&dataword.shift(&ctx.new_from_u64(8, dataword.size()), general_assembly::shift::Shift::Lsl))
-                        .resize_unsigned(8);
-                    writes.insert(address, v2);
-                    address += 1;
-                }
-            }
-            4 => {
-                for _ in 0..4 {
-                    let old_v = dataword.clone();
-                    dataword = dataword.shift(&ctx.new_from_u64(8, dataword.size()), general_assembly::shift::Shift::Lsr);
-                    let v2 = old_v
-                        .sub(&dataword.shift(&ctx.new_from_u64(8, dataword.size()), general_assembly::shift::Shift::Lsl))
-                        .resize_unsigned(8);
-                    writes.insert(address, v2);
-                    address += 1;
-                }
-            }
-            8 => {
-                for _ in 0..8 {
+            bytes @ (2 | 4 | 8) => {
+                let bytes = u64::from(bytes);
+                // Split the value starting from the least significant byte.
+                for byte in 0..bytes {
                     let old_v = dataword.clone();
                     dataword = dataword.shift(&ctx.new_from_u64(8, dataword.size()), general_assembly::shift::Shift::Lsr);
                     let v2 = old_v
                         .sub(&dataword.shift(&ctx.new_from_u64(8, dataword.size()), general_assembly::shift::Shift::Lsl))
                         .resize_unsigned(8);
-                    writes.insert(address, v2);
-                    address += 1;
+                    let offset = match self.endianness {
+                        Endianness::Little => byte,
+                        Endianness::Big => bytes - 1 - byte,
+                    };
+                    writes.insert(address + offset, v2);
                 }
             }
             _ => unimplemented!("Unsupported bitwidth"),
@@ -335,11 +419,220 @@ impl<S: SmtSolver> ProgramMemory<S::Expression> for std::sync::Arc<Project<S>> {
     fn read_only_regions(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
         self.segments.read_only_sections()
     }
+
+    fn executable_regions(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.segments.executable_sections()
+    }
 }
 
 #[allow(clippy::missing_fields_in_debug)]
 impl<S: SmtSolver> Debug for Project<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Project").field("word_size", &self.word_size).field("endianness", &self.endianness).finish()
+        f.debug_struct("Project")
+            .field("word_size", &self.word_size)
+            .field("endianness", &self.endianness)
+            .field("entry_point", &self.entry_point)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::arc_with_non_send_sync)]
+mod test {
+    use std::sync::Arc;
+
+    use hashbrown::HashMap;
+    use object::{Object, ObjectSymbol};
+
+    use general_assembly::prelude::DataWord;
+
+    use super::{dwarf_helper::SubProgramMap, Project, ProjectError};
+    use crate::{
+        smt::{bitwuzla::Bitwuzla, ProgramMemory, SmtSolver},
+        Endianness,
+        WordSize,
+    };
+
+    /// Builds a minimal 32 bit ARM PE image with a single `.text` section and a
+    /// `main` symbol at the start of it.
+    fn pe() -> Vec<u8> {
+        // DOS header, only the magic and the offset to the PE header are needed.
+        let mut pe = vec![0u8; 0x40];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+
+        // PE signature and COFF header.
+        pe.extend(b"PE\0\0");
+        pe.extend(0x01c4u16.to_le_bytes()); // ARMNT
+        pe.extend(1u16.to_le_bytes()); // Number of sections.
+        pe.extend(0u32.to_le_bytes()); // Time stamp.
+        pe.extend(0x400u32.to_le_bytes()); // Pointer to symbol table.
+        pe.extend(1u32.to_le_bytes()); // Number of symbols.
+        pe.extend(0xe0u16.to_le_bytes()); // Size of optional header.
+        pe.extend(0x0102u16.to_le_bytes()); // Executable, 32 bit.
+
+        // PE32 optional header.
+        pe.extend(0x10bu16.to_le_bytes()); // Magic.
+        pe.extend([0, 0]); // Linker version.
+        pe.extend(0x200u32.to_le_bytes()); // Size of code.
+        pe.extend(0u32.to_le_bytes()); // Size of initialized data.
+        pe.extend(0u32.to_le_bytes()); // Size of uninitialized data.
+        pe.extend(0x1001u32.to_le_bytes()); // Entry point.
+        pe.extend(0x1000u32.to_le_bytes()); // Base of code.
+        pe.extend(0u32.to_le_bytes()); // Base of data.
+        pe.extend(0x40_0000u32.to_le_bytes()); // Image base.
+        pe.extend(0x1000u32.to_le_bytes()); // Section alignment.
+        pe.extend(0x200u32.to_le_bytes()); // File alignment.
+        pe.extend([0; 16]); // OS, image and subsystem versions, Win32 version value.
+        pe.extend(0x2000u32.to_le_bytes()); // Size of image.
+        pe.extend(0x200u32.to_le_bytes()); // Size of headers.
+        pe.extend(0u32.to_le_bytes()); // Checksum.
+        pe.extend(0u16.to_le_bytes()); // Subsystem.
+        pe.extend(0u16.to_le_bytes()); // Dll characteristics.
+        pe.extend([0; 20]); // Stack and heap sizes, loader flags.
+        pe.extend(16u32.to_le_bytes()); // Number of data directories.
+        pe.extend([0; 16 * 8]);
+
+        // Section table.
+        pe.extend(b".text\0\0\0");
+        pe.extend(4u32.to_le_bytes()); // Virtual size.
+        pe.extend(0x1000u32.to_le_bytes()); // Virtual address.
+        pe.extend(0x200u32.to_le_bytes()); // Size of raw data.
+        pe.extend(0x200u32.to_le_bytes()); // Pointer to raw data.
+        pe.extend([0; 12]); // Relocations and line numbers.
+        pe.extend((object::pe::IMAGE_SCN_CNT_CODE | object::pe::IMAGE_SCN_MEM_EXECUTE | object::pe::IMAGE_SCN_MEM_READ).to_le_bytes());
+        pe.resize(0x200, 0);
+
+        // .text, `movs r0, #42; bx lr`.
+        pe.extend([0x2a, 0x20, 0x70, 0x47]);
+        pe.resize(0x400, 0);
+
+        // Symbol table followed by an empty string table.
+        pe.extend(b"main\0\0\0\0");
+        pe.extend(0u32.to_le_bytes()); // Value.
+        pe.extend(1u16.to_le_bytes()); // Section number.
+        pe.extend(0x20u16.to_le_bytes()); // Function.
+        pe.push(object::pe::IMAGE_SYM_CLASS_EXTERNAL);
+        pe.push(0); // Number of aux symbols.
+        pe.extend(4u32.to_le_bytes());
+        pe
+    }
+
+    /// Builds a minimal 32 bit ARM ELF image with a `.text` segment and a
+    /// writable segment holding a 4 byte `.data` followed by a 4 byte `.bss`.
+    fn elf() -> Vec<u8> {
+        // ELF header.
+        let mut elf = vec![0x7f, b'E', b'L', b'F', 1, 1, 1]; // 32 bit, little endian, version 1.
+        elf.resize(16, 0);
+        elf.extend(object::elf::ET_EXEC.to_le_bytes());
+        elf.extend(object::elf::EM_ARM.to_le_bytes());
+        elf.extend(1u32.to_le_bytes()); // Version.
+        elf.extend(0x1001u32.to_le_bytes()); // Entry point.
+        elf.extend(52u32.to_le_bytes()); // Program header offset.
+        elf.extend(0u32.to_le_bytes()); // Section header offset.
+        elf.extend(0u32.to_le_bytes()); // Flags.
+        elf.extend(52u16.to_le_bytes()); // Header size.
+        elf.extend(32u16.to_le_bytes()); // Program header size.
+        elf.extend(2u16.to_le_bytes()); // Number of program headers.
+        elf.extend(40u16.to_le_bytes()); // Section header size.
+        elf.extend(0u16.to_le_bytes()); // Number of section headers.
+        elf.extend(0u16.to_le_bytes()); // Section name string table index.
+
+        // Program headers, the `.data` load image is placed after `.text`.
+        for (offset, address, load_address, file_size, memory_size, flags) in [
+            (0x100u32, 0x1000u32, 0x1000u32, 4u32, 4u32, object::elf::PF_R | object::elf::PF_X),
+            (0x104, 0x2000_0000, 0x1004, 4, 8, object::elf::PF_R | object::elf::PF_W),
+        ] {
+            elf.extend(object::elf::PT_LOAD.to_le_bytes());
+            elf.extend(offset.to_le_bytes());
+            elf.extend(address.to_le_bytes());
+            elf.extend(load_address.to_le_bytes());
+            elf.extend(file_size.to_le_bytes());
+            elf.extend(memory_size.to_le_bytes());
+            elf.extend(flags.to_le_bytes());
+            elf.extend(4u32.to_le_bytes()); // Alignment.
+        }
+        elf.resize(0x100, 0);
+
+        // .text, `movs r0, #42; bx lr`.
+        elf.extend([0x2a, 0x20, 0x70, 0x47]);
+        // .data
+        elf.extend(0x1122_3344u32.to_le_bytes());
+        elf
+    }
+
+    #[test]
+    fn test_load_elf_data_and_bss() {
+        let data = elf();
+        let file = object::File::parse(&*data).unwrap();
+        assert_eq!(file.format(), object::BinaryFormat::Elf);
+
+        let mut ctx = Bitwuzla::new();
+        let project = Project::from_binary(&mut ctx, &file, SubProgramMap::default()).unwrap();
+        // Initialized globals hold their initializer and `.bss` globals are zero.
+        assert!(matches!(project.get_word(0x2000_0000), Ok(DataWord::Word32(0x1122_3344))));
+        assert!(matches!(project.get_word(0x2000_0004), Ok(DataWord::Word32(0))));
+        assert!(!project.address_in_range(0x2000_0008));
+        assert_eq!(project.read_only_regions().collect::<Vec<_>>(), vec![(0x1000, 0x1004)]);
+    }
+
+    #[test]
+    fn test_load_pe() {
+        let data = pe();
+        let file = object::File::parse(&*data).unwrap();
+        assert_eq!(file.format(), object::BinaryFormat::Pe);
+
+        let mut symtab = HashMap::new();
+        for symbol in file.symbols() {
+            symtab.insert(symbol.name().unwrap().to_owned(), symbol.address());
+        }
+        let mut map = SubProgramMap::default();
+        map.insert_symtab(symtab);
+
+        let mut ctx = Bitwuzla::new();
+        let project = Arc::new(Project::from_binary(&mut ctx, &file, map).unwrap());
+        assert_eq!(project.entry_point(), 0x40_1001);
+        assert_eq!(project.get_symbol_address("main"), Some(0x40_1000));
+        assert_eq!(project.get_byte(0x40_1000).unwrap(), 0x2a);
+        assert_eq!(project.executable_regions().collect::<Vec<_>>(), vec![(0x40_1000, 0x40_1004)]);
+        assert_eq!(project.read_only_regions().collect::<Vec<_>>(), vec![(0x40_1000, 0x40_1004)]);
+    }
+
+    #[test]
+    fn test_override_layout() {
+        let data = pe();
+        let file = object::File::parse(&*data).unwrap();
+        let mut ctx = Bitwuzla::new();
+        let mut project = Project::from_binary(&mut ctx, &file, SubProgramMap::default()).unwrap();
+        assert!(matches!(project.get_word(0x40_1000), Ok(DataWord::Word32(0x4770_202a))));
+
+        project.override_layout(WordSize::Bit16, Endianness::Big).unwrap();
+        assert!(matches!(project.get_word(0x40_1000), Ok(DataWord::Word16(0x2a20))));
+
+        assert_eq!(
+            project.override_layout(WordSize::Bit8, Endianness::Little),
+            Err(ProjectError::UnsupportedLayout(WordSize::Bit8, Endianness::Little))
+        );
+    }
+
+    #[test]
+    fn test_word_size_from_bits() {
+        assert_eq!(WordSize::try_from(32), Ok(WordSize::Bit32));
+        assert_eq!(WordSize::try_from(7), Err(ProjectError::InvalidWordSize(7)));
+        assert_eq!(WordSize::try_from(0), Err(ProjectError::InvalidWordSize(0)));
+
+        // Valid word sizes can still be rejected as a layout.
+        let word_size = WordSize::try_from(8).unwrap();
+        assert!(super::validate_layout(word_size, Endianness::Little).is_err());
+
+        // A project is only built with a word size that converts.
+        let data = pe();
+        let file = object::File::parse(&*data).unwrap();
+        let mut ctx = Bitwuzla::new();
+        let mut project = Project::from_binary(&mut ctx, &file, SubProgramMap::default()).unwrap();
+        let mut layout = |bits: u32| WordSize::try_from(bits).and_then(|word_size| project.override_layout(word_size, Endianness::Little));
+        assert_eq!(layout(7), Err(ProjectError::InvalidWordSize(7)));
+        assert_eq!(layout(16), Ok(()));
+        assert!(matches!(project.get_word(0x40_1000), Ok(DataWord::Word16(0x202a))));
     }
 }
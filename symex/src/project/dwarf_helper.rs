@@ -45,6 +45,28 @@ pub struct SubProgram {
     pub call_file: Option<(String, usize)>,
 }
 
+impl SubProgram {
+    /// Returns the demangled name of the sub program, or the raw name if it is
+    /// not a mangled Rust or C++ symbol.
+    #[must_use]
+    pub fn demangled_name(&self) -> String {
+        demangle(&self.name).unwrap_or_else(|| self.name.clone())
+    }
+}
+
+/// Demangles a Rust (legacy or v0) or Itanium C++ symbol.
+///
+/// Rust hashes and C++ parameter lists are omitted so that the result can be
+/// written by hand, e.g. `core::panicking::panic`.
+#[must_use]
+pub fn demangle(name: &str) -> Option<String> {
+    if let Ok(demangled) = rustc_demangle::try_demangle(name) {
+        return Some(format!("{demangled:#}"));
+    }
+    let symbol = cpp_demangle::Symbol::new(name).ok()?;
+    symbol.demangle(&cpp_demangle::DemangleOptions::new().no_params().no_return_type()).ok()
+}
+
 impl Hash for SubProgram {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.name.hash(state);
@@ -59,6 +81,8 @@ pub struct SubProgramMap {
     pub map: HashMap<u64, SubProgram>,
     counter: u64,
     pub symtab: HashMap<String, SubProgram>,
+    /// Maps demangled symbol names to the mangled names in `symtab`.
+    demangled: HashMap<String, String>,
 }
 
 impl Default for SubProgramMap {
@@ -75,6 +99,7 @@ impl SubProgramMap {
             map: HashMap::new(),
             counter: 0,
             symtab: HashMap::new(),
+            demangled: HashMap::new(),
         }
     }
 
@@ -86,11 +111,16 @@ impl SubProgramMap {
             map: HashMap::new(),
             counter: 0,
             symtab: HashMap::new(),
+            demangled: HashMap::new(),
         }
     }
 
     pub fn insert_symtab(&mut self, symtab: HashMap<String, u64>) {
         for (key, value) in symtab {
+            if let Some(demangled) = demangle(&key) {
+                // Generic instances share a demangled name, the first one is used.
+                self.demangled.entry(demangled).or_insert_with(|| key.clone());
+            }
             let _ = self.symtab.insert(key.clone(), SubProgram {
                 name: key,
                 bounds: (value & ((u64::MAX >> 1) << 1), value & ((u64::MAX >> 1) << 1)),
@@ -114,6 +144,7 @@ impl SubProgramMap {
     pub fn get_all_names(&self) -> Vec<String> {
         let mut ret: Vec<String> = self.symtab.keys().cloned().collect::<Vec<_>>();
         ret.extend(self.index_1.keys().cloned());
+        ret.extend(self.demangled.keys().cloned());
         ret
     }
 
@@ -124,10 +155,11 @@ impl SubProgramMap {
         self.counter += 1;
     }
 
+    /// Looks up a sub program by either its mangled or demangled name.
     #[must_use]
     pub fn get_by_name(&self, name: &str) -> Option<&SubProgram> {
         let Some(idx) = self.index_1.get(name) else {
-            return self.symtab.get(name);
+            return self.symtab.get(name).or_else(|| self.symtab.get(self.demangled.get(name)?));
         };
         self.map.get(idx)
     }
@@ -138,6 +170,45 @@ impl SubProgramMap {
         self.map.get(idx)
     }
 
+    /// Returns the function whose `[start, end)` contains `address`.
+    ///
+    /// If functions overlap, such as inlined sub routines, the smallest one is
+    /// returned. Symbols without a size, such as those only in the symbol
+    /// table, extend to the next symbol.
+    #[must_use]
+    pub fn get_enclosing(&self, address: u64) -> Option<&SubProgram> {
+        let address = address & ((u64::MAX >> 1) << 1);
+        let programs = || self.map.values().chain(self.symtab.values());
+        // Ties are broken by name as the maps are unordered.
+        if let Some(program) = programs()
+            .filter(|program| (program.bounds.0..program.bounds.1).contains(&address))
+            .min_by_key(|program| (program.bounds.1 - program.bounds.0, &program.name))
+        {
+            return Some(program);
+        }
+        programs()
+            .filter(|program| program.bounds.0 <= address)
+            .max_by_key(|program| (program.bounds.0, program.bounds.1, std::cmp::Reverse(&program.name)))
+            .filter(|program| program.bounds.0 == program.bounds.1)
+    }
+
+    /// Formats `address` as `function+offset`, see
+    /// [`get_enclosing`](Self::get_enclosing).
+    #[must_use]
+    pub fn symbolize(&self, address: u64) -> Option<String> {
+        let program = self.get_enclosing(address)?;
+        let offset = (address & ((u64::MAX >> 1) << 1)) - program.bounds.0;
+        Some(format!("{}+{offset:#x}", program.demangled_name()))
+    }
+
+    /// Returns the function starting at `address`, using the symbol table if
+    /// there is no debug info for it.
+    #[must_use]
+    pub fn get_by_entry(&self, address: u64) -> Option<&SubProgram> {
+        let address = address & ((u64::MAX >> 1) << 1);
+        self.get_by_address(&address).or_else(|| self.symtab.values().find(|program| program.bounds.0 == address))
+    }
+
     #[must_use]
     pub fn get_by_regex(&self, pattern: &'static str) -> Option<&SubProgram> {
         let regex = Regex::new(pattern).ok()?;
@@ -256,6 +327,21 @@ pub struct LineInfo {
     text: Option<String>,
 }
 
+impl LineInfo {
+    #[must_use]
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    #[must_use]
+    pub const fn line(&self) -> u64 {
+        self.line
+    }
+}
+
+/// Maps instruction addresses to source locations.
+///
+/// This is empty if the binary does not contain any DWARF line info.
 #[repr(transparent)]
 #[derive(Clone, Debug)]
 pub struct LineMap {
@@ -266,6 +352,7 @@ impl LineMap {
         Self { map: None }
     }
 
+    /// Returns the source location of the instruction at `address`, if known.
     #[must_use]
     pub fn lookup(&self, address: u64) -> Option<&LineInfo> {
         let map = self.map?;
@@ -375,6 +462,14 @@ pub struct CallStack {
 impl DebugData {
     pub const fn unwind_symbolic(&self) {}
 
+    /// Debug data for binaries without any DWARF sections.
+    pub(crate) fn empty() -> Self {
+        Self {
+            dwarf: Box::leak(Box::default()),
+            debug_frame: Box::leak(Box::new(DebugFrame::new(&[], RunTimeEndian::default()))),
+        }
+    }
+
     pub(crate) fn new(object: &'static object::File<'static>, endian: gimli::RunTimeEndian) -> Result<Self, Box<dyn std::error::Error>> {
         let load_section = |id: gimli::SectionId| -> Result<std::borrow::Cow<'_, [u8]>, Box<dyn std::error::Error>> {
             Ok(match object.section_by_name(id.name()) {
@@ -392,11 +487,10 @@ impl DebugData {
         // Create `EndianSlice`s for all of the sections.
         let dwarf = dwarf_sections.borrow(borrow_section);
 
-        let sec = object
-            .section_by_name(".debug_frame")
-            .expect("debug frame to exist")
-            .uncompressed_data()
-            .expect("Data to be readable");
+        let sec = match object.section_by_name(".debug_frame") {
+            Some(section) => section.uncompressed_data().expect("Data to be readable"),
+            None => std::borrow::Cow::Borrowed(&[][..]),
+        };
         let frame = DebugFrame::new(Box::leak(Box::new(sec)), endian);
 
         Ok(Self {
@@ -498,3 +592,57 @@ where
         Some(buffer)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use hashbrown::HashMap;
+
+    use super::{SubProgram, SubProgramMap};
+
+    #[test]
+    fn test_demangled_lookup() {
+        let mut map = SubProgramMap::empty();
+        let mut symtab = HashMap::new();
+        symtab.insert("_ZN4core9panicking5panic17h0123456789abcdefE".to_string(), 0x1001);
+        symtab.insert("_ZN3foo3barEi".to_string(), 0x2000);
+        map.insert_symtab(symtab);
+
+        let mangled = map.get_by_name("_ZN4core9panicking5panic17h0123456789abcdefE").expect("Mangled name to resolve");
+        let demangled = map.get_by_name("core::panicking::panic").expect("Demangled name to resolve");
+        assert_eq!(mangled, demangled);
+        assert_eq!(demangled.bounds, (0x1000, 0x1000));
+        assert_eq!(demangled.demangled_name(), "core::panicking::panic");
+
+        let cpp = map.get_by_name("foo::bar").expect("Demangled C++ name to resolve");
+        assert_eq!(cpp.name, "_ZN3foo3barEi");
+        assert!(map.get_by_name("core::panicking").is_none());
+    }
+
+    #[test]
+    fn test_enclosing_lookup() {
+        let mut map = SubProgramMap::empty();
+        let program = |name: &str, start: u64, end: u64| SubProgram {
+            name: name.to_owned(),
+            bounds: (start, end),
+            file: None,
+            call_file: None,
+        };
+        map.insert("outer".to_owned(), 0x1000, program("outer", 0x1000, 0x1040));
+        map.insert("inlined".to_owned(), 0x1010, program("inlined", 0x1010, 0x1020));
+        let mut symtab = HashMap::new();
+        symtab.insert("unsized".to_string(), 0x2001);
+        map.insert_symtab(symtab);
+
+        let name = |address| map.get_enclosing(address).map(|program| program.name.as_str());
+        assert_eq!(name(0x1008), Some("outer"));
+        assert_eq!(name(0x1014), Some("inlined"));
+        assert_eq!(name(0x1020), Some("outer"));
+        // Past the end of a function with a known size.
+        assert_eq!(name(0x1040), None);
+        assert_eq!(name(0x0ffe), None);
+        assert_eq!(name(0x2008), Some("unsized"));
+
+        assert_eq!(map.symbolize(0x1015).as_deref(), Some("inlined+0x4"));
+        assert_eq!(map.symbolize(0x2000).as_deref(), Some("unsized+0x0"));
+    }
+}
@@ -1,13 +1,21 @@
-//! A loader that can load all segments from a elf file properly.
+//! A loader that can load all segments from a ELF, Mach-O or PE file properly.
 
-use object::{read::elf::ProgramHeader, File, Object};
+use object::{
+    read::elf::{ElfFile, FileHeader, ProgramHeader},
+    File,
+    Object,
+    ObjectSegment,
+    SegmentFlags,
+};
 
+use super::ProjectError;
 use crate::smt::{Lambda, SmtExpr, SmtSolver};
 pub struct Segment {
     data: Vec<u8>,
     start_address: u64,
     end_address: u64,
     constants: bool,
+    executable: bool,
 }
 
 #[must_use]
@@ -40,6 +48,7 @@ impl<S: SmtSolver> Segments<S> {
                 start_address: start_addr,
                 end_address: end_addr,
                 constants,
+                executable: true,
             }],
             None,
         )
@@ -49,6 +58,10 @@ impl<S: SmtSolver> Segments<S> {
         self.0.iter().filter(|el| el.constants).map(|seg| (seg.start_address, seg.end_address))
     }
 
+    pub fn executable_sections(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.0.iter().filter(|el| el.executable).map(|seg| (seg.start_address, seg.end_address))
+    }
+
     pub(crate) fn could_possibly_be_out_of_bounds(&self, addr: S::Expression) -> S::Expression {
         self.1.as_ref().unwrap().apply(addr)
     }
@@ -63,36 +76,94 @@ impl<S: SmtSolver> Segments<S> {
         ret
     }
 
-    #[allow(deprecated)]
-    pub fn from_file(ctx: &mut S, file: &File<'_>) -> Self {
-        let elf_file = match file {
-            File::Elf32(elf_file) => elf_file,
-            File::Elf64(_elf_file) => todo!(),
-            _ => todo!(),
+    pub fn from_file(ctx: &mut S, file: &File<'_>) -> Result<Self, ProjectError> {
+        let ret = match file {
+            File::Elf32(elf_file) => Self::elf_segments(elf_file),
+            File::Elf64(elf_file) => Self::elf_segments(elf_file),
+            File::MachO32(_) | File::MachO64(_) | File::Pe32(_) | File::Pe64(_) => Self::object_segments(file),
+            _ => return Err(ProjectError::UnsupportedFileFormat(file.format())),
         };
+        // TODO: Correct this.
+        let lookup = construct_lookup(ctx, 32, &ret);
+        Ok(Self(ret, Some(lookup)))
+    }
 
-        let mut ret = vec![];
-        for segment in elf_file.raw_segments() {
-            let segment_type = segment.p_type.get(file.endianness());
+    /// Maps a raw binary in to a single read only, executable segment starting
+    /// at `base_addr`.
+    pub fn from_raw(ctx: &mut S, data: Vec<u8>, base_addr: u64) -> Self {
+        let ret = vec![Segment {
+            end_address: base_addr + data.len() as u64,
+            data,
+            start_address: base_addr,
+            constants: true,
+            executable: true,
+        }];
+        let lookup = construct_lookup(ctx, 32, &ret);
+        Self(ret, Some(lookup))
+    }
 
-            if segment_type == 1 {
-                // if it is a LOAD segment
-                let addr_start = segment.p_vaddr.get(file.endianness()) as u64;
-                //let size = segment.p_memsz.get(file.endianness());
-                let data = segment.data(file.endianness(), elf_file.data()).unwrap();
+    /// Loads all LOAD segments from the program headers.
+    ///
+    /// Segments are mapped at their virtual address, so writable segments hold
+    /// the initial values of `.data` and the zeroed `.bss`.
+    fn elf_segments<Elf: FileHeader>(elf_file: &ElfFile<'_, Elf>) -> Vec<Segment> {
+        let endian = elf_file.endian();
+        let mut ret = vec![];
+        for segment in elf_file.elf_program_headers() {
+            if segment.p_type(endian) == object::elf::PT_LOAD {
+                let addr_start: u64 = segment.p_vaddr(endian).into();
+                let mut data = segment.data(endian, elf_file.data()).unwrap().to_owned();
+                let flags = segment.p_flags(endian);
+
+                // Memory past the file image, such as `.bss`, is zero initialized.
+                let memory_size: u64 = segment.p_memsz(endian).into();
+                if memory_size > data.len() as u64 {
+                    data.resize(memory_size as usize, 0);
+                }
 
                 let new = Segment {
-                    data: data.to_owned(),
+                    data,
                     start_address: addr_start,
                     end_address: addr_start + data.len() as u64,
-                    constants: segment.p_flags.get(file.endianness()) & 0b010 == 0b000,
+                    constants: flags & object::elf::PF_W == 0,
+                    executable: flags & object::elf::PF_X == object::elf::PF_X,
                 };
                 ret.push(new);
             }
         }
-        // TODO: Correct this.
-        let lookup = construct_lookup(ctx, 32, &ret);
-        Self(ret, Some(lookup))
+        ret
+    }
+
+    /// Loads all segments from a Mach-O or PE file.
+    ///
+    /// For PE files each section is a segment.
+    fn object_segments(file: &File<'_>) -> Vec<Segment> {
+        let mut ret = vec![];
+        for segment in file.segments() {
+            let (writable, executable) = match segment.flags() {
+                SegmentFlags::MachO { initprot, .. } => (initprot & object::macho::VM_PROT_WRITE != 0, initprot & object::macho::VM_PROT_EXECUTE != 0),
+                SegmentFlags::Coff { characteristics } => (
+                    characteristics & object::pe::IMAGE_SCN_MEM_WRITE != 0,
+                    characteristics & object::pe::IMAGE_SCN_MEM_EXECUTE != 0,
+                ),
+                _ => continue,
+            };
+            // Segments such as __PAGEZERO have no data in the file.
+            let data = match segment.data() {
+                Ok(data) if !data.is_empty() => data,
+                _ => continue,
+            };
+            let addr_start = segment.address();
+
+            ret.push(Segment {
+                data: data.to_owned(),
+                start_address: addr_start,
+                end_address: addr_start + data.len() as u64,
+                constants: !writable,
+                executable,
+            });
+        }
+        ret
     }
 
     pub fn read_raw_bytes(&self, mut address: u64, bytes: usize) -> Option<Vec<u8>> {
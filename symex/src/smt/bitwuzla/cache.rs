@@ -0,0 +1,222 @@
+use std::{
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+    rc::Rc,
+};
+
+use hashbrown::HashMap;
+
+/// A satisfiability check, identified by the printed representation of the
+/// assumptions it is made under.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Query {
+    /// Hash of the asserted constraints and of the assumptions.
+    key: (u64, u64),
+    assumptions: Vec<String>,
+}
+
+/// Least recently used cache of satisfiability results.
+///
+/// Terms are identified by their printed representation, the cache tracks
+/// the constraints asserted in each pushed frame so that a result is only
+/// reused while the same constraints are active. Hashes are only used to find
+/// candidate entries, a result is reused only if the printed constraints and
+/// assumptions match exactly.
+#[derive(Debug)]
+pub(super) struct SatCache {
+    capacity: usize,
+    /// Printed representation of every asserted constraint, oldest first.
+    asserted: Vec<Rc<str>>,
+    frames: Vec<Frame>,
+    entries: HashMap<(u64, u64), Entry>,
+    /// Incremented on every lookup and insertion, used to find the least
+    /// recently used entry.
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct Frame {
+    /// Number of constraints asserted before the frame was pushed.
+    start: usize,
+    /// Hash of all constraints asserted up to and including this frame.
+    hash: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    asserted: Vec<Rc<str>>,
+    assumptions: Vec<String>,
+    result: bool,
+    last_used: u64,
+}
+
+fn text_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl SatCache {
+    /// Creates a cache holding at most `capacity` results, a capacity of zero
+    /// disables the cache.
+    pub(super) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            asserted: Vec::new(),
+            frames: vec![Frame { start: 0, hash: 0 }],
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    pub(super) const fn is_enabled(&self) -> bool {
+        self.capacity != 0
+    }
+
+    pub(super) fn push(&mut self) {
+        self.frames.push(Frame {
+            start: self.asserted.len(),
+            hash: self.top(),
+        });
+    }
+
+    pub(super) fn pop(&mut self) {
+        if self.frames.len() > 1 {
+            if let Some(frame) = self.frames.pop() {
+                self.asserted.truncate(frame.start);
+            }
+        }
+    }
+
+    /// Records that `constraint` was asserted in the current frame.
+    pub(super) fn assert<T: Debug>(&mut self, constraint: &T) {
+        if !self.is_enabled() {
+            return;
+        }
+        let text = format!("{constraint:?}");
+        let hash = text_hash(&text);
+        if let Some(top) = self.frames.last_mut() {
+            top.hash = top.hash.wrapping_add(hash);
+        }
+        self.asserted.push(text.into());
+    }
+
+    /// Returns the query for a check under `assumptions` with the currently
+    /// asserted constraints.
+    pub(super) fn query<T: Debug>(&self, assumptions: &[T]) -> Query {
+        let assumptions = assumptions.iter().map(|assumption| format!("{assumption:?}")).collect::<Vec<_>>();
+        let hash = assumptions.iter().fold(0u64, |acc, assumption| acc.wrapping_add(text_hash(assumption)));
+        Query {
+            key: (self.top(), hash),
+            assumptions,
+        }
+    }
+
+    pub(super) fn get(&mut self, query: &Query) -> Option<bool> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(&query.key)?;
+        if entry.asserted != self.asserted || entry.assumptions != query.assumptions {
+            return None;
+        }
+        entry.last_used = self.clock;
+        Some(entry.result)
+    }
+
+    pub(super) fn insert(&mut self, query: &Query, result: bool) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.clock += 1;
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&query.key) {
+            let oldest = self.entries.iter().min_by_key(|(_, entry)| entry.last_used).map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(query.key, Entry {
+            asserted: self.asserted.clone(),
+            assumptions: query.assumptions.clone(),
+            result,
+            last_used: self.clock,
+        });
+    }
+
+    fn top(&self) -> u64 {
+        self.frames.last().map_or(0, |frame| frame.hash)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SatCache;
+
+    #[test]
+    fn test_frames() {
+        let mut cache = SatCache::new(8);
+        let empty = cache.query::<&str>(&[]);
+
+        cache.push();
+        assert_eq!(cache.query::<&str>(&[]), empty);
+        cache.assert(&"a");
+        let a = cache.query::<&str>(&[]);
+        assert_ne!(a, empty);
+
+        cache.push();
+        cache.assert(&"b");
+        assert_ne!(cache.query::<&str>(&[]), a);
+        cache.pop();
+        assert_eq!(cache.query::<&str>(&[]), a);
+        cache.pop();
+        assert_eq!(cache.query::<&str>(&[]), empty);
+
+        // The base frame is never popped.
+        cache.pop();
+        assert_eq!(cache.query::<&str>(&[]), empty);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = SatCache::new(2);
+        let a = cache.query(&["a"]);
+        let b = cache.query(&["b"]);
+        let c = cache.query(&["c"]);
+
+        cache.insert(&a, true);
+        cache.insert(&b, false);
+        assert_eq!(cache.get(&a), Some(true));
+
+        // `b` was used least recently.
+        cache.insert(&c, true);
+        assert_eq!(cache.get(&b), None);
+        assert_eq!(cache.get(&a), Some(true));
+        assert_eq!(cache.get(&c), Some(true));
+    }
+
+    #[test]
+    fn test_hits_require_identical_constraints() {
+        let mut cache = SatCache::new(8);
+        cache.push();
+        cache.assert(&"a");
+        cache.assert(&"b");
+        let query = cache.query(&["c"]);
+        cache.insert(&query, true);
+        assert_eq!(cache.get(&query), Some(true));
+        cache.pop();
+
+        // The same constraints in a different order hash the same, the result
+        // is still not reused.
+        cache.push();
+        cache.assert(&"b");
+        cache.assert(&"a");
+        assert_eq!(cache.query(&["c"]), query);
+        assert_eq!(cache.get(&query), None);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut cache = SatCache::new(0);
+        let a = cache.query(&["a"]);
+        cache.insert(&a, true);
+        assert_eq!(cache.get(&a), None);
+    }
+}
@@ -220,6 +220,16 @@ impl SmtExpr for BitwuzlaExpr {
         }
     }
 
+    fn rotate_left(&self, amount: &Self) -> Self {
+        assert_eq!(self.size(), amount.size());
+        Self(self.0.rol(&amount.0))
+    }
+
+    fn rotate_right(&self, amount: &Self) -> Self {
+        assert_eq!(self.size(), amount.size());
+        Self(self.0.ror(&amount.0))
+    }
+
     fn ite(&self, then_bv: &Self, else_bv: &Self) -> Self {
         Self(self.0.cond_bv(&then_bv.0, &else_bv.0))
     }
@@ -348,7 +358,7 @@ impl SmtExpr for BitwuzlaExpr {
 
         let result = self.add(other).simplify();
         let overflow = self.uaddo(other).simplify();
-        let saturated = Self(BV::max_signed(self.get_ctx(), self.size() as u64));
+        let saturated = Self(BV::zero(self.get_ctx(), self.size() as u64)).not();
 
         overflow.ite(&saturated, &result)
     }
@@ -44,6 +44,14 @@ fn conv_rm(rm: &RoundingMode) -> RM {
 }
 
 impl FpExpr {
+    /// Creates a constant of type `ty` from `value`.
+    pub fn from_f64(ctx: Rc<Btor>, value: f64, ty: OperandType) -> Self {
+        Self {
+            ctx: FpOrBv::Fp(FP::new_from_f64(ctx, value, conv_ty(&ty))),
+            ty,
+        }
+    }
+
     pub fn unconstrained(ctx: Rc<Btor>, ty: &OperandType, name: Option<&str>) -> Self {
         let converted_type = conv_ty(ty);
         Self {
@@ -257,6 +265,11 @@ impl SmtFPExpr for FpExpr {
         })
     }
 
+    fn convert(&self, ty: OperandType, rm: RoundingMode) -> crate::Result<Self> {
+        let ctx: &FP<Rc<Bitwuzla>> = (&self.ctx).try_into()?;
+        Ok(FP::from_fp(ctx, conv_rm(&rm), &conv_ty(&ty)).conv(ty))
+    }
+
     fn convert_from_bv(bv: Self::Expression, rm: RoundingMode, source_ty: OperandType, dest_ty: OperandType, signed: bool) -> crate::Result<Self> {
         match source_ty {
             OperandType::Binary16 => {
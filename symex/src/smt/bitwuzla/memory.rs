@@ -1,22 +1,34 @@
-use std::{fmt::Display, marker::PhantomData, rc::Rc};
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    marker::PhantomData,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use anyhow::Context as _;
 use bitwuzla::{Array, Btor, BV};
 use general_assembly::extension::ieee754::{OperandType, RoundingMode};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 
 use super::{expr::BitwuzlaExpr, fpexpr::FpExpr, Bitwuzla};
 use crate::{
     executor::ResultOrTerminate,
     memory::{MemoryError, BITS_IN_BYTE},
     project::Project,
-    smt::{Context, ProgramMemory, SmtExpr, SmtFPExpr, SmtMap, SmtSolver},
+    smt::{Context, ProgramMemory, SmtExpr, SmtFPExpr, SmtMap, SmtSolver, WriteLog},
     trace,
     warn,
     Endianness,
     UserStateContainer,
 };
 
+/// Memory modelled as a single SMT array from pointer sized addresses to
+/// bytes.
+///
+/// Reads and writes at symbolic addresses are left to the solver instead of
+/// being resolved to concrete addresses, wider accesses are split in to byte
+/// accesses in the byte order of the target.
 #[derive(Debug, Clone)]
 #[must_use]
 pub struct ArrayMemory {
@@ -135,6 +147,9 @@ impl Context for ArrayMemory {
     }
 }
 
+/// Source of unique [`BitwuzlaMemory`] generations.
+static GENERATIONS: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "exact", allow(dead_code))]
 pub struct BitwuzlaMemory<State: UserStateContainer> {
@@ -150,6 +165,31 @@ pub struct BitwuzlaMemory<State: UserStateContainer> {
     initial_sp: BitwuzlaExpr,
     static_writes: HashMap<u64, BitwuzlaExpr>,
     cycles: u64,
+    /// Whether or not reads from uninitialized memory should be tracked.
+    poison_tracking: bool,
+    /// All constant byte addresses that have been written to, including
+    /// uninitialized bytes replaced by poisoned symbols. Only tracked while
+    /// poison tracking is enabled.
+    initialized: HashSet<u64>,
+    /// Addresses of the bytes replaced by poisoned symbols, see
+    /// [`poisoned_name`].
+    poisoned: HashSet<u64>,
+    /// Address and size in bytes of every write to a symbolic address made
+    /// while poison tracking is enabled, any of which may have initialized a
+    /// byte.
+    symbolic_writes: Vec<(BitwuzlaExpr, u64)>,
+    /// The writes since [`SmtMap::start_write_log`], if a log is active.
+    write_log: Option<WriteLog<BitwuzlaExpr>>,
+    /// Symbols to reuse instead of creating new ones, see
+    /// [`SmtMap::seed_symbols`].
+    seeded: HashMap<String, BitwuzlaExpr>,
+    /// Number of unnamed symbols created, see [`SmtMap::unnamed_symbols`].
+    unnamed_symbols: usize,
+    /// Changes whenever memory or a floating point register is written. Two
+    /// memories with the same generation hold the same values in those.
+    generation: u64,
+    /// The writes that changed the generation, if merge tracking is enabled.
+    journal: Option<Rc<Journal>>,
     _0: PhantomData<State>,
 }
 
@@ -190,6 +230,15 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
             initial_sp,
             static_writes: HashMap::new(),
             cycles: 0,
+            poison_tracking: false,
+            initialized: HashSet::new(),
+            poisoned: HashSet::new(),
+            symbolic_writes: Vec::new(),
+            write_log: None,
+            seeded: HashMap::new(),
+            unnamed_symbols: 0,
+            generation: GENERATIONS.fetch_add(1, Ordering::Relaxed),
+            journal: None,
             _0: PhantomData,
         })
     }
@@ -198,6 +247,9 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
         if let Some(address) = idx.get_constant() {
             if !self.program_memory.address_in_range(address) {
                 trace!("Got deterministic address ({address:#x}) from ram");
+                if self.poison_tracking {
+                    self.poison_uninitialized(idx, address, size);
+                }
                 return ResultOrTerminate::Result(
                     self.ram
                         .read(idx, size)
@@ -216,12 +268,23 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
     }
 
     fn set(&mut self, idx: &Self::Expression, value: Self::Expression) -> Result<(), crate::smt::MemoryError> {
+        let bytes = u64::from(value.size().div_ceil(BITS_IN_BYTE));
+        self.next_generation(match idx.get_constant() {
+            Some(address) => Written::Bytes { address, bytes },
+            None => Written::Untracked,
+        });
+        self.log_write(idx, &value, bytes);
         if let Some(address) = idx.get_constant() {
+            if self.poison_tracking {
+                self.initialized.extend(address..(address + bytes));
+            }
             if self.program_memory.address_in_range(address) {
                 assert!(value.size() % 8 == 0, "Value must be a multiple of 8 bits to be written to program memory");
                 let _ = self.program_memory.set(address, value, &mut self.static_writes, &mut self.ram);
                 return Ok(());
             }
+        } else if self.poison_tracking {
+            self.track_symbolic_write(idx, bytes);
         }
         self.ram.write(idx, value);
         Ok(())
@@ -270,6 +333,7 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
 
     #[cfg(not(feature = "bitwuzla-exact-fp"))]
     fn set_fp_register(&mut self, idx: &str, value: <Self::SMT as SmtSolver>::FpExpression, _rm: RoundingMode, _signed: bool) -> Result<(), crate::smt::MemoryError> {
+        self.next_generation(Written::Untracked);
         self.fp_registers.insert(idx.to_string(), value);
         Ok(())
     }
@@ -305,6 +369,7 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
             Some(val) => val.clone(),
             None => {
                 let ret = self.unconstrained_fp(source_ty, idx);
+                self.next_generation(Written::Untracked);
                 self.fp_registers.insert(idx.to_owned(), ret.clone());
                 ret
             }
@@ -331,9 +396,8 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
             OperandType::Integral { size, signed: _ } => size,
         };
         let value = match ty {
-            OperandType::Binary16 => {
-                todo!("No support in the rust compiler for binary16");
-            }
+            // There is no native binary16 type, let the solver round the value instead.
+            OperandType::Binary16 => return Ok(FpExpr::from_f64(self.ram.ctx.clone(), value, ty)),
             OperandType::Binary32 => {
                 let value = u64::from((value as f32).to_bits());
                 self.from_u64(value, size)
@@ -361,9 +425,10 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
 
     fn unconstrained(&mut self, name: &str, size: u32) -> Self::Expression {
         assert!(size != 0, "Tried to create a 0 width unconstrained value");
-        let ret = BV::new(self.ram.ctx.clone(), size as u64, Some(name));
-        let ret = BitwuzlaExpr(ret);
-        let ret = ret.resize_unsigned(size);
+        let ret = match self.seeded.get(name) {
+            Some(seeded) if seeded.size() == size => seeded.clone(),
+            _ => BitwuzlaExpr(BV::new(self.ram.ctx.clone(), size as u64, Some(name))).resize_unsigned(size),
+        };
         if !self.variables.contains_key(name) {
             trace!("Added a named variabled");
             self.variables.insert(name.to_string(), ret.clone());
@@ -374,6 +439,7 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
 
     fn unconstrained_unnamed(&mut self, size: u32) -> Self::Expression {
         assert!(size != 0, "Tried to create a 0 width unconstrained value");
+        self.unnamed_symbols += 1;
         let ret = BV::new(self.ram.ctx.clone(), size as u64, None);
         let ret = BitwuzlaExpr(ret);
         let _ = ret.resize_unsigned(size);
@@ -381,6 +447,7 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
     }
 
     fn unconstrained_fp_unnamed(&mut self, ty: general_assembly::extension::ieee754::OperandType) -> <Self::SMT as crate::smt::SmtSolver>::FpExpression {
+        self.unnamed_symbols += 1;
         FpExpr::unconstrained(self.ram.ctx.clone(), &ty, None)
     }
 
@@ -407,10 +474,34 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
         (self.initial_sp.clone(), self.register_file.get("SP").expect("Could not get register SP").clone())
     }
 
+    fn named_variables(&self) -> impl Iterator<Item = (&str, &Self::Expression)> + '_ {
+        self.variables.iter().map(|(name, value)| (name.as_str(), value))
+    }
+
+    const LOGS_WRITES: bool = true;
+
+    fn start_write_log(&mut self) {
+        self.write_log = Some(WriteLog::default());
+    }
+
+    fn write_log(&self) -> Option<&WriteLog<Self::Expression>> {
+        self.write_log.as_ref()
+    }
+
     fn clear_named_variables(&mut self) {
         self.variables.clear();
     }
 
+    const SEEDS_SYMBOLS: bool = true;
+
+    fn seed_symbols(&mut self, symbols: Vec<(String, Self::Expression)>) {
+        self.seeded.extend(symbols);
+    }
+
+    fn unnamed_symbols(&self) -> usize {
+        self.unnamed_symbols
+    }
+
     fn program_memory(&self) -> &Self::ProgramMemory {
         &self.program_memory
     }
@@ -434,6 +525,258 @@ impl<State: UserStateContainer> SmtMap for BitwuzlaMemory<State> {
     fn get_registers(&mut self) -> HashMap<String, Self::Expression> {
         self.register_file.clone()
     }
+
+    fn set_poison_tracking(&mut self, enabled: bool) {
+        self.poison_tracking = enabled;
+    }
+
+    fn set_merge_tracking(&mut self, enabled: bool) {
+        self.journal = enabled.then(|| {
+            Rc::new(Journal {
+                written: Written::Start,
+                depth: 0,
+                previous: None,
+            })
+        });
+    }
+
+    fn is_poisoned(&self, expr: &Self::Expression) -> bool {
+        if !self.poison_tracking || self.poisoned.is_empty() || expr.get_constant().is_some() {
+            return false;
+        }
+        // Symbols are matched as whole tokens, so a symbol whose name merely
+        // starts with the name of a poisoned symbol is not mistaken for it.
+        format!("{expr:?}")
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .filter_map(poisoned_address)
+            .any(|address| self.poisoned.contains(&address))
+    }
+
+    /// Registers, flags and the bytes written at constant addresses since the
+    /// paths split are combined with `ite`. Writes to symbolic addresses and
+    /// floating point registers can not be combined, neither can memories
+    /// written since they split unless merge tracking is enabled.
+    fn merge(&self, other: &Self, condition: &Self::Expression) -> Option<Self> {
+        if self.pc != other.pc {
+            return None;
+        }
+        let written = if self.generation == other.generation {
+            BTreeSet::new()
+        } else {
+            self.written_since_split(other)?
+        };
+        let register_file = merge_values(&self.register_file, &other.register_file, condition)?;
+        let flags = merge_values(&self.flags, &other.flags, condition)?;
+
+        let mut merged = self.clone();
+        merged.register_file = register_file;
+        merged.flags = flags;
+        for address in written {
+            let value = condition.ite(&self.read_byte(address), &other.read_byte(address));
+            merged.write_byte(address, value);
+        }
+        merged.initialized.extend(other.initialized.iter().copied());
+        merged.poisoned.extend(other.poisoned.iter().copied());
+        for (name, value) in &other.variables {
+            merged.variables.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+        for (name, value) in &other.fp_variables {
+            merged.fp_variables.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+        for (name, value) in &other.seeded {
+            merged.seeded.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+        merged.cycles = self.cycles.max(other.cycles);
+        merged.unnamed_symbols = self.unnamed_symbols.max(other.unnamed_symbols);
+        Some(merged)
+    }
+}
+
+/// Selects between the values in `then_values` and `else_values` on
+/// `condition`.
+///
+/// Returns `None` unless both hold values of the same size for the same
+/// names.
+fn merge_values(then_values: &HashMap<String, BitwuzlaExpr>, else_values: &HashMap<String, BitwuzlaExpr>, condition: &BitwuzlaExpr) -> Option<HashMap<String, BitwuzlaExpr>> {
+    if then_values.len() != else_values.len() {
+        return None;
+    }
+    then_values
+        .iter()
+        .map(|(name, then_value)| {
+            let else_value = else_values.get(name).filter(|else_value| else_value.size() == then_value.size())?;
+            Some((name.clone(), condition.ite(then_value, else_value)))
+        })
+        .collect()
+}
+
+/// What a write recorded in a [`Journal`] changed.
+#[derive(Debug)]
+enum Written {
+    /// Nothing, merge tracking was enabled.
+    Start,
+    /// `bytes` bytes from the constant `address`.
+    Bytes { address: u64, bytes: u64 },
+    /// Memory at a symbolic address or a floating point register.
+    Untracked,
+}
+
+/// The writes made to a memory, newest first.
+///
+/// Forked paths share the writes made before they split, so the writes made
+/// since are the ones up to the first entry they have in common.
+#[derive(Debug)]
+struct Journal {
+    written: Written,
+    /// The number of entries before this one.
+    depth: usize,
+    previous: Option<Rc<Journal>>,
+}
+
+impl<State: UserStateContainer> BitwuzlaMemory<State> {
+    /// Marks memory or a floating point register as written.
+    fn next_generation(&mut self, written: Written) {
+        self.generation = GENERATIONS.fetch_add(1, Ordering::Relaxed);
+        if let Some(previous) = self.journal.take() {
+            self.journal = Some(Rc::new(Journal {
+                written,
+                depth: previous.depth + 1,
+                previous: Some(previous),
+            }));
+        }
+    }
+
+    /// Returns the addresses of the bytes written by either `self` or `other`
+    /// since they split.
+    ///
+    /// Returns `None` if merge tracking is disabled, a write log is active or
+    /// either has made a write that is not tracked, see [`Written`].
+    fn written_since_split(&self, other: &Self) -> Option<BTreeSet<u64>> {
+        if self.write_log.is_some() || other.write_log.is_some() {
+            return None;
+        }
+        let (mut this, mut that) = (self.journal.clone()?, other.journal.clone()?);
+        let mut written = BTreeSet::new();
+        while !Rc::ptr_eq(&this, &that) {
+            let newest = if this.depth >= that.depth { &mut this } else { &mut that };
+            match newest.written {
+                Written::Bytes { address, bytes } => written.extend(address..(address + bytes)),
+                // Memories that do not share a start did not split from the same path.
+                Written::Start | Written::Untracked => return None,
+            }
+            let previous = newest.previous.clone()?;
+            *newest = previous;
+        }
+        Some(written)
+    }
+
+    /// Replaces all bytes in `[address, address + size)` that have never been
+    /// written with fresh symbols that are tracked as poisoned.
+    ///
+    /// A byte that a write to a symbolic address may have initialized keeps
+    /// the written value whenever the write covers it.
+    fn poison_uninitialized(&mut self, idx: &BitwuzlaExpr, address: u64, size: u32) {
+        if size % BITS_IN_BYTE != 0 {
+            return;
+        }
+        for (offset, byte_address) in (address..(address + u64::from(size / BITS_IN_BYTE))).enumerate() {
+            if self.initialized.contains(&byte_address) {
+                continue;
+            }
+            trace!("Read from uninitialized memory at {byte_address:#x}");
+            let offset = self.from_u64(offset as u64, self.ram.ptr_size);
+            let byte = idx.add(&offset);
+            let mut value = BitwuzlaExpr(BV::new(self.ram.ctx.clone(), u64::from(BITS_IN_BYTE), Some(poisoned_name(byte_address).as_str())));
+            let covered = self
+                .symbolic_writes
+                .iter()
+                .map(|(write, bytes)| byte.sub(write).ult(&self.from_u64(*bytes, self.ram.ptr_size)))
+                .reduce(|covered, write_covers| covered.or(&write_covers));
+            if let Some(covered) = covered {
+                let current = self.ram.read(&byte, BITS_IN_BYTE).expect("Byte reads to succeed");
+                value = covered.ite(&current, &value);
+            }
+            self.next_generation(Written::Bytes { address: byte_address, bytes: 1 });
+            self.ram.write(&byte, value);
+            self.initialized.insert(byte_address);
+            self.poisoned.insert(byte_address);
+        }
+    }
+
+    /// Logs the value of every byte a write of `bytes` bytes at `idx` is about
+    /// to overwrite, or the write itself if the address is symbolic.
+    fn log_write(&mut self, idx: &BitwuzlaExpr, value: &BitwuzlaExpr, bytes: u64) {
+        let Some(log) = &mut self.write_log else {
+            return;
+        };
+        let Some(address) = idx.get_constant() else {
+            log.symbolic_writes.push((idx.clone(), value.clone()));
+            return;
+        };
+        let first_writes = (address..(address + bytes))
+            .filter(|byte_address| !log.previous.contains_key(byte_address))
+            .collect::<Vec<_>>();
+        for byte_address in first_writes {
+            let previous = self.read_byte(byte_address);
+            self.write_log.as_mut().expect("The log to be active").previous.insert(byte_address, previous);
+        }
+    }
+
+    /// Reads the byte at `address` without poisoning it.
+    fn read_byte(&self, address: u64) -> BitwuzlaExpr {
+        if self.program_memory.address_in_range(address) {
+            if let Ok(byte) = self.program_memory.get(address, BITS_IN_BYTE, &self.static_writes, &self.ram) {
+                return byte;
+            }
+        }
+        self.ram.read(&self.from_u64(address, self.ram.ptr_size), BITS_IN_BYTE).expect("Byte reads to succeed")
+    }
+
+    /// Writes the byte at `address`, bypassing poison tracking and the write
+    /// log.
+    fn write_byte(&mut self, address: u64, value: BitwuzlaExpr) {
+        self.next_generation(Written::Bytes { address, bytes: 1 });
+        if self.program_memory.address_in_range(address) {
+            let _ = self.program_memory.set(address, value, &mut self.static_writes, &mut self.ram);
+            return;
+        }
+        let idx = self.from_u64(address, self.ram.ptr_size);
+        self.ram.write(&idx, value);
+    }
+
+    /// Records a write to the symbolic address `idx` for poison tracking.
+    ///
+    /// Past [`MAX_SYMBOLIC_POISON_WRITES`] writes, poison tracking is disabled
+    /// as nearly any byte may have been initialized.
+    fn track_symbolic_write(&mut self, idx: &BitwuzlaExpr, bytes: u64) {
+        if self.symbolic_writes.len() == MAX_SYMBOLIC_POISON_WRITES {
+            warn!("Too many writes to symbolic addresses, no longer tracking reads from uninitialized memory");
+            self.poison_tracking = false;
+            self.symbolic_writes.clear();
+            self.initialized.clear();
+            return;
+        }
+        self.symbolic_writes.push((idx.clone(), bytes));
+    }
+}
+
+/// The number of writes to symbolic addresses a path may make before reads
+/// from uninitialized memory are no longer tracked.
+const MAX_SYMBOLIC_POISON_WRITES: usize = 32;
+
+/// The name of the poisoned symbol for the byte at `address`.
+fn poisoned_name(address: u64) -> String {
+    format!("uninitialized_{address:016x}")
+}
+
+/// The address of the byte a poisoned symbol was created for, if `symbol` is
+/// the name of one, see [`poisoned_name`].
+fn poisoned_address(symbol: &str) -> Option<u64> {
+    let address = symbol.strip_prefix("uninitialized_")?;
+    if address.len() != 16 {
+        return None;
+    }
+    u64::from_str_radix(address, 16).ok()
 }
 
 impl<State: UserStateContainer> Display for BitwuzlaMemory<State> {
@@ -477,3 +820,121 @@ fn strip(s: String) -> String {
     }
     s
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use hashbrown::HashMap;
+
+    use super::{ArrayMemory, BitwuzlaMemory};
+    use crate::{
+        arch::{arm::v6::ArmV6M, Architecture, NoArchitectureOverride, SupportedArchitecture},
+        project::Project,
+        smt::{bitwuzla::Bitwuzla, SmtExpr, SmtMap, SmtSolver},
+        Endianness,
+        WordSize,
+    };
+
+    fn setup_program_memory(data: Vec<u8>, endianness: Endianness) -> (Bitwuzla, BitwuzlaMemory<()>) {
+        let smt = Bitwuzla::new();
+        let end = 0x1000 + data.len() as u64;
+        let project = Arc::new(Project::manual_project(data, 0x1000, end, WordSize::Bit32, endianness, HashMap::new()));
+        let architecture = SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new());
+        let initial_sp = smt.from_u64(0, 32);
+        let memory = BitwuzlaMemory::new(smt.clone(), project, endianness, initial_sp, &(), &architecture).unwrap();
+        (smt, memory)
+    }
+
+    #[test]
+    fn test_big_endian_ram() {
+        let smt = Bitwuzla::new();
+        let mut memory = ArrayMemory::new(smt.ctx.clone(), 32, Endianness::Big);
+        let addr = smt.from_u64(0x100, 32);
+        memory.write(&addr, smt.from_u64(0x0102_0304, 32));
+
+        for (offset, expected) in [0x01, 0x02, 0x03, 0x04].into_iter().enumerate() {
+            let byte = memory.read(&smt.from_u64(0x100 + offset as u64, 32), 8).unwrap();
+            assert_eq!(byte.get_constant().unwrap(), expected);
+        }
+        assert_eq!(memory.read(&addr, 32).unwrap().get_constant().unwrap(), 0x0102_0304);
+    }
+
+    #[test]
+    fn test_symbolic_address_word_access() {
+        let smt = Bitwuzla::new();
+        let mut memory = ArrayMemory::new(smt.ctx.clone(), 32, Endianness::Little);
+        let addr = smt.unconstrained(32, "addr");
+        let value = smt.unconstrained(32, "value");
+        memory.write(&addr, value.clone());
+
+        // The word reads back at the same address and the bytes are laid out
+        // least significant byte first, whatever the address is.
+        let read = memory.read(&addr, 32).unwrap();
+        assert!(!smt.is_sat_with_constraint(&read._ne(&value)).unwrap());
+        let offset = smt.from_u64(3, 32);
+        let byte = memory.read(&addr.add(&offset), 8).unwrap();
+        assert!(!smt.is_sat_with_constraint(&byte._ne(&value.slice(24, 31))).unwrap());
+    }
+
+    #[test]
+    fn test_poisoned_symbols_match_exactly() {
+        let (smt, mut memory) = setup_program_memory(vec![0; 4], Endianness::Little);
+        memory.set_poison_tracking(true);
+        let uninitialized = memory.get(&smt.from_u64(0x2000, 32), 8).ok().unwrap();
+        assert!(memory.is_poisoned(&uninitialized));
+
+        // A symbol that only shares a prefix with a poisoned symbol is not
+        // poisoned.
+        let similar = smt.unconstrained(8, "uninitialized_0000000000002000_copy");
+        assert!(!memory.is_poisoned(&similar));
+    }
+
+    #[test]
+    fn test_poisoning_keeps_symbolic_writes() {
+        let (smt, mut memory) = setup_program_memory(vec![0; 4], Endianness::Little);
+        memory.set_poison_tracking(true);
+        let addr = smt.unconstrained(32, "addr");
+        let value = smt.unconstrained(8, "value");
+        memory.set(&addr, value.clone()).unwrap();
+
+        // The byte may have been written through the symbolic address.
+        let read = memory.get(&smt.from_u64(0x2000, 32), 8).ok().unwrap();
+        assert!(memory.is_poisoned(&read));
+        let at_byte = addr._eq(&smt.from_u64(0x2000, 32));
+        assert!(!smt.is_sat_with_constraints(&[at_byte, read._ne(&value)]).unwrap());
+    }
+
+    #[test]
+    fn test_big_endian_program_memory() {
+        let (smt, mut memory) = setup_program_memory(vec![0x01, 0x02, 0x03, 0x04, 0, 0, 0, 0], Endianness::Big);
+
+        // Words in the image are assembled most significant byte first.
+        let addr = smt.from_u64(0x1000, 32);
+        assert_eq!(memory.get(&addr, 32).ok().unwrap().get_constant().unwrap(), 0x0102_0304);
+
+        let addr = smt.from_u64(0x1004, 32);
+        memory.set(&addr, smt.from_u64(0x0506_0708, 32)).unwrap();
+        assert_eq!(memory.get(&addr, 32).ok().unwrap().get_constant().unwrap(), 0x0506_0708);
+        for (offset, expected) in [0x05, 0x06, 0x07, 0x08].into_iter().enumerate() {
+            let byte = memory.get(&smt.from_u64(0x1004 + offset as u64, 32), 8).ok().unwrap();
+            assert_eq!(byte.get_constant().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_little_endian_program_memory() {
+        let (smt, mut memory) = setup_program_memory(vec![0x04, 0x03, 0x02, 0x01, 0, 0, 0, 0], Endianness::Little);
+
+        let addr = smt.from_u64(0x1000, 32);
+        assert_eq!(memory.get(&addr, 32).ok().unwrap().get_constant().unwrap(), 0x0102_0304);
+
+        let addr = smt.from_u64(0x1004, 32);
+        memory.set(&addr, smt.from_u64(0x0506_0708, 32)).unwrap();
+        assert_eq!(memory.get(&addr, 32).ok().unwrap().get_constant().unwrap(), 0x0506_0708);
+        for (offset, expected) in [0x08, 0x07, 0x06, 0x05].into_iter().enumerate() {
+            let byte = memory.get(&smt.from_u64(0x1004 + offset as u64, 32), 8).ok().unwrap();
+            assert_eq!(byte.get_constant().unwrap(), expected);
+        }
+    }
+}
@@ -1,31 +1,97 @@
 #![allow(clippy::used_underscore_items)]
-use std::{ffi::CStr, rc::Rc};
+use std::{cell::RefCell, ffi::CStr, rc::Rc, time::Instant};
 
 use bitwuzla::{option::ModelGen, SolverResult, BV};
 
+mod cache;
 pub mod expr;
 pub mod fpexpr;
 pub mod memory;
 
+use cache::SatCache;
 use expr::BitwuzlaExpr;
 
 // Re-exports.
-use super::{SmtExpr, SmtSolver, Solutions, SolverError};
+use super::{RewriteLevel, SmtExpr, SmtSolver, Solutions, SolverConfig, SolverError, Statistics};
 use crate::warn;
 
 #[derive(Clone, Debug)]
 pub struct Bitwuzla {
     pub ctx: Rc<bitwuzla::Bitwuzla>,
+    profile: Rc<RefCell<Profile>>,
+    cache: Rc<RefCell<SatCache>>,
 }
 
+/// Profiling data shared between all clones of a solver.
+#[derive(Debug)]
+struct Profile {
+    statistics: Statistics,
+    /// Number of constraints asserted in each pushed frame.
+    frames: Vec<usize>,
+    /// Internal error reported while asserting, pushing or popping.
+    ///
+    /// The solver no longer holds the expected constraints, so every later
+    /// check fails with this error.
+    failure: Option<String>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            statistics: Statistics::default(),
+            frames: vec![0],
+            failure: None,
+        }
+    }
+}
+
+thread_local! {
+    /// The first internal error reported by Bitwuzla on this thread that has
+    /// not yet been returned by [`checked`].
+    static INTERNAL_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Called by Bitwuzla when it fails internally.
+///
+/// Unwinding out of the callback would abort the process, instead the error
+/// is recorded and Bitwuzla returns a default value from the failing call once
+/// the callback returns. Solver calls are wrapped in [`checked`] which turns
+/// the recorded error into a [`SolverError::Internal`] for that call.
 unsafe extern "C" fn abort_callback(data: *const std::os::raw::c_char) {
     let data = unsafe { CStr::from_ptr(data) };
-    match data.to_str() {
-        Ok(val) => eprintln!("Bitwuzla failed internally with message {val}"),
-        Err(_) => eprintln!("Bitwuzla failed internally with invalid cstring message"),
-    }
+    let message = match data.to_str() {
+        Ok(val) => val.to_owned(),
+        Err(_) => "invalid cstring message".to_owned(),
+    };
+    record_internal_error(message);
+}
 
-    panic!("Bitwuzla internal error");
+/// Records an internal solver error, keeping the first one if several are
+/// reported before they are returned.
+fn record_internal_error(message: String) {
+    INTERNAL_ERROR.with_borrow_mut(|error| {
+        error.get_or_insert(message);
+    });
+}
+
+/// Takes the internal solver error recorded on this thread, if any.
+fn take_internal_error() -> Option<String> {
+    INTERNAL_ERROR.with_borrow_mut(Option::take)
+}
+
+/// Runs a single Bitwuzla call and returns the internal error it reported.
+///
+/// Errors recorded before the call come from building terms, which cannot
+/// fail, and are returned here as the call is the first to use those terms.
+fn checked<R, F: FnOnce() -> R>(call: F) -> Result<R, SolverError> {
+    if let Some(message) = take_internal_error() {
+        return Err(SolverError::Internal(message));
+    }
+    let result = call();
+    match take_internal_error() {
+        Some(message) => Err(SolverError::Internal(message)),
+        None => Ok(result),
+    }
 }
 
 impl super::Lambda for bitwuzla::lambda::Lambda<Rc<bitwuzla::Bitwuzla>, 1> {
@@ -91,14 +157,28 @@ impl SmtSolver for Bitwuzla {
     }
 
     fn new() -> Self {
+        Self::with_config(&SolverConfig::default())
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn with_config(config: &SolverConfig) -> Self {
+        let rewrite_level = match config.rewrite_level {
+            RewriteLevel::None => bitwuzla::option::RewriteLevel::None,
+            RewriteLevel::Term => bitwuzla::option::RewriteLevel::More,
+            RewriteLevel::Full => bitwuzla::option::RewriteLevel::Full,
+        };
         let solver = bitwuzla::Bitwuzla::builder()
-            .n_threads(24)
-            .rewrite_level(bitwuzla::option::RewriteLevel::None)
+            .n_threads(config.threads.max(1) as _)
+            .rewrite_level(rewrite_level)
             .model_gen(ModelGen::Disabled)
             .set_abort_callback(abort_callback)
             .incremental(true)
             .build();
-        Self { ctx: Rc::new(solver) }
+        Self {
+            ctx: Rc::new(solver),
+            profile: Rc::new(RefCell::new(Profile::default())),
+            cache: Rc::new(RefCell::new(SatCache::new(config.cache_size))),
+        }
     }
 
     fn one(&self, bits: u32) -> Self::Expression {
@@ -166,6 +246,10 @@ impl SmtSolver for Bitwuzla {
         self._get_solutions(expr, upper_bound)
     }
 
+    fn statistics(&self) -> Statistics {
+        self.profile.borrow().statistics
+    }
+
     fn unconstrained_fp(&self, ty: general_assembly::extension::ieee754::OperandType, name: &str) -> Self::FpExpression {
         fpexpr::FpExpr::unconstrained(self.ctx.clone(), &ty, Some(name))
     }
@@ -195,7 +279,7 @@ impl Bitwuzla {
         let result = || {
             if self.is_sat()? {
                 self.is_sat()?;
-                let solution = expr.0.get_a_solution().disambiguate();
+                let solution = checked(|| expr.0.get_a_solution())?.disambiguate();
                 let solution = solution.as_01x_str();
 
                 let solution = BitwuzlaExpr(BV::from_binary_str(self.ctx.clone(), &solution));
@@ -209,12 +293,50 @@ impl Bitwuzla {
         //self.ctx.set_opt(BtorOption::ModelGen(ModelGen::Disabled));
     }
 
+    /// Records an internal error of a call that leaves the solver state
+    /// unknown, failing every later check.
+    fn record_failure(&self, result: Result<(), SolverError>) {
+        if let Err(SolverError::Internal(message)) = result {
+            self.profile.borrow_mut().failure.get_or_insert(message);
+        }
+    }
+
     pub fn _push(&self) {
-        self.ctx.push(1);
+        self.record_failure(checked(|| self.ctx.push(1)));
+        self.profile.borrow_mut().frames.push(0);
+        self.cache.borrow_mut().push();
     }
 
     pub fn _pop(&self) {
-        self.ctx.pop(1);
+        self.record_failure(checked(|| self.ctx.pop(1)));
+        let mut profile = self.profile.borrow_mut();
+        if profile.frames.len() > 1 {
+            profile.frames.pop();
+        }
+        self.cache.borrow_mut().pop();
+    }
+
+    /// Runs a satisfiability check with `assumptions` additional constraints,
+    /// recording it in the solver [`Statistics`].
+    ///
+    /// Returns [`SolverError::Internal`] if the check failed internally or if
+    /// an earlier assert, push or pop did.
+    fn profiled_sat<F: FnOnce() -> SolverResult>(&self, assumptions: usize, check: F) -> Result<SolverResult, SolverError> {
+        if let Some(message) = &self.profile.borrow().failure {
+            return Err(SolverError::Internal(message.clone()));
+        }
+
+        let start = Instant::now();
+        let result = checked(check);
+        let elapsed = start.elapsed();
+
+        let mut profile = self.profile.borrow_mut();
+        let formula_size = profile.frames.iter().sum::<usize>() + assumptions;
+        let statistics = &mut profile.statistics;
+        statistics.sat_calls += 1;
+        statistics.solver_time += elapsed;
+        statistics.max_formula_size = statistics.max_formula_size.max(formula_size);
+        result
     }
 
     /// Solve for the current solver state, and returns if the result is
@@ -224,17 +346,47 @@ impl Bitwuzla {
     /// Returns true or false, and [`SolverError::Unknown`] if the result
     /// cannot be determined.
     pub fn _is_sat(&self) -> Result<bool, SolverError> {
-        Ok(match self.ctx.sat() {
+        Ok(match self.profiled_sat(0, || self.ctx.sat())? {
             SolverResult::Sat => true,
             SolverResult::Unsat => false,
             SolverResult::Unknown => false,
         })
     }
 
+    /// Runs a satisfiability check under `assumptions`, reusing the result of
+    /// an identical earlier check if the same constraints are asserted.
+    ///
+    /// Checks without assumptions are never cached as the caller may query
+    /// the model afterwards.
+    fn cached_sat(&self, assumptions: &[BitwuzlaExpr]) -> Result<SolverResult, SolverError> {
+        let check = || {
+            let assumptions = assumptions.iter().map(|assumption| assumption.0.clone()).collect::<Vec<_>>();
+            self.profiled_sat(assumptions.len(), || self.ctx.check_sat_assuming(&assumptions))
+        };
+        if !self.cache.borrow().is_enabled() {
+            return check();
+        }
+
+        let query = self.cache.borrow().query(assumptions);
+        if let Some(result) = self.cache.borrow_mut().get(&query) {
+            self.profile.borrow_mut().statistics.cache_hits += 1;
+            return Ok(if result { SolverResult::Sat } else { SolverResult::Unsat });
+        }
+        self.profile.borrow_mut().statistics.cache_misses += 1;
+
+        let result = check()?;
+        match result {
+            SolverResult::Sat => self.cache.borrow_mut().insert(&query, true),
+            SolverResult::Unsat => self.cache.borrow_mut().insert(&query, false),
+            SolverResult::Unknown => {}
+        }
+        Ok(result)
+    }
+
     /// Solve for the solver state with the assumption of the passed constraint.
     pub fn _is_sat_with_constraint(&self, constraint: &BitwuzlaExpr) -> Result<bool, SolverError> {
         // Assume the constraint, will be forgotten after the next call to `is_sat`.
-        Ok(match self.ctx.check_sat_assuming(&[constraint.0.clone()]) {
+        Ok(match self.cached_sat(std::slice::from_ref(constraint))? {
             SolverResult::Sat => true,
             SolverResult::Unsat => false,
             SolverResult::Unknown => false,
@@ -244,12 +396,7 @@ impl Bitwuzla {
     /// Solve for the solver state with the assumption of the passed
     /// constraints.
     pub fn _is_sat_with_constraints(&self, constraints: &[BitwuzlaExpr]) -> Result<bool, SolverError> {
-        let mut constraints_new = Vec::with_capacity(constraints.len());
-        for constraint in constraints {
-            constraints_new.push(constraint.0.clone());
-        }
-
-        Ok(match self.ctx.check_sat_assuming(&constraints_new) {
+        Ok(match self.cached_sat(constraints)? {
             SolverResult::Sat => true,
             SolverResult::Unsat => false,
             SolverResult::Unknown => false,
@@ -262,7 +409,12 @@ impl Bitwuzla {
     /// The passed constraint will be implicitly combined with the current state
     /// in a boolean `and`. Asserted constraints cannot be removed.
     pub fn _assert(&self, constraint: &BitwuzlaExpr) {
-        BitwuzlaExpr::_ne(constraint, &self.from_u64(0, constraint.size())).0.assert();
+        let constraint_is_true = BitwuzlaExpr::_ne(constraint, &self.from_u64(0, constraint.size()));
+        self.record_failure(checked(|| constraint_is_true.0.assert()));
+        if let Some(frame) = self.profile.borrow_mut().frames.last_mut() {
+            *frame += 1;
+        }
+        self.cache.borrow_mut().assert(constraint);
     }
 
     /// Find solutions to `expr`.
@@ -325,7 +477,7 @@ impl Bitwuzla {
 
         let result = || {
             while solutions.len() < upper_bound as usize && self.is_sat()? {
-                let solution = expr.0.get_a_solution().disambiguate();
+                let solution = checked(|| expr.0.get_a_solution())?.disambiguate();
                 let solution = solution.as_01x_str();
                 let solution = BitwuzlaExpr(BV::from_binary_str(self.ctx.clone(), &solution));
 
@@ -427,12 +579,98 @@ impl Bitwuzla {
 
 #[cfg(test)]
 mod test_smt_expr {
-    use crate::smt::{bitwuzla::Bitwuzla, SmtExpr, SmtSolver};
+    use general_assembly::shift::Shift;
+
+    use crate::smt::{bitwuzla::Bitwuzla, SmtExpr, SmtSolver, SolverConfig};
 
     fn smt() -> Bitwuzla {
         Bitwuzla::new()
     }
 
+    #[test]
+    fn test_statistics() {
+        let smt = smt();
+        assert_eq!(smt.statistics().sat_calls, 0);
+
+        let a = smt.unconstrained(32, "a");
+        smt.assert(&a.ult(&smt.from_u64(3, 32)));
+        smt.push();
+        smt.assert(&a.ugt(&smt.from_u64(0, 32)));
+        assert!(smt.is_sat().unwrap());
+        smt.pop();
+        assert!(smt.is_sat_with_constraint(&a._eq(&smt.from_u64(1, 32))).unwrap());
+
+        // Statistics are shared between clones of the solver.
+        let statistics = smt.clone().statistics();
+        assert_eq!(statistics.sat_calls, 2);
+        assert_eq!(statistics.max_formula_size, 2);
+    }
+
+    #[test]
+    fn test_cached_sat() {
+        let smt = Bitwuzla::with_config(&SolverConfig {
+            cache_size: 16,
+            ..SolverConfig::single_threaded()
+        });
+        let a = smt.unconstrained(32, "a");
+        smt.assert(&a.ult(&smt.from_u64(3, 32)));
+        let query = a._eq(&smt.from_u64(1, 32));
+
+        assert!(smt.is_sat_with_constraint(&query).unwrap());
+        assert!(smt.is_sat_with_constraint(&query).unwrap());
+        let statistics = smt.statistics();
+        assert_eq!(statistics.sat_calls, 1);
+        assert_eq!((statistics.cache_hits, statistics.cache_misses), (1, 1));
+
+        // Asserting a constraint invalidates the result.
+        smt.push();
+        smt.assert(&a.ugt(&smt.from_u64(1, 32)));
+        assert!(!smt.is_sat_with_constraint(&query).unwrap());
+        assert_eq!(smt.statistics().sat_calls, 2);
+
+        // Popping the constraint makes the first result valid again.
+        smt.pop();
+        assert!(smt.is_sat_with_constraint(&query).unwrap());
+        let statistics = smt.statistics();
+        assert_eq!(statistics.sat_calls, 2);
+        assert_eq!((statistics.cache_hits, statistics.cache_misses), (2, 2));
+        assert!((statistics.cache_hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_simplify_keeps_constants() {
+        let smt = smt();
+        let a = smt.unconstrained(32, "a");
+        let one = smt.from_u64(1, 32);
+
+        assert_eq!(one.clone().simplify().get_constant(), Some(1));
+        // (a + 1) - a
+        let value = a.add(&one).sub(&a).simplify();
+        assert_eq!(value.get_constant(), Some(1));
+        assert_eq!(a.clone().simplify().get_constant(), None);
+    }
+
+    #[test]
+    fn test_solutions_iter() {
+        let smt = smt();
+        let a = smt.unconstrained(32, "a");
+        smt.assert(&a.ult(&smt.from_u64(3, 32)));
+
+        let mut solutions = smt.solutions_iter(&a, 10).map(|sol| sol.unwrap().get_constant().unwrap()).collect::<Vec<_>>();
+        solutions.sort_unstable();
+        assert_eq!(solutions, vec![0, 1, 2]);
+
+        // Only the requested number of solutions are produced.
+        assert_eq!(smt.solutions_iter(&a, 2).count(), 2);
+
+        // The blocking constraints are removed once the iterator is dropped.
+        assert!(smt.is_sat_with_constraint(&a._eq(&smt.from_u64(1, 32))).unwrap());
+
+        let c = smt.from_u64(7, 32);
+        let solutions = smt.solutions_iter(&c, 10).map(|sol| sol.unwrap().get_constant().unwrap()).collect::<Vec<_>>();
+        assert_eq!(solutions, vec![7]);
+    }
+
     #[test]
     fn test_add() {
         let smt = smt();
@@ -476,6 +714,59 @@ mod test_smt_expr {
         assert!(a.sub(&b).get_constant() == Some(2));
     }
 
+    #[test]
+    fn test_concat() {
+        let smt = smt();
+        let high = smt.from_u64(0xab, 8);
+        let low = smt.from_u64(0x1234, 16);
+        let ret = high.concat(&low);
+        assert_eq!(ret.size(), 24);
+        assert_eq!(ret.get_constant(), Some(0xab_1234));
+    }
+
+    #[test]
+    fn test_extract() {
+        let smt = smt();
+        let a = smt.from_u64(0xdead_beef, 32);
+        let ret = a.extract(15, 8);
+        assert_eq!(ret.size(), 8);
+        assert_eq!(ret.get_constant(), Some(0xbe));
+
+        // Splitting a value and concatenating the parts restores it.
+        let a = smt.unconstrained(32, "a");
+        let restored = a.extract(31, 16).concat(&a.extract(15, 0));
+        assert_eq!(restored.size(), 32);
+        assert!(!smt.is_sat_with_constraint(&restored._ne(&a)).unwrap());
+    }
+
+    #[test]
+    fn test_min_max() {
+        let smt = smt();
+        let one = smt.from_u64(1, 32);
+        let two = smt.from_u64(2, 32);
+        assert_eq!(one.umax(&two).get_constant(), Some(2));
+        assert_eq!(one.umin(&two).get_constant(), Some(1));
+        assert_eq!(one.smax(&two).get_constant(), Some(2));
+        assert_eq!(one.smin(&two).get_constant(), Some(1));
+
+        // Ties.
+        assert_eq!(two.umax(&two).get_constant(), Some(2));
+        assert_eq!(two.smin(&two).get_constant(), Some(2));
+
+        // The most significant bit makes the value large when unsigned but
+        // negative when signed.
+        let smax = smt.signed_max(32);
+        let smin = smt.signed_min(32);
+        let umax = smt.unsigned_max(32);
+        assert_eq!(smax.umax(&smin).get_constant(), Some(i32::MIN as u32 as u64));
+        assert_eq!(smax.umin(&smin).get_constant(), Some(i32::MAX as u64));
+        assert_eq!(smax.smax(&smin).get_constant(), Some(i32::MAX as u64));
+        assert_eq!(smax.smin(&smin).get_constant(), Some(i32::MIN as u32 as u64));
+        assert_eq!(umax.umax(&one).get_constant(), Some(u32::MAX as u64));
+        assert_eq!(umax.smax(&one).get_constant(), Some(1));
+        assert_eq!(umax.smin(&smin).get_constant(), Some(i32::MIN as u32 as u64));
+    }
+
     #[test]
     fn test_len() {
         let smt = smt();
@@ -619,6 +910,25 @@ mod test_smt_expr {
         let b = smt.from_u64(0b1010_1101_0011, 32);
         assert!(SmtExpr::ugte(&a, &b).get_constant() == Some(1));
     }
+
+    #[test]
+    fn test_rotate() {
+        let smt = smt();
+        let word_size = smt.from_u64(32, 32);
+        for value in [0u32, 1, 0x8000_0001, 0xdead_beef, u32::MAX] {
+            let a = smt.from_u64(value as u64, 32);
+            for steps in 0..=33u32 {
+                let n = smt.from_u64(steps as u64, 32);
+                assert_eq!(a.rotate_right(&n).get_constant(), Some(value.rotate_right(steps) as u64));
+                assert_eq!(a.rotate_left(&n).get_constant(), Some(value.rotate_left(steps) as u64));
+
+                // Shift and or based rotation.
+                let n = n.srem(&word_size);
+                let composed = a.shift(&n, Shift::Lsr).or(&a.shift(&word_size.sub(&n), Shift::Lsl));
+                assert_eq!(a.rotate_right(&n).get_constant(), composed.get_constant());
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -634,31 +944,42 @@ mod test {
         operation::Operation,
     };
     use hashbrown::HashMap;
+    use transpiler::pseudo;
 
     use crate::{
         arch::{arm::v6::ArmV6M, Architecture, NoArchitectureOverride},
-        defaults::bitwuzla::{DefaultComposition, DefaultCompositionNoLogger},
+        defaults::{
+            bitwuzla::{DefaultComposition, DefaultCompositionNoLogger},
+            logger::SimplePathLogger,
+        },
         executor::{
             add_with_carry,
-            hooks::HookContainer,
+            hooks::{HookContainer, LangagueHooks},
             instruction::{CycleCount, Instruction},
+            memory_interface::MemoryBucketingFilter,
             state::GAState,
             util::UtilityCloures,
             vm::VM,
             GAExecutor,
         },
+        initiation::test::raw_executor,
         logging::NoLogger,
-        path_selection::PathSelector,
-        project::Project,
+        path_selection::{DFSPathSelection, PathSelector},
+        project::{dwarf_helper::SubProgramMap, Project},
         smt::{
-            bitwuzla::{Bitwuzla, BitwuzlaExpr},
+            bitwuzla::{fpexpr::FpExpr, memory::BitwuzlaMemory, Bitwuzla, BitwuzlaExpr},
             Lambda,
             ProgramMemory,
+            RewriteLevel,
             SmtExpr,
             SmtFPExpr,
             SmtMap,
             SmtSolver,
+            Solutions,
+            SolverConfig,
+            SolverError,
         },
+        Composition,
         Endianness,
         WordSize,
     };
@@ -667,7 +988,6 @@ mod test {
     fn test_count_ones_concrete() {
         let ctx = Bitwuzla::new();
         let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
-        let word_size = project.get_word_size();
         let state = GAState::<DefaultComposition>::create_test_state(
             project,
             ctx.clone(),
@@ -678,15 +998,14 @@ mod test {
             (),
             crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
         );
-        let util = UtilityCloures::new(&state, word_size);
         let num1 = state.memory.from_u64(1, 32);
         let num32 = state.memory.from_u64(32, 32);
         let numff = state.memory.from_u64(0xff, 32);
-        let result: BitwuzlaExpr = Lambda::apply(&util.count_ones, num1);
+        let result: BitwuzlaExpr = num1.popcount();
         assert_eq!(result.get_constant().unwrap(), 1);
-        let result: BitwuzlaExpr = Lambda::apply(&util.count_ones, num32);
+        let result: BitwuzlaExpr = num32.popcount();
         assert_eq!(result.get_constant().unwrap(), 1);
-        let result: BitwuzlaExpr = Lambda::apply(&util.count_ones, numff);
+        let result: BitwuzlaExpr = numff.popcount();
         assert_eq!(result.get_constant().unwrap(), 8);
     }
 
@@ -694,7 +1013,6 @@ mod test {
     fn test_count_ones_symbolic() {
         let ctx = Bitwuzla::new();
         let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
-        let word_size = project.get_word_size();
         let state = GAState::<DefaultComposition>::create_test_state(
             project,
             ctx.clone(),
@@ -705,12 +1023,11 @@ mod test {
             (),
             crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
         );
-        let util = UtilityCloures::new(&state, word_size);
         let any_u32 = ctx.unconstrained(32, "any1");
         let num_0x100 = ctx.from_u64(0x100, 32);
         let num_8 = ctx.from_u64(8, 32);
         ctx.assert(&any_u32.ult(&num_0x100));
-        let result = Lambda::apply(&util.count_ones, any_u32);
+        let result = any_u32.popcount();
         let result_below_or_equal_8 = result.ulte(&num_8);
         let result_above_8 = result.ugt(&num_8);
         let can_be_below_or_equal_8 = ctx.is_sat_with_constraint(&result_below_or_equal_8).unwrap();
@@ -723,7 +1040,6 @@ mod test {
     fn test_count_zeroes_concrete() {
         let ctx = Bitwuzla::new();
         let project = Arc::new(Project::manual_project(vec![], 0, 0, WordSize::Bit32, Endianness::Little, HashMap::new()));
-        let word_size = project.get_word_size();
         let state = GAState::<DefaultComposition>::create_test_state(
             project,
             ctx.clone(),
@@ -734,15 +1050,14 @@ mod test {
             (),
             crate::arch::SupportedArchitecture::Armv6M(<ArmV6M as Architecture<NoArchitectureOverride>>::new()),
         );
-        let util = UtilityCloures::new(&state, word_size);
         let num1 = state.memory.from_u64(!1, 32);
         let num32 = state.memory.from_u64(!32, 32);
         let numff = state.memory.from_u64(!0xff, 32);
-        let result = Lambda::apply(&util.count_zeroes, num1);
+        let result = num1.not().popcount();
         assert_eq!(result.get_constant().unwrap(), 1);
-        let result = Lambda::apply(&util.count_zeroes, num32);
+        let result = num32.not().popcount();
         assert_eq!(result.get_constant().unwrap(), 1);
-        let result = Lambda::apply(&util.count_zeroes, numff);
+        let result = numff.not().popcount();
         assert_eq!(result.get_constant().unwrap(), 8);
     }
 
@@ -892,7 +1207,7 @@ mod test {
     fn test_fp_div_un_even_ties_to_even_explicit() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -1007,7 +1322,7 @@ mod test {
     fn test_fp_div_un_even_ties_to_even_system_level() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -1119,12 +1434,101 @@ mod test {
         assert_eq!(r0, 2);
     }
 
+    #[test]
+    fn test_fp_div_literals_pseudo() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let r0 = Operand::Register("R0".to_owned());
+        let r1 = Operand::Register("R1".to_owned());
+
+        let operations = pseudo!([
+            r0:u32;
+            r1:u32;
+            let lower:f32 = 3.0f32 / 2.0f32;
+            r0 = Resize(lower, u32, ToEven);
+            let upper:f32 = 5.0f32 / 2.0f32;
+            r1 = Resize(upper, u32, ToEven);
+        ]);
+        for operation in &operations {
+            executor.execute_operation(operation, &mut NoLogger).unwrap();
+        }
+
+        let r0 = executor.get_operand_value(&r0, &NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(r0, 2);
+        let r1 = executor.get_operand_value(&r1, &NoLogger).unwrap().get_constant().unwrap();
+        assert_eq!(r1, 2);
+    }
+
+    /// Rounds ties to even where the default composition rounds towards zero.
+    #[derive(Clone, Debug)]
+    struct TiesToEvenComposition {}
+
+    impl Composition for TiesToEvenComposition {
+        type ArchitectureOverride = NoArchitectureOverride;
+        type Logger = SimplePathLogger;
+        type Memory = BitwuzlaMemory<()>;
+        type MemoryFilter = MemoryBucketingFilter<Self>;
+        type PathPayload = ();
+        type PathSelector = DFSPathSelection<Self>;
+        type ProgramMemory = std::sync::Arc<Project<Self::SMT>>;
+        type SMT = Bitwuzla;
+        type SmtExpression = BitwuzlaExpr;
+        type SmtFPExpression = FpExpr;
+        type StateContainer = ();
+
+        const ROUNDING_MODE: RoundingMode = RoundingMode::TiesToEven;
+    }
+
+    /// Computes `7.0 / 2.0` without requesting a rounding mode on an executor
+    /// set up for the composition `C`.
+    ///
+    /// Returns the rounding mode the executor uses by default and the result
+    /// converted to an integer.
+    fn default_rounding<C>() -> (RoundingMode, u64)
+    where
+        C: Composition<SMT = Bitwuzla, ArchitectureOverride = NoArchitectureOverride, Logger = SimplePathLogger, StateContainer = ()>,
+        C::Memory: SmtMap<ProgramMemory = std::sync::Arc<Project<Bitwuzla>>>,
+    {
+        // bx lr
+        let mut arbiter = raw_executor::<C>(&[0x70, 0x47], &[]);
+        let mut runner = arbiter.run_from_pc(0x1000, &LangagueHooks::None).unwrap();
+        let mut stepper = runner.stepper().unwrap().expect("A path to step through");
+        let executor = stepper.executor();
+        let mut logger = SimplePathLogger::from_sub_programs(&SubProgramMap::default());
+        let r0 = Operand::Register("R0".to_owned());
+
+        let operations = pseudo!([
+            r0:u32;
+            let value:f32 = 7.0f32 / 2.0f32;
+            r0 = Resize(value, u32);
+        ]);
+        for operation in &operations {
+            executor.execute_operation(operation, &mut logger).unwrap();
+        }
+        let result = executor.get_operand_value(&r0, &logger).unwrap().get_constant().unwrap();
+
+        assert!(executor.state.fp_state.set_rounding_mode(RoundingMode::Exact).is_err());
+        (executor.state.fp_state.rounding_mode.clone(), result)
+    }
+
+    #[test]
+    fn test_fp_default_rounding_mode() {
+        let (rounding_mode, result) = default_rounding::<DefaultComposition>();
+        assert!(matches!(rounding_mode, RoundingMode::TiesTowardZero));
+        assert_eq!(result, 3);
+
+        let (rounding_mode, result) = default_rounding::<TiesToEvenComposition>();
+        assert!(matches!(rounding_mode, RoundingMode::TiesToEven));
+        assert_eq!(result, 4);
+    }
+
     #[test]
     #[allow(clippy::too_many_lines)]
     fn test_fp_div_mul() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -1295,7 +1699,7 @@ mod test {
     fn test_fp_non_computational() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -1456,12 +1860,234 @@ mod test {
         assert_eq!(r0, 0);
     }
 
+    fn fp_register(id: &str) -> ieee754::Operand {
+        ieee754::Operand {
+            ty: ieee754::OperandType::Binary32,
+            value: ieee754::OperandStorage::Register {
+                id: id.to_owned(),
+                ty: ieee754::OperandType::Binary32,
+            },
+        }
+    }
+
+    /// Converts the integer `value` to a floating point value in `destination`.
+    fn load_fp(executor: &mut GAExecutor<'_, DefaultCompositionNoLogger>, value: u32, destination: &str) {
+        let operation = Operation::Move {
+            destination: Operand::Register("R1".to_owned()),
+            source: Operand::Immediate(DataWord::Word32(value)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        let operation = Operation::Ieee754(ieee754::Operations::ConvertFromInt {
+            operand: ieee754::Operand {
+                ty: ieee754::OperandType::Integral { size: 32, signed: true },
+                value: ieee754::OperandStorage::CoreRegister {
+                    id: "R1".to_owned(),
+                    ty: ieee754::OperandType::Integral { size: 32, signed: true },
+                    signed: true,
+                },
+            },
+            destination: fp_register(destination),
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+    }
+
+    /// Applies `operation` to `source` and returns the resulting boolean.
+    fn check_fp(executor: &mut GAExecutor<'_, DefaultCompositionNoLogger>, source: &str, operation: ieee754::NonComputational) -> u64 {
+        let destination = Operand::Register("R0".to_owned());
+        let operation = Operation::Ieee754(ieee754::Operations::NonComputational {
+            operand: fp_register(source),
+            operation,
+            destination: destination.clone(),
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        executor.get_operand_value(&destination, &NoLogger).unwrap().get_constant().unwrap()
+    }
+
+    /// Converts `value` to binary16 and back using the default rounding mode.
+    fn binary16_round_trip(rounding_mode: RoundingMode, value: u32) -> u64 {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        executor.state.fp_state.set_rounding_mode(rounding_mode).unwrap();
+        let integral = ieee754::OperandType::Integral { size: 32, signed: true };
+        let half = ieee754::Operand {
+            ty: ieee754::OperandType::Binary16,
+            value: ieee754::OperandStorage::Register {
+                id: "FPR1".to_owned(),
+                ty: ieee754::OperandType::Binary16,
+            },
+        };
+        let core_register = |id: &str| ieee754::Operand {
+            ty: integral.clone(),
+            value: ieee754::OperandStorage::CoreRegister {
+                id: id.to_owned(),
+                ty: integral.clone(),
+                signed: true,
+            },
+        };
+
+        let operation = Operation::Move {
+            destination: Operand::Register("R1".to_owned()),
+            source: Operand::Immediate(DataWord::Word32(value)),
+        };
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        let operation = Operation::Ieee754(ieee754::Operations::ConvertFromInt {
+            operand: core_register("R1"),
+            destination: half.clone(),
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        let operation = Operation::Ieee754(ieee754::Operations::RoundToInt {
+            source: half,
+            destination: core_register("R0"),
+            rounding: None,
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+
+        let r0 = executor.get_operand_value(&Operand::Register("R0".to_owned()), &NoLogger).unwrap();
+        assert_eq!(r0.size(), 32);
+        r0.get_constant().unwrap()
+    }
+
+    #[test]
+    fn test_fp_binary16_precision() {
+        // Every integer up to 2048 is representable.
+        assert_eq!(binary16_round_trip(RoundingMode::TiesToEven, 2047), 2047);
+        assert_eq!(binary16_round_trip(RoundingMode::TiesToEven, 2048), 2048);
+        // Above 2048 only every other integer is representable.
+        assert_eq!(binary16_round_trip(RoundingMode::TiesToEven, 2049), 2048);
+        assert_eq!(binary16_round_trip(RoundingMode::TiesToEven, 2051), 2052);
+        assert_eq!(binary16_round_trip(RoundingMode::TiesTowardZero, 2051), 2050);
+        // Above 4096 only every fourth integer is representable.
+        assert_eq!(binary16_round_trip(RoundingMode::TiesTowardZero, 4103), 4100);
+    }
+
+    /// Loads `value` as a binary32 value, converts it to binary16 with
+    /// `rounding_mode` and back to an integer.
+    fn binary32_through_binary16(rounding_mode: RoundingMode, value: u32) -> u64 {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+        let integral = ieee754::OperandType::Integral { size: 32, signed: true };
+        let half = ieee754::Operand {
+            ty: ieee754::OperandType::Binary16,
+            value: ieee754::OperandStorage::Register {
+                id: "FPR2".to_owned(),
+                ty: ieee754::OperandType::Binary16,
+            },
+        };
+
+        load_fp(&mut executor, value, "FPR1");
+        let operation = Operation::Ieee754(ieee754::Operations::Convert {
+            source: fp_register("FPR1"),
+            destination: half.clone(),
+            rounding: Some(rounding_mode),
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        let operation = Operation::Ieee754(ieee754::Operations::RoundToInt {
+            source: half,
+            destination: ieee754::Operand {
+                ty: integral.clone(),
+                value: ieee754::OperandStorage::CoreRegister {
+                    id: "R0".to_owned(),
+                    ty: integral,
+                    signed: true,
+                },
+            },
+            rounding: None,
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+
+        executor.get_operand_value(&Operand::Register("R0".to_owned()), &NoLogger).unwrap().get_constant().unwrap()
+    }
+
+    #[test]
+    fn test_fp_convert_binary32_to_binary16() {
+        assert_eq!(binary32_through_binary16(RoundingMode::TiesToEven, 2047), 2047);
+        // 2049 and 2051 lie between two binary16 values.
+        assert_eq!(binary32_through_binary16(RoundingMode::TiesToEven, 2049), 2048);
+        assert_eq!(binary32_through_binary16(RoundingMode::TiesToEven, 2051), 2052);
+        assert_eq!(binary32_through_binary16(RoundingMode::TiesTowardZero, 2051), 2050);
+        assert_eq!(binary32_through_binary16(RoundingMode::TiesTowardPositive, 2049), 2050);
+        assert_eq!(binary32_through_binary16(RoundingMode::TiesTowardNegative, 2051), 2050);
+    }
+
+    #[test]
+    fn test_fp_nan() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        load_fp(&mut executor, 0, "FPR1");
+        load_fp(&mut executor, 1, "FPR2");
+        // 0.0 / 0.0
+        let operation = Operation::Ieee754(ieee754::Operations::Division {
+            nominator: fp_register("FPR1"),
+            denominator: fp_register("FPR1"),
+            destination: fp_register("FPR3"),
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+
+        assert_eq!(check_fp(&mut executor, "FPR3", ieee754::NonComputational::IsNan), 1);
+        assert_eq!(check_fp(&mut executor, "FPR3", ieee754::NonComputational::IsNormal), 0);
+        assert_eq!(check_fp(&mut executor, "FPR3", ieee754::NonComputational::IsInfinite), 0);
+        assert_eq!(check_fp(&mut executor, "FPR2", ieee754::NonComputational::IsNan), 0);
+        assert_eq!(check_fp(&mut executor, "FPR2", ieee754::NonComputational::IsNormal), 1);
+        assert_eq!(check_fp(&mut executor, "FPR1", ieee754::NonComputational::IsNormal), 0);
+
+        // NaN + 1.0
+        let operation = Operation::Ieee754(ieee754::Operations::Addition {
+            lhs: fp_register("FPR3"),
+            rhs: fp_register("FPR2"),
+            destination: fp_register("FPR4"),
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        assert_eq!(check_fp(&mut executor, "FPR4", ieee754::NonComputational::IsNan), 1);
+    }
+
+    #[test]
+    fn test_fp_infinity() {
+        let mut vm = setup_test_vm();
+        let project = vm.project.clone();
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
+
+        load_fp(&mut executor, 0, "FPR1");
+        load_fp(&mut executor, 1, "FPR2");
+        // 1.0 / 0.0
+        let operation = Operation::Ieee754(ieee754::Operations::Division {
+            nominator: fp_register("FPR2"),
+            denominator: fp_register("FPR1"),
+            destination: fp_register("FPR3"),
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        assert_eq!(check_fp(&mut executor, "FPR3", ieee754::NonComputational::IsInfinite), 1);
+
+        // inf + 1.0
+        let operation = Operation::Ieee754(ieee754::Operations::Addition {
+            lhs: fp_register("FPR3"),
+            rhs: fp_register("FPR2"),
+            destination: fp_register("FPR4"),
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        assert_eq!(check_fp(&mut executor, "FPR4", ieee754::NonComputational::IsInfinite), 1);
+        assert_eq!(check_fp(&mut executor, "FPR4", ieee754::NonComputational::IsSignMinus), 0);
+
+        // inf - inf
+        let operation = Operation::Ieee754(ieee754::Operations::Subtraction {
+            lhs: fp_register("FPR3"),
+            rhs: fp_register("FPR4"),
+            destination: fp_register("FPR5"),
+        });
+        executor.execute_operation(&operation, &mut NoLogger).unwrap();
+        assert_eq!(check_fp(&mut executor, "FPR5", ieee754::NonComputational::IsInfinite), 0);
+        assert_eq!(check_fp(&mut executor, "FPR5", ieee754::NonComputational::IsNan), 1);
+    }
+
     #[test]
     #[allow(clippy::too_many_lines)]
     fn test_fp_compare() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -1766,7 +2392,7 @@ mod test {
     fn test_fp_load_store_address() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -1844,7 +2470,7 @@ mod test {
     fn test_fp_load_store_register() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
         let operand_r1 = Operand::Register("R1".to_owned());
 
@@ -1967,7 +2593,7 @@ mod test {
     fn test_fp_sqrt() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2046,7 +2672,7 @@ mod test {
     fn test_fp_fma() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
         let operand_r1 = Operand::Register("R1".to_owned());
         let operand_r2 = Operand::Register("R2".to_owned());
@@ -2195,7 +2821,7 @@ mod test {
     fn test_fp_abs() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2343,7 +2969,7 @@ mod test {
     fn test_fp_div_un_even() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2458,7 +3084,7 @@ mod test {
     fn test_fp_mul() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2573,7 +3199,7 @@ mod test {
     fn test_fp_sub() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2688,7 +3314,7 @@ mod test {
     fn test_fp_add() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2803,7 +3429,7 @@ mod test {
     fn test_move() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // move imm into reg
@@ -2857,7 +3483,7 @@ mod test {
     fn test_add_vm() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let r0 = Operand::Register("R0".to_owned());
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -2914,7 +3540,7 @@ mod test {
     fn test_adc() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
         let imm_12 = Operand::Immediate(DataWord::Word32(12));
@@ -2968,7 +3594,7 @@ mod test {
     fn test_sub() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let r0 = Operand::Register("R0".to_owned());
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -3025,7 +3651,7 @@ mod test {
     fn test_mul() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let r0 = Operand::Register("R0".to_owned());
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -3082,7 +3708,7 @@ mod test {
     fn test_set_v_flag() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
         let imm_12 = Operand::Immediate(DataWord::Word32(12));
@@ -3130,7 +3756,7 @@ mod test {
     fn test_conditional_execution() {
         let mut vm = setup_test_vm();
         let project = vm.project.clone();
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let imm_0 = Operand::Immediate(DataWord::Word32(0));
         let imm_1 = Operand::Immediate(DataWord::Word32(1));
         let r0 = Operand::Register("R0".to_owned());
@@ -3186,4 +3812,62 @@ mod test {
         let a_word = bw.unconstrained(32, "a_word");
         a_word.get_constant().unwrap();
     }
+
+    #[test]
+    fn test_internal_error_is_returned() {
+        let bw = Bitwuzla::new();
+        let x = bw.unconstrained(8, "x");
+        bw.assert(&x.ult(&bw.from_u64(2, 8)));
+
+        // Simulates Bitwuzla reporting a failure through the abort callback
+        // while a term is built.
+        let message = std::ffi::CString::new("simulated failure").unwrap();
+        unsafe { super::abort_callback(message.as_ptr()) };
+        assert_eq!(bw.is_sat(), Err(SolverError::Internal("simulated failure".to_owned())));
+
+        // The error is only returned once, later queries are unaffected.
+        assert_eq!(bw.is_sat(), Ok(true));
+    }
+
+    #[test]
+    fn test_solver_abort_is_returned_from_failing_call() {
+        let bw = Bitwuzla::new();
+        let x = bw.unconstrained(8, "x");
+        bw.assert(&x.ult(&bw.from_u64(2, 8)));
+
+        // No error is pending before the failing call.
+        assert!(super::take_internal_error().is_none());
+
+        // Popping without a pushed frame makes Bitwuzla abort.
+        bw.pop();
+        let result = bw.is_sat();
+        assert!(matches!(result, Err(SolverError::Internal(_))), "expected internal error, got {result:?}");
+        assert!(super::take_internal_error().is_none());
+
+        // The solver state is unknown after a failed pop so it keeps failing,
+        // while a fresh solver is unaffected.
+        assert!(matches!(bw.is_sat(), Err(SolverError::Internal(_))));
+        let fresh = Bitwuzla::new();
+        assert_eq!(fresh.is_sat(), Ok(true));
+    }
+
+    #[test]
+    fn test_configured_solver() {
+        for config in [
+            SolverConfig::single_threaded(),
+            SolverConfig {
+                threads: 2,
+                rewrite_level: RewriteLevel::Full,
+                cache_size: 0,
+            },
+        ] {
+            let bw = Bitwuzla::with_config(&config);
+            let x = bw.unconstrained(8, "x");
+            bw.assert(&x.ult(&bw.from_u64(2, 8)));
+            match bw.get_values(&x, 3).unwrap() {
+                Solutions::Exactly(values) => assert_eq!(values.len(), 2),
+                Solutions::AtLeast(_) => panic!("x has exactly two solutions"),
+            }
+        }
+    }
 }
@@ -1514,7 +1514,7 @@ mod test {
     fn test_move() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let mut local = HashMap::new();
         let operand_r0 = Operand::Register("R0".to_owned());
 
@@ -1569,7 +1569,7 @@ mod test {
     fn test_add_vm() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let mut local = HashMap::new();
 
         let r0 = Operand::Register("R0".to_owned());
@@ -1627,7 +1627,7 @@ mod test {
     fn test_adc() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let mut local = HashMap::new();
 
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -1682,7 +1682,7 @@ mod test {
     fn test_sub() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let mut local = HashMap::new();
 
         let r0 = Operand::Register("R0".to_owned());
@@ -1740,7 +1740,7 @@ mod test {
     fn test_mul() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let mut local = HashMap::new();
 
         let r0 = Operand::Register("R0".to_owned());
@@ -1798,7 +1798,7 @@ mod test {
     fn test_set_v_flag() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let mut local = HashMap::new();
 
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -1847,7 +1847,7 @@ mod test {
     fn test_conditional_execution() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let imm_0 = Operand::Immediate(DataWord::Word32(0));
         let imm_1 = Operand::Immediate(DataWord::Word32(1));
         let local = HashMap::new();
@@ -1,4 +1,7 @@
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    time::Duration,
+};
 
 use general_assembly::{
     extension::ieee754::{OperandType, RoundingMode},
@@ -8,7 +11,7 @@ use hashbrown::HashMap;
 
 use crate::{
     arch::{ArchitectureOverride, SupportedArchitecture},
-    executor::ResultOrTerminate,
+    executor::{memory_interface::Access, ResultOrTerminate},
     memory::MemoryError as MemoryFileError,
     project::dwarf_helper::SubProgramMap,
     Endianness,
@@ -23,6 +26,30 @@ pub mod smt_boolector;
 #[cfg(feature = "z3")]
 pub mod z3;
 
+/// The writes made since a write log was started, see
+/// [`SmtMap::start_write_log`].
+#[derive(Clone, Debug)]
+pub struct WriteLog<E> {
+    /// The value each byte written at a constant address held before it was
+    /// first written.
+    pub previous: HashMap<u64, E>,
+    /// The address and value of every write to a symbolic address.
+    pub symbolic_writes: Vec<(E, E)>,
+}
+
+impl<E> Default for WriteLog<E> {
+    fn default() -> Self {
+        Self {
+            previous: HashMap::new(),
+            symbolic_writes: Vec::new(),
+        }
+    }
+}
+
+/// The theory reported by [`SolverError::Unsupported`] for floating point
+/// operations.
+pub const FLOATING_POINT: &str = "floating point operations";
+
 #[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
 pub enum SolverError {
     /// The set of constraints added to the solution are unsatisfiable.
@@ -40,6 +67,14 @@ pub enum SolverError {
     #[error("Typically denotes logic errors in the glue layer. {0}")]
     /// A generic error that occurs when the glue layer fails.
     Generic(String),
+
+    /// The solver does not support the requested theory.
+    #[error("The solver does not support {0}")]
+    Unsupported(&'static str),
+
+    /// The solver failed internally, the result of the query is not known.
+    #[error("The solver failed internally: {0}")]
+    Internal(String),
 }
 
 #[derive(Debug)]
@@ -48,6 +83,170 @@ pub enum Solutions<E> {
     AtLeast(Vec<E>),
 }
 
+/// Profiling data accumulated by a solver.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Statistics {
+    /// Number of satisfiability checks issued to the solver.
+    pub sat_calls: u64,
+    /// Cumulative time spent in satisfiability checks.
+    pub solver_time: Duration,
+    /// The largest number of constraints that were active in a single
+    /// satisfiability check.
+    pub max_formula_size: usize,
+    /// Number of satisfiability checks answered from the solver cache.
+    pub cache_hits: u64,
+    /// Number of cacheable satisfiability checks that were not in the cache.
+    pub cache_misses: u64,
+}
+
+impl Statistics {
+    /// Returns the fraction of cacheable satisfiability checks that were
+    /// answered from the cache, or zero if no checks were cacheable.
+    #[must_use]
+    pub const fn cache_hit_rate(&self) -> f64 {
+        let lookups = self.cache_hits + self.cache_misses;
+        if lookups == 0 {
+            return 0.;
+        }
+        self.cache_hits as f64 / lookups as f64
+    }
+}
+
+impl Display for Statistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} satisfiability checks taking {:.3}s in total, largest formula had {} constraints, {:.1}% cache hit rate",
+            self.sat_calls,
+            self.solver_time.as_secs_f64(),
+            self.max_formula_size,
+            self.cache_hit_rate() * 100.
+        )
+    }
+}
+
+/// How much a solver rewrites and preprocesses the formulas it is given.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RewriteLevel {
+    /// No rewriting.
+    #[default]
+    None,
+    /// Term level rewriting.
+    Term,
+    /// Term level rewriting and full preprocessing.
+    Full,
+}
+
+/// Options used when creating a solver.
+///
+/// Solvers ignore the options they do not support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolverConfig {
+    /// Number of threads the solver may use.
+    pub threads: usize,
+    /// How much the solver rewrites the formulas it is given.
+    pub rewrite_level: RewriteLevel,
+    /// Number of satisfiability results the solver may cache, zero disables
+    /// the cache.
+    ///
+    /// The cache is disabled by default. Cached checks are identified by the
+    /// printed constraints, so distinct symbols with the same name are not
+    /// told apart, and every constraint is printed while the cache is in use.
+    pub cache_size: usize,
+}
+
+impl SolverConfig {
+    /// A configuration that only uses a single thread, so that the solutions
+    /// returned are the same between runs.
+    #[must_use]
+    pub const fn single_threaded() -> Self {
+        Self {
+            threads: 1,
+            rewrite_level: RewriteLevel::None,
+            cache_size: 0,
+        }
+    }
+}
+
+impl Default for SolverConfig {
+    /// Uses one thread per available core and no rewriting.
+    fn default() -> Self {
+        Self {
+            threads: std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get),
+            rewrite_level: RewriteLevel::None,
+            cache_size: 0,
+        }
+    }
+}
+
+/// Lazy iterator over the solutions to an expression.
+///
+/// Created by [`SmtSolver::solutions_iter`].
+#[derive(Debug)]
+pub struct SolutionsIter<'a, S: SmtSolver> {
+    solver: &'a S,
+    expr: S::Expression,
+    remaining: u32,
+    /// Set once it is known that no more solutions exist.
+    exhausted: bool,
+    /// Whether or not the iterator pushed a frame on to the solver.
+    pushed: bool,
+}
+
+impl<'a, S: SmtSolver> SolutionsIter<'a, S> {
+    fn new(solver: &'a S, expr: &S::Expression, upper_bound: u32) -> Self {
+        // Constant expressions need no solver interaction.
+        let pushed = expr.get_constant().is_none() && upper_bound != 0;
+        if pushed {
+            solver.push();
+        }
+        Self {
+            solver,
+            expr: expr.clone(),
+            remaining: upper_bound,
+            exhausted: false,
+            pushed,
+        }
+    }
+}
+
+impl<S: SmtSolver> Iterator for SolutionsIter<'_, S> {
+    type Item = Result<S::Expression, SolverError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted || self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        if !self.pushed {
+            self.exhausted = true;
+            return Some(Ok(self.expr.clone()));
+        }
+
+        // `get_solutions` blocks the found solution and reports if more exist.
+        match self.solver.get_solutions(&self.expr, 1) {
+            Ok(Solutions::Exactly(mut solutions)) => {
+                self.exhausted = true;
+                solutions.pop().map(Ok)
+            }
+            Ok(Solutions::AtLeast(mut solutions)) => solutions.pop().map(Ok),
+            Err(err) => {
+                self.exhausted = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl<S: SmtSolver> Drop for SolutionsIter<'_, S> {
+    fn drop(&mut self) {
+        if self.pushed {
+            self.solver.pop();
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
 pub enum MemoryError {
     #[error("Memory file encountered error {0}")]
@@ -55,6 +254,18 @@ pub enum MemoryError {
 
     #[error("Program counter is non deterministic.")]
     PcNonDetmerinistic,
+
+    #[error("Uninitialized memory used as {0}.")]
+    UninitializedRead(&'static str),
+
+    #[error("{0} access to {1:#x} is not permitted.")]
+    PermissionViolation(Access, u64),
+
+    #[error("{0} access through a symbolic address may not be permitted.")]
+    SymbolicPermissionViolation(Access),
+
+    #[error("Solver error: {0}")]
+    SolverError(#[from] SolverError),
 }
 
 pub trait ProgramMemory<E: SmtExpr>: Debug + Clone {
@@ -115,6 +326,11 @@ pub trait ProgramMemory<E: SmtExpr>: Debug + Clone {
     fn regions(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
         Vec::new().into_iter()
     }
+
+    /// Returns the regions that contain executable code.
+    fn executable_regions(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        Vec::new().into_iter()
+    }
 }
 
 pub trait Lambda: Clone + Debug {
@@ -183,7 +399,11 @@ pub trait SmtMap: Debug + Clone + Display {
     fn set_register(&mut self, idx: &str, value: Self::Expression) -> Result<(), MemoryError>;
 
     fn set_fp_register(&mut self, idx: &str, value: <Self::SMT as SmtSolver>::FpExpression, rm: RoundingMode, signed: bool) -> Result<(), MemoryError> {
-        self.set_register(idx, value.to_bv(rm, signed).expect("Float conversion to be valid"))
+        // Solvers without floating point support report this as a solver error.
+        let value = value
+            .to_bv(rm, signed)
+            .map_err(|e| MemoryError::from(e.downcast::<SolverError>().expect("Float conversion to be valid")))?;
+        self.set_register(idx, value)
     }
 
     fn get_fp_register(
@@ -195,7 +415,8 @@ pub trait SmtMap: Debug + Clone + Display {
         signed: bool,
     ) -> Result<<Self::SMT as SmtSolver>::FpExpression, MemoryError> {
         let reg = self.get_register(idx)?;
-        Ok(reg.to_fp(source_ty, dest_ty, rm, signed).expect("Floatingpoint conversion to be valid"))
+        reg.to_fp(source_ty, dest_ty, rm, signed)
+            .map_err(|e| MemoryError::from(e.downcast::<SolverError>().expect("Floatingpoint conversion to be valid")))
     }
 
     // NOTE: Might be a poor assumption that the word size for PC is 32 bit.
@@ -256,12 +477,45 @@ pub trait SmtMap: Debug + Clone + Display {
 
     fn get_from_instruction_memory(&self, address: u64) -> crate::Result<Vec<u8>>;
 
+    /// Returns all named variables and their values.
+    fn named_variables(&self) -> impl Iterator<Item = (&str, &Self::Expression)> + '_ {
+        std::iter::empty()
+    }
+
+    /// Whether or not [`start_write_log`](Self::start_write_log) is supported.
+    const LOGS_WRITES: bool = false;
+
+    /// Starts logging writes, replacing any earlier log.
+    fn start_write_log(&mut self) {}
+
+    /// Returns the log started by [`start_write_log`](Self::start_write_log),
+    /// if any.
+    fn write_log(&self) -> Option<&WriteLog<Self::Expression>> {
+        None
+    }
+
     /// Clears all named variables.
     ///
     /// This does not remove them from the SMT solver, it merely resets the
     /// logging output.
     fn clear_named_variables(&mut self);
 
+    /// Whether or not [`seed_symbols`](Self::seed_symbols) is supported.
+    const SEEDS_SYMBOLS: bool = false;
+
+    /// Makes [`unconstrained`](Self::unconstrained) return the given symbols,
+    /// rather than creating new ones, when called with a matching name and
+    /// size.
+    fn seed_symbols(&mut self, _symbols: Vec<(String, Self::Expression)>) {}
+
+    /// Returns the number of unnamed symbols created so far.
+    ///
+    /// Unnamed symbols can not be seeded, a state that created one since it
+    /// was copied can not be re-derived from that copy.
+    fn unnamed_symbols(&self) -> usize {
+        0
+    }
+
     /// Returns the underlying program memory.
     fn program_memory(&self) -> &Self::ProgramMemory;
 
@@ -297,6 +551,31 @@ pub trait SmtMap: Debug + Clone + Display {
     fn regions(&self) -> impl Iterator<Item = (u64, u64)> {
         Vec::new().into_iter()
     }
+
+    /// Enables or disables tracking of reads from uninitialized memory.
+    fn set_poison_tracking(&mut self, _enabled: bool) {}
+
+    /// Enables or disables tracking of the memory written since a path was
+    /// forked, which lets [`merge`](Self::merge) combine memories that have
+    /// both been written since they split.
+    fn set_merge_tracking(&mut self, _enabled: bool) {}
+
+    /// Returns true if the expression depends on data read from uninitialized
+    /// memory.
+    ///
+    /// Always returns false if poison tracking is not enabled.
+    fn is_poisoned(&self, _expr: &Self::Expression) -> bool {
+        false
+    }
+
+    /// Combines `self` and `other` in to a single memory that holds the values
+    /// of `self` where `condition` is true and the values of `other` where it
+    /// is false.
+    ///
+    /// Returns `None` if the memories can not be combined.
+    fn merge(&self, _other: &Self, _condition: &Self::Expression) -> Option<Self> {
+        None
+    }
 }
 
 /// Defines a type that can be used as an SMT solver.
@@ -310,6 +589,21 @@ pub trait SmtSolver: Debug + Clone {
     #[must_use]
     fn new() -> Self;
 
+    /// Creates a new solver using the options in `config`.
+    ///
+    /// Solvers that have no options do not need to override this.
+    #[must_use]
+    fn with_config(_config: &SolverConfig) -> Self {
+        Self::new()
+    }
+
+    /// Creates a new solver that only uses a single thread, so that the
+    /// solutions it returns are the same between runs.
+    #[must_use]
+    fn new_single_threaded() -> Self {
+        Self::with_config(&SolverConfig::single_threaded())
+    }
+
     #[must_use]
     /// Creates a new unconstrained value of size `size` with the label `name`.
     fn unconstrained(&self, size: u32, name: &str) -> Self::Expression;
@@ -395,39 +689,60 @@ pub trait SmtSolver: Debug + Clone {
     /// is returned.
     fn get_solutions(&self, expr: &Self::Expression, upper_bound: u32) -> Result<Solutions<Self::Expression>, SolverError>;
 
+    /// Lazily find solutions to `expr`.
+    ///
+    /// Each call to `next` blocks the previously yielded solutions and asks
+    /// the solver for a new one, yielding at most `upper_bound` solutions.
+    /// The solver state is restored when the iterator is dropped.
+    fn solutions_iter(&self, expr: &Self::Expression, upper_bound: u32) -> SolutionsIter<'_, Self> {
+        SolutionsIter::new(self, expr, upper_bound)
+    }
+
+    /// Returns the [`Statistics`] accumulated by the solver so far.
+    ///
+    /// Solvers that do not track statistics return [`Statistics::default`].
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+
     #[inline(always)]
     fn has_lambdas(&self) -> bool {
         false
     }
 }
 
+/// Floating point values for solvers that lack floating point sorts.
+///
+/// The value is stored as a plain bit-vector and every operation on it fails
+/// with [`SolverError::Unsupported`].
 impl<E> SmtFPExpr for (E, OperandType)
 where
     E: SmtExpr<FPExpression = Self>,
 {
     type Expression = E;
 
-    fn any(&self, ty: OperandType) -> crate::Result<Self> {
-        let size = ty.size();
-
-        Ok((self.0.any(size), ty))
+    fn any(&self, _ty: OperandType) -> crate::Result<Self> {
+        Err(SolverError::Unsupported(FLOATING_POINT).into())
     }
 
     fn ty(&self) -> OperandType {
         self.1.clone()
     }
 
-    fn convert_from_bv(bv: Self::Expression, _rm: RoundingMode, _source_ty: OperandType, dest_ty: OperandType, _signed: bool) -> crate::Result<Self> {
-        let size = dest_ty.size();
-        Ok((bv.any(size), dest_ty))
+    fn convert_from_bv(_bv: Self::Expression, _rm: RoundingMode, _source_ty: OperandType, _dest_ty: OperandType, _signed: bool) -> crate::Result<Self> {
+        Err(SolverError::Unsupported(FLOATING_POINT).into())
+    }
+
+    fn convert(&self, _ty: OperandType, _rm: RoundingMode) -> crate::Result<Self> {
+        Err(SolverError::Unsupported(FLOATING_POINT).into())
     }
 
     fn compare(&self, _other: &Self, _cmp: general_assembly::extension::ieee754::ComparisonMode, _rm: RoundingMode) -> crate::Result<Self::Expression> {
-        crate::Result::Ok(self.0.any(1))
+        Err(SolverError::Unsupported(FLOATING_POINT).into())
     }
 
     fn check_meta(&self, _op: general_assembly::extension::ieee754::NonComputational, _rm: RoundingMode) -> crate::Result<Self::Expression> {
-        crate::Result::Ok(self.0.any(1))
+        Err(SolverError::Unsupported(FLOATING_POINT).into())
     }
 
     fn get_const(&self) -> Option<f64> {
@@ -488,6 +803,10 @@ pub trait SmtFPExpr: Debug + Clone {
         self.any(self.ty())
     }
 
+    /// Converts the value to the floating point format `ty`, rounding it with
+    /// `rm` if it can not be represented exactly.
+    fn convert(&self, ty: OperandType, rm: RoundingMode) -> crate::Result<Self>;
+
     /// Converts to a bitvector representation of a ieee754 value to a bitvector
     /// without rounding.
     ///
@@ -588,6 +907,34 @@ pub trait SmtExpr: Debug + Clone {
     #[must_use]
     fn slte(&self, other: &Self) -> Self;
 
+    /// The unsigned maximum of `self` and `other`. Both [`SmtExpr`]s must have
+    /// the same bit width.
+    #[must_use]
+    fn umax(&self, other: &Self) -> Self {
+        self.ugte(other).ite(self, other)
+    }
+
+    /// The unsigned minimum of `self` and `other`. Both [`SmtExpr`]s must have
+    /// the same bit width.
+    #[must_use]
+    fn umin(&self, other: &Self) -> Self {
+        self.ulte(other).ite(self, other)
+    }
+
+    /// The signed maximum of `self` and `other`. Both [`SmtExpr`]s must have
+    /// the same bit width.
+    #[must_use]
+    fn smax(&self, other: &Self) -> Self {
+        self.sgte(other).ite(self, other)
+    }
+
+    /// The signed minimum of `self` and `other`. Both [`SmtExpr`]s must have
+    /// the same bit width.
+    #[must_use]
+    fn smin(&self, other: &Self) -> Self {
+        self.slte(other).ite(self, other)
+    }
+
     #[must_use]
     fn add(&self, other: &Self) -> Self;
 
@@ -624,15 +971,59 @@ pub trait SmtExpr: Debug + Clone {
     #[must_use]
     fn shift(&self, steps: &Self, direction: Shift) -> Self;
 
+    /// Rotates `self` left by `amount` bits.
+    ///
+    /// Requires that `self` and `amount` have the same width.
+    #[must_use]
+    fn rotate_left(&self, amount: &Self) -> Self;
+
+    /// Rotates `self` right by `amount` bits.
+    ///
+    /// Requires that `self` and `amount` have the same width.
+    #[must_use]
+    fn rotate_right(&self, amount: &Self) -> Self;
+
     #[must_use]
     fn ite(&self, then_bv: &Self, else_bv: &Self) -> Self;
 
+    /// Concatenates `self` and `other`, with `self` as the most significant
+    /// bits.
+    ///
+    /// The width of the result is the sum of the widths of the operands.
     #[must_use]
     fn concat(&self, other: &Self) -> Self;
 
+    /// Counts the number of set bits, the result has the same width as `self`.
+    ///
+    /// The bits are summed pairwise in a tree where each level is one bit
+    /// wider than the one below it, so the adders stay narrow rather than
+    /// adding every bit at the full width.
+    #[must_use]
+    fn popcount(&self) -> Self {
+        fn count<E: SmtExpr>(expr: &E, low: u32, high: u32) -> E {
+            if low == high {
+                return expr.slice(low, high);
+            }
+            let mid = low + (high - low) / 2;
+            let (lower, upper) = (count(expr, low, mid), count(expr, mid + 1, high));
+            let width = lower.size().max(upper.size()) + 1;
+            lower.zero_ext(width).add(&upper.zero_ext(width))
+        }
+        count(self, 0, self.size() - 1).resize_unsigned(self.size())
+    }
+
     #[must_use]
     fn slice(&self, low: u32, high: u32) -> Self;
 
+    /// Extracts the bits `high` down to `low`, inclusive.
+    ///
+    /// The width of the result is `high - low + 1`.
+    #[must_use]
+    fn extract(&self, high: u32, low: u32) -> Self {
+        assert!(high < self.size(), "Tried to extract bit {high} from a {} bit value", self.size());
+        self.slice(low, high)
+    }
+
     #[must_use]
     fn uaddo(&self, other: &Self) -> Self;
 
@@ -651,12 +1042,25 @@ pub trait SmtExpr: Debug + Clone {
     #[must_use]
     fn smulo(&self, other: &Self) -> Self;
 
+    /// Returns an equivalent expression that is no larger than `self`.
+    ///
+    /// Constants remain constants, so [`SmtExpr::get_constant`] gives the same
+    /// result before and after simplification.
     #[must_use]
     fn simplify(self) -> Self;
 
     #[must_use]
     fn get_constant(&self) -> Option<u64>;
 
+    /// Returns the value of the expression if it simplifies to a constant.
+    ///
+    /// This never queries the solver, use it to skip solving for expressions
+    /// that are already concrete.
+    #[must_use]
+    fn try_concrete(&self) -> Option<u64> {
+        self.clone().simplify().get_constant()
+    }
+
     #[must_use]
     fn get_a_solution(&self, e: &[Self]) -> Option<u64>;
 
@@ -334,6 +334,10 @@ impl<State: UserStateContainer> SmtMap for BoolectorMemory<State> {
         (self.initial_sp.clone(), current.clone())
     }
 
+    fn named_variables(&self) -> impl Iterator<Item = (&str, &Self::Expression)> + '_ {
+        self.variables.iter().map(|(name, value)| (name.as_str(), value))
+    }
+
     fn clear_named_variables(&mut self) {
         self.variables.clear();
     }
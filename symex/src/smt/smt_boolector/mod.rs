@@ -515,9 +515,9 @@ impl SmtExpr for BoolectorExpr {
         Self(BV::new(self.0.get_btor(), width, None))
     }
 
-    /// Converts from a floating point value.
-    fn from_fp(fp: &Self::FPExpression, _rm: general_assembly::extension::ieee754::RoundingMode, _signed: bool) -> crate::Result<Self> {
-        Ok(fp.0.any(fp.1.size()))
+    /// Boolector does not support floating point sorts.
+    fn from_fp(_fp: &Self::FPExpression, _rm: general_assembly::extension::ieee754::RoundingMode, _signed: bool) -> crate::Result<Self> {
+        Err(SolverError::Unsupported(super::FLOATING_POINT).into())
     }
 
     /// Returns the bit width of the [`BoolectorExpr`].
@@ -701,6 +701,16 @@ impl SmtExpr for BoolectorExpr {
         }
     }
 
+    fn rotate_left(&self, amount: &Self) -> Self {
+        assert_eq!(self.len(), amount.len());
+        Self(self.0.rol(&amount.0))
+    }
+
+    fn rotate_right(&self, amount: &Self) -> Self {
+        assert_eq!(self.len(), amount.len());
+        Self(self.0.ror(&amount.0))
+    }
+
     fn ite(&self, then_bv: &Self, else_bv: &Self) -> Self {
         assert_eq!(self.len(), 1);
         Self(self.0.cond_bv(&then_bv.0, &else_bv.0))
@@ -925,3 +935,32 @@ impl Lambda for NoTrinaryLambda {
         Self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use general_assembly::extension::ieee754::{OperandType, RoundingMode};
+
+    use super::Boolector;
+    use crate::smt::{SmtExpr, SmtFPExpr, SmtSolver, SolverError, FLOATING_POINT};
+
+    fn is_unsupported<T>(result: crate::Result<T>) -> bool {
+        result.is_err_and(|e| e.downcast_ref::<SolverError>() == Some(&SolverError::Unsupported(FLOATING_POINT)))
+    }
+
+    #[test]
+    fn test_fp_unsupported() {
+        let smt = Boolector::new();
+        let lhs = smt.unconstrained_fp(OperandType::Binary32, "lhs");
+        let rhs = smt.unconstrained_fp_unnamed(OperandType::Binary32);
+        assert!(is_unsupported(lhs.add(&rhs, RoundingMode::TiesToEven)));
+        assert!(is_unsupported(lhs.to_bv(RoundingMode::TiesToEven, false)));
+
+        let bv = smt.from_u64(1, 32);
+        assert!(is_unsupported(bv.to_fp(
+            OperandType::Integral { size: 32, signed: false },
+            OperandType::Binary32,
+            RoundingMode::TiesToEven,
+            false
+        )));
+    }
+}
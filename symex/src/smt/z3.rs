@@ -241,6 +241,10 @@ impl<UserState: UserStateContainer> SmtMap for Z3Array<UserState> {
         (self.initial_sp.clone(), self.register_file.get("SP").expect("Could not get register SP").clone())
     }
 
+    fn named_variables(&self) -> impl Iterator<Item = (&str, &Self::Expression)> + '_ {
+        self.variables.iter().map(|(name, value)| (name.as_str(), value))
+    }
+
     fn clear_named_variables(&mut self) {
         self.variables.clear();
     }
@@ -570,6 +574,15 @@ impl SmtFPExpr for Z3Fp {
         Ok(self.from_term(term))
     }
 
+    fn convert(&self, ty: OperandType, rm: RoundingMode) -> crate::Result<Self> {
+        let rm = self.conv(rm);
+        let term = unsafe {
+            let sort = z3_sys::Z3_mk_fpa_sort(*self.ctx.ctx, ty.exponent() as u32, ty.fraction() as u32);
+            z3_sys::Z3_mk_fpa_to_fp_float(*self.ctx.ctx, rm, self.term, sort)
+        };
+        Ok(Self { term, ctx: self.ctx.clone(), ty })
+    }
+
     fn to_bv(&self, rm: RoundingMode, signed: bool) -> crate::Result<Self::Expression> {
         let term = unsafe { z3_sys::Z3_mk_fpa_to_ieee_bv(*self.ctx.ctx, self.term) };
         Ok(Z3Bv { term, ctx: self.ctx.clone() })
@@ -1065,6 +1078,18 @@ impl SmtExpr for Z3Bv {
         self._from_term(term)
     }
 
+    fn rotate_left(&self, amount: &Self) -> Self {
+        let term = unsafe { z3_sys::Z3_mk_ext_rotate_left(*self.ctx.ctx, self.term, amount.term) };
+
+        self._from_term(term)
+    }
+
+    fn rotate_right(&self, amount: &Self) -> Self {
+        let term = unsafe { z3_sys::Z3_mk_ext_rotate_right(*self.ctx.ctx, self.term, amount.term) };
+
+        self._from_term(term)
+    }
+
     fn uadds(&self, other: &Self) -> Self {
         todo!()
     }
@@ -1543,7 +1568,7 @@ mod test {
     fn test_fp_div_un_even_ties_to_even_explicit() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -1659,7 +1684,7 @@ mod test {
     fn test_fp_div_un_even_ties_to_even_system_level() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -1776,7 +1801,7 @@ mod test {
     fn test_fp_div_mul() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -1947,7 +1972,7 @@ mod test {
     fn test_fp_non_computational() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2113,7 +2138,7 @@ mod test {
     fn test_fp_compare() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2419,7 +2444,7 @@ mod test {
     fn test_fp_load_store_address() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2498,7 +2523,7 @@ mod test {
     fn test_fp_load_store_register() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2581,7 +2606,7 @@ mod test {
     fn test_fp_sqrt() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2660,7 +2685,7 @@ mod test {
     fn test_fp_fma() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
         let operand_r1 = Operand::Register("R1".to_owned());
         let operand_r2 = Operand::Register("R2".to_owned());
@@ -2808,7 +2833,7 @@ mod test {
     fn test_fp_abs() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -2957,7 +2982,7 @@ mod test {
     fn test_fp_div_un_even() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -3073,7 +3098,7 @@ mod test {
     fn test_fp_mul() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -3188,7 +3213,7 @@ mod test {
     fn test_fp_sub() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -3303,7 +3328,7 @@ mod test {
     fn test_fp_add() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // 1. Load an integer in to a register.
@@ -3439,7 +3464,7 @@ mod test {
     fn test_move() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let operand_r0 = Operand::Register("R0".to_owned());
 
         // move imm into reg
@@ -3493,7 +3518,7 @@ mod test {
     fn test_add_vm() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let r0 = Operand::Register("R0".to_owned());
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -3550,7 +3575,7 @@ mod test {
     fn test_adc() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
         let imm_12 = Operand::Immediate(DataWord::Word32(12));
@@ -3604,7 +3629,7 @@ mod test {
     fn test_sub() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let r0 = Operand::Register("R0".to_owned());
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -3661,7 +3686,7 @@ mod test {
     fn test_mul() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let r0 = Operand::Register("R0".to_owned());
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
@@ -3718,7 +3743,7 @@ mod test {
     fn test_set_v_flag() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
 
         let imm_42 = Operand::Immediate(DataWord::Word32(42));
         let imm_12 = Operand::Immediate(DataWord::Word32(12));
@@ -3766,7 +3791,7 @@ mod test {
     fn test_conditional_execution() {
         let mut vm = setup_test_vm();
         let project = vm.project;
-        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state, &mut vm, project);
+        let mut executor = GAExecutor::from_state(vm.paths.get_path().unwrap().state.materialize(), &mut vm, project);
         let imm_0 = Operand::Immediate(DataWord::Word32(0));
         let imm_1 = Operand::Immediate(DataWord::Word32(1));
         let r0 = Operand::Register("R0".to_owned());
@@ -5,11 +5,11 @@ pub mod operand;
 pub mod operations;
 
 use function::Function;
-use operand::SetType;
-use operations::{Assign, BinOp, UnOp};
-use syn::{Expr, Ident};
+use operand::{Operand, SetType, Type};
+use operations::{Assign, BinOp, CompareOperation, Ternary, UnOp};
+use syn::{Expr, ExprRange, Ident, LitInt};
 
-use self::function::Jump;
+use self::function::{Call, Jump};
 
 #[derive(Debug, Clone, PartialEq)]
 /// Top level intermediate representation of the program.
@@ -18,6 +18,12 @@ pub struct IR {
     pub ret: Option<Ident>,
     /// The values to insert into `ret`.
     pub extensions: Vec<Statement>,
+    /// The word size of the target architecture in bits, set with a leading
+    /// `#[word_size = N]` attribute.
+    ///
+    /// Registers and otherwise untyped values are this wide, defaults to 32
+    /// bits.
+    pub word_size: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -29,15 +35,120 @@ pub enum Statement {
     /// rust. The body of the if statement contains [`Statement`]s
     /// so does the optional else block.
     If(Expr, Box<Vec<Statement>>, Option<Box<Vec<Statement>>>),
+    /// An if statement evaluated at execution time.
+    ///
+    /// The type checker turns an [`Statement::If`] in to this if its
+    /// condition reads values declared in the pseudo code, such as
+    /// `if (a < b) { .. }` where `a` and `b` are typed operands.
+    Ite(
+        Box<WhileCondition>,
+        Box<Vec<Statement>>,
+        Option<Box<Vec<Statement>>>,
+    ),
     /// A general for loop.
     ///
     /// The for loop follows normal rust syntax.
     /// The body of the for statement contains [`Statement`]s.
     For(Ident, Expr, Box<Vec<Statement>>),
+    /// A loop over a [`ConstantRange`] evaluated at execution time.
+    ///
+    /// The loop variable is declared as a local of the range type.
+    /// The body of the for statement contains [`Statement`]s.
+    ForRange(Ident, Box<ConstantRange>, Box<Vec<Statement>>),
+    /// A loop evaluated at execution time.
+    ///
+    /// The body is executed for as long as the [`WhileCondition`] holds.
+    /// The body of the while statement contains [`Statement`]s.
+    While(Box<WhileCondition>, Box<Vec<Statement>>),
+    /// Selects a set of statements based on the value of an operand.
+    ///
+    /// The match is evaluated at execution time.
+    Match(Box<Match>),
+    /// Declares a named [`Block`] of statements.
+    ///
+    /// The block is expanded in place wherever it is [called](Call).
+    Block(Box<Block>),
     /// A collection of [`IRExpr`]s.
     Exprs(Vec<Box<IRExpr>>),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// A named block of statements that can be inlined using a [`Call`].
+///
+/// ```ignore
+/// fn name(a: u32, b: u32) {
+///     ...
+/// }
+/// ```
+pub struct Block {
+    /// The name of the block.
+    pub name: Ident,
+    /// The arguments of the block and their types.
+    pub args: Vec<(Ident, Type)>,
+    /// The statements to insert at each call site.
+    pub body: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A match over the value of an integer operand.
+///
+/// ```ignore
+/// match a {
+///     0 => { ... }
+///     1 => { ... }
+///     _ => { ... }
+/// }
+/// ```
+pub struct Match {
+    /// The operand to match on.
+    pub scrutinee: Operand,
+    /// The arms of the match in the order they are tested.
+    pub arms: Vec<MatchArm>,
+    /// The statements to execute if no arm matches.
+    pub default: Option<Vec<Statement>>,
+    /// The type of the scrutinee.
+    pub ty: Option<Type>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A single arm of a [`Match`].
+pub struct MatchArm {
+    /// The integer constant to compare the scrutinee against.
+    pub pattern: LitInt,
+    /// The statements to execute if the scrutinee equals the pattern.
+    pub body: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A range with constant bounds used in a [`Statement::ForRange`] loop.
+pub struct ConstantRange {
+    /// The range to iterate over.
+    ///
+    /// The start of the range defaults to zero.
+    pub range: ExprRange,
+    /// The type of the loop variable.
+    pub ty: Option<Type>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The condition of a [`Statement::While`] loop or a [`Statement::Ite`].
+#[allow(clippy::large_enum_variant)]
+pub enum WhileCondition {
+    /// A single 1-bit operand.
+    Operand(Operand),
+    /// A comparison between two operands.
+    Compare {
+        /// The left hand side of the comparison operation.
+        lhs: Operand,
+        /// The comparison operation to apply.
+        operation: CompareOperation,
+        /// The right hand side of the comparison operation.
+        rhs: Operand,
+        /// The type of the comparison operands.
+        comparison_type: Option<Type>,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq)]
 /// Intermediate representation expression.
 ///
@@ -47,6 +158,8 @@ pub enum IRExpr {
     UnOp(UnOp),
     /// A binary operation.
     BinOp(Box<BinOp>),
+    /// A conditional selection between two values.
+    Ternary(Box<Ternary>),
     /// A simple assignment operation.
     Assign(Assign),
     /// A function call.
@@ -55,4 +168,6 @@ pub enum IRExpr {
     Jump(Jump),
     /// Sets the type of an operand.
     SetType(SetType),
+    /// Inlines a previously declared [`Block`].
+    Call(Call),
 }
@@ -1,6 +1,6 @@
 //! Defines all AST types that concern functions.
 use proc_macro2::{Span, TokenStream};
-use syn::{Expr, Ident, Lit};
+use syn::{Expr, Ident, Lit, LitStr};
 
 use super::{
     operand::{Operand, Type},
@@ -21,6 +21,18 @@ pub enum Function {
     Intrinsic(Box<Intrinsic>),
 }
 
+/// A call to a previously declared [`Block`](crate::ast::Block).
+///
+/// The arguments are substituted for the arguments of the block and the
+/// body of the block is expanded in place.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    /// The name of the block to call.
+    pub name: Ident,
+    /// The operands to pass to the block.
+    pub args: Vec<Operand>,
+}
+
 /// A simple representation of a normal rust function call
 ///
 /// These refer to functions outside of the macro call.
@@ -92,6 +104,12 @@ pub enum Intrinsic {
     /// Aborts the current path returning a message to the user.
     Abort(Abort),
 
+    /// Discards the current path if the condition can not hold.
+    Assume(Assume),
+
+    /// Aborts the current path if the condition can be false.
+    Assert(Assert),
+
     /// Computes the absolute value of a value.
     Abs(Abs),
     // /// Determines whether or not a fp value is normal.
@@ -105,6 +123,9 @@ pub enum Intrinsic {
     /// Logs a message to the terminal.
     Log(Log),
 
+    /// An annotation that is logged at debug level when executed.
+    Comment(Comment),
+
     // FP specific
     /// Checks if a value is NaN.
     IsNaN(IsNaN),
@@ -309,6 +330,22 @@ pub struct Abort {
     pub inner: TokenStream,
 }
 
+/// Constrains the current path such that the condition holds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assume {
+    /// The boolean condition to assume.
+    pub condition: Operand,
+}
+
+/// Aborts the current path if the condition can be false.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assert {
+    /// The boolean condition to check.
+    pub condition: Operand,
+    /// The condition as written in the source, used in the error message.
+    pub text: String,
+}
+
 /// Computes the square root of an operand.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Sqrt {
@@ -357,6 +394,15 @@ pub struct MultiplyAndAccumulate {
     pub addend: Operand,
 }
 
+/// An annotation in the pseudo code.
+///
+/// Written either as `Comment("...")` or as a doc comment `/// ...`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// The text of the comment.
+    pub text: LitStr,
+}
+
 impl PartialEq for Abort {
     fn eq(&self, other: &Self) -> bool {
         self.inner.to_string() == other.inner.to_string()
@@ -13,6 +13,8 @@ pub enum Type {
     I(u32),
     /// A unsigned integer.
     U(u32),
+    /// A single bit truth value.
+    Bool,
     /// A 16 bit floating point value.
     F16,
     /// A 32 bit floating point value.
@@ -25,6 +27,25 @@ pub enum Type {
     Unit,
 }
 
+impl Type {
+    /// Returns true if the type is one of the floating point types.
+    pub const fn is_float(&self) -> bool {
+        matches!(self, Self::F16 | Self::F32 | Self::F64 | Self::F128)
+    }
+
+    /// Returns true if a value of type `other` can be used where a value of
+    /// this type is expected.
+    ///
+    /// Booleans are single bit unsigned values and can be mixed with them.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        self == other
+            || matches!(
+                (self, other),
+                (Self::Bool, Self::U(1)) | (Self::U(1), Self::Bool)
+            )
+    }
+}
+
 impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match self {
@@ -33,8 +54,9 @@ impl Display for Type {
             Self::F64 => "f64".to_string(),
             Self::F128 => "f128".to_string(),
             Self::Unit => "()".to_string(),
+            Self::Bool => "bool".to_string(),
             Self::I(bits) => format!("i{bits}"),
-            Self::U(bits) => format!("i{bits}"),
+            Self::U(bits) => format!("u{bits}"),
         })
     }
 }
@@ -50,6 +72,8 @@ pub enum Operand {
     FieldExtract((FieldExtract, Option<Type>)),
     /// Field extraction.
     DynamicFieldExtract((DynamicFieldExtract, Option<Type>)),
+    /// Reads an element from memory, the type is the element type.
+    Index((Index, Option<Type>)),
 
     /// A wrapped literal.
     WrappedLiteral(WrappedLiteral),
@@ -138,6 +162,23 @@ pub struct DynamicFieldExtract {
     pub ty: Option<syn::Type>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// Indexes in to a table in memory.
+///
+/// ```ignore
+/// base[index{:element type}]
+/// ```
+///
+/// The element is read from `base + index * element size`.
+pub struct Index {
+    /// The operand holding the address of the first element.
+    pub base: Ident,
+    /// Which element to access.
+    pub index: Box<Operand>,
+    /// The type of the base address.
+    pub base_ty: Option<Type>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 /// Sets the operand type.
 pub struct SetType {
@@ -39,6 +39,10 @@ pub enum BinaryOperation {
     LogicalLeftShift,
     LogicalRightShift,
     ArithmeticRightShift,
+    /// Logical and (&&) of two boolean values.
+    LogicalAnd,
+    /// Logical or (||) of two boolean values.
+    LogicalOr,
     /// Compares two values.
     Compare(CompareOperation),
 }
@@ -124,6 +128,26 @@ pub struct UnOp {
     pub rhs: Operand,
 }
 
+/// Selects one of two values based on a 1-bit condition.
+///
+/// This is syntactically equivalent to
+/// ```ignore
+/// a = cond ? b : c;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ternary {
+    /// Where to store the result.
+    pub dest: Operand,
+    /// The condition to select the value based on.
+    pub condition: Operand,
+    /// The value to store if the condition holds.
+    pub then: Operand,
+    /// The value to store if the condition does not hold.
+    pub otherwise: Operand,
+    /// The type of the result.
+    pub result_ty: Option<crate::ast::operand::Type>,
+}
+
 /// A binary operation.
 ///
 /// This is syntactically equivalent to
@@ -0,0 +1,266 @@
+//! Simplifies the [`IR`] before it is lowered.
+//!
+//! Binary operations where both operands are literals of a known type are
+//! folded in to a single assignment and operations that would lower to a
+//! [`Nop`](general_assembly::operation::Operation::Nop) are removed.
+
+use syn::{Lit, LitInt};
+
+use crate::ast::{
+    operand::{Operand, Type, WrappedLiteral},
+    operations::{Assign, BinOp, BinaryOperation},
+    IRExpr,
+    Statement,
+    IR,
+};
+
+impl IR {
+    /// Folds constant operations and removes no-ops.
+    ///
+    /// This should be called after type checking as the types of the
+    /// destinations are needed to fold an operation.
+    pub fn filter(&mut self) {
+        filter_statements(&mut self.extensions);
+    }
+}
+
+impl Assign {
+    /// Returns true if the assignment copies a value in to itself.
+    pub fn is_nop(&self) -> bool {
+        match (&self.dest, &self.rhs) {
+            (Operand::Expr((i1, _)), Operand::Expr((i2, _))) => *i1 == *i2,
+            (Operand::Ident((i1, _)), Operand::Ident((i2, _))) => *i1 == *i2,
+            (Operand::FieldExtract((i1, _)), Operand::FieldExtract((i2, _))) => *i1 == *i2,
+            _ => false,
+        }
+    }
+}
+
+fn filter_statements(statements: &mut Vec<Statement>) {
+    for statement in statements.iter_mut() {
+        match statement {
+            Statement::If(_, happy_case, sad_case) | Statement::Ite(_, happy_case, sad_case) => {
+                filter_statements(happy_case);
+                if let Some(sad_case) = sad_case {
+                    filter_statements(sad_case);
+                }
+            }
+            Statement::For(_, _, body)
+            | Statement::ForRange(_, _, body)
+            | Statement::While(_, body) => filter_statements(body),
+            Statement::Match(m) => {
+                for arm in m.arms.iter_mut() {
+                    filter_statements(&mut arm.body);
+                }
+                if let Some(default) = &mut m.default {
+                    filter_statements(default);
+                }
+            }
+            Statement::Block(block) => filter_statements(&mut block.body),
+            Statement::Exprs(exprs) => {
+                for expr in exprs.iter_mut() {
+                    fold(expr);
+                }
+                exprs.retain(|expr| !is_nop(expr));
+            }
+        }
+    }
+    statements.retain(|statement| match statement {
+        Statement::Exprs(exprs) => !exprs.is_empty(),
+        _ => true,
+    });
+}
+
+fn is_nop(expr: &IRExpr) -> bool {
+    match expr {
+        IRExpr::SetType(_) => true,
+        IRExpr::Assign(assign) => assign.is_nop(),
+        _ => false,
+    }
+}
+
+/// Replaces a binary operation on two literals with an assignment of the
+/// result.
+fn fold(expr: &mut IRExpr) {
+    let IRExpr::BinOp(binop) = expr else {
+        return;
+    };
+    let Some(value) = fold_binop(binop) else {
+        return;
+    };
+    *expr = IRExpr::Assign(Assign {
+        dest: binop.dest.clone(),
+        rhs: Operand::WrappedLiteral(value),
+    });
+}
+
+fn fold_binop(binop: &BinOp) -> Option<WrappedLiteral> {
+    let (lhs, lit, ty) = literal(&binop.lhs)?;
+    let (rhs, _, rhs_ty) = literal(&binop.rhs)?;
+    if ty != rhs_ty || known_type(&binop.dest)? != ty {
+        return None;
+    }
+    // Both operands are stored as their bit patterns, only division depends on
+    // the signedness of the operands.
+    let (Type::U(bits) | Type::I(bits)) = ty else {
+        return None;
+    };
+    let mask = u128::MAX >> (128 - bits);
+    let value = match binop.op {
+        BinaryOperation::Add => lhs.wrapping_add(rhs),
+        BinaryOperation::Sub => lhs.wrapping_sub(rhs),
+        BinaryOperation::Mul => lhs.wrapping_mul(rhs),
+        BinaryOperation::Div if rhs != 0 => match ty {
+            Type::I(_) => {
+                let (lhs, rhs) = (sign_extend(lhs, bits), sign_extend(rhs, bits));
+                // The quotient of the smallest value and -1 does not fit.
+                if lhs == i128::MIN >> (128 - bits) && rhs == -1 {
+                    return None;
+                }
+                (lhs / rhs) as u128
+            }
+            _ => lhs / rhs,
+        },
+        BinaryOperation::BitwiseAnd => lhs & rhs,
+        BinaryOperation::BitwiseOr => lhs | rhs,
+        BinaryOperation::BitwiseXor => lhs ^ rhs,
+        BinaryOperation::LogicalLeftShift if rhs < bits as u128 => lhs << rhs,
+        BinaryOperation::LogicalRightShift if rhs < bits as u128 => lhs >> rhs,
+        _ => return None,
+    } & mask;
+    let repr = match ty {
+        Type::I(_) => format!("{}i{bits}", sign_extend(value, bits)),
+        _ => format!("{value}u{bits}"),
+    };
+    Some(WrappedLiteral {
+        val: Lit::Int(LitInt::new(&repr, lit.span())),
+        ty,
+    })
+}
+
+/// Interprets the lowest `bits` bits of `value` as a two's complement number.
+fn sign_extend(value: u128, bits: u32) -> i128 {
+    ((value << (128 - bits)) as i128) >> (128 - bits)
+}
+
+/// Returns the bit pattern of a literal operand if it is an integer with a
+/// width that can be lowered.
+fn literal(operand: &Operand) -> Option<(u128, &LitInt, Type)> {
+    let Operand::WrappedLiteral(WrappedLiteral {
+        val: Lit::Int(lit),
+        ty,
+    }) = operand
+    else {
+        return None;
+    };
+    let value = match ty {
+        Type::U(8 | 16 | 32 | 64) => lit.base10_parse::<u128>().ok()?,
+        Type::I(bits @ (8 | 16 | 32 | 64)) => {
+            let value = lit.base10_parse::<i128>().ok()?;
+            value as u128 & (u128::MAX >> (128 - bits))
+        }
+        _ => return None,
+    };
+    Some((value, lit, *ty))
+}
+
+fn known_type(operand: &Operand) -> Option<Type> {
+    match operand {
+        Operand::Expr((_, ty))
+        | Operand::Ident((_, ty))
+        | Operand::FieldExtract((_, ty))
+        | Operand::DynamicFieldExtract((_, ty))
+        | Operand::Index((_, ty)) => *ty,
+        Operand::WrappedLiteral(lit) => Some(lit.ty),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proc_macro2::TokenStream;
+
+    use crate::{ast::IR, TypeCheck, TypeCheckMeta};
+
+    fn lower(input: &str, filter: bool) -> String {
+        let mut ir: IR = syn::parse_str(input).unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        if filter {
+            ir.filter();
+        }
+        let lowered: Result<TokenStream, _> = ir.into();
+        lowered.unwrap().to_string()
+    }
+
+    #[test]
+    fn folds_constant_operations() {
+        let input = "[
+            a:u32;
+            let b = 2u32 + 3u32;
+            let c = 0xf0u32 << 28u32;
+            a = b + c;
+            a = a + 1u32;
+        ]";
+        let unfolded = lower(input, false);
+        let folded = lower(input, true);
+
+        assert_eq!(unfolded.matches("Operation :: Add").count(), 3);
+        assert_eq!(unfolded.matches("Operation :: Sl {").count(), 1);
+        // Only the operations on literals are folded.
+        assert_eq!(folded.matches("Operation :: Add").count(), 2);
+        assert_eq!(folded.matches("Operation :: Sl {").count(), 0);
+        assert!(folded.contains("Word32 (5u32"));
+        assert!(folded.contains("Word32 (0u32"));
+    }
+
+    #[test]
+    fn removes_nops() {
+        let input = "[
+            a:u32;
+            b:u32;
+            a = a;
+            if (true) {
+                b = b;
+                a = b;
+            }
+        ]";
+        let unfolded = lower(input, false);
+        let folded = lower(input, true);
+
+        assert_eq!(unfolded.matches("Operation :: Nop").count(), 4);
+        assert_eq!(folded.matches("Operation :: Nop").count(), 0);
+        assert_eq!(
+            unfolded.matches("Operation :: Move").count(),
+            folded.matches("Operation :: Move").count()
+        );
+    }
+
+    #[test]
+    fn folds_signed_operations() {
+        let input = "[
+            a:i32;
+            b:i32;
+            c:i32;
+            a = 2i32 - 5i32;
+            b = -7i32 / 2i32;
+            c = -16i32 >> 28i32;
+        ]";
+        let unfolded = lower(input, false);
+        let folded = lower(input, true);
+
+        assert_eq!(unfolded.matches("Operation :: Sub").count(), 1);
+        assert_eq!(unfolded.matches("Operation :: SDiv").count(), 1);
+        assert_eq!(unfolded.matches("Operation :: Srl").count(), 1);
+        assert_eq!(folded.matches("Operation :: Move").count(), 3);
+
+        let assigned = |dest: &str, value: &str| {
+            folded.contains(&format!(
+                "destination : {dest} . clone () , source : general_assembly :: operand :: Operand :: Immediate (general_assembly :: prelude :: DataWord :: Word32 (({value}) . cast_unsigned ()))"
+            ))
+        };
+        assert!(assigned("a", "- 3i32"));
+        // Division truncates towards zero.
+        assert!(assigned("b", "- 3i32"));
+        // The right shift is logical for signed operands as well.
+        assert!(assigned("c", "15i32"));
+    }
+}
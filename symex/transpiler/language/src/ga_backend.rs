@@ -7,11 +7,13 @@ pub mod operations;
 
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use syn::Ident;
+use syn::{spanned::Spanned, Expr, ExprLit, Ident, Lit, LitInt, RangeLimits};
 
 use crate::{
     ast::{
         operand::{Type, WrappedLiteral},
+        function::Call,
+        operations::CompareOperation,
         *,
     },
     Compile,
@@ -41,7 +43,7 @@ impl From<IR> for Result<TokenStream, Error> {
             ),
             None => quote!(
                 {
-                    let mut ret =  Vec::new();
+                    let mut ret: Vec<general_assembly::operation::Operation> = Vec::new();
                     #(#declarations)*
                     #(#ext;)*
                     ret
@@ -62,9 +64,14 @@ impl Compile for IRExpr {
             Self::Assign(assign) => assign.compile(state),
             Self::UnOp(unop) => unop.compile(state),
             Self::BinOp(binop) => binop.compile(state),
+            Self::Ternary(ternary) => ternary.compile(state),
             Self::Function(f) => (f.clone(), Type::Unit).compile(state),
             Self::Jump(j) => j.compile(state),
             Self::SetType(_) => Ok(quote! {general_assembly::operation::Operation::Nop}),
+            Self::Call(call) => Err(Error::InternalError(format!(
+                "Call to {} must be expanded as a statement",
+                call.name
+            ))),
         }
     }
 }
@@ -84,6 +91,10 @@ impl Type {
                 size:#size,
                 signed:false,
             }},
+            Self::Bool => quote! {general_assembly::extension::ieee754::OperandType::Integral {
+                size:1u32,
+                signed:false,
+            }},
             Self::Unit => {
                 quote! {compile_error!("Cannot use a unit type as a floating point value")}
             }
@@ -94,7 +105,7 @@ impl Type {
         let name_str = i.to_string();
         let fp = self.fp_name();
         match self {
-            Self::I(_) | Self::U(_) => {
+            Self::I(_) | Self::U(_) | Self::Bool => {
                 quote! {let #i = general_assembly::operand::Operand::Local(#name_str.to_string());}
             }
             Self::F16 | Self::F32 | Self::F64 | Self::F128 => {
@@ -178,6 +189,31 @@ impl Compile for (Ident, Statement) {
                     }
                 ))
             }
+            Statement::Ite(condition, happy_case_in, sad_case_in) => {
+                state.enter_scope();
+                let (compute_condition, condition) = condition.compile(state)?;
+                let happy_case = Match::body(*happy_case_in, state)?;
+                let sad_case = match sad_case_in {
+                    Some(sad_case_in) => Match::body(*sad_case_in, state)?,
+                    None => quote!(Vec::new()),
+                };
+                let to_declare_condition: Vec<WrappedLocalDeclaration> =
+                    state.to_declare()?.iter().map(|el| el.into()).collect();
+                let ret = self.0.clone();
+                Ok(quote!(
+                    {
+                        #(#to_declare_condition)*
+                        #ret.extend([
+                            #(#compute_condition,)*
+                            general_assembly::operation::Operation::Ite {
+                                condition: #condition,
+                                then: #happy_case,
+                                otherwise: #sad_case,
+                            },
+                        ])
+                    }
+                ))
+            }
             Statement::For(i, e, block_in) => {
                 state.enter_scope();
                 let mut block: Vec<TokenStream> = Vec::new();
@@ -193,12 +229,197 @@ impl Compile for (Ident, Statement) {
                     }
                 ))
             }
+            Statement::ForRange(i, range, block_in) => {
+                let ty = range
+                    .ty
+                    .expect("Range type must be known. Type checker must be faulty");
+                let start = range.bound(&range.range.start, state)?;
+                let end = range.bound(&range.range.end, state)?;
+                let operation = (
+                    match range.range.limits {
+                        RangeLimits::Closed(_) => CompareOperation::Leq,
+                        RangeLimits::HalfOpen(_) => CompareOperation::Lt,
+                    },
+                    ty,
+                )
+                    .compile(state)?;
+                let one = WrappedLiteral {
+                    val: ConstantRange::literal("1", ty, i.span()),
+                    ty,
+                }
+                .compile(state)?;
+
+                state.enter_scope();
+                state.declare_local(i.clone(), ty)?;
+                state.access(i.clone());
+                let condition = state
+                    .intermediate(Type::U(1), range.range.span())
+                    .compile(state)?;
+
+                state.enter_scope();
+                let body = format_ident!("while_body");
+                let mut block: Vec<TokenStream> = Vec::new();
+                for el in (*block_in).into_iter() {
+                    block.push((body.clone(), el).compile(state)?);
+                }
+                let to_declare_inner: Vec<WrappedLocalDeclaration> =
+                    state.to_declare()?.iter().map(|el| el.into()).collect();
+                let to_declare_loop: Vec<WrappedLocalDeclaration> =
+                    state.to_declare()?.iter().map(|el| el.into()).collect();
+                let ret = self.0.clone();
+                Ok(quote!(
+                    {
+                        #(#to_declare_loop)*
+                        #ret.push(general_assembly::operation::Operation::Move {
+                            destination: #i.clone(),
+                            source: #start,
+                        });
+                        #ret.push(general_assembly::operation::Operation::While {
+                            compute_condition: vec![general_assembly::operation::Operation::Compare {
+                                lhs: #i.clone(),
+                                rhs: #end,
+                                operation: #operation,
+                                destination: #condition,
+                            }],
+                            condition: #condition,
+                            operations: {
+                                let mut #body = Vec::new();
+                                #(#to_declare_inner)*
+                                #(#block;)*
+                                #body.push(general_assembly::operation::Operation::Add {
+                                    destination: #i.clone(),
+                                    operand1: #i.clone(),
+                                    operand2: #one,
+                                });
+                                #body
+                            },
+                        })
+                    }
+                ))
+            }
+            Statement::While(condition, block_in) => {
+                state.enter_scope();
+                let (compute_condition, condition) = condition.compile(state)?;
+
+                state.enter_scope();
+                let body = format_ident!("while_body");
+                let mut block: Vec<TokenStream> = Vec::new();
+                for el in (*block_in).into_iter() {
+                    block.push((body.clone(), el).compile(state)?);
+                }
+                let to_declare_inner: Vec<WrappedLocalDeclaration> =
+                    state.to_declare()?.iter().map(|el| el.into()).collect();
+                let to_declare_condition: Vec<WrappedLocalDeclaration> =
+                    state.to_declare()?.iter().map(|el| el.into()).collect();
+                let ret = self.0.clone();
+                Ok(quote!(
+                    {
+                        #(#to_declare_condition)*
+                        #ret.push(general_assembly::operation::Operation::While {
+                            compute_condition: vec![#(#compute_condition),*],
+                            condition: #condition,
+                            operations: {
+                                let mut #body = Vec::new();
+                                #(#to_declare_inner)*
+                                #(#block;)*
+                                #body
+                            },
+                        })
+                    }
+                ))
+            }
+            Statement::Match(m) => {
+                let ty =
+                    m.ty.expect("Match type must be known. Type checker must be faulty");
+                state.enter_scope();
+                state.access_operand(m.scrutinee.clone());
+                let scrutinee = m.scrutinee.compile(state)?;
+                let compute_scrutinee: Vec<TokenStream> = state.to_insert_above.drain(..).collect();
+                let operation = (CompareOperation::Eq, ty).compile(state)?;
+
+                let mut arms = Vec::with_capacity(m.arms.len());
+                for arm in m.arms {
+                    let span = arm.pattern.span();
+                    let pattern = WrappedLiteral {
+                        val: ConstantRange::literal(arm.pattern.base10_digits(), ty, span),
+                        ty,
+                    }
+                    .compile(state)?;
+                    let condition = state.intermediate(Type::U(1), span).compile(state)?;
+                    let body = Match::body(arm.body, state)?;
+                    arms.push((pattern, condition, body));
+                }
+
+                // Lower to a chain of Ites where each arm is tested in the otherwise
+                // branch of the previous arm.
+                let mut chain = match m.default {
+                    Some(default) => Match::body(default, state)?,
+                    None => quote!(Vec::new()),
+                };
+                for (pattern, condition, body) in arms.into_iter().rev() {
+                    chain = quote!(vec![
+                        general_assembly::operation::Operation::Compare {
+                            lhs: #scrutinee,
+                            rhs: #pattern,
+                            operation: #operation,
+                            destination: #condition,
+                        },
+                        general_assembly::operation::Operation::Ite {
+                            condition: #condition,
+                            then: #body,
+                            otherwise: #chain,
+                        },
+                    ]);
+                }
+                let to_declare_match: Vec<WrappedLocalDeclaration> =
+                    state.to_declare()?.iter().map(|el| el.into()).collect();
+                let ret = self.0.clone();
+                let compute_scrutinee = match compute_scrutinee.is_empty() {
+                    true => quote!(),
+                    false => quote!(#ret.extend([#(#compute_scrutinee),*]);),
+                };
+                Ok(quote!(
+                    {
+                        #(#to_declare_match)*
+                        #compute_scrutinee
+                        #ret.extend(#chain)
+                    }
+                ))
+            }
+            Statement::Block(block) => {
+                state.blocks.insert(block.name.to_string(), *block);
+                Ok(quote!({}))
+            }
             Statement::Exprs(extensions) => {
+                let ret = self.0.clone();
+                let mut chunks = Vec::new();
                 let mut ext = Vec::new();
                 for el in extensions {
-                    ext.push(el.compile(state)?);
+                    let call = match *el {
+                        IRExpr::Call(call) => call,
+                        el => {
+                            ext.push(el.compile(state)?);
+                            continue;
+                        }
+                    };
+                    // Arguments might need to be computed before the call, so
+                    // compile them before flushing the preceding expressions.
+                    let mut args = Vec::with_capacity(call.args.len());
+                    for arg in call.args.iter() {
+                        state.access_operand(arg.clone());
+                        args.push(arg.compile(state)?);
+                    }
+                    let to_insert_above: Vec<TokenStream> =
+                        state.to_insert_above.drain(..).collect();
+                    chunks.push(quote!(
+                        #ret.extend([
+                            #(#to_insert_above,)*
+                            #(#ext,)*
+                        ])
+                    ));
+                    ext.clear();
+                    chunks.push(call.inline(ret.clone(), args, state)?);
                 }
-                let ret = self.0.clone();
                 let declarations: Vec<WrappedLocalDeclaration> = state
                     .to_declare
                     .last_mut()
@@ -209,6 +430,7 @@ impl Compile for (Ident, Statement) {
                 let to_insert_above: Vec<TokenStream> = state.to_insert_above.drain(..).collect();
                 Ok(quote!(
                 #(#declarations)*
+                #(#chunks;)*
                 #ret.extend([
                     #(#to_insert_above,)*
                     #(#ext,)*
@@ -220,6 +442,158 @@ impl Compile for (Ident, Statement) {
     }
 }
 
+impl Call {
+    /// Expands the called block in place, binding the compiled arguments to
+    /// the arguments of the block.
+    fn inline(
+        &self,
+        ret: Ident,
+        args: Vec<TokenStream>,
+        state: &mut TranspilerState<TokenStream>,
+    ) -> Result<TokenStream, Error> {
+        let block = match state.blocks.get(&self.name.to_string()) {
+            Some(block) => block.clone(),
+            None => {
+                return Err(Error::UseBeforeDeclaration(self.name.to_string()));
+            }
+        };
+        let names = block.args.iter().map(|(name, _)| name);
+
+        state.enter_scope();
+        let mut body: Vec<TokenStream> = Vec::new();
+        for el in block.body {
+            body.push((ret.clone(), el).compile(state)?);
+        }
+        let to_declare: Vec<WrappedLocalDeclaration> =
+            state.to_declare()?.iter().map(|el| el.into()).collect();
+        Ok(quote!(
+            {
+                #(let #names = #args.clone();)*
+                #(#to_declare)*
+                #(#body;)*
+            }
+        ))
+    }
+}
+
+impl Match {
+    /// Compiles the statements of an arm in to an expression that evaluates
+    /// to the operations of the arm.
+    fn body(
+        statements: Vec<Statement>,
+        state: &mut TranspilerState<TokenStream>,
+    ) -> Result<TokenStream, Error> {
+        state.enter_scope();
+        let body = format_ident!("match_body");
+        let mut block: Vec<TokenStream> = Vec::new();
+        for el in statements {
+            block.push((body.clone(), el).compile(state)?);
+        }
+        let to_declare: Vec<WrappedLocalDeclaration> =
+            state.to_declare()?.iter().map(|el| el.into()).collect();
+        Ok(quote!(
+            {
+                let mut #body = Vec::new();
+                #(#to_declare)*
+                #(#block;)*
+                #body
+            }
+        ))
+    }
+}
+
+impl ConstantRange {
+    /// Creates an integer literal with the suffix of the range type.
+    fn literal(digits: &str, ty: Type, span: proc_macro2::Span) -> Lit {
+        let suffix = match ty {
+            Type::I(bits) => format!("i{bits}"),
+            Type::U(bits) => format!("u{bits}"),
+            _ => unreachable!("Range types are always integers"),
+        };
+        Lit::Int(LitInt::new(&format!("{digits}{suffix}"), span))
+    }
+
+    /// Compiles a bound of the range in to an immediate operand.
+    fn bound(
+        &self,
+        bound: &Option<Box<Expr>>,
+        state: &mut TranspilerState<TokenStream>,
+    ) -> Result<TokenStream, Error> {
+        let ty = self
+            .ty
+            .expect("Range type must be known. Type checker must be faulty");
+        let val = match bound.as_deref() {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            })) => Self::literal(lit.base10_digits(), ty, lit.span()),
+            Some(_) => {
+                return Err(Error::InternalError(
+                    "Range bounds must be literals. Type checker must be faulty".to_string(),
+                ))
+            }
+            None => Self::literal("0", ty, self.range.span()),
+        };
+        WrappedLiteral { val, ty }.compile(state)
+    }
+}
+
+impl WhileCondition {
+    /// Compiles the condition in to the operations that compute it and the
+    /// operand that holds the result.
+    fn compile(
+        &self,
+        state: &mut TranspilerState<TokenStream>,
+    ) -> Result<(Vec<TokenStream>, TokenStream), Error> {
+        match self {
+            Self::Operand(operand) => {
+                state.access_operand(operand.clone());
+                let condition = operand.compile(state)?;
+                Ok((state.to_insert_above.drain(..).collect(), condition))
+            }
+            Self::Compare {
+                lhs,
+                operation,
+                rhs,
+                comparison_type,
+            } => {
+                let span = lhs.span();
+                state.access_operand(lhs.clone());
+                let lhs = lhs.compile(state)?;
+                state.access_operand(rhs.clone());
+                let rhs = rhs.compile(state)?;
+                let ty = comparison_type
+                    .expect("Could not get comparison type. Type checker must be faulty");
+                let operation = (operation.clone(), ty).compile(state)?;
+                let intermediate = state.intermediate(Type::U(1), span).compile(state)?;
+                let compare = match ty {
+                    Type::I(_) | Type::U(_) | Type::Bool => quote! {
+                        general_assembly::operation::Operation::Compare {
+                            lhs: #lhs.clone(),
+                            rhs: #rhs.clone(),
+                            operation: #operation,
+                            destination: #intermediate,
+                        }
+                    },
+                    Type::F16 | Type::F32 | Type::F64 | Type::F128 => quote! {
+                        general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Compare {
+                            lhs: #lhs.clone(),
+                            rhs: #rhs.clone(),
+                            operation: #operation,
+                            destination: #intermediate,
+                            signal: false,
+                        })
+                    },
+                    Type::Unit => quote! {compile_error!("Cannot compare unit types.")},
+                };
+                let mut compute_condition: Vec<TokenStream> =
+                    state.to_insert_above.drain(..).collect();
+                compute_condition.push(compare);
+                Ok((compute_condition, intermediate))
+            }
+        }
+    }
+}
+
 impl crate::ast::operand::Operand {
     fn get_type(&self) -> Type {
         match self {
@@ -228,6 +602,373 @@ impl crate::ast::operand::Operand {
             Self::Ident((_, ty)) => ty.expect("Type checker failed"),
             Self::WrappedLiteral(WrappedLiteral { val: _, ty }) => *ty,
             Self::DynamicFieldExtract((_, ty)) => ty.expect("Type checker failed"),
+            Self::Index((_, ty)) => ty.expect("Type checker failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proc_macro2::TokenStream;
+
+    use crate::{ast::IR, Error, TypeCheck, TypeCheckMeta};
+
+    #[test]
+    fn match_lowering() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                match a {
+                    0 => { b = 10u32; }
+                    1 => { b = 11u32; }
+                    _ => { b = 12u32; }
+                }
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        let lowered = lowered.unwrap().to_string();
+
+        // One compare and one Ite per arm, the default arm has no compare.
+        assert_eq!(lowered.matches("Operation :: Compare").count(), 2);
+        assert_eq!(lowered.matches("Operation :: Ite").count(), 2);
+
+        // Each arm is tested in the otherwise branch of the previous arm.
+        let position = |needle: &str| lowered.find(needle).unwrap();
+        let first_arm = position("Word32 (10u32");
+        let first_otherwise = position("otherwise");
+        let second_pattern = position("Word32 (1u32");
+        let second_arm = position("Word32 (11u32");
+        let default_arm = position("Word32 (12u32");
+        assert!(position("Word32 (0u32") < first_arm);
+        assert!(first_arm < first_otherwise);
+        assert!(first_otherwise < second_pattern);
+        assert!(second_pattern < second_arm);
+        assert!(second_arm < lowered.rfind("otherwise").unwrap());
+        assert!(lowered.rfind("otherwise").unwrap() < default_arm);
+    }
+
+    #[test]
+    fn logical_and_lowering() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                c:u32;
+                let lower = a < b;
+                let upper = b < c;
+                let between = lower && upper;
+                c = between ? a : b;
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        let lowered = lowered.unwrap().to_string();
+
+        assert_eq!(lowered.matches("Operation :: Compare").count(), 2);
+        assert_eq!(lowered.matches("Operation :: And").count(), 1);
+        let position = |needle: &str| lowered.find(needle).unwrap();
+        assert!(position("Operation :: And") < position("Operation :: Ite"));
+    }
+
+    #[test]
+    fn reassigned_local_lowering() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                c:u32;
+                let d = a;
+                d = b;
+                c = d;
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        let lowered = lowered.unwrap().to_string();
+
+        // The local is declared once and both assignments write to it.
+        assert_eq!(lowered.matches("let d =").count(), 1);
+        assert_eq!(lowered.matches("destination : d . clone ()").count(), 2);
+        assert_eq!(lowered.matches("source : d . clone ()").count(), 1);
+    }
+
+    #[test]
+    fn reassigned_local_must_be_read() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                let d = a;
+                d = b;
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        match lowered {
+            Err(Error::UnusedDeclartion(ident)) => assert_eq!(ident, "d"),
+            other => panic!("Expected an unused declaration error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn shadowed_and_used() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                c:u32;
+                let d = a;
+                c = d;
+                if (c == b) {
+                    let d = b;
+                    c = d;
+                }
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        assert!(lowered.is_ok());
+    }
+
+    #[test]
+    fn shadowed_and_unused() {
+        // The read in the inner scope belongs to the inner declaration.
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                c:u32;
+                let d = a;
+                if (c == b) {
+                    let d = b;
+                    c = d;
+                }
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        match lowered {
+            Err(Error::UnusedDeclartion(ident)) => assert_eq!(ident, "d"),
+            other => panic!("Expected an unused declaration error, got {other:?}"),
+        }
+
+        // Declarations shadowed in the same scope must be used before they
+        // are shadowed.
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                c:u32;
+                let d = a;
+                let d = b;
+                c = d;
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        assert!(matches!(lowered, Err(Error::UnusedDeclartion(_))));
+    }
+
+    #[test]
+    fn comparison_lowering() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                c:i32;
+                d:i32;
+                let unsigned = a < b;
+                let signed = c < d;
+                a = unsigned ? a : b;
+                c = signed ? c : d;
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        let lowered = lowered.unwrap().to_string();
+
+        assert_eq!(lowered.matches("Operation :: Compare").count(), 2);
+        assert_eq!(lowered.matches("Comparison :: ULt").count(), 1);
+        assert_eq!(lowered.matches("Comparison :: SLt").count(), 1);
+    }
+
+    #[test]
+    fn comparison_condition_lowering() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:i32;
+                b:i32;
+                c:i32;
+                if (a < b) {
+                    c = a;
+                } else {
+                    c = b;
+                }
+                if (cond) {
+                    c = c + 1i32;
+                }
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        let lowered = lowered.unwrap().to_string();
+
+        // Comparing pseudo code values is evaluated at execution time.
+        assert_eq!(lowered.matches("Operation :: Compare").count(), 1);
+        assert_eq!(lowered.matches("Comparison :: SLt").count(), 1);
+        assert_eq!(lowered.matches("Operation :: Ite").count(), 1);
+        let position = |needle: &str| lowered.find(needle).unwrap();
+        assert!(position("Operation :: Compare") < position("Operation :: Ite"));
+        // Other conditions are still rust expressions.
+        assert_eq!(lowered.matches("if cond").count(), 1);
+    }
+
+    #[test]
+    fn word_size_lowering() {
+        let lower = |word_size: u32| {
+            let mut ir: IR = syn::parse_str(&format!(
+                "#[word_size = {word_size}] [
+                    let a = Register(\"r0\") + Register(\"r3\");
+                    SetNFlag(a);
+                    SetZFlag(a);
+                    Register(\"r1\") = a;
+                    let b = Resize(a, i64);
+                    Register(\"r2\", i64) = b;
+                ]"
+            ))
+            .unwrap();
+            ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+            let lowered: Result<TokenStream, _> = ir.into();
+            lowered.unwrap().to_string()
+        };
+
+        // Only the width of the sign extended register value differs.
+        let half = lower(16);
+        let word = lower(32);
+        assert!(half.contains("sign_bit : 16u32"));
+        assert!(word.contains("sign_bit : 32u32"));
+        assert_eq!(half.replace("sign_bit : 16u32", "sign_bit : 32u32"), word);
+
+        // Without the attribute the word size defaults to 32 bits.
+        let mut ir: IR = syn::parse_str(
+            "[
+                let a = Register(\"r0\") + Register(\"r3\");
+                SetNFlag(a);
+                SetZFlag(a);
+                Register(\"r1\") = a;
+                let b = Resize(a, i64);
+                Register(\"r2\", i64) = b;
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        assert_eq!(lowered.unwrap().to_string(), word);
+    }
+
+    #[test]
+    fn register_width_follows_word_size() {
+        let mut ir: IR = syn::parse_str(
+            "#[word_size = 16] [ let a = Register(\"r0\") + 1u32; Register(\"r1\") = a; ]",
+        )
+        .unwrap();
+        assert!(ir.type_check(&mut TypeCheckMeta::new()).is_err());
+        let mut ir: IR = syn::parse_str(
+            "#[word_size = 16] [ let a = Register(\"r0\") + 1u16; Register(\"r1\") = a; ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+    }
+
+    #[test]
+    fn readme_word_size_example() {
+        let mut ir: IR = syn::parse_str(
+            "#[word_size = 16] ret.extend[
+                let result = Register(\"r0\") + Register(\"r1\");
+                SetNFlag(result);
+                Register(\"r2\") = Resize(result, u16);
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+        let lowered: Result<TokenStream, _> = ir.into();
+        lowered.unwrap();
+    }
+
+    #[test]
+    fn invalid_word_size() {
+        assert!(syn::parse_str::<IR>("#[word_size = 12] [ a:u32; a = a; ]").is_err());
+        assert!(syn::parse_str::<IR>("#[inline] [ a:u32; a = a; ]").is_err());
+    }
+
+    #[test]
+    fn range_loop_lowering() {
+        let lower = |code: &str| {
+            let mut ir: IR = syn::parse_str(code).unwrap();
+            ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+            let lowered: Result<TokenStream, _> = ir.into();
+            lowered.unwrap().to_string()
+        };
+
+        // Literal bounds are evaluated at execution time.
+        let runtime = lower("[ a:u32; for i in 0..4u32 { a = a + i; } ]");
+        assert_eq!(runtime.matches("Operation :: While").count(), 1);
+
+        // Any other bound is a rust range that is unrolled at compile time.
+        let unrolled = lower("[ a:u32; for i in 0..n { a = a + 1u32; } ]");
+        assert!(unrolled.contains("for i in 0 .. n"));
+        assert!(!unrolled.contains("Operation :: While"));
+    }
+
+    #[test]
+    fn int_float_assignment_lowering() {
+        use crate::{
+            ast::{
+                operand::{ExprOperand, Operand, Type},
+                operations::Assign,
+            },
+            Compile,
+            TranspilerState,
+        };
+
+        // The type checker requires an explicit conversion so the assignment
+        // is constructed directly.
+        let lower = |dest: &str, dest_ty: Type, rhs: &str, rhs_ty: Type| {
+            let operand = |name: &str, ty: Type| {
+                Operand::Expr((ExprOperand::Ident(syn::parse_str(name).unwrap()), Some(ty)))
+            };
+            let assign = Assign {
+                dest: operand(dest, dest_ty),
+                rhs: operand(rhs, rhs_ty),
+            };
+            assign
+                .compile(&mut TranspilerState::new())
+                .unwrap()
+                .to_string()
+        };
+        let core_operand = |operand: &str, signed: bool| {
+            format!("CoreOperand {{ operand : {operand} . clone () , ty : general_assembly :: extension :: ieee754 :: OperandType :: Binary32 , signed : {signed} }}")
+        };
+
+        for (ty, signed) in [(Type::U(32), false), (Type::I(32), true)] {
+            let to_float = lower("x", Type::F32, "r", ty);
+            assert!(to_float.contains("destination : x . clone ()"));
+            assert!(to_float.contains(&core_operand("r", signed)));
+
+            let from_float = lower("r", ty, "x", Type::F32);
+            assert!(from_float.contains("source : x . clone ()"));
+            assert!(from_float.contains(&core_operand("r", signed)));
         }
     }
 }
@@ -45,6 +45,8 @@ impl Compile for (Intrinsic, Type) {
             Intrinsic::Sra(s) => s.compile(state),
             Intrinsic::Ite(i) => i.compile(state),
             Intrinsic::Abort(a) => a.compile(state),
+            Intrinsic::Assume(a) => a.compile(state),
+            Intrinsic::Assert(a) => a.compile(state),
             Intrinsic::Abs(a) => a.compile(state),
             Intrinsic::Sqrt(s) => s.compile(state),
             Intrinsic::Cast(c) => c.compile(state),
@@ -52,6 +54,7 @@ impl Compile for (Intrinsic, Type) {
             Intrinsic::IsNormal(i) => i.compile(state),
             Intrinsic::IsFinite(i) => i.compile(state),
             Intrinsic::Log(l) => l.compile(state),
+            Intrinsic::Comment(c) => c.compile(state),
             Intrinsic::MultiplyAndAccumulate(m) => m.compile(state),
             // Intrinsic::Saturate(s) => s.compile(state),
         }
@@ -317,8 +320,12 @@ impl Compile for Resize {
     type Output = TokenStream;
 
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
-        let intermediate = state.intermediate(self.target_ty).compile(state)?;
-        let source_ty = self.operand.get_type();
+        let intermediate = state.intermediate(self.target_ty, self.operand.span()).compile(state)?;
+        // Booleans are resized as single bit unsigned values.
+        let source_ty = match self.operand.get_type() {
+            Type::Bool => Type::U(1),
+            ty => ty,
+        };
         let operand = self.operand.compile(state)?;
         state.access_operand(self.operand.clone());
 
@@ -459,6 +466,9 @@ impl Compile for Resize {
             (Type::Unit, _) => {
                 return Ok(quote! {compile_error!("Cannot resize from a unit type")})
             }
+            (Type::Bool, _) | (_, Type::Bool) => {
+                return Ok(quote! {compile_error!("Cannot resize in to a boolean, use a comparison instead")})
+            }
             (_, Type::Unit) => {
                 return Ok(quote! {compile_error!("Cannot resize in to a unit type")})
             }
@@ -471,7 +481,7 @@ impl Compile for SignExtend {
     type Output = TokenStream;
 
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
-        let intermediate = state.intermediate(self.operand.get_type()).compile(state)?;
+        let intermediate = state.intermediate(self.operand.get_type(), self.operand.span()).compile(state)?;
         let operand = self.operand.compile(state)?;
         let sign_bit = self.sign_bit.clone();
         let size = self.target_size;
@@ -492,7 +502,7 @@ impl Compile for ZeroExtend {
     type Output = TokenStream;
 
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
-        let intermediate = state.intermediate(Type::U(self.bits)).compile(state)?;
+        let intermediate = state.intermediate(Type::U(self.bits), self.operand.span()).compile(state)?;
         let operand = self.operand.compile(state)?;
         state.access_operand(self.operand.clone());
         let bits = self.bits;
@@ -511,7 +521,7 @@ impl Compile for Sra {
     type Output = TokenStream;
 
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
-        let intermediate = state.intermediate(self.operand.get_type()).compile(state)?;
+        let intermediate = state.intermediate(self.operand.get_type(), self.operand.span()).compile(state)?;
         let operand = self.operand.compile(state)?;
         state.access_operand(self.operand.clone());
         let shift = self.n.clone();
@@ -528,14 +538,14 @@ impl Compile for Ror {
     type Output = TokenStream;
 
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
-        let intermediate = state.intermediate(self.operand.get_type()).compile(state)?;
+        let intermediate = state.intermediate(self.operand.get_type(), self.operand.span()).compile(state)?;
         let operand = self.operand.compile(state)?;
         let shift = self.n.clone();
         state
             .to_insert_above
-            .push(quote!(general_assembly::operation::Operation::Sror {
+            .push(quote!(general_assembly::operation::Operation::RotateRight {
                     destination: #intermediate.clone(),
-                    operand: #operand, shift: #shift.clone()
+                    operand: #operand, amount: #shift.clone()
             }));
         Ok(quote!(#intermediate))
     }
@@ -560,9 +570,9 @@ impl Compile for Ite {
         for el in &self.otherwise {
             otherwise.push(el.compile(state)?);
         }
-        let intermediate = state.intermediate(Type::U(1)).compile(state)?;
+        let intermediate = state.intermediate(Type::U(1), self.lhs.span()).compile(state)?;
         let ret = match ty {
-            Type::I(_) | Type::U(_) => {
+            Type::I(_) | Type::U(_) | Type::Bool => {
                 quote! {
                     general_assembly::operation::Operation::Compare {
                          lhs:#lhs.clone(),
@@ -609,13 +619,57 @@ impl Compile for Abort {
     }
 }
 
+impl Compile for Assume {
+    type Output = TokenStream;
+
+    fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
+        let condition = self.condition.compile(state)?;
+        let to_insert: Vec<TokenStream> = state.to_insert_above.drain(..).collect();
+        Ok(quote! {
+            #(#to_insert,)*
+            general_assembly::operation::Operation::Assume { condition: #condition }
+        })
+    }
+}
+
+impl Compile for Assert {
+    type Output = TokenStream;
+
+    fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
+        let condition = self.condition.compile(state)?;
+        let to_insert: Vec<TokenStream> = state.to_insert_above.drain(..).collect();
+        let error = format!("Assertion failed: {}", self.text);
+        Ok(quote! {
+            #(#to_insert,)*
+            general_assembly::operation::Operation::Ite {
+                condition: #condition,
+                then: vec![],
+                otherwise: vec![general_assembly::operation::Operation::Abort { error: #error.to_string() }],
+            }
+        })
+    }
+}
+
+impl Compile for Comment {
+    type Output = TokenStream;
+
+    fn compile(&self, _state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
+        let text = self.text.value().trim().to_string();
+        Ok(quote_spanned! {self.text.span() => general_assembly::operation::Operation::Log {
+            level: general_assembly::operand::LogLevel::Debug,
+            operand: general_assembly::operand::Operand::Immediate(general_assembly::prelude::DataWord::Bit(false)),
+            meta: #text.to_string(),
+        }})
+    }
+}
+
 impl Compile for Abs {
     type Output = TokenStream;
 
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
         let inner = self.operand.compile(state)?;
         let ty = self.operand.get_type();
-        let intermediate = state.intermediate(ty).compile(state)?;
+        let intermediate = state.intermediate(ty, self.operand.span()).compile(state)?;
         state.to_insert_above.push(match ty {
             Type::F128 | Type::F64 | Type::F32 | Type::F16 => {
                 quote! {general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Abs {
@@ -625,7 +679,7 @@ impl Compile for Abs {
             }
             Type::I(_) => todo!("Compute value of abs."),
             Type::Unit => return Err(Error::InternalError("Type checker faulty, cannot compute absolute value of unit type".to_string())),
-            Type::U(_) => return Ok(quote! {general_assembly::operation::Operation::Nop}),
+            Type::U(_) | Type::Bool => return Ok(quote! {general_assembly::operation::Operation::Nop}),
         });
         Ok(intermediate)
     }
@@ -637,7 +691,7 @@ impl Compile for Sqrt {
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
         let inner = self.operand.compile(state)?;
         let ty = self.operand.get_type();
-        let intermediate = state.intermediate(ty).compile(state)?;
+        let intermediate = state.intermediate(ty, self.operand.span()).compile(state)?;
 
         // TODO: implement for other types.
 
@@ -658,8 +712,9 @@ impl Compile for MultiplyAndAccumulate {
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
         let Self { lhs, rhs, addend } = self;
         let lhs_ty = lhs.get_type();
+        let span = lhs.span();
         let lhs = lhs.compile(state)?;
-        let intermediate = state.intermediate(lhs_ty).compile(state)?;
+        let intermediate = state.intermediate(lhs_ty, span).compile(state)?;
         let rhs = rhs.compile(state)?;
         let add = addend.compile(state)?;
 
@@ -682,7 +737,7 @@ impl Compile for IsFinite {
 
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
         let inner = self.operand.compile(state)?;
-        let intermediate = state.intermediate(Type::U(1)).compile(state)?;
+        let intermediate = state.intermediate(Type::U(1), self.operand.span()).compile(state)?;
 
         state.to_insert_above.push(quote! {
             general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::NonComputational {
@@ -701,7 +756,7 @@ impl Compile for IsNaN {
 
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
         let inner = self.operand.compile(state)?;
-        let intermediate = state.intermediate(Type::U(1)).compile(state)?;
+        let intermediate = state.intermediate(Type::U(1), self.operand.span()).compile(state)?;
 
         state.to_insert_above.push(quote! {
             general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::NonComputational {
@@ -720,7 +775,7 @@ impl Compile for IsNormal {
 
     fn compile(&self, state: &mut TranspilerState<Self::Output>) -> Result<Self::Output, Error> {
         let inner = self.operand.compile(state)?;
-        let intermediate = state.intermediate(Type::U(1)).compile(state)?;
+        let intermediate = state.intermediate(Type::U(1), self.operand.span()).compile(state)?;
 
         state.to_insert_above.push(quote! {
             general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::NonComputational {
@@ -743,7 +798,7 @@ impl Compile for Cast {
         let target_ty = self.target_type;
         let target_ty_fp = target_ty.fp_name();
         let ty_fp = ty.fp_name();
-        let intermediate = state.intermediate(target_ty).compile(state)?;
+        let intermediate = state.intermediate(target_ty, self.operand.span()).compile(state)?;
         state.to_insert_above.push( match (ty, target_ty) {
             (Type::U(16), Type::F16) | (Type::U(32), Type::F32) | (Type::U(64),Type::F64) | (Type::U(128), Type::F128) => {
                 quote! {
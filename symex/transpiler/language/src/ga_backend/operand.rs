@@ -2,6 +2,7 @@
 //! [`Operands`](crate::ast::operand::Operand).
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
+use syn::{Lit, LitInt};
 
 use crate::{ast::operand::*, Compile, Error};
 
@@ -22,10 +23,35 @@ impl Compile for Operand {
             Self::FieldExtract(f) => f.compile(state),
             Self::WrappedLiteral(l) => l.compile(state),
             Self::DynamicFieldExtract(f) => f.compile(state),
+            Self::Index(i) => i.compile(state),
         }
     }
 }
 
+impl Operand {
+    /// Compiles the operand as the destination of an operation.
+    ///
+    /// Assigning to a local does not count as using it, only reads do.
+    pub(crate) fn compile_destination(
+        &self,
+        state: &mut crate::TranspilerState<TokenStream>,
+    ) -> Result<TokenStream, Error> {
+        let ident = match self {
+            Self::Ident((
+                IdentOperand {
+                    define: false,
+                    ident,
+                },
+                _,
+            ))
+            | Self::Expr((ExprOperand::Ident(ident), _)) => ident,
+            _ => return self.compile(state),
+        };
+        let span = ident.span();
+        Ok(quote_spanned!(span => #ident.clone()))
+    }
+}
+
 impl Compile for WrappedLiteral {
     type Output = TokenStream;
 
@@ -123,7 +149,7 @@ impl Compile for (IdentOperand, Type) {
     ) -> Result<Self::Output, Error> {
         match self.0.define {
             // TODO: Inform decare local of type.
-            true => state.declare_local(self.0.ident.clone(), self.1),
+            true => state.declare_local(self.0.ident.clone(), self.1)?,
             false => {
                 state.access(self.0.ident.clone());
             }
@@ -158,7 +184,7 @@ impl Compile for (DynamicFieldExtract, Option<Type>) {
         &self,
         state: &mut crate::TranspilerState<Self::Output>,
     ) -> Result<Self::Output, Error> {
-        let intermediate = state.intermediate(self.1.expect("Bitfield extractions cannot be performed if the type is not known. Type checker must be faulty")).compile(state)?;
+        let intermediate = state.intermediate(self.1.expect("Bitfield extractions cannot be performed if the type is not known. Type checker must be faulty"), self.0.span()).compile(state)?;
         let operand = self.0.operand.clone();
         state.access(operand.clone());
         let (start, end) = (
@@ -185,7 +211,7 @@ impl Compile for (FieldExtract, Option<Type>) {
         &self,
         state: &mut crate::TranspilerState<Self::Output>,
     ) -> Result<Self::Output, Error> {
-        let intermediate = state.intermediate(self.1.expect("Bitfield extractions cannot be performed if the type is not known. Type checker must be faulty")).compile(state)?;
+        let intermediate = state.intermediate(self.1.expect("Bitfield extractions cannot be performed if the type is not known. Type checker must be faulty"), self.0.span()).compile(state)?;
         let operand = self.0.operand.clone();
         state.access(operand.clone());
         let (start, end) = (self.0.start, self.0.end);
@@ -201,3 +227,54 @@ impl Compile for (FieldExtract, Option<Type>) {
         Ok(quote! {#intermediate})
     }
 }
+
+impl Compile for (Index, Option<Type>) {
+    type Output = TokenStream;
+
+    fn compile(
+        &self,
+        state: &mut crate::TranspilerState<Self::Output>,
+    ) -> Result<Self::Output, Error> {
+        let (index, ty) = self;
+        let bits = match ty.expect("Element type must be known. Type checker must be faulty") {
+            Type::U(bits) | Type::I(bits) => bits,
+            _ => {
+                return Err(Error::UnsupportedInstruction(
+                    "Only integer elements can be indexed.".to_string(),
+                ))
+            }
+        };
+        let base_ty = index
+            .base_ty
+            .expect("Base address type must be known. Type checker must be faulty");
+        let span = index.base.span();
+
+        let (address, _) = state.intermediate(base_ty, span);
+        let name = address.ident.to_string();
+        let address = (address, base_ty).compile(state)?;
+        let base = index.base.clone();
+        state.access(base.clone());
+        let offset = index.index.compile(state)?;
+        let size = WrappedLiteral {
+            val: Lit::Int(LitInt::new(&(bits / 8).to_string(), span)),
+            ty: base_ty,
+        }
+        .compile(state)?;
+
+        state.to_insert_above.extend([
+            quote!(general_assembly::operation::Operation::Mul {
+                destination: #address.clone(),
+                operand1: #offset.clone(),
+                operand2: #size,
+            }),
+            quote!(general_assembly::operation::Operation::Add {
+                destination: #address.clone(),
+                operand1: #address.clone(),
+                operand2: #base.clone(),
+            }),
+        ]);
+        Ok(
+            quote_spanned! {span => general_assembly::operand::Operand::AddressInLocal(#name.to_owned(), #bits)},
+        )
+    }
+}
@@ -19,20 +19,10 @@ impl Compile for Assign {
         &self,
         state: &mut crate::TranspilerState<Self::Output>,
     ) -> Result<Self::Output, Error> {
-        match (&self.dest, &self.rhs) {
-            (Operand::Expr((i1, _)), Operand::Expr((i2, _))) if *i1 == *i2 => {
-                return Ok(quote! {general_assembly::operation::Operation::Nop})
-            }
-            (Operand::Ident((i1, _)), Operand::Ident((i2, _))) if *i1 == *i2 => {
-                return Ok(quote! {general_assembly::operation::Operation::Nop})
-            }
-            (Operand::FieldExtract((i1, _)), Operand::FieldExtract((i2, _))) if *i1 == *i2 => {
-                return Ok(quote! {general_assembly::operation::Operation::Nop})
-            }
-
-            _ => {}
-        };
-        let dst: TokenStream = self.dest.compile(state)?;
+        if self.is_nop() {
+            return Ok(quote! {general_assembly::operation::Operation::Nop});
+        }
+        let dst: TokenStream = self.dest.compile_destination(state)?;
         let target_ty = self.dest.get_type();
         let rhs: TokenStream = self.rhs.compile(state)?;
         let rhs_ty = self.rhs.get_type();
@@ -42,13 +32,13 @@ impl Compile for Assign {
             if let Type::U(_size) = rhs_ty {
                 return Ok(quote! {
                     #(#to_insert,)*
-                    general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Copy{ destination: #dst, source: general_assembly::extension::ieee754::OperandStorage::CoreOperand{operand:#rhs, ty:#target_ty, signed:false }})
+                    general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Copy{ destination: #dst, source: general_assembly::extension::ieee754::Operand { ty: #target_ty, value: general_assembly::extension::ieee754::OperandStorage::CoreOperand{operand:#rhs, ty:#target_ty, signed:false }}})
                 });
             }
-            if let Type::U(_size) = rhs_ty {
+            if let Type::I(_size) = rhs_ty {
                 return Ok(quote! {
                     #(#to_insert,)*
-                    general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Copy{ destination: #dst, source: general_assembly::extension::ieee754::OperandStorage::CoreOperand{operand:#rhs, ty:#target_ty, signed:true }})
+                    general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Copy{ destination: #dst, source: general_assembly::extension::ieee754::Operand { ty: #target_ty, value: general_assembly::extension::ieee754::OperandStorage::CoreOperand{operand:#rhs, ty:#target_ty, signed:true }}})
                 });
             }
 
@@ -62,13 +52,13 @@ impl Compile for Assign {
             if let Type::U(_size) = target_ty {
                 return Ok(quote! {
                     #(#to_insert,)*
-                    general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Copy{ source: #dst, destination: general_assembly::extension::ieee754::OperandStorage::CoreOperand{operand:#rhs, ty:#rhs_ty, signed:false }})
+                    general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Copy{ source: #rhs, destination: general_assembly::extension::ieee754::Operand { ty: #rhs_ty, value: general_assembly::extension::ieee754::OperandStorage::CoreOperand{operand:#dst, ty:#rhs_ty, signed:false }}})
                 });
             }
-            if let Type::U(_size) = target_ty {
+            if let Type::I(_size) = target_ty {
                 return Ok(quote! {
                     #(#to_insert,)*
-                    general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Copy{ source: #dst, destination: general_assembly::extension::ieee754::OperandStorage::CoreOperand{operand:#rhs, ty:#rhs_ty, signed:true }})
+                    general_assembly::operation::Operation::Ieee754(general_assembly::extension::ieee754::Operations::Copy{ source: #rhs, destination: general_assembly::extension::ieee754::Operand { ty: #rhs_ty, value: general_assembly::extension::ieee754::OperandStorage::CoreOperand{operand:#dst, ty:#rhs_ty, signed:true }}})
                 });
             }
         }
@@ -86,7 +76,7 @@ impl Compile for UnOp {
         &self,
         state: &mut crate::TranspilerState<Self::Output>,
     ) -> Result<Self::Output, Error> {
-        let dst: TokenStream = self.dest.compile(state)?;
+        let dst: TokenStream = self.dest.compile_destination(state)?;
         let rhs: TokenStream = self.rhs.compile(state)?;
         let ret = match self.op {
             UnaryOperation::BitwiseNot => quote!(
@@ -102,6 +92,44 @@ impl Compile for UnOp {
     }
 }
 
+impl Compile for Ternary {
+    type Output = TokenStream;
+
+    fn compile(
+        &self,
+        state: &mut crate::TranspilerState<Self::Output>,
+    ) -> Result<Self::Output, Error> {
+        let condition: TokenStream = self.condition.compile(state)?;
+        let to_insert: Vec<TokenStream> = state.to_insert_above.drain(..).collect();
+
+        let then = Assign {
+            dest: self.dest.clone(),
+            rhs: self.then.clone(),
+        }
+        .compile(state)?;
+
+        // The destination is already declared by the then branch.
+        let mut dest = self.dest.clone();
+        if let Operand::Ident((ident, _)) = &mut dest {
+            ident.define = false;
+        }
+        let otherwise = Assign {
+            dest,
+            rhs: self.otherwise.clone(),
+        }
+        .compile(state)?;
+
+        Ok(quote! {
+            #(#to_insert,)*
+            general_assembly::operation::Operation::Ite {
+                condition: #condition,
+                then: vec![#then],
+                otherwise: vec![#otherwise],
+            }
+        })
+    }
+}
+
 impl Compile for BinOp {
     type Output = TokenStream;
 
@@ -111,7 +139,7 @@ impl Compile for BinOp {
     ) -> Result<Self::Output, Error> {
         let ty = self.dest.get_type();
 
-        let dst: TokenStream = self.dest.compile(state)?;
+        let dst: TokenStream = self.dest.compile_destination(state)?;
         let rhs: TokenStream = self.rhs.compile(state)?;
         state.access_operand(self.rhs.clone());
         let lhs: TokenStream = self.lhs.compile(state)?;
@@ -129,6 +157,10 @@ impl Compile for BinOp {
             BinaryOperation::BitwiseOr => ty.bvor(lhs, rhs, dst),
             BinaryOperation::BitwiseAnd => ty.bvand(lhs, rhs, dst),
             BinaryOperation::BitwiseXor => ty.bvxor(lhs, rhs, dst),
+            // Both operands are a single bit so the bitwise operations are
+            // equivalent to the logical ones.
+            BinaryOperation::LogicalAnd => ty.bvand(lhs, rhs, dst),
+            BinaryOperation::LogicalOr => ty.bvor(lhs, rhs, dst),
             BinaryOperation::LogicalLeftShift => ty.bvsl(lhs, rhs, dst),
             BinaryOperation::LogicalRightShift => ty.bvlsr(lhs, rhs, dst),
             BinaryOperation::ArithmeticRightShift => ty.bvasr(lhs, rhs, dst),
@@ -136,7 +168,7 @@ impl Compile for BinOp {
                 let ty = self.lhs.get_type();
                 let op = (c.clone(), ty).compile(state)?;
                 match ty {
-                    Type::I(_) | Type::U(_) => quote! {
+                    Type::I(_) | Type::U(_) | Type::Bool => quote! {
                         general_assembly::operation::Operation::Compare {
                             lhs: #lhs,
                             rhs: #rhs,
@@ -186,6 +218,7 @@ impl Type {
                     rhs: #rhs
                 })
             },
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
@@ -202,6 +235,7 @@ impl Type {
             Self::F16 | Self::F32 | Self::F64 | Self::F128 => quote! {
                 compile_error!("Cannot add float with carry");
             },
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
@@ -227,6 +261,7 @@ impl Type {
             Self::F16 | Self::F32 | Self::F64 | Self::F128 => {
                 quote!(compile_error!("saturating add makes no sense for floats."))
             }
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
@@ -252,13 +287,14 @@ impl Type {
             Self::F16 | Self::F32 | Self::F64 | Self::F128 => {
                 quote!(compile_error!("saturating sub makes no sense for floats."))
             }
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
 
     fn bvor(&self, lhs: TokenStream, rhs: TokenStream, dest: TokenStream) -> TokenStream {
         match self {
-            Self::I(_) | Self::U(_) => quote! {
+            Self::I(_) | Self::U(_) | Self::Bool => quote! {
                         general_assembly::operation::Operation::Or {
                             destination: #dest,
                             operand1: #lhs,
@@ -276,7 +312,7 @@ impl Type {
 
     fn bvand(&self, lhs: TokenStream, rhs: TokenStream, dest: TokenStream) -> TokenStream {
         match self {
-            Self::I(_) | Self::U(_) => quote! {
+            Self::I(_) | Self::U(_) | Self::Bool => quote! {
                         general_assembly::operation::Operation::And {
                             destination: #dest,
                             operand1: #lhs,
@@ -307,6 +343,7 @@ impl Type {
                 ))
             }
 
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
@@ -325,6 +362,7 @@ impl Type {
                     "Bitwise operations make no sense for floats."
                 ))
             }
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
@@ -343,13 +381,14 @@ impl Type {
                     "Bitwise operations make no sense for floats."
                 ))
             }
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
 
     fn bvxor(&self, lhs: TokenStream, rhs: TokenStream, dest: TokenStream) -> TokenStream {
         match self {
-            Self::I(_) | Self::U(_) => quote! {
+            Self::I(_) | Self::U(_) | Self::Bool => quote! {
                         general_assembly::operation::Operation::Xor {
                             destination: #dest,
                             operand1: #lhs,
@@ -381,6 +420,7 @@ impl Type {
                     rhs: #rhs
                 })
             },
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
@@ -401,6 +441,7 @@ impl Type {
                     rhs: #rhs
                 })
             },
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
@@ -433,6 +474,7 @@ impl Type {
                         denominator: #denominator
                     })
             },
+            Self::Bool => quote! {compile_error!("Cannot use booleans in arithmetic expressions")},
             Self::Unit => quote! {compile_error!("Cannot use unit types for expressions")},
         }
     }
@@ -451,25 +493,25 @@ impl Compile for (CompareOperation, crate::ast::operand::Type) {
             (CompareOperation::Lt, Type::I(_)) => {
                 quote! {general_assembly::condition::Comparison::SLt}
             }
-            (CompareOperation::Lt, Type::U(_)) => {
+            (CompareOperation::Lt, Type::U(_) | Type::Bool) => {
                 quote! {general_assembly::condition::Comparison::ULt}
             }
             (CompareOperation::Gt, Type::I(_)) => {
                 quote! {general_assembly::condition::Comparison::SGt}
             }
-            (CompareOperation::Gt, Type::U(_)) => {
+            (CompareOperation::Gt, Type::U(_) | Type::Bool) => {
                 quote! {general_assembly::condition::Comparison::UGt}
             }
             (CompareOperation::Leq, Type::I(_)) => {
                 quote! {general_assembly::condition::Comparison::SLeq}
             }
-            (CompareOperation::Leq, Type::U(_)) => {
+            (CompareOperation::Leq, Type::U(_) | Type::Bool) => {
                 quote! {general_assembly::condition::Comparison::ULeq}
             }
             (CompareOperation::Geq, Type::I(_)) => {
                 quote! {general_assembly::condition::Comparison::SGeq}
             }
-            (CompareOperation::Geq, Type::U(_)) => {
+            (CompareOperation::Geq, Type::U(_) | Type::Bool) => {
                 quote! {general_assembly::condition::Comparison::UGeq}
             }
             (CompareOperation::Lt, Type::F16 | Type::F32 | Type::F64 | Type::F128) => {
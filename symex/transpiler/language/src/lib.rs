@@ -7,6 +7,7 @@
 #![allow(clippy::match_like_matches_macro)]
 
 pub mod ast;
+pub mod filter;
 pub mod ga_backend;
 pub mod parser;
 pub mod type_checker;
@@ -15,7 +16,7 @@ use std::collections::HashMap;
 
 use ast::operand::{ExprOperand, FieldExtract, Operand, Type};
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote_spanned, ToTokens};
+use quote::{quote_spanned, ToTokens};
 use syn::Ident;
 
 /// All possible errors that can occur when transpiling against some target.
@@ -59,6 +60,7 @@ struct TranspilerState<T: std::fmt::Debug> {
     to_insert_above: Vec<T>,
     usage_counter: Vec<HashMap<String, (Ident, u32)>>,
     intermediate_counter: u32,
+    blocks: HashMap<String, ast::Block>,
 }
 
 trait Compile {
@@ -110,8 +112,6 @@ impl TypeError {
                 got,
                 span,
             } => {
-                let got = format!("{got:?}");
-                let expected = format!("{expected:?}");
                 let str = format!("Invalid type, expected {expected} but got {got}");
                 quote_spanned! {*span => compile_error!(#str)}
             }
@@ -119,20 +119,32 @@ impl TypeError {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 /// TODO: docs
 pub struct TypeCheckMeta {
     lookup: HashMap<Ident, crate::ast::operand::Type>,
+    /// The argument types of all declared [`Block`](crate::ast::Block)s.
+    signatures: HashMap<Ident, Vec<crate::ast::operand::Type>>,
+    /// The width of registers and otherwise untyped values.
+    word_size: u32,
 }
 impl TypeCheckMeta {
     /// TODO: docs
     pub fn new() -> Self {
         Self {
             lookup: HashMap::new(),
+            signatures: HashMap::new(),
+            word_size: 32,
         }
     }
 }
 
+impl Default for TypeCheckMeta {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// TODO: docs
 pub trait TypeCheck {
     /// TODO: Docs
@@ -149,11 +161,14 @@ impl<T: std::fmt::Debug> TranspilerState<T> {
             to_insert_above: Vec::new(),
             usage_counter: vec![HashMap::new()],
             intermediate_counter: 0,
+            blocks: HashMap::new(),
         }
     }
 
+    /// Returns the number of accesses to the innermost declaration of that
+    /// name.
     fn access_count(&self, name: &String) -> Option<u32> {
-        for scope in self.usage_counter.iter() {
+        for scope in self.usage_counter.iter().rev() {
             if let Some(value) = scope.get(name) {
                 return Some(value.1);
             }
@@ -161,11 +176,13 @@ impl<T: std::fmt::Debug> TranspilerState<T> {
         None
     }
 
-    /// Increments the first occurrence of that name.
+    /// Increments the innermost declaration of that name, any declarations it
+    /// shadows are not accessed.
     fn increment_access(&mut self, name: &String) {
-        for scope in self.usage_counter.iter_mut() {
+        for scope in self.usage_counter.iter_mut().rev() {
             if let Some(value) = scope.get_mut(name) {
                 value.1 += 1;
+                return;
             }
         }
     }
@@ -183,7 +200,7 @@ impl<T: std::fmt::Debug> TranspilerState<T> {
             match self.access_count(&key) {
                 Some(value) => {
                     if value == 0 {
-                        return Err(Error::UnusedDeclartion(Ident::new(&key, Span::call_site())));
+                        return Err(Error::UnusedDeclartion(el.0.clone()));
                     }
                 }
                 None => {
@@ -201,16 +218,25 @@ impl<T: std::fmt::Debug> TranspilerState<T> {
         Ok(to_declare)
     }
 
-    /// Declares a new local variable.
-    pub fn declare_local(&mut self, ident: Ident, ty: Type) {
+    /// Declares a new local variable in the current scope.
+    ///
+    /// If the local shadows a declaration in the same scope that has not been
+    /// used we throw an error, declarations in outer scopes are checked once
+    /// their scope ends.
+    pub fn declare_local(&mut self, ident: Ident, ty: Type) -> Result<(), Error> {
         self.to_declare
             .last_mut()
             .unwrap()
             .push((ident.clone(), ty));
-        self.usage_counter
-            .first_mut()
+        let shadowed = self
+            .usage_counter
+            .last_mut()
             .expect("declare local borked")
             .insert(ident.to_string(), (ident, 0));
+        match shadowed {
+            Some((id, 0)) => Err(Error::UnusedDeclartion(id)),
+            _ => Ok(()),
+        }
     }
 
     /// Accesses the variable by identifier.
@@ -257,8 +283,11 @@ impl<T: std::fmt::Debug> TranspilerState<T> {
     }
 
     /// Declares a new intermediate variable.
-    pub fn intermediate(&mut self, ty: Type) -> (ast::operand::IdentOperand, Type) {
-        let new_ident = format_ident!("intermediate_{}", self.intermediate_counter);
+    ///
+    /// The `span` should point to the source that the intermediate is derived
+    /// from, this is where any errors regarding the intermediate are reported.
+    pub fn intermediate(&mut self, ty: Type, span: Span) -> (ast::operand::IdentOperand, Type) {
+        let new_ident = Ident::new(&format!("intermediate_{}", self.intermediate_counter), span);
         self.to_declare
             .last_mut()
             .expect("Intermediate broken")
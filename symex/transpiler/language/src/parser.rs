@@ -4,24 +4,32 @@ pub mod function;
 pub mod operand;
 pub mod operation;
 
+use quote::ToTokens;
 use syn::{
     parenthesized,
     parse::{discouraged::Speculative, Parse, ParseStream},
+    Attribute,
     Expr,
     Ident,
+    LitInt,
     Result,
     Token,
 };
 
 use self::operations::{BinOp, BinaryOperation};
 use crate::ast::{
+    function::{Call, Function, Intrinsic},
     operand::{Operand, Type},
     *,
 };
 
 impl IR {
     fn parse_internal(input: ParseStream) -> Result<Self> {
-        // Expected syntax : ret.extend[ .. ]
+        // Expected syntax : {#[word_size = N]} ret.extend[ .. ]
+        let mut word_size = None;
+        for attr in input.call(Attribute::parse_outer)? {
+            word_size = Some(Self::word_size(&attr)?);
+        }
         let speculative = input.fork();
         let ret: Option<Ident> = match Ident::parse(&speculative) {
             Ok(ret) => match syn::token::Dot::parse(&speculative) {
@@ -45,14 +53,105 @@ impl IR {
         while !content.is_empty() {
             extensions.push(content.parse()?);
         }
+        resolve_calls(&mut extensions, &mut Vec::new())?;
 
         let ret = Self {
             ret,
             extensions: extensions.into_iter().collect(),
+            word_size,
         };
         Ok(ret)
     }
+
+    /// Parses a `#[word_size = N]` attribute.
+    fn word_size(attr: &Attribute) -> Result<u32> {
+        if let syn::Meta::NameValue(syn::MetaNameValue {
+            path,
+            value:
+                Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(bits),
+                    ..
+                }),
+            ..
+        }) = &attr.meta
+        {
+            if path.is_ident("word_size") {
+                let size = bits.base10_parse::<u32>()?;
+                if ![8, 16, 32, 64].contains(&size) {
+                    return Err(syn::Error::new_spanned(
+                        bits,
+                        "The word size must be 8, 16, 32 or 64 bits",
+                    ));
+                }
+                return Ok(size);
+            }
+        }
+        Err(syn::Error::new_spanned(
+            attr,
+            "Expected a word size attribute, `#[word_size = N]`",
+        ))
+    }
+}
+impl ConstantRange {
+    /// Returns true if all bounds of the range are literals.
+    fn is_constant(range: &syn::ExprRange) -> bool {
+        [&range.start, &range.end]
+            .into_iter()
+            .flatten()
+            .all(|bound| matches!(**bound, Expr::Lit(_)))
+    }
+}
+
+/// Replaces function calls to previously declared [`Block`]s with [`Call`]s.
+///
+/// Blocks are only visible after their declaration and within the scope that
+/// declared them.
+fn resolve_calls(statements: &mut [Statement], declared: &mut Vec<Ident>) -> Result<()> {
+    for statement in statements.iter_mut() {
+        match statement {
+            Statement::If(_, happy_case, sad_case) | Statement::Ite(_, happy_case, sad_case) => {
+                resolve_calls(happy_case, &mut declared.clone())?;
+                if let Some(sad_case) = sad_case {
+                    resolve_calls(sad_case, &mut declared.clone())?;
+                }
+            }
+            Statement::For(_, _, block)
+            | Statement::ForRange(_, _, block)
+            | Statement::While(_, block) => resolve_calls(block, &mut declared.clone())?,
+            Statement::Match(m) => {
+                for arm in m.arms.iter_mut() {
+                    resolve_calls(&mut arm.body, &mut declared.clone())?;
+                }
+                if let Some(default) = &mut m.default {
+                    resolve_calls(default, &mut declared.clone())?;
+                }
+            }
+            Statement::Block(block) => {
+                resolve_calls(&mut block.body, &mut declared.clone())?;
+                declared.push(block.name.clone());
+            }
+            Statement::Exprs(exprs) => {
+                for expr in exprs.iter_mut() {
+                    let (name, args) = match expr.as_ref() {
+                        IRExpr::Function(Function::Ident(name, args))
+                            if declared.contains(name) =>
+                        {
+                            (name.clone(), args)
+                        }
+                        _ => continue,
+                    };
+                    let args = args
+                        .iter()
+                        .map(|arg| syn::parse2(arg.to_token_stream()))
+                        .collect::<Result<Vec<Operand>>>()?;
+                    **expr = IRExpr::Call(Call { name, args });
+                }
+            }
+        }
+    }
+    Ok(())
 }
+
 impl Parse for IR {
     fn parse(input: ParseStream) -> Result<Self> {
         let ret = match Self::parse_internal(input) {
@@ -104,7 +203,7 @@ impl Parse for Statement {
             let _: Token![for] = input.parse()?;
             let var: Ident = input.parse()?;
             let _: Token![in] = input.parse()?;
-            let e: Expr = input.parse()?;
+            let e: Expr = Expr::parse_without_eager_brace(input)?;
             let content;
             syn::braced!(content in input);
             let mut block: Box<Vec<Statement>> = Box::default();
@@ -112,15 +211,116 @@ impl Parse for Statement {
                 let further_values: Statement = content.parse()?;
                 block.push(further_values);
             }
-            return Ok(Self::For(var, e, block));
+            // Only ranges over literals are evaluated at execution time, any
+            // other range is a rust range that is unrolled at compile time.
+            match e {
+                Expr::Range(range) if ConstantRange::is_constant(&range) => {
+                    return Ok(Self::ForRange(
+                        var,
+                        Box::new(ConstantRange { range, ty: None }),
+                        block,
+                    ));
+                }
+                e => return Ok(Self::For(var, e, block)),
+            }
+        }
+        if input.peek(Token![fn]) {
+            let _: Token![fn] = input.parse()?;
+            let name: Ident = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let mut args = Vec::new();
+            while !content.is_empty() {
+                let arg: Ident = content.parse()?;
+                let _: Token![:] = content.parse()?;
+                let ty: Type = content.parse()?;
+                args.push((arg, ty));
+                if !content.is_empty() {
+                    let _: Token![,] = content.parse()?;
+                }
+            }
+            let content;
+            syn::braced!(content in input);
+            let mut body = Vec::new();
+            while !content.is_empty() {
+                body.push(content.parse()?);
+            }
+            return Ok(Self::Block(Box::new(Block { name, args, body })));
+        }
+        if input.peek(Token![match]) {
+            let _: Token![match] = input.parse()?;
+            let scrutinee: Operand = input.parse()?;
+            let content;
+            syn::braced!(content in input);
+            let mut arms = Vec::new();
+            let mut default = None;
+            while !content.is_empty() {
+                if default.is_some() {
+                    return Err(content.error("The default arm must be the last arm"));
+                }
+                let pattern = if content.peek(Token![_]) {
+                    let _: Token![_] = content.parse()?;
+                    None
+                } else {
+                    Some(content.parse::<LitInt>()?)
+                };
+                let _: Token![=>] = content.parse()?;
+                let inner;
+                syn::braced!(inner in content);
+                let mut body = Vec::new();
+                while !inner.is_empty() {
+                    body.push(inner.parse()?);
+                }
+                if content.peek(Token![,]) {
+                    let _: Token![,] = content.parse()?;
+                }
+                match pattern {
+                    Some(pattern) => arms.push(MatchArm { pattern, body }),
+                    None => default = Some(body),
+                }
+            }
+            return Ok(Self::Match(Box::new(Match {
+                scrutinee,
+                arms,
+                default,
+                ty: None,
+            })));
+        }
+        if input.peek(Token![while]) {
+            let _: Token![while] = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            let condition: WhileCondition = content.parse()?;
+            let content;
+            syn::braced!(content in input);
+            let mut block: Box<Vec<Statement>> = Box::default();
+            while !content.is_empty() {
+                let further_values: Statement = content.parse()?;
+                block.push(further_values);
+            }
+            return Ok(Self::While(Box::new(condition), block));
         }
 
         let mut ret: Vec<Box<IRExpr>> = vec![];
 
         while !input.is_empty() {
-            if input.peek(Token![if]) | input.peek(Token![for]) {
+            if input.peek(Token![if])
+                | input.peek(Token![for])
+                | input.peek(Token![while])
+                | input.peek(Token![match])
+                | input.peek(Token![fn])
+            {
                 break;
             }
+            // Doc comments are passed to us as `#[doc = "..."]` attributes.
+            if input.peek(Token![#]) {
+                for attr in input.call(Attribute::parse_outer)? {
+                    ret.push(Box::new(IRExpr::Function(Function::Intrinsic(Box::new(
+                        Intrinsic::Comment(attr.try_into()?),
+                    )))));
+                }
+                continue;
+            }
             let speculative = input.fork();
             match speculative.parse() {
                 Ok(val) => {
@@ -140,18 +340,82 @@ impl Parse for Statement {
     }
 }
 
+impl Parse for WhileCondition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lhs: Operand = input.parse()?;
+        if input.is_empty() {
+            return Ok(Self::Operand(lhs));
+        }
+        let operation = input.parse()?;
+        let rhs = input.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("Expected : (<Operand>) or (<Operand> <Comparison> <Operand>)"));
+        }
+        Ok(Self::Compare {
+            lhs,
+            operation,
+            rhs,
+            comparison_type: None,
+        })
+    }
+}
+
+/// Keeps the error of the alternative that parsed the most tokens before
+/// failing, as that error points at the offending token.
+#[derive(Default)]
+struct FurthestError(Option<(usize, syn::Error)>);
+
+impl FurthestError {
+    fn record(&mut self, speculative: ParseStream, error: syn::Error) {
+        let remaining = remaining_tokens(speculative);
+        if self.0.as_ref().is_none_or(|(best, _)| remaining < *best) {
+            self.0 = Some((remaining, error));
+        }
+    }
+
+    /// Returns the recorded error if any alternative got past the first token
+    /// of `input`.
+    fn into_error(self, input: ParseStream) -> Option<syn::Error> {
+        let start = remaining_tokens(input);
+        self.0
+            .and_then(|(remaining, error)| (remaining < start).then_some(error))
+    }
+}
+
+/// Returns the number of tokens left in `input`.
+fn remaining_tokens(input: ParseStream) -> usize {
+    input.cursor().token_stream().into_iter().count()
+}
+
 impl Parse for IRExpr {
     fn parse(input: ParseStream) -> Result<Self> {
+        let mut furthest = FurthestError::default();
+
         let speculative = input.fork();
-        if let Ok(unop) = speculative.parse() {
-            input.advance_to(&speculative);
-            return Ok(Self::UnOp(unop));
+        match speculative.parse() {
+            Ok(unop) => {
+                input.advance_to(&speculative);
+                return Ok(Self::UnOp(unop));
+            }
+            Err(e) => furthest.record(&speculative, e),
         }
 
         let speculative = input.fork();
-        if let Ok(assign) = speculative.parse() {
-            input.advance_to(&speculative);
-            return Ok(Self::Assign(assign));
+        match speculative.parse() {
+            Ok(assign) => {
+                input.advance_to(&speculative);
+                return Ok(Self::Assign(assign));
+            }
+            Err(e) => furthest.record(&speculative, e),
+        }
+
+        let speculative = input.fork();
+        match speculative.parse() {
+            Ok(ternary) => {
+                input.advance_to(&speculative);
+                return Ok(Self::Ternary(Box::new(ternary)));
+            }
+            Err(e) => furthest.record(&speculative, e),
         }
 
         // Things like a |= 1
@@ -173,14 +437,14 @@ impl Parse for IRExpr {
             }
             let _eq: Token![=] = match speculative.parse() {
                 Ok(val) => val,
-                _ => return Err(input.error("Expected =")),
+                _ => return Err(speculative.error("Expected =")),
             };
             let operand: Operand = match speculative.parse() {
                 Ok(val) => val,
-                _ => return Err(input.error("Expected operand")),
+                _ => return Err(speculative.error("Expected operand")),
             };
             if !speculative.peek(Token![;]) {
-                return Err(input.error("Expected ;"));
+                return Err(speculative.error("Expected ;"));
             }
             input.advance_to(&speculative);
             return Ok(Self::BinOp(Box::new(BinOp {
@@ -193,27 +457,148 @@ impl Parse for IRExpr {
         }
 
         let speculative = input.fork();
-        if let Ok(res) = speculative.parse() {
-            input.advance_to(&speculative);
-            return Ok(Self::BinOp(res));
+        match speculative.parse() {
+            Ok(res) => {
+                input.advance_to(&speculative);
+                return Ok(Self::BinOp(res));
+            }
+            Err(e) => furthest.record(&speculative, e),
         }
 
         let speculative = input.fork();
-        if let Ok(res) = speculative.parse() {
-            input.advance_to(&speculative);
-            return Ok(Self::Jump(res));
+        match speculative.parse() {
+            Ok(res) => {
+                input.advance_to(&speculative);
+                return Ok(Self::Jump(res));
+            }
+            Err(e) => furthest.record(&speculative, e),
         }
 
         let speculative = input.fork();
-        if let Ok(func) = speculative.parse() {
-            input.advance_to(&speculative);
-            return Ok(Self::Function(func));
+        match speculative.parse() {
+            Ok(func) => {
+                input.advance_to(&speculative);
+                return Ok(Self::Function(func));
+            }
+            Err(e) => furthest.record(&speculative, e),
         }
         let speculative = input.fork();
-        if let Ok(settyp) = speculative.parse() {
-            input.advance_to(&speculative);
-            return Ok(Self::SetType(settyp));
+        match speculative.parse() {
+            Ok(settyp) => {
+                input.advance_to(&speculative);
+                return Ok(Self::SetType(settyp));
+            }
+            Err(e) => furthest.record(&speculative, e),
+        }
+
+        // Two words in a row, such as `frobnicate a;`, can only be a statement
+        // keyword that does not exist.
+        let speculative = input.fork();
+        if let Ok(keyword) = speculative.parse::<Ident>() {
+            if speculative.peek(Ident) || speculative.peek(syn::Lit) {
+                return Err(syn::Error::new(
+                    keyword.span(),
+                    format!("Unknown keyword `{keyword}`"),
+                ));
+            }
         }
-        Err(input.error("Expected a valid IRExpr here"))
+
+        Err(furthest
+            .into_error(input)
+            .unwrap_or_else(|| input.error("Expected a valid IRExpr here")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ast::{
+        function::{Comment, Function, Intrinsic},
+        IRExpr,
+        Statement,
+        IR,
+    };
+
+    /// Splits the statements in to comments and all other expressions.
+    fn split(statements: &[Statement]) -> (Vec<String>, Vec<IRExpr>) {
+        let (mut comments, mut exprs) = (Vec::new(), Vec::new());
+        for statement in statements {
+            let inner = match statement {
+                Statement::Exprs(inner) => {
+                    for expr in inner {
+                        match expr.as_ref() {
+                            IRExpr::Function(Function::Intrinsic(intrinsic)) => {
+                                match intrinsic.as_ref() {
+                                    Intrinsic::Comment(Comment { text }) => {
+                                        comments.push(text.value().trim().to_string())
+                                    }
+                                    _ => exprs.push(*expr.clone()),
+                                }
+                            }
+                            _ => exprs.push(*expr.clone()),
+                        }
+                    }
+                    continue;
+                }
+                Statement::If(_, happy_case, _) | Statement::Ite(_, happy_case, _) => {
+                    split(happy_case)
+                }
+                Statement::For(_, _, block)
+                | Statement::ForRange(_, _, block)
+                | Statement::While(_, block) => split(block),
+                Statement::Match(m) => {
+                    let mut inner = (Vec::new(), Vec::new());
+                    for body in m.arms.iter().map(|arm| &arm.body).chain(&m.default) {
+                        let (comments, exprs) = split(body);
+                        inner.0.extend(comments);
+                        inner.1.extend(exprs);
+                    }
+                    inner
+                }
+                Statement::Block(block) => split(&block.body),
+            };
+            comments.extend(inner.0);
+            exprs.extend(inner.1);
+        }
+        (comments, exprs)
+    }
+
+    #[test]
+    fn commented_block() {
+        let commented: IR = syn::parse_str(
+            r#"[
+                a:u32;
+                /// Compute the sum.
+                let b = a + 1;
+                Comment("Check the result.");
+                if (cond) {
+                    /// Only when cond holds.
+                    b = a;
+                }
+                Jump(b);
+            ]"#,
+        )
+        .unwrap();
+        let plain: IR = syn::parse_str(
+            "[
+                a:u32;
+                let b = a + 1;
+                if (cond) {
+                    b = a;
+                }
+                Jump(b);
+            ]",
+        )
+        .unwrap();
+
+        let (comments, exprs) = split(&commented.extensions);
+        assert_eq!(
+            comments,
+            vec![
+                "Compute the sum.",
+                "Check the result.",
+                "Only when cond holds."
+            ]
+        );
+        assert_eq!(exprs, split(&plain.extensions).1);
     }
 }
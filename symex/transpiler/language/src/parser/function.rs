@@ -124,6 +124,24 @@ impl Parse for Intrinsic {
             return Ok(Self::Abort(el));
         }
 
+        let speculative = input.fork();
+        if let Ok(el) = speculative.parse() {
+            input.advance_to(&speculative);
+            return Ok(Self::Assume(el));
+        }
+
+        let speculative = input.fork();
+        if let Ok(el) = speculative.parse() {
+            input.advance_to(&speculative);
+            return Ok(Self::Assert(el));
+        }
+
+        let speculative = input.fork();
+        if let Ok(el) = speculative.parse() {
+            input.advance_to(&speculative);
+            return Ok(Self::Comment(el));
+        }
+
         let speculative = input.fork();
         if let Ok(el) = speculative.parse() {
             input.advance_to(&speculative);
@@ -702,6 +720,77 @@ impl Parse for Abort {
     }
 }
 
+impl Parse for Assume {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident.to_string().to_lowercase().as_str() != "assume" {
+            return Err(syn::Error::new(ident.span(), "Expected assume"));
+        }
+
+        let content;
+        parenthesized!(content in input);
+        let condition: Operand = content.parse()?;
+        Ok(Self { condition })
+    }
+}
+
+impl Parse for Assert {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident.to_string().to_lowercase().as_str() != "assert" {
+            return Err(syn::Error::new(ident.span(), "Expected assert"));
+        }
+
+        let content;
+        parenthesized!(content in input);
+        let text = content.fork().parse::<TokenStream>()?.to_string();
+        let condition: Operand = content.parse()?;
+        Ok(Self { condition, text })
+    }
+}
+
+impl Parse for Comment {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident.to_string().to_lowercase().as_str() != "comment" {
+            return Err(syn::Error::new(ident.span(), "Expected comment"));
+        }
+
+        let content;
+        parenthesized!(content in input);
+        let text: LitStr = content.parse()?;
+        if !content.is_empty() {
+            return Err(content.error("Expected : Comment(\"<text>\")"));
+        }
+        Ok(Self { text })
+    }
+}
+
+impl TryFrom<syn::Attribute> for Comment {
+    type Error = syn::Error;
+
+    fn try_from(attr: syn::Attribute) -> Result<Self> {
+        if let syn::Meta::NameValue(syn::MetaNameValue {
+            path,
+            value:
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(text),
+                    ..
+                }),
+            ..
+        }) = &attr.meta
+        {
+            if path.is_ident("doc") {
+                return Ok(Self { text: text.clone() });
+            }
+        }
+        Err(syn::Error::new_spanned(
+            attr,
+            "Only doc comments are supported inside of pseudo code",
+        ))
+    }
+}
+
 impl Parse for Abs {
     fn parse(input: ParseStream) -> Result<Self> {
         let _: Token![|] = input.parse()?;
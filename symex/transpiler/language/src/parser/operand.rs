@@ -92,6 +92,13 @@ impl Parse for Operand {
             return Ok(Self::DynamicFieldExtract((val, None)));
         }
 
+        let speculative = input.fork();
+        if let Ok(val) = speculative.parse() {
+            let val: ParseableIndex = val;
+            input.advance_to(&speculative);
+            return Ok(Self::Index(val.0));
+        }
+
         let speculative = input.fork();
         if let Ok(lit) = speculative.parse() {
             input.advance_to(&speculative);
@@ -117,6 +124,35 @@ impl Parse for Operand {
     }
 }
 
+struct ParseableIndex((Index, Option<crate::ast::operand::Type>));
+impl Parse for ParseableIndex {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let base: Ident = input.parse()?;
+        if !input.peek(syn::token::Bracket) {
+            return Err(input.error("Expected [index{:ty}]"));
+        }
+        let content;
+        syn::bracketed!(content in input);
+        let index: Operand = content.parse()?;
+        let mut ty = None;
+        if content.peek(Token![:]) {
+            let _: Token![:] = content.parse()?;
+            ty = Some(content.parse()?);
+        }
+        if !content.is_empty() {
+            return Err(content.error("Expected [index{:ty}]"));
+        }
+        Ok(Self((
+            Index {
+                base,
+                index: Box::new(index),
+                base_ty: None,
+            },
+            ty,
+        )))
+    }
+}
+
 struct ParseableIdent((IdentOperand, Option<crate::ast::operand::Type>));
 impl Parse for ParseableIdent {
     fn parse(input: ParseStream) -> Result<Self> {
@@ -167,6 +203,7 @@ impl Parse for crate::ast::operand::Type {
             "f32" => Self::F32,
             "f64" => Self::F64,
             "f128" => Self::F128,
+            "bool" => Self::Bool,
             _ => return Err(input.error("Invalid type")),
         })
     }
@@ -8,7 +8,15 @@ use syn::{
 
 use crate::ast::{
     operand::{Operand, Type},
-    operations::{Assign, BinOp, BinaryOperation, CompareOperation, UnOp, UnaryOperation},
+    operations::{
+        Assign,
+        BinOp,
+        BinaryOperation,
+        CompareOperation,
+        Ternary,
+        UnOp,
+        UnaryOperation,
+    },
 };
 impl Parse for Assign {
     fn parse(input: ParseStream) -> Result<Self> {
@@ -42,6 +50,7 @@ impl Operand {
             Self::Ident((_, inner)) => *inner = Some(ty),
             Self::FieldExtract((_, inner)) => *inner = Some(ty),
             Self::DynamicFieldExtract((_, inner)) => *inner = Some(ty),
+            Self::Index((_, inner)) => *inner = Some(ty),
             Self::WrappedLiteral(_) => {}
         }
     }
@@ -97,6 +106,33 @@ impl Parse for BinOp {
         })
     }
 }
+impl Parse for Ternary {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let dest: Operand = input.parse()?;
+        let mut ty = None;
+        if input.peek(Token![:]) {
+            let _: Token![:] = input.parse()?;
+            let inner_ty: Type = input.parse()?;
+            ty = Some(inner_ty);
+        }
+        let _: Token![=] = input.parse()?;
+        let condition: Operand = input.parse()?;
+        let _: Token![?] = input.parse()?;
+        let then: Operand = input.parse()?;
+        let _: Token![:] = input.parse()?;
+        let otherwise: Operand = input.parse()?;
+        if !input.peek(syn::token::Semi) {
+            return Err(input.error("Expected ;"));
+        }
+        Ok(Self {
+            dest,
+            condition,
+            then,
+            otherwise,
+            result_ty: ty,
+        })
+    }
+}
 impl Parse for UnaryOperation {
     fn parse(input: ParseStream) -> Result<Self> {
         if input.peek(Token![!]) {
@@ -149,6 +185,14 @@ impl Parse for BinaryOperation {
             let _: Token![*] = input.parse()?;
             return Ok(Self::Mul);
         }
+        if input.peek(Token![&&]) {
+            let _: Token![&&] = input.parse()?;
+            return Ok(Self::LogicalAnd);
+        }
+        if input.peek(Token![||]) {
+            let _: Token![||] = input.parse()?;
+            return Ok(Self::LogicalOr);
+        }
         if input.peek(Token![&]) {
             let _: Token![&] = input.parse()?;
             return Ok(Self::BitwiseAnd);
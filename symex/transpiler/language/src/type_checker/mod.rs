@@ -1,6 +1,7 @@
 //! Provides type checking utilities for IR.
 
 use proc_macro2::Span;
+use quote::ToTokens;
 use syn::spanned::Spanned;
 
 use crate::{
@@ -8,6 +9,9 @@ use crate::{
         function::{
             self,
             Abs,
+            Assert,
+            Assume,
+            Call,
             Cast,
             Function,
             Intrinsic,
@@ -35,14 +39,26 @@ use crate::{
             ExprOperand,
             FieldExtract,
             IdentOperand,
+            Index,
             Operand,
             SetType,
             Type,
             WrappedLiteral,
         },
-        operations::{Assign, BinOp, BinaryOperation, CompareOperation, UnOp, UnaryOperation},
+        operations::{
+            Assign,
+            BinOp,
+            BinaryOperation,
+            CompareOperation,
+            Ternary,
+            UnOp,
+            UnaryOperation,
+        },
+        ConstantRange,
         IRExpr,
+        Match,
         Statement,
+        WhileCondition,
         IR,
     },
     TypeCheck,
@@ -60,8 +76,28 @@ impl TypeCheckMeta {
     }
 
     fn register(&self) -> crate::ast::operand::Type {
-        // TODO: replace this with a setable word size.
-        Type::U(32)
+        Type::U(self.word_size)
+    }
+
+    /// Returns true if the operand is a value declared in the pseudo code.
+    fn declares(&self, operand: &Operand) -> bool {
+        match operand {
+            Operand::Expr((ExprOperand::Ident(ident), _))
+            | Operand::Ident((IdentOperand { ident, .. }, _)) => self.get_ty(ident).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Returns the condition of an if statement as a [`WhileCondition`] if it
+    /// reads values declared in the pseudo code, such conditions must be
+    /// evaluated at execution time.
+    fn runtime_condition(&self, condition: &syn::Expr) -> Option<WhileCondition> {
+        let condition: WhileCondition = syn::parse2(condition.to_token_stream()).ok()?;
+        let reads_declared = match &condition {
+            WhileCondition::Operand(operand) => self.declares(operand),
+            WhileCondition::Compare { lhs, rhs, .. } => self.declares(lhs) || self.declares(rhs),
+        };
+        reads_declared.then_some(condition)
     }
 
     fn set_type(&mut self, operand: &mut Operand, ty: &Type) {
@@ -77,6 +113,7 @@ impl TypeCheckMeta {
             Operand::Expr((_, inner_ty)) => *inner_ty = Some(*ty),
             Operand::FieldExtract((_, inner_ty)) => *inner_ty = Some(*ty),
             Operand::DynamicFieldExtract((_, inner_ty)) => *inner_ty = Some(*ty),
+            Operand::Index((_, inner_ty)) => *inner_ty = Some(*ty),
             Operand::WrappedLiteral(_) => {}
         }
     }
@@ -87,7 +124,25 @@ impl TypeCheck for Statement {
         &mut self,
         meta: &mut TypeCheckMeta,
     ) -> Result<Option<crate::ast::operand::Type>, TypeError> {
+        if let Statement::If(condition, t, e) = self {
+            if let Some(condition) = meta.runtime_condition(condition) {
+                *self = Statement::Ite(Box::new(condition), std::mem::take(t), e.take());
+            }
+        }
         match self {
+            Statement::Ite(condition, t, e) => {
+                condition.type_check(meta)?;
+                let mut inner_meta = meta.clone();
+                for stmt in t.iter_mut() {
+                    stmt.type_check(&mut inner_meta)?;
+                }
+                if let Some(e) = e {
+                    let mut inner_meta = meta.clone();
+                    for stmt in e.iter_mut() {
+                        stmt.type_check(&mut inner_meta)?;
+                    }
+                }
+            }
             Statement::If(_i, t, Some(e)) => {
                 let mut inner_meta = meta.clone();
                 for stmt in t.iter_mut() {
@@ -110,6 +165,48 @@ impl TypeCheck for Statement {
                     stmt.type_check(&mut inner_meta)?;
                 }
             }
+            Self::ForRange(var, range, body) => {
+                let ty = range.type_check(meta)?.expect("Range type is always known");
+                let mut inner_meta = meta.clone();
+                inner_meta.set_ty(var.clone(), ty);
+                for stmt in body.iter_mut() {
+                    stmt.type_check(&mut inner_meta)?;
+                }
+            }
+            Self::While(condition, body) => {
+                condition.type_check(meta)?;
+                let mut inner_meta = meta.clone();
+                for stmt in body.iter_mut() {
+                    stmt.type_check(&mut inner_meta)?;
+                }
+            }
+            Self::Match(m) => {
+                m.type_check(meta)?;
+                for body in m
+                    .arms
+                    .iter_mut()
+                    .map(|arm| &mut arm.body)
+                    .chain(&mut m.default)
+                {
+                    let mut inner_meta = meta.clone();
+                    for stmt in body.iter_mut() {
+                        stmt.type_check(&mut inner_meta)?;
+                    }
+                }
+            }
+            Self::Block(block) => {
+                let mut inner_meta = meta.clone();
+                for (arg, ty) in block.args.iter() {
+                    inner_meta.set_ty(arg.clone(), *ty);
+                }
+                for stmt in block.body.iter_mut() {
+                    stmt.type_check(&mut inner_meta)?;
+                }
+                meta.signatures.insert(
+                    block.name.clone(),
+                    block.args.iter().map(|(_, ty)| *ty).collect(),
+                );
+            }
             Self::Exprs(e) => {
                 for e in e.iter_mut() {
                     e.type_check(meta)?;
@@ -119,12 +216,210 @@ impl TypeCheck for Statement {
         Ok(None)
     }
 }
+impl ConstantRange {
+    /// Returns the type of a bound if it is suffixed.
+    fn bound_type(bound: &Option<Box<syn::Expr>>, span: Span) -> Result<Option<Type>, TypeError> {
+        let bound = match bound {
+            Some(bound) => bound,
+            None => return Ok(None),
+        };
+        let lit = match &**bound {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(lit),
+                ..
+            }) => lit,
+            _ => {
+                return Err(TypeError::TypeMustBeKnown(
+                    "Range bounds must be constant integer literals.".to_string(),
+                    bound.span(),
+                ))
+            }
+        };
+        int_literal_type(lit, span)
+    }
+}
+
+/// Returns the type of an integer literal if it is suffixed.
+fn int_literal_type(lit: &syn::LitInt, span: Span) -> Result<Option<Type>, TypeError> {
+    let suffix = lit.suffix().to_lowercase();
+    if suffix.is_empty() {
+        return Ok(None);
+    }
+    let bits = suffix[1..].parse::<u32>().map_err(|_| {
+        TypeError::UnsupportedType(format!("{suffix} is not a sized integer type"), span)
+    })?;
+    match &suffix[..1] {
+        "u" => Ok(Some(Type::U(bits))),
+        "i" => Ok(Some(Type::I(bits))),
+        _ => Err(TypeError::TypeMustBeKnown(
+            "Expected a constant integer literal.".to_string(),
+            lit.span(),
+        )),
+    }
+}
+
+impl TypeCheck for ConstantRange {
+    fn type_check(
+        &mut self,
+        meta: &mut TypeCheckMeta,
+    ) -> Result<Option<crate::ast::operand::Type>, TypeError> {
+        let span = self.range.span();
+        if self.range.end.is_none() {
+            return Err(TypeError::TypeMustBeKnown(
+                "Range loops must have an upper bound.".to_string(),
+                span,
+            ));
+        }
+        let start = Self::bound_type(&self.range.start, span)?;
+        let end = Self::bound_type(&self.range.end, span)?;
+        let ty = match (start, end) {
+            (Some(start), Some(end)) if start != end => {
+                return Err(TypeError::InvalidType {
+                    expected: start,
+                    got: end,
+                    span,
+                })
+            }
+            (Some(ty), _) | (None, Some(ty)) => ty,
+            (None, None) => meta.register(),
+        };
+        if let Type::U(bits) | Type::I(bits) = ty {
+            if ![8, 16, 32, 64, 128].contains(&bits) {
+                return Err(TypeError::UnsupportedType(
+                    format!("Cannot iterate over a range of {ty:?}"),
+                    span,
+                ));
+            }
+        }
+        self.ty = Some(ty);
+        Ok(Some(ty))
+    }
+}
+
+impl TypeCheck for Match {
+    fn type_check(
+        &mut self,
+        meta: &mut TypeCheckMeta,
+    ) -> Result<Option<crate::ast::operand::Type>, TypeError> {
+        let span = self.scrutinee.span();
+        let mut ty = self.scrutinee.type_check(meta)?;
+        for arm in self.arms.iter() {
+            let pattern_ty = int_literal_type(&arm.pattern, arm.pattern.span())?;
+            match (ty, pattern_ty) {
+                (Some(expected), Some(got)) if expected != got => {
+                    return Err(TypeError::InvalidType {
+                        expected,
+                        got,
+                        span: arm.pattern.span(),
+                    })
+                }
+                (None, Some(got)) => {
+                    meta.set_type(&mut self.scrutinee, &got);
+                    ty = Some(got);
+                }
+                _ => {}
+            }
+        }
+        let ty = ty.ok_or_else(|| {
+            TypeError::TypeMustBeKnown(
+                "Cannot match on an operand of unknown type.".to_string(),
+                span,
+            )
+        })?;
+        let bits = match ty {
+            Type::U(bits) => bits,
+            // Only non negative patterns can be written.
+            Type::I(bits) => bits - 1,
+            _ => {
+                return Err(TypeError::UnsupportedType(
+                    format!("Cannot match on {ty}"),
+                    span,
+                ))
+            }
+        };
+
+        let mut values = Vec::with_capacity(self.arms.len());
+        for arm in self.arms.iter() {
+            let value = arm.pattern.base10_parse::<u128>().map_err(|_| {
+                TypeError::UnsupportedOperation(
+                    "Match arms must be integer constants.".to_string(),
+                    arm.pattern.span(),
+                )
+            })?;
+            if bits < 128 && value >> bits != 0 {
+                return Err(TypeError::UnsupportedOperation(
+                    format!("{value} does not fit in {ty}"),
+                    arm.pattern.span(),
+                ));
+            }
+            if values.contains(&value) {
+                return Err(TypeError::UnsupportedOperation(
+                    format!("{value} is matched more than once"),
+                    arm.pattern.span(),
+                ));
+            }
+            values.push(value);
+        }
+
+        let exhaustive =
+            matches!(ty, Type::U(bits) if bits < 128 && values.len() as u128 == 1 << bits);
+        if self.default.is_none() && !exhaustive {
+            return Err(TypeError::UnsupportedOperation(
+                "Match must have a default arm or cover all values.".to_string(),
+                span,
+            ));
+        }
+        self.ty = Some(ty);
+        Ok(None)
+    }
+}
+
+impl TypeCheck for WhileCondition {
+    fn type_check(
+        &mut self,
+        meta: &mut TypeCheckMeta,
+    ) -> Result<Option<crate::ast::operand::Type>, TypeError> {
+        match self {
+            Self::Operand(operand) => match operand.type_check(meta)? {
+                Some(Type::U(1) | Type::Bool) => {}
+                Some(ty) => {
+                    return Err(TypeError::InvalidType {
+                        expected: Type::U(1),
+                        got: ty,
+                        span: operand.span(),
+                    })
+                }
+                None => {
+                    meta.set_type(operand, &Type::U(1));
+                }
+            },
+            Self::Compare {
+                lhs,
+                operation,
+                rhs,
+                comparison_type,
+            } => {
+                operation.type_check(lhs, rhs, meta)?;
+                *comparison_type = lhs.type_check(meta)?;
+            }
+        }
+        Ok(Some(Type::U(1)))
+    }
+}
+
 impl TypeCheck for IR {
     fn type_check(
         &mut self,
         meta: &mut TypeCheckMeta,
     ) -> Result<Option<crate::ast::operand::Type>, TypeError> {
-        let IR { ret: _, extensions } = self;
+        let IR {
+            ret: _,
+            extensions,
+            word_size,
+        } = self;
+        if let Some(word_size) = word_size {
+            meta.word_size = *word_size;
+        }
         for ext in extensions.iter_mut() {
             ext.type_check(meta)?;
         }
@@ -133,19 +428,20 @@ impl TypeCheck for IR {
 }
 
 impl Operand {
-    fn span(&self) -> Span {
+    pub(crate) fn span(&self) -> Span {
         match self {
             Self::Expr((e, _ty)) => e.span(),
             Self::Ident((i, _)) => i.span(),
             Self::FieldExtract((f, _)) => f.span(),
             Self::DynamicFieldExtract((f, _)) => f.span(),
+            Self::Index((i, _)) => i.base.span(),
             Self::WrappedLiteral(WrappedLiteral { val, ty: _ }) => val.span(),
         }
     }
 }
 
 impl FieldExtract {
-    fn span(&self) -> Span {
+    pub(crate) fn span(&self) -> Span {
         let FieldExtract {
             operand,
             start: _,
@@ -157,7 +453,7 @@ impl FieldExtract {
 }
 
 impl DynamicFieldExtract {
-    fn span(&self) -> Span {
+    pub(crate) fn span(&self) -> Span {
         let DynamicFieldExtract {
             operand,
             start: _,
@@ -168,13 +464,13 @@ impl DynamicFieldExtract {
     }
 }
 impl IdentOperand {
-    fn span(&self) -> Span {
+    pub(crate) fn span(&self) -> Span {
         let Self { define: _, ident } = self;
         ident.span()
     }
 }
 impl ExprOperand {
-    fn span(&self) -> Span {
+    pub(crate) fn span(&self) -> Span {
         match self {
             ExprOperand::Paren(p) => p.span(),
             ExprOperand::Chain(i, c) => {
@@ -300,6 +596,10 @@ impl TypeCheck for IRExpr {
                         ))
                     }
                     Type::U(_) | Type::I(_) => Ok(None),
+                    Type::Bool => Err(TypeError::UnsupportedOperation(
+                        "Cannot branch to a boolean address.".to_string(),
+                        target_id.span(),
+                    )),
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 }
                 // condition is encoded as a rust expressions.
@@ -339,7 +639,19 @@ impl TypeCheck for IRExpr {
                 }
 
                 let (lhs, rhs) = match (dest_ty, lhs, rhs) {
+                    (_, Some(lhs), Some(rhs)) if lhs.is_float() != rhs.is_float() => {
+                        return Err(TypeError::UnsupportedOperation(
+                            format!("Cannot mix {lhs} and {rhs} operands without an explicit conversion, use Resize or Cast"),
+                            lhs_operand
+                                .span()
+                                .join(rhs_operand.span())
+                                .expect("Multi file is not supported"),
+                        ))
+                    }
                     (Some(_), Some(lhs), Some(rhs)) => (lhs, rhs),
+                    // The operands of a comparison do not share a type with the result.
+                    (_, Some(lhs), None) if matches!(op, BinaryOperation::Compare(_)) => (lhs, lhs),
+                    (_, None, Some(rhs)) if matches!(op, BinaryOperation::Compare(_)) => (rhs, rhs),
                     (Some(ty), Some(lhs), None) if ty == lhs => (ty, ty),
                     (Some(ty), Some(lhs), None) => {
                         return Err(TypeError::InvalidType {
@@ -383,7 +695,17 @@ impl TypeCheck for IRExpr {
                         // TODO: Is this reasonable?
                         (rhs, rhs)
                     }
-                    (None, Some(lhs), Some(rhs)) if lhs == rhs => (lhs, rhs),
+                    (None, Some(lhs), Some(rhs)) if lhs.is_compatible(&rhs) => (lhs, rhs),
+                    (None, Some(lhs), Some(rhs)) if matches!(op, BinaryOperation::Compare(_)) => {
+                        return Err(TypeError::InvalidType {
+                            expected: lhs,
+                            got: rhs,
+                            span: lhs_operand
+                                .span()
+                                .join(rhs_operand.span())
+                                .expect("Multi file is not supported"),
+                        })
+                    }
                     (None, Some(lhs), Some(rhs)) if !op.is_shift() => {
                         return Err(TypeError::UnsupportedOperation(
                             format!("Cannot perform {lhs:?} {op:?} {rhs:?}"),
@@ -424,7 +746,17 @@ impl TypeCheck for IRExpr {
                 rhs_operand.set_type(rhs);
                 lhs_operand.set_type(lhs);
 
-                if lhs != rhs && !op.is_shift() {
+                if !lhs.is_compatible(&rhs) && matches!(op, BinaryOperation::Compare(_)) {
+                    return Err(TypeError::InvalidType {
+                        expected: lhs,
+                        got: rhs,
+                        span: lhs_operand
+                            .span()
+                            .join(rhs_operand.span())
+                            .expect("Multi file is not supported"),
+                    });
+                }
+                if !lhs.is_compatible(&rhs) && !op.is_shift() {
                     return Err(TypeError::UnsupportedOperation(
                         format!("Cannot apply binary operation to operands of differing types. {lhs:?} != {rhs:?}"),
                                 {
@@ -439,15 +771,50 @@ impl TypeCheck for IRExpr {
                 }
 
                 let result_ty = match (&op, lhs) {
+                    // Flags are single bit unsigned values, they are accepted as booleans.
+                    (
+                        BinaryOperation::LogicalAnd | BinaryOperation::LogicalOr,
+                        Type::Bool | Type::U(1),
+                    ) => match dest_ty {
+                        Some(Type::Bool) => Type::Bool,
+                        _ => lhs,
+                    },
+                    (BinaryOperation::LogicalAnd | BinaryOperation::LogicalOr, _) => {
+                        return Err(TypeError::InvalidType {
+                            expected: Type::Bool,
+                            got: lhs,
+                            span: lhs_operand
+                                .span()
+                                .join(rhs_operand.span())
+                                .expect("Multi file is not supported"),
+                        })
+                    }
+                    (_, Type::Bool)
+                        if !matches!(
+                            op,
+                            BinaryOperation::BitwiseAnd
+                                | BinaryOperation::BitwiseOr
+                                | BinaryOperation::BitwiseXor
+                                | BinaryOperation::Compare(_)
+                        ) =>
+                    {
+                        return Err(TypeError::UnsupportedOperation(
+                            format!("Cannot apply {op:?} to booleans"),
+                            lhs_operand
+                                .span()
+                                .join(rhs_operand.span())
+                                .expect("Multi file is not supported"),
+                        ))
+                    }
                     (BinaryOperation::Sub, _) => lhs,
                     (BinaryOperation::Add, _) => lhs,
                     (BinaryOperation::Mul, _) => lhs,
                     (BinaryOperation::Div, _) => lhs,
                     (BinaryOperation::SSub, Type::I(_) | Type::U(_)) => lhs,
                     (BinaryOperation::SAdd, Type::I(_) | Type::U(_)) => lhs,
-                    (BinaryOperation::BitwiseOr, Type::U(_) | Type::I(_)) => lhs,
-                    (BinaryOperation::BitwiseAnd, Type::U(_) | Type::I(_)) => lhs,
-                    (BinaryOperation::BitwiseXor, Type::U(_) | Type::I(_)) => lhs,
+                    (BinaryOperation::BitwiseOr, Type::U(_) | Type::I(_) | Type::Bool) => lhs,
+                    (BinaryOperation::BitwiseAnd, Type::U(_) | Type::I(_) | Type::Bool) => lhs,
+                    (BinaryOperation::BitwiseXor, Type::U(_) | Type::I(_) | Type::Bool) => lhs,
                     (BinaryOperation::AddWithCarry, Type::U(_) | Type::I(_)) => lhs,
                     (BinaryOperation::LogicalLeftShift, Type::U(_size) | Type::I(_size))
                     | (BinaryOperation::LogicalRightShift, Type::U(_size) | Type::I(_size))
@@ -470,7 +837,12 @@ impl TypeCheck for IRExpr {
                         }
 
                     }
-                    (BinaryOperation::Compare(_), _) => Type::U(1),
+                    // Comparisons yield booleans unless they are assigned to a flag or a
+                    // single bit local.
+                    (BinaryOperation::Compare(_), _) => match dest_ty {
+                        Some(Type::U(1)) => Type::U(1),
+                        _ => Type::Bool,
+                    },
                     _ => {
                         return Err(TypeError::UnsupportedOperation(
                             format!("Cannot apply {op:?} to {lhs:?}",),
@@ -485,7 +857,7 @@ impl TypeCheck for IRExpr {
                     }
                 };
                 if let Some(ty) = dest_ty {
-                    if ty != result_ty {
+                    if !ty.is_compatible(&result_ty) {
                         return Err(TypeError::InvalidType {
                             expected: ty,
                             got: result_ty,
@@ -507,6 +879,87 @@ impl TypeCheck for IRExpr {
                     Ok(None)
                 }
             }
+            Self::Ternary(ternary) => {
+                let Ternary {
+                    dest,
+                    condition,
+                    then,
+                    otherwise,
+                    result_ty,
+                } = &mut **ternary;
+                match condition.type_check(meta)? {
+                    Some(Type::U(1) | Type::Bool) => {}
+                    Some(ty) => {
+                        return Err(TypeError::InvalidType {
+                            expected: Type::U(1),
+                            got: ty,
+                            span: condition.span(),
+                        })
+                    }
+                    None => meta.set_type(condition, &Type::U(1)),
+                }
+
+                let mut dest_ty = dest.type_check(meta)?;
+                if let Some(ty) = result_ty {
+                    match dest_ty {
+                        Some(ty2) if ty2 != *ty => {
+                            return Err(TypeError::InvalidType {
+                                expected: ty2,
+                                got: *ty,
+                                span: dest.span(),
+                            })
+                        }
+                        Some(_) => {}
+                        None => {
+                            meta.set_type(dest, ty);
+                            dest_ty = Some(*ty);
+                        }
+                    }
+                }
+
+                let span = |then: &Operand, otherwise: &Operand| {
+                    then.span()
+                        .join(otherwise.span())
+                        .expect("Multi file is not supported")
+                };
+                let ty = match (then.type_check(meta)?, otherwise.type_check(meta)?) {
+                    (Some(lhs), Some(rhs)) if lhs != rhs => {
+                        return Err(TypeError::InvalidType {
+                            expected: lhs,
+                            got: rhs,
+                            span: span(then, otherwise),
+                        })
+                    }
+                    (Some(ty), _) | (None, Some(ty)) => ty,
+                    (None, None) => match dest_ty {
+                        Some(ty) => ty,
+                        None => {
+                            return Err(TypeError::TypeMustBeKnown(
+                                "Cannot select between two values of unknown type.".to_string(),
+                                span(then, otherwise),
+                            ))
+                        }
+                    },
+                };
+                meta.set_type(then, &ty);
+                meta.set_type(otherwise, &ty);
+
+                match dest_ty {
+                    Some(dest_ty) if dest_ty != ty => Err(TypeError::InvalidType {
+                        expected: dest_ty,
+                        got: ty,
+                        span: dest
+                            .span()
+                            .join(span(then, otherwise))
+                            .expect("Multi file is not supported"),
+                    }),
+                    Some(_) => Ok(Some(Type::Unit)),
+                    None => {
+                        meta.set_type(dest, &ty);
+                        Ok(None)
+                    }
+                }
+            }
             Self::Assign(Assign { dest, rhs }) => {
                 let dest_ty = dest.type_check(meta)?;
                 let result_ty = match rhs.type_check(meta)? {
@@ -528,7 +981,7 @@ impl TypeCheck for IRExpr {
 
                 if let Some(ty) = dest_ty {
                     match (ty, result_ty) {
-                        (t1, t2) if t1 == t2 => Ok(Some(Type::Unit)),
+                        (t1, t2) if t1.is_compatible(&t2) => Ok(Some(Type::Unit)),
                         _ => Err(TypeError::InvalidType {
                             expected: ty,
                             got: result_ty,
@@ -554,6 +1007,42 @@ impl TypeCheck for IRExpr {
                 meta.set_ty(operand.clone(), *ty);
                 Ok(Some(Type::Unit))
             }
+            Self::Call(Call { name, args }) => {
+                let signature = match meta.signatures.get(name) {
+                    Some(signature) => signature.clone(),
+                    None => {
+                        return Err(TypeError::UnsupportedOperation(
+                            format!("{name} is not a declared block"),
+                            name.span(),
+                        ))
+                    }
+                };
+                if signature.len() != args.len() {
+                    return Err(TypeError::UnsupportedOperation(
+                        format!(
+                            "{name} takes {} arguments but {} were supplied",
+                            signature.len(),
+                            args.len()
+                        ),
+                        name.span(),
+                    ));
+                }
+                for (arg, expected) in args.iter_mut().zip(signature) {
+                    match arg.type_check(meta)? {
+                        Some(ty) if ty != expected => {
+                            return Err(TypeError::UnsupportedOperation(
+                                format!(
+                                    "{name} expected an argument of type {expected} but got {ty}"
+                                ),
+                                arg.span(),
+                            ))
+                        }
+                        Some(_) => {}
+                        None => meta.set_type(arg, &expected),
+                    }
+                }
+                Ok(None)
+            }
         }
     }
 }
@@ -647,7 +1136,62 @@ impl TypeCheck for Operand {
 
                 ret
             }
+            Self::Index((index, ty)) => {
+                index.type_check(meta)?;
+                match ty {
+                    Some(Type::U(bits) | Type::I(bits)) if *bits % 8 == 0 => {}
+                    Some(ty) => {
+                        return Err(TypeError::UnsupportedType(
+                            format!("Cannot index in to a table of {ty}, elements must be whole bytes wide integers"),
+                            index.base.span(),
+                        ))
+                    }
+                    None => {}
+                }
+                Ok(*ty)
+            }
+        }
+    }
+}
+impl TypeCheck for Index {
+    fn type_check(
+        &mut self,
+        meta: &mut TypeCheckMeta,
+    ) -> Result<Option<crate::ast::operand::Type>, TypeError> {
+        let Index {
+            base,
+            index,
+            base_ty,
+        } = self;
+
+        let ty = match meta.get_ty(base) {
+            Some(ty @ Type::U(_)) => ty,
+            Some(ty) => {
+                return Err(TypeError::InvalidType {
+                    expected: meta.register(),
+                    got: ty,
+                    span: base.span(),
+                })
+            }
+            None => {
+                let ty = meta.register();
+                meta.set_ty(base.clone(), ty);
+                ty
+            }
+        };
+        match index.type_check(meta)? {
+            Some(index_ty) if index_ty != ty => {
+                return Err(TypeError::InvalidType {
+                    expected: ty,
+                    got: index_ty,
+                    span: index.span(),
+                })
+            }
+            Some(_) => {}
+            None => meta.set_type(index, &ty),
         }
+        *base_ty = Some(ty);
+        Ok(Some(ty))
     }
 }
 impl TypeCheck for DynamicFieldExtract {
@@ -679,6 +1223,10 @@ impl TypeCheck for DynamicFieldExtract {
             )),
             Type::I(size) | Type::U(size) => Ok(Some(Type::U(size))),
 
+            Type::Bool => Err(TypeError::UnsupportedOperation(
+                "Cannot dynamically bitfield extract on a boolean".to_string(),
+                self.span(),
+            )),
             Type::Unit => panic!("Cannot use unit types for expressions"),
         }
     }
@@ -724,6 +1272,10 @@ impl TypeCheck for FieldExtract {
                 self.span(),
             )),
 
+            Type::Bool => Err(TypeError::UnsupportedOperation(
+                "Cannot bitfield extract on a boolean".to_string(),
+                self.span(),
+            )),
             Type::Unit => panic!("Cannot use unit types for expressions"),
         }
     }
@@ -754,7 +1306,7 @@ impl TypeCheck for ExprOperand {
                     let mut suffix = i.suffix().to_string();
                     let sign = match suffix.clone().chars().next() {
                         Some('i') => {
-                            suffix
+                            suffix = suffix
                                 .strip_prefix("i")
                                 .expect("Invalid checks")
                                 .to_string();
@@ -859,6 +1411,10 @@ impl TypeCheck for Intrinsic {
                             operand.span(),
                         ))
                     }
+                    Type::Bool => Err(TypeError::UnsupportedOperation(
+                        "Cannot apply ror to a boolean".to_string(),
+                        operand.span(),
+                    )),
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 }
             }
@@ -882,6 +1438,10 @@ impl TypeCheck for Intrinsic {
                             operand.span(),
                         ))
                     }
+                    Type::Bool => Err(TypeError::UnsupportedOperation(
+                        "Cannot apply sra to a boolean".to_string(),
+                        operand.span(),
+                    )),
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 }
             }
@@ -910,6 +1470,23 @@ impl TypeCheck for Intrinsic {
             }
             Intrinsic::Flag(_) => Ok(Some(Type::U(1))),
             Intrinsic::Abort(_) => Ok(None),
+            Intrinsic::Assume(Assume { condition })
+            | Intrinsic::Assert(Assert { condition, .. }) => {
+                match condition.type_check(meta)? {
+                    Some(Type::U(1) | Type::Bool) => {}
+                    Some(ty) => {
+                        return Err(TypeError::InvalidType {
+                            expected: Type::U(1),
+                            got: ty,
+                            span: condition.span(),
+                        })
+                    }
+                    None => meta.set_type(condition, &Type::U(1)),
+                }
+                Ok(None)
+            }
+            // Comments carry no value and do not take part in type inference.
+            Intrinsic::Comment(_) => Ok(None),
             Intrinsic::Resize(resize) => {
                 let Resize {
                     operand,
@@ -955,6 +1532,7 @@ impl TypeCheck for Intrinsic {
                         "Cannot set n flag for zero sized operands".to_string(),
                         operand.span(),
                     )),
+                    Type::Bool => Ok(None),
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 }
             }
@@ -984,6 +1562,7 @@ impl TypeCheck for Intrinsic {
                         "Cannot set z flag for zero sized operands".to_string(),
                         operand.span(),
                     )),
+                    Type::Bool => Ok(None),
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 }
             }
@@ -1031,6 +1610,7 @@ impl TypeCheck for Intrinsic {
                             operand1.span(),
                         ))
                     }
+                    Type::Bool => {}
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 };
                 match ty2 {
@@ -1048,6 +1628,7 @@ impl TypeCheck for Intrinsic {
                             operand2.span(),
                         ))
                     }
+                    Type::Bool => {}
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 }
                 Ok(None)
@@ -1096,6 +1677,7 @@ impl TypeCheck for Intrinsic {
                             operand1.span(),
                         ))
                     }
+                    Type::Bool => {}
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 };
                 match ty2 {
@@ -1113,6 +1695,7 @@ impl TypeCheck for Intrinsic {
                             operand2.span(),
                         ))
                     }
+                    Type::Bool => {}
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 }
                 Ok(None)
@@ -1152,6 +1735,7 @@ impl TypeCheck for Intrinsic {
                     Type::U(_) => Ok(Some(Type::U(*bits))),
                     Type::I(_) => Ok(Some(Type::I(*bits))),
 
+                    Type::Bool => Ok(Some(Type::U(*bits))),
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 }
             }
@@ -1188,6 +1772,10 @@ impl TypeCheck for Intrinsic {
 
                     // Should we considered sign extended unsigned values signed?
                     Type::U(_) | Type::I(_) => Ok(Some(Type::I(*target_size))),
+                    Type::Bool => Err(TypeError::UnsupportedOperation(
+                        "Cannot sign extend a boolean".to_string(),
+                        operand.span(),
+                    )),
                     Type::Unit => panic!("Cannot use unit types for expressions"),
                 }
             }
@@ -1239,6 +1827,10 @@ impl TypeCheck for Intrinsic {
                     Some(Type::U(size)) => Ok(Some(Type::U(size))),
                     Some(Type::F16 | Type::F32 | Type::F64 | Type::F128) => Ok(ty),
 
+                    Some(Type::Bool) => Err(TypeError::UnsupportedOperation(
+                        "Cannot compute absolute value of a boolean.".to_string(),
+                        operand.span(),
+                    )),
                     Some(Type::Unit) => Err(TypeError::UnsupportedOperation(
                         "Cannot compute absolute value of a unit type value.".to_string(),
                         operand.span(),
@@ -1304,10 +1896,12 @@ impl TypeCheck for Intrinsic {
                     "Cannot compute is nan for untyped variables.".to_string(),
                     operand.span(),
                 )),
-                Some(Type::U(_) | Type::I(_) | Type::Unit) => Err(TypeError::UnsupportedOperation(
-                    "Cannot compute isNaN for non floating point values".to_string(),
-                    operand.span(),
-                )),
+                Some(Type::U(_) | Type::I(_) | Type::Bool | Type::Unit) => {
+                    Err(TypeError::UnsupportedOperation(
+                        "Cannot compute isNaN for non floating point values".to_string(),
+                        operand.span(),
+                    ))
+                }
             },
             Intrinsic::MultiplyAndAccumulate(MultiplyAndAccumulate { lhs, rhs, addend }) => {
                 let ty = lhs.type_check(meta)?;
@@ -1321,7 +1915,7 @@ impl TypeCheck for Intrinsic {
                             lhs.span(),
                         ))
                     }
-                    Some(Type::U(_) | Type::I(_) | Type::Unit) => {
+                    Some(Type::U(_) | Type::I(_) | Type::Bool | Type::Unit) => {
                         return Err(TypeError::UnsupportedOperation(
                             "Cannot compute fma for non floating point values".to_string(),
                             lhs.span(),
@@ -1340,7 +1934,7 @@ impl TypeCheck for Intrinsic {
                             rhs.span(),
                         ))
                     }
-                    Some(Type::U(_) | Type::I(_) | Type::Unit) => {
+                    Some(Type::U(_) | Type::I(_) | Type::Bool | Type::Unit) => {
                         return Err(TypeError::UnsupportedOperation(
                             "Cannot compute fma for non floating point values".to_string(),
                             rhs.span(),
@@ -1359,7 +1953,7 @@ impl TypeCheck for Intrinsic {
                             addend.span(),
                         ))
                     }
-                    Some(Type::U(_) | Type::I(_) | Type::Unit) => {
+                    Some(Type::U(_) | Type::I(_) | Type::Bool | Type::Unit) => {
                         return Err(TypeError::UnsupportedOperation(
                             "Cannot compute fma for non floating point values".to_string(),
                             addend.span(),
@@ -1392,10 +1986,12 @@ impl TypeCheck for Intrinsic {
                     "Cannot compute is normal for untyped variables.".to_string(),
                     operand.span(),
                 )),
-                Some(Type::U(_) | Type::I(_) | Type::Unit) => Err(TypeError::UnsupportedOperation(
-                    "Cannot compute is normal for non floating point values".to_string(),
-                    operand.span(),
-                )),
+                Some(Type::U(_) | Type::I(_) | Type::Bool | Type::Unit) => {
+                    Err(TypeError::UnsupportedOperation(
+                        "Cannot compute is normal for non floating point values".to_string(),
+                        operand.span(),
+                    ))
+                }
             },
             Intrinsic::IsFinite(IsFinite { operand }) => match operand.type_check(meta)? {
                 Some(Type::F16 | Type::F32 | Type::F64 | Type::F128) => Ok(Some(Type::U(1))),
@@ -1403,10 +1999,12 @@ impl TypeCheck for Intrinsic {
                     "Cannot compute is finite for untyped variables.".to_string(),
                     operand.span(),
                 )),
-                Some(Type::U(_) | Type::I(_) | Type::Unit) => Err(TypeError::UnsupportedOperation(
-                    "Cannot compute is finite for non floating point values".to_string(),
-                    operand.span(),
-                )),
+                Some(Type::U(_) | Type::I(_) | Type::Bool | Type::Unit) => {
+                    Err(TypeError::UnsupportedOperation(
+                        "Cannot compute is finite for non floating point values".to_string(),
+                        operand.span(),
+                    ))
+                }
             },
             Intrinsic::Log(Log {
                 level: _,
@@ -1416,7 +2014,7 @@ impl TypeCheck for Intrinsic {
             }) => {
                 let ty = operand.type_check(meta)?;
                 match ty {
-                    Some(Type::I(_) | Type::U(_)) => Ok(Some(Type::Unit)),
+                    Some(Type::I(_) | Type::U(_) | Type::Bool) => Ok(Some(Type::Unit)),
                     Some(Type::F16 | Type::F32 | Type::F64 | Type::F128) => {
                         Err(TypeError::UnsupportedOperation(
                             "Cannot log floating point values yet.".to_string(),
@@ -1471,7 +2069,7 @@ impl Rotation {
                     rhs.span(),
                 ))
             }
-            Type::U(_) | Type::I(_) => {}
+            Type::U(_) | Type::I(_) | Type::Bool => {}
             Type::Unit => panic!("Cannot use unit types for expressions"),
         }
 
@@ -1482,7 +2080,7 @@ impl Rotation {
                     lhs.span(),
                 ))
             }
-            Type::U(_) | Type::I(_) => {}
+            Type::U(_) | Type::I(_) | Type::Bool => {}
             Type::Unit => panic!("Cannot use unit types for expressions"),
         }
 
@@ -1515,15 +2113,16 @@ impl CompareOperation {
                 ));
             }
         };
-        if lhs_ty != rhs_ty {
-            return Err(TypeError::UnsupportedOperation(
-                "Cannot compare different types.".to_string(),
-                {
+        if !lhs_ty.is_compatible(&rhs_ty) {
+            return Err(TypeError::InvalidType {
+                expected: lhs_ty,
+                got: rhs_ty,
+                span: {
                     let mut span = lhs.span();
                     span = span.join(rhs.span()).expect("Multi file not supported.");
                     span
                 },
-            ));
+            });
         }
 
         Ok(Type::U(1))
@@ -1536,7 +2135,228 @@ impl crate::ast::operand::Type {
             Self::F16 | Self::F32 | Self::F64 | Self::F128 => Err(TypeError::UnsupportedOperation("Cannot bit field extract on floating point values. Please convert to a bitvector first.".to_owned(),Span::call_site())),
             Self::I(_) => Ok(()),
             Self::U(_) => Ok(()),
+            Self::Bool => Err(TypeError::UnsupportedOperation("Cannot bit field extract on booleans.".to_owned(),Span::call_site())),
             Type::Unit => panic!("Cannot use unit types for expressions"),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use quote::format_ident;
+
+    use crate::{ast::operand::Type, ast::IR, TypeCheck, TypeCheckMeta, TypeError};
+
+    #[test]
+    fn compare_signed_with_unsigned() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:i32;
+                b:u32;
+                let c = a < b;
+            ]",
+        )
+        .unwrap();
+        match ir.type_check(&mut TypeCheckMeta::new()) {
+            Err(TypeError::InvalidType { expected, got, .. }) => {
+                assert_eq!(expected, Type::I(32));
+                assert_eq!(got, Type::U(32));
+            }
+            other => panic!("Expected an invalid type error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn comparison_yields_boolean() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                let c = a < b;
+                let d:u1 = a == b;
+                let e = c & d;
+            ]",
+        )
+        .unwrap();
+        let mut meta = TypeCheckMeta::new();
+        ir.type_check(&mut meta).unwrap();
+        assert_eq!(meta.get_ty(&format_ident!("c")), Some(Type::Bool));
+        assert_eq!(meta.get_ty(&format_ident!("d")), Some(Type::U(1)));
+        assert_eq!(meta.get_ty(&format_ident!("e")), Some(Type::Bool));
+    }
+
+    #[test]
+    fn compare_boolean_with_integer() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                let c = a < b;
+                let d = c == a;
+            ]",
+        )
+        .unwrap();
+        match ir.type_check(&mut TypeCheckMeta::new()) {
+            Err(TypeError::InvalidType { expected, got, .. }) => {
+                assert_eq!(expected, Type::Bool);
+                assert_eq!(got, Type::U(32));
+            }
+            other => panic!("Expected an invalid type error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn mix_integer_and_float() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                let b = a + 1.5f32;
+            ]",
+        )
+        .unwrap();
+        match ir.type_check(&mut TypeCheckMeta::new()) {
+            Err(TypeError::UnsupportedOperation(..)) => {}
+            other => panic!("Expected an unsupported operation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn call_arity_mismatch() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                fn double(x: u32) {
+                    x = x + x;
+                }
+                double(a, a);
+            ]",
+        )
+        .unwrap();
+        match ir.type_check(&mut TypeCheckMeta::new()) {
+            Err(TypeError::UnsupportedOperation(..)) => {}
+            other => panic!("Expected an unsupported operation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn call_type_mismatch() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:i32;
+                fn double(x: u32) {
+                    x = x + x;
+                }
+                double(a);
+            ]",
+        )
+        .unwrap();
+        match ir.type_check(&mut TypeCheckMeta::new()) {
+            Err(TypeError::UnsupportedOperation(..)) => {}
+            other => panic!("Expected an unsupported operation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ternary_branch_type_mismatch() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u16;
+                c:u1;
+                let d = c ? a : b;
+            ]",
+        )
+        .unwrap();
+        match ir.type_check(&mut TypeCheckMeta::new()) {
+            Err(TypeError::InvalidType { expected, got, .. }) => {
+                assert_eq!(expected, Type::U(32));
+                assert_eq!(got, Type::U(16));
+            }
+            other => panic!("Expected an invalid type error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ternary_non_boolean_condition() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                let d = a ? a : b;
+            ]",
+        )
+        .unwrap();
+        match ir.type_check(&mut TypeCheckMeta::new()) {
+            Err(TypeError::InvalidType { expected, got, .. }) => {
+                assert_eq!(expected, Type::U(1));
+                assert_eq!(got, Type::U(32));
+            }
+            other => panic!("Expected an invalid type error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn assert_non_boolean_condition() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                assert(a);
+            ]",
+        )
+        .unwrap();
+        match ir.type_check(&mut TypeCheckMeta::new()) {
+            Err(TypeError::InvalidType { expected, got, .. }) => {
+                assert_eq!(expected, Type::U(1));
+                assert_eq!(got, Type::U(32));
+            }
+            other => panic!("Expected an invalid type error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn logical_operation_on_non_boolean() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                let c = a && b;
+            ]",
+        )
+        .unwrap();
+        match ir.type_check(&mut TypeCheckMeta::new()) {
+            Err(TypeError::InvalidType { expected, got, .. }) => {
+                assert_eq!(expected, Type::Bool);
+                assert_eq!(got, Type::U(32));
+            }
+            other => panic!("Expected an invalid type error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn boolean_locals() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u32;
+                b:u32;
+                c:bool = a < b;
+                let d = c || c;
+                let e = !d;
+                assert(e);
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+    }
+
+    #[test]
+    fn assume_boolean_condition() {
+        let mut ir: IR = syn::parse_str(
+            "[
+                a:u1;
+                assume(a);
+            ]",
+        )
+        .unwrap();
+        ir.type_check(&mut TypeCheckMeta::new()).unwrap();
+    }
+}
@@ -31,6 +31,9 @@ use syn::parse_macro_input;
 ///     Jump(c);
 /// ]);
 /// ```
+///
+/// Registers are assumed to be 32 bits wide, prefix the pseudo code with
+/// `#[word_size = N]` to target an architecture with a different word size.
 pub fn pseudo(item: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(item as IR);
     //println!("Output \n{:?}\n\n\n", input);
@@ -39,8 +42,7 @@ pub fn pseudo(item: TokenStream) -> TokenStream {
         return e.compile_error().into();
     };
 
-    // TODO: Filter out noops.
-    //input.filter();
+    input.filter();
     let input: proc_macro2::TokenStream = match input.into() {
         Ok(val) => val,
         Err(e) => return e.compile_error().into(),
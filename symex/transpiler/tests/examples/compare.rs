@@ -0,0 +1,24 @@
+//! Defines a comparison example
+use general_assembly::operand::Operand;
+use transpiler::pseudo;
+
+fn main() {
+    let a = Operand::Register("a".to_owned());
+    let b = Operand::Register("b".to_owned());
+    let c = Operand::Register("c".to_owned());
+    let cond = true;
+    let _ret = pseudo!([
+        a:u32;
+        b:u32;
+        c:u32;
+        if(cond) {
+            let lower = a < b;
+            c = lower ? a : b;
+        }
+        if(a < b) {
+            c = a;
+        } else {
+            c = b;
+        }
+    ]);
+}
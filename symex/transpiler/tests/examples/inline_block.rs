@@ -0,0 +1,18 @@
+//! Defines a simple example of inlining a named block
+use general_assembly::operand::Operand;
+use transpiler::pseudo;
+
+fn main() {
+    let a = Operand::Register("a".to_owned());
+    let b = Operand::Register("b".to_owned());
+    let _ret = pseudo!([
+        a:u32;
+        b:u32;
+        fn accumulate(dst: u32, value: u32) {
+            let sum = dst + value;
+            dst = sum + value;
+        }
+        accumulate(a, b);
+        accumulate(b, 3u32);
+    ]);
+}
@@ -0,0 +1,17 @@
+//! Defines an example of assigning to a local after it was declared
+use general_assembly::operand::Operand;
+use transpiler::pseudo;
+
+fn main() {
+    let a = Operand::Register("a".to_owned());
+    let b = Operand::Register("b".to_owned());
+    let c = Operand::Register("c".to_owned());
+    let _ret = pseudo!([
+        a:u32;
+        b:u32;
+        c:u32;
+        let d = a;
+        d = b;
+        c = d;
+    ]);
+}
@@ -0,0 +1,19 @@
+//! Defines a simple while loop example
+use general_assembly::{operand::DataWord, operand::Operand};
+use transpiler::pseudo;
+
+fn main() {
+    let a = Operand::Register("a".to_owned());
+    let n = Operand::Immediate(DataWord::Word32(10));
+    let _ret = pseudo!([
+        a:u32;
+        n:u32;
+        let sum:u32 = 0u32;
+        let i:u32 = 0u32;
+        while(i < n) {
+            i = i + 1u32;
+            sum = sum + i;
+        }
+        a = sum;
+    ]);
+}
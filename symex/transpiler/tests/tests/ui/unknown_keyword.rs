@@ -0,0 +1,13 @@
+use general_assembly::operand::Operand;
+use transpiler::pseudo;
+
+fn main() {
+    let a = Operand::Register("a".to_owned());
+    let b = Operand::Register("b".to_owned());
+    let _ret = pseudo!([
+        a:u32;
+        b:u32;
+        frobnicate a;
+        b = a;
+    ]);
+}